@@ -2,7 +2,7 @@ import { defineConfig } from "drizzle-kit";
 import { databaseUrl } from "./src/config/database";
 
 export default defineConfig({
-  schema: "./src/schema",
+  schema: "./src/schema/sqlite",
   out: "./src/migrations",
   dialect: "sqlite",
   dbCredentials: {
@@ -0,0 +1,11 @@
+import { defineConfig } from "drizzle-kit";
+import { databaseUrl } from "./src/config/database";
+
+export default defineConfig({
+  schema: "./src/schema/pg",
+  out: "./src/migrations-pg",
+  dialect: "postgresql",
+  dbCredentials: {
+    url: databaseUrl,
+  },
+});
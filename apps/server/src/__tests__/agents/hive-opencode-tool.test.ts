@@ -4,6 +4,7 @@ import { join } from "node:path";
 import { afterEach, describe, expect, it, vi } from "vitest";
 
 import {
+  open_pull_request as hiveOpenPullRequestTool,
   rerun_setup as hiveRerunSetupTool,
   restart_services as hiveRestartServicesTool,
   restart_service as hiveRestartServiceTool,
@@ -39,6 +40,7 @@ async function writeHiveToolConfig(args: {
   worktreePath: string;
   cellId: string;
   hiveUrl: string;
+  apiToken?: string;
 }): Promise<void> {
   const dir = join(args.worktreePath, ".hive");
   await fs.mkdir(dir, { recursive: true });
@@ -47,6 +49,9 @@ async function writeHiveToolConfig(args: {
     JSON.stringify({ cellId: args.cellId, hiveUrl: args.hiveUrl }, null, 2),
     "utf-8"
   );
+  if (args.apiToken) {
+    await fs.writeFile(join(dir, "api-token"), `${args.apiToken}\n`, "utf-8");
+  }
 }
 
 describe("Hive OpenCode tools", () => {
@@ -59,7 +64,12 @@ describe("Hive OpenCode tools", () => {
     const cellId = "test-cell";
     const hiveUrl = "http://hive.local";
 
-    await writeHiveToolConfig({ worktreePath, cellId, hiveUrl });
+    await writeHiveToolConfig({
+      worktreePath,
+      cellId,
+      hiveUrl,
+      apiToken: "hive_cell-token",
+    });
 
     const fetchSpy = vi
       .spyOn(globalThis, "fetch")
@@ -68,6 +78,9 @@ describe("Hive OpenCode tools", () => {
 
         expect(url).toContain(`${hiveUrl}/api/cells/${cellId}/services`);
         expect(init?.signal).toBeDefined();
+        expect(init?.headers).toEqual({
+          authorization: "Bearer hive_cell-token",
+        });
 
         const payload = {
           services: [
@@ -349,4 +362,78 @@ describe("Hive OpenCode tools", () => {
 
     await fs.rm(worktreePath, { recursive: true, force: true });
   });
+
+  it("opens a pull request via the Hive API", async () => {
+    const worktreePath = await createTempWorktree();
+    const cellId = "test-cell";
+    const hiveUrl = "http://hive.local";
+
+    await writeHiveToolConfig({ worktreePath, cellId, hiveUrl });
+
+    const fetchSpy = vi
+      .spyOn(globalThis, "fetch")
+      .mockImplementation((input, init) => {
+        const url = resolveFetchUrl(input);
+        const method = init?.method ?? "GET";
+
+        if (url === `${hiveUrl}/api/cells/${cellId}/pull-request`) {
+          expect(method).toBe("POST");
+          expect(JSON.parse(String(init?.body))).toEqual({
+            title: "Add feature",
+            draft: true,
+          });
+          const payload = {
+            number: 12,
+            url: "https://github.com/acme/widgets/pull/12",
+            head: "hive/test-cell",
+            base: "main",
+            commit: "0123456789abcdef",
+          };
+          return Promise.resolve(
+            new Response(JSON.stringify(payload), {
+              status: HTTP_OK,
+              headers: { "content-type": "application/json" },
+            })
+          );
+        }
+
+        throw new Error(`Unexpected fetch: ${method} ${url}`);
+      });
+
+    const controller = new AbortController();
+    const context = {
+      sessionID: "session",
+      messageID: "message",
+      agent: "test",
+      directory: worktreePath,
+      worktree: worktreePath,
+      abort: controller.signal,
+      metadata() {
+        // no-op for tests
+      },
+      ask: async () => {
+        // no-op for tests
+      },
+    };
+
+    const refused = await hiveOpenPullRequestTool.execute(
+      { title: "Add feature" },
+      context
+    );
+    expect(refused).toContain("confirm=true");
+    expect(fetchSpy).not.toHaveBeenCalled();
+
+    const output = await hiveOpenPullRequestTool.execute(
+      { title: "Add feature", draft: true, confirm: true },
+      context
+    );
+
+    expect(fetchSpy).toHaveBeenCalledTimes(1);
+    expect(output).toContain(
+      "Opened pull request #12: https://github.com/acme/widgets/pull/12"
+    );
+    expect(output).toContain("hive/test-cell -> main");
+
+    await fs.rm(worktreePath, { recursive: true, force: true });
+  });
 });
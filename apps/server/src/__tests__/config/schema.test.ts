@@ -57,6 +57,26 @@ describe("Template Schema", () => {
     expect(result.services?.api?.type).toBe(EXPECTED.serviceType);
   });
 
+  it("should publish only one docker port on the assigned port", () => {
+    const dockerTemplate = (ports: string[]) => ({
+      id: "db",
+      label: "Database",
+      type: "manual" as const,
+      services: { db: { type: "docker" as const, image: "redis:7", ports } },
+    });
+
+    expect(
+      templateSchema.safeParse(dockerTemplate(["6379", "6380:6380"])).success
+    ).toBe(true);
+    const result = templateSchema.safeParse(
+      dockerTemplate(["$PORT:6379", "8001"])
+    );
+    expect(result.success).toBe(false);
+    expect(result.error?.issues[0]?.message).toMatch(
+      /Only one port can be published/
+    );
+  });
+
   it("should accept agent configuration metadata", () => {
     const templateWithAgent = {
       id: "agent-template",
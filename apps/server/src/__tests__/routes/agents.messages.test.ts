@@ -0,0 +1,149 @@
+import { Elysia } from "elysia";
+import { beforeEach, describe, expect, it, vi } from "vitest";
+import { publishAgentEvent, subscribeAgentEvents } from "../../agents/events";
+// biome-ignore lint/performance/noNamespaceImport: vi.spyOn requires a module namespace reference
+import * as AgentService from "../../agents/service";
+import type { AgentSessionRecord, AgentStreamEvent } from "../../agents/types";
+import { agentsRoutes } from "../../routes/agents";
+
+const TEST_SESSION: AgentSessionRecord = {
+  id: "session-messages-test",
+  cellId: "cell-messages-test",
+  templateId: "template-messages-test",
+  provider: "opencode",
+  status: "awaiting_input",
+  workspacePath: "/tmp/workspace",
+  createdAt: new Date().toISOString(),
+  updatedAt: new Date().toISOString(),
+};
+
+const HTTP_ACCEPTED = 202;
+const HTTP_NOT_FOUND = 404;
+
+const postMessage = (sessionId: string, content: string) =>
+  new Elysia().use(agentsRoutes).handle(
+    new Request(`http://localhost/api/agents/sessions/${sessionId}/messages`, {
+      method: "POST",
+      headers: { "content-type": "application/json" },
+      body: JSON.stringify({ content }),
+    })
+  );
+
+const openEventStream = async (query = "") => {
+  const response = await new Elysia()
+    .use(agentsRoutes)
+    .handle(
+      new Request(
+        `http://localhost/api/agents/sessions/${TEST_SESSION.id}/events${query}`
+      )
+    );
+  const reader = response.body?.getReader();
+  if (!reader) {
+    throw new Error("Expected event stream body");
+  }
+  const decoder = new TextDecoder();
+  const readChunk = async () => {
+    const { value } = await reader.read();
+    if (typeof value === "string") {
+      return value;
+    }
+    return value instanceof Uint8Array ? decoder.decode(value) : "";
+  };
+  return { reader, readChunk };
+};
+
+const textPartEvent = (role: "user" | "assistant") =>
+  ({
+    type: "message.part.updated",
+    properties: {
+      part: {
+        id: `part-${role}`,
+        sessionID: TEST_SESSION.id,
+        messageID: `msg-${role}`,
+        type: "text",
+        text: "Hello there",
+      },
+      delta: "there",
+    },
+  }) as unknown as AgentStreamEvent;
+
+describe("agent message routes", () => {
+  beforeEach(() => {
+    vi.restoreAllMocks();
+    vi.spyOn(AgentService, "fetchAgentSession").mockImplementation(
+      async (id: string) => (id === TEST_SESSION.id ? TEST_SESSION : null)
+    );
+  });
+
+  it("accepts a prompt and sends it to the agent", async () => {
+    const sendAgentMessage = vi
+      .spyOn(AgentService, "sendAgentMessage")
+      .mockResolvedValue();
+
+    const response = await postMessage(TEST_SESSION.id, "Run the tests");
+
+    expect(response.status).toBe(HTTP_ACCEPTED);
+    expect(await response.json()).toEqual({
+      sessionId: TEST_SESSION.id,
+      status: "accepted",
+    });
+    expect(sendAgentMessage).toHaveBeenCalledWith(
+      TEST_SESSION.id,
+      "Run the tests"
+    );
+  });
+
+  it("reports prompt failures as an error status", async () => {
+    vi.spyOn(AgentService, "sendAgentMessage").mockRejectedValue(
+      new Error("Agent runtime unavailable")
+    );
+    const events: AgentStreamEvent[] = [];
+    const unsubscribe = subscribeAgentEvents(TEST_SESSION.id, (event) => {
+      events.push(event);
+    });
+
+    const response = await postMessage(TEST_SESSION.id, "Run the tests");
+    await new Promise((resolve) => setTimeout(resolve, 0));
+    unsubscribe();
+
+    expect(response.status).toBe(HTTP_ACCEPTED);
+    expect(events).toEqual([
+      {
+        type: "status",
+        status: "error",
+        error: "Agent runtime unavailable",
+      },
+    ]);
+  });
+
+  it("returns 404 for unknown sessions", async () => {
+    const response = await postMessage("missing", "hello");
+    expect(response.status).toBe(HTTP_NOT_FOUND);
+  });
+
+  it("streams message parts only when requested", async () => {
+    const withParts = await openEventStream("?includeParts=true");
+    expect(await withParts.readChunk()).toContain("event: status");
+
+    publishAgentEvent(TEST_SESSION.id, {
+      type: "message.updated",
+      properties: { info: { id: "msg-assistant", role: "assistant" } },
+    } as unknown as AgentStreamEvent);
+    const message = await withParts.readChunk();
+    expect(message).toContain("event: message");
+    expect(message).toContain('"role":"assistant"');
+
+    publishAgentEvent(TEST_SESSION.id, textPartEvent("assistant"));
+    const part = await withParts.readChunk();
+    expect(part).toContain("event: part");
+    expect(part).toContain('"delta":"there"');
+    await withParts.reader.cancel();
+
+    const withoutParts = await openEventStream();
+    expect(await withoutParts.readChunk()).toContain("event: status");
+    publishAgentEvent(TEST_SESSION.id, textPartEvent("assistant"));
+    publishAgentEvent(TEST_SESSION.id, { type: "status", status: "working" });
+    expect(await withoutParts.readChunk()).toContain('"status":"working"');
+    await withoutParts.reader.cancel();
+  });
+});
@@ -0,0 +1,289 @@
+import { Elysia } from "elysia";
+import { beforeAll, beforeEach, describe, expect, it } from "vitest";
+import {
+  createApiAuthPlugin,
+  redactRequestUrl,
+  resolveApiAuthMode,
+  resolveRequiredScope,
+} from "../../auth/plugin";
+import {
+  createApiToken,
+  hashApiToken,
+  issueCellApiToken,
+} from "../../auth/tokens";
+import { createAuthRoutes } from "../../routes/auth";
+import { apiTokens } from "../../schema/api-tokens";
+import { cells } from "../../schema/cells";
+import { setupTestDb, testDb } from "../test-db";
+
+const HTTP_OK = 200;
+const HTTP_CREATED = 201;
+const HTTP_FOUND = 302;
+const HTTP_UNAUTHORIZED = 401;
+const HTTP_FORBIDDEN = 403;
+const HTTP_NOT_FOUND = 404;
+const JSON_HEADERS = { "content-type": "application/json" };
+
+type TokenRecordPayload = {
+  id: string;
+  name: string;
+  tokenPrefix: string;
+  scope: string;
+  revokedAt: string | null;
+};
+
+describe("API token auth", () => {
+  beforeAll(async () => {
+    await setupTestDb();
+  });
+
+  beforeEach(async () => {
+    await testDb.delete(apiTokens);
+    await testDb.delete(cells);
+    await testDb.insert(cells).values({
+      id: "c1",
+      name: "c1",
+      templateId: "basic",
+      workspacePath: "/tmp/auth-c1",
+      workspaceId: "workspace-auth",
+      workspaceRootPath: "/tmp/auth-workspace",
+      createdAt: new Date(),
+      status: "ready",
+    });
+  });
+
+  it("resolves scopes from method and path", () => {
+    expect(resolveRequiredScope("GET", "/health")).toBeNull();
+    expect(resolveRequiredScope("OPTIONS", "/api/cells")).toBeNull();
+    expect(resolveRequiredScope("GET", "/api/auth/login")).toBeNull();
+    expect(resolveRequiredScope("GET", "/api/cells")).toBe("read");
+    expect(resolveRequiredScope("GET", "/api/cells/c1/services/stream")).toBe(
+      "read"
+    );
+    expect(resolveRequiredScope("GET", "/api/cells/c1/terminal/ws")).toBe(
+      "operate"
+    );
+    expect(resolveRequiredScope("POST", "/api/cells")).toBe("operate");
+    expect(resolveRequiredScope("GET", "/api/workspaces")).toBe("read");
+    expect(resolveRequiredScope("POST", "/api/workspaces")).toBe("admin");
+    expect(resolveRequiredScope("DELETE", "/api/webhooks/w1")).toBe("admin");
+    expect(resolveRequiredScope("PUT", "/api/secrets/DB_PASSWORD")).toBe(
+      "admin"
+    );
+    expect(resolveRequiredScope("GET", "/api/auth/tokens")).toBe("admin");
+  });
+
+  it("requires tokens off loopback unless HIVE_AUTH overrides", () => {
+    expect(resolveApiAuthMode("localhost", undefined)).toBe("off");
+    expect(resolveApiAuthMode("127.0.0.1", undefined)).toBe("off");
+    expect(resolveApiAuthMode("0.0.0.0", undefined)).toBe("required");
+    expect(resolveApiAuthMode("localhost", "required")).toBe("required");
+    expect(resolveApiAuthMode("0.0.0.0", "OFF")).toBe("off");
+  });
+
+  it("rejects requests without a valid token", async () => {
+    const app = createApp();
+
+    const missing = await app.handle(request("GET", "/api/cells"));
+    expect(missing.status).toBe(HTTP_UNAUTHORIZED);
+    expect(missing.headers.get("www-authenticate")).toContain("Bearer");
+
+    const bogus = await app.handle(
+      request("GET", "/api/cells", { token: "hive_not-a-real-token" })
+    );
+    expect(bogus.status).toBe(HTTP_UNAUTHORIZED);
+
+    const health = await app.handle(request("GET", "/health"));
+    expect(health.status).toBe(HTTP_OK);
+  });
+
+  it("enforces token scopes", async () => {
+    const app = createApp();
+    const { token } = await createApiToken(testDb, {
+      name: "dashboard",
+      scope: "read",
+    });
+
+    const read = await app.handle(request("GET", "/api/cells", { token }));
+    expect(read.status).toBe(HTTP_OK);
+
+    const write = await app.handle(request("POST", "/api/cells", { token }));
+    expect(write.status).toBe(HTTP_FORBIDDEN);
+    expect(((await write.json()) as { message: string }).message).toContain(
+      '"operate"'
+    );
+  });
+
+  it("accepts cookie, query string and cell agent tokens", async () => {
+    const app = createApp();
+    const { token } = await createApiToken(testDb, {
+      name: "browser",
+      scope: "operate",
+    });
+
+    const viaCookie = await app.handle(
+      new Request("http://localhost/api/cells", {
+        method: "POST",
+        headers: { cookie: `theme=dark; hive_token=${token}` },
+      })
+    );
+    expect(viaCookie.status).toBe(HTTP_OK);
+
+    const viaQuery = await app.handle(
+      new Request(
+        `http://localhost/api/cells/c1/services/stream?access_token=${token}`
+      )
+    );
+    expect(viaQuery.status).toBe(HTTP_OK);
+
+    const queryOutsideStreams = await app.handle(
+      new Request(`http://localhost/api/cells?access_token=${token}`)
+    );
+    expect(queryOutsideStreams.status).toBe(HTTP_UNAUTHORIZED);
+
+    const agentToken = await issueCellApiToken(testDb, "c1");
+    const internal = await app.handle(
+      request("GET", "/api/cells/c1/services/stream", { token: agentToken })
+    );
+    expect(internal.status).toBe(HTTP_OK);
+
+    const internalAdmin = await app.handle(
+      request("GET", "/api/auth/tokens", { token: agentToken })
+    );
+    expect(internalAdmin.status).toBe(HTTP_FORBIDDEN);
+
+    const reissued = await issueCellApiToken(testDb, "c1");
+    const stale = await app.handle(
+      request("GET", "/api/cells/c1/services/stream", { token: agentToken })
+    );
+    expect(stale.status).toBe(HTTP_UNAUTHORIZED);
+    const current = await app.handle(
+      request("GET", "/api/cells/c1/services/stream", { token: reissued })
+    );
+    expect(current.status).toBe(HTTP_OK);
+  });
+
+  it("creates, lists and revokes tokens", async () => {
+    const app = createApp();
+    const { token: adminToken } = await createApiToken(testDb, {
+      name: "admin",
+      scope: "admin",
+    });
+
+    const created = await app.handle(
+      request("POST", "/api/auth/tokens", {
+        token: adminToken,
+        body: { name: "ci", scope: "operate" },
+      })
+    );
+    expect(created.status).toBe(HTTP_CREATED);
+    const payload = (await created.json()) as {
+      token: string;
+      record: TokenRecordPayload;
+    };
+    expect(payload.token.startsWith(payload.record.tokenPrefix)).toBe(true);
+    expect(payload.record).toMatchObject({ name: "ci", scope: "operate" });
+    await issueCellApiToken(testDb, "c1");
+
+    const rows = await testDb.select().from(apiTokens);
+    expect(rows.map((row) => row.tokenHash)).toContain(
+      hashApiToken(payload.token)
+    );
+    expect(JSON.stringify(rows)).not.toContain(payload.token);
+
+    const listed = await app.handle(
+      request("GET", "/api/auth/tokens", { token: adminToken })
+    );
+    const { tokens } = (await listed.json()) as {
+      tokens: TokenRecordPayload[];
+    };
+    expect(tokens.map((entry) => entry.name).sort()).toEqual(["admin", "ci"]);
+
+    const revoked = await app.handle(
+      request("DELETE", `/api/auth/tokens/${payload.record.tokenPrefix}`, {
+        token: adminToken,
+      })
+    );
+    expect(revoked.status).toBe(HTTP_OK);
+    expect(
+      ((await revoked.json()) as TokenRecordPayload).revokedAt
+    ).not.toBeNull();
+
+    const afterRevoke = await app.handle(
+      request("GET", "/api/cells", { token: payload.token })
+    );
+    expect(afterRevoke.status).toBe(HTTP_UNAUTHORIZED);
+
+    const missing = await app.handle(
+      request("DELETE", `/api/auth/tokens/${payload.record.id}`, {
+        token: adminToken,
+      })
+    );
+    expect(missing.status).toBe(HTTP_NOT_FOUND);
+  });
+
+  it("masks query string tokens in logged URLs", () => {
+    expect(
+      redactRequestUrl(
+        "http://localhost/api/cells/c1/terminal/ws?access_token=hive_secret"
+      )
+    ).toBe(
+      "http://localhost/api/cells/c1/terminal/ws?access_token=%5Bredacted%5D"
+    );
+    expect(
+      redactRequestUrl("http://localhost/api/auth/login?token=hive_secret")
+    ).not.toContain("hive_secret");
+    expect(redactRequestUrl("http://localhost/api/cells?token=abc")).toBe(
+      "http://localhost/api/cells?token=abc"
+    );
+  });
+
+  it("signs browsers in with a cookie via the login link", async () => {
+    const app = createApp();
+    const { token } = await createApiToken(testDb, {
+      name: "browser",
+      scope: "read",
+    });
+
+    const login = await app.handle(
+      new Request(
+        `http://localhost/api/auth/login?token=${token}&redirect=${encodeURIComponent("https://evil.example/")}`
+      )
+    );
+    expect(login.status).toBe(HTTP_FOUND);
+    expect(login.headers.get("location")).toBe("/");
+    const cookie = login.headers.get("set-cookie") ?? "";
+    expect(cookie).toContain(`hive_token=${token}`);
+    expect(cookie.toLowerCase()).toContain("httponly");
+
+    const rejected = await app.handle(
+      new Request("http://localhost/api/auth/login?token=hive_wrong")
+    );
+    expect(rejected.status).toBe(HTTP_UNAUTHORIZED);
+  });
+
+  function createApp() {
+    return new Elysia()
+      .use(createApiAuthPlugin({ db: testDb, mode: "required" }))
+      .get("/health", () => ({ status: "ok" }))
+      .get("/api/cells", () => ({ cells: [] }))
+      .post("/api/cells", () => ({ ok: true }))
+      .get("/api/cells/:id/services/stream", () => ({ ok: true }))
+      .use(createAuthRoutes({ db: testDb, mode: "required" }));
+  }
+});
+
+function request(
+  method: string,
+  path: string,
+  options: { token?: string; body?: Record<string, unknown> } = {}
+) {
+  return new Request(`http://localhost${path}`, {
+    method,
+    headers: {
+      ...(options.token ? { authorization: `Bearer ${options.token}` } : {}),
+      ...(options.body ? JSON_HEADERS : {}),
+    },
+    ...(options.body ? { body: JSON.stringify(options.body) } : {}),
+  });
+}
@@ -0,0 +1,567 @@
+import { randomUUID } from "node:crypto";
+import { mkdir, mkdtemp, rm, writeFile } from "node:fs/promises";
+import { createServer, type Server } from "node:http";
+import { tmpdir } from "node:os";
+import { join } from "node:path";
+import { eq } from "drizzle-orm";
+import { Elysia } from "elysia";
+import { afterEach, beforeAll, beforeEach, describe, expect, it } from "vitest";
+import type { GitConfig } from "../../config/schema";
+import {
+  type CellGitRouteDependencies,
+  createCellGitRoutes,
+} from "../../routes/cell-git";
+import { cellActivityEvents } from "../../schema/activity-events";
+import { cells } from "../../schema/cells";
+import type {
+  ResolveWorkspaceContext,
+  WorkspaceRuntimeContext,
+} from "../../workspaces/context";
+import { setupTestDb, testDb } from "../test-db";
+
+const HTTP_OK = 200;
+const HTTP_BAD_REQUEST = 400;
+const HTTP_CONFLICT = 409;
+const BRANCH_NAME = "hive/cell-git-test";
+const JSON_HEADERS = { "content-type": "application/json" };
+
+type ForgeRequest = {
+  method: string;
+  url: string;
+  authorization: string | undefined;
+  body: Record<string, unknown>;
+};
+
+describe("cell git routes", () => {
+  let tempRoot = "";
+  let workspacePath = "";
+  let remotePath = "";
+  let cellId = "";
+  let forgeServer: Server | null = null;
+  const forgeRequests: ForgeRequest[] = [];
+
+  beforeAll(async () => {
+    await setupTestDb();
+  });
+
+  beforeEach(async () => {
+    await testDb.delete(cellActivityEvents);
+    await testDb.delete(cells);
+    forgeRequests.length = 0;
+
+    tempRoot = await mkdtemp(join(tmpdir(), "hive-cell-git-"));
+    remotePath = join(tempRoot, "remote.git");
+    workspacePath = join(tempRoot, "workspace");
+    await mkdir(workspacePath, { recursive: true });
+
+    runGit(tempRoot, ["init", "--bare", remotePath]);
+    runGit(workspacePath, ["init", "--initial-branch", "main"]);
+    runGit(workspacePath, ["config", "user.email", "test@example.com"]);
+    runGit(workspacePath, ["config", "user.name", "Test User"]);
+    await writeFile(join(workspacePath, "README.md"), "# test\n", "utf8");
+    runGit(workspacePath, ["add", "."]);
+    runGit(workspacePath, ["commit", "-m", "initial"]);
+    runGit(workspacePath, ["remote", "add", "origin", remotePath]);
+    runGit(workspacePath, ["push", "origin", "main"]);
+    runGit(workspacePath, ["checkout", "-b", BRANCH_NAME]);
+
+    cellId = randomUUID();
+    await testDb.insert(cells).values({
+      id: cellId,
+      name: "Git cell",
+      templateId: "basic",
+      workspacePath,
+      workspaceId: "workspace-git",
+      workspaceRootPath: workspacePath,
+      createdAt: new Date(),
+      status: "ready",
+      branchName: BRANCH_NAME,
+      baseCommit: runGitRead(workspacePath, ["rev-parse", "HEAD"]),
+      startPointMode: "head",
+      startPointRef: "main",
+    });
+  });
+
+  afterEach(async () => {
+    if (forgeServer) {
+      const server = forgeServer;
+      await new Promise<void>((resolve) => server.close(() => resolve()));
+      forgeServer = null;
+    }
+    await rm(tempRoot, { recursive: true, force: true });
+  });
+
+  it("commits workspace changes and records activity", async () => {
+    await writeFile(join(workspacePath, "feature.txt"), "hello\n", "utf8");
+    const app = createApp({});
+
+    const response = await post(app, "git/commit", { message: "Add feature" });
+    expect(response.status).toBe(HTTP_OK);
+    const payload = (await response.json()) as {
+      commit: string;
+      files: string[];
+      branchName: string;
+    };
+
+    expect(payload.files).toEqual(["feature.txt"]);
+    expect(payload.branchName).toBe(BRANCH_NAME);
+    expect(payload.commit).toBe(
+      runGitRead(workspacePath, ["rev-parse", "HEAD"])
+    );
+    expect(runGitRead(workspacePath, ["log", "-1", "--format=%s"])).toBe(
+      "Add feature"
+    );
+
+    const events = await testDb
+      .select()
+      .from(cellActivityEvents)
+      .where(eq(cellActivityEvents.cellId, cellId));
+    expect(events.map((event) => event.type)).toEqual(["cell.commit"]);
+  });
+
+  it("commits only the requested files and leaves other staged work", async () => {
+    await writeFile(join(workspacePath, "feature.txt"), "hello\n", "utf8");
+    await writeFile(join(workspacePath, "notes.txt"), "draft\n", "utf8");
+    runGit(workspacePath, ["add", "notes.txt"]);
+    const app = createApp({});
+
+    const response = await post(app, "git/commit", {
+      message: "Add feature",
+      files: ["feature.txt"],
+    });
+    expect(response.status).toBe(HTTP_OK);
+    const payload = (await response.json()) as { files: string[] };
+
+    expect(payload.files).toEqual(["feature.txt"]);
+    expect(
+      runGitRead(workspacePath, ["show", "--name-only", "--format=", "HEAD"])
+    ).toBe("feature.txt");
+    expect(runGitRead(workspacePath, ["diff", "--cached", "--name-only"])).toBe(
+      "notes.txt"
+    );
+  });
+
+  it("rejects commits when nothing changed", async () => {
+    const app = createApp({});
+
+    const response = await post(app, "git/commit", { message: "Empty" });
+    expect(response.status).toBe(HTTP_CONFLICT);
+  });
+
+  it("pushes the cell branch to the configured remote", async () => {
+    await writeFile(join(workspacePath, "feature.txt"), "hello\n", "utf8");
+    const app = createApp({});
+    await post(app, "git/commit", { message: "Add feature" });
+
+    const response = await post(app, "git/push", {});
+    expect(response.status).toBe(HTTP_OK);
+    const payload = (await response.json()) as {
+      remote: string;
+      branchName: string;
+      commit: string;
+    };
+
+    expect(payload.remote).toBe("origin");
+    expect(payload.branchName).toBe(BRANCH_NAME);
+    expect(
+      runGitRead(remotePath, ["rev-parse", `refs/heads/${BRANCH_NAME}`])
+    ).toBe(payload.commit);
+  });
+
+  it("pushes and opens a pull request through the forge adapter", async () => {
+    const apiUrl = await startForgeServer({
+      number: 42,
+      html_url: "https://forge.example/acme/widgets/pull/42",
+    });
+    await writeFile(join(workspacePath, "feature.txt"), "hello\n", "utf8");
+    const app = createApp({
+      forge: { type: "github", apiUrl, repository: "acme/widgets" },
+    });
+    await post(app, "git/commit", { message: "Add feature" });
+
+    const response = await post(app, "pull-request", {
+      title: "Add feature",
+      body: "Implements the feature",
+    });
+    expect(response.status).toBe(HTTP_OK);
+    const payload = (await response.json()) as {
+      number: number;
+      url: string;
+      head: string;
+      base: string;
+      commit: string;
+    };
+
+    expect(payload).toMatchObject({
+      number: 42,
+      url: "https://forge.example/acme/widgets/pull/42",
+      head: BRANCH_NAME,
+      base: "main",
+    });
+    expect(
+      runGitRead(remotePath, ["rev-parse", `refs/heads/${BRANCH_NAME}`])
+    ).toBe(payload.commit);
+
+    expect(forgeRequests).toHaveLength(1);
+    expect(forgeRequests[0]).toMatchObject({
+      method: "POST",
+      url: "/repos/acme/widgets/pulls",
+      authorization: "Bearer test-token",
+      body: {
+        head: BRANCH_NAME,
+        base: "main",
+        title: "Add feature",
+        body: "Implements the feature",
+      },
+    });
+  });
+
+  it("requires a forge token before opening pull requests", async () => {
+    const app = createApp(
+      { forge: { type: "gitlab", repository: "acme/widgets" } },
+      {}
+    );
+
+    const response = await post(app, "pull-request", { title: "Add feature" });
+    expect(response.status).toBe(HTTP_BAD_REQUEST);
+    const payload = (await response.json()) as { message: string };
+    expect(payload.message).toContain("GITLAB_TOKEN");
+  });
+
+  it("merges a clean cell branch into its base branch", async () => {
+    await writeFile(join(workspacePath, "feature.txt"), "hello\n", "utf8");
+    const app = createApp({});
+    await post(app, "git/commit", { message: "Add feature" });
+
+    const response = await post(app, "merge", {});
+    expect(response.status).toBe(HTTP_OK);
+    const payload = (await response.json()) as {
+      status: string;
+      applied: boolean;
+      baseBranch: string;
+      resultCommit: string | null;
+      conflicts: unknown[];
+    };
+
+    expect(payload).toMatchObject({
+      status: "clean",
+      applied: true,
+      baseBranch: "main",
+      conflicts: [],
+    });
+    expect(runGitRead(workspacePath, ["rev-parse", "main"])).toBe(
+      payload.resultCommit
+    );
+    expect(runGitRead(workspacePath, ["show", "main:feature.txt"])).toBe(
+      "hello"
+    );
+
+    const events = await testDb
+      .select()
+      .from(cellActivityEvents)
+      .where(eq(cellActivityEvents.cellId, cellId));
+    expect(events.map((event) => event.type)).toContain("cell.merge");
+  });
+
+  it("reports conflicting files without moving the base branch", async () => {
+    await writeFile(join(workspacePath, "README.md"), "# cell\n", "utf8");
+    runGit(workspacePath, ["commit", "-am", "Cell readme"]);
+    runGit(workspacePath, ["checkout", "main"]);
+    await writeFile(join(workspacePath, "README.md"), "# main\n", "utf8");
+    runGit(workspacePath, ["commit", "-am", "Main readme"]);
+    const baseTip = runGitRead(workspacePath, ["rev-parse", "HEAD"]);
+    runGit(workspacePath, ["checkout", BRANCH_NAME]);
+    const app = createApp({});
+
+    const response = await post(app, "merge", {});
+    expect(response.status).toBe(HTTP_OK);
+    const payload = (await response.json()) as {
+      status: string;
+      applied: boolean;
+      conflicts: Array<Record<string, unknown>>;
+    };
+
+    expect(payload.status).toBe("conflicted");
+    expect(payload.applied).toBe(false);
+    expect(payload.conflicts).toEqual([
+      { path: "README.md", status: "modified", additions: 1, deletions: 1 },
+    ]);
+    expect(runGitRead(workspacePath, ["rev-parse", "main"])).toBe(baseTip);
+    expect(runGitRead(workspacePath, ["status", "--porcelain"])).toBe("");
+  });
+
+  it("merges into a base branch that only exists on the remote", async () => {
+    await writeFile(join(workspacePath, "feature.txt"), "hello\n", "utf8");
+    const app = createApp({});
+    await post(app, "git/commit", { message: "Add feature" });
+    runGit(workspacePath, ["branch", "-D", "main"]);
+
+    const response = await post(app, "merge", {});
+    expect(response.status).toBe(HTTP_OK);
+    const payload = (await response.json()) as {
+      applied: boolean;
+      resultCommit: string | null;
+    };
+
+    expect(payload.applied).toBe(true);
+    expect(runGitRead(workspacePath, ["rev-parse", "main"])).toBe(
+      payload.resultCommit
+    );
+    expect(
+      runGitRead(workspacePath, ["rev-parse", "--abbrev-ref", "main@{u}"])
+    ).toBe("origin/main");
+  });
+
+  it("refuses to merge while the cell worktree has uncommitted changes", async () => {
+    await writeFile(join(workspacePath, "README.md"), "# draft\n", "utf8");
+    const baseTip = runGitRead(workspacePath, ["rev-parse", "main"]);
+    const app = createApp({});
+
+    const response = await post(app, "merge", {});
+    expect(response.status).toBe(HTTP_CONFLICT);
+    const payload = (await response.json()) as { message: string };
+    expect(payload.message).toContain("Commit or stash");
+    expect(runGitRead(workspacePath, ["rev-parse", "main"])).toBe(baseTip);
+  });
+
+  it("dry-runs a rebase without applying it", async () => {
+    await writeFile(join(workspacePath, "feature.txt"), "hello\n", "utf8");
+    const app = createApp({});
+    await post(app, "git/commit", { message: "Add feature" });
+    const baseTip = runGitRead(workspacePath, ["rev-parse", "main"]);
+
+    const response = await post(app, "merge", {
+      strategy: "rebase",
+      dryRun: true,
+    });
+    expect(response.status).toBe(HTTP_OK);
+    const payload = (await response.json()) as {
+      status: string;
+      applied: boolean;
+      strategy: string;
+    };
+
+    expect(payload).toMatchObject({
+      status: "clean",
+      applied: false,
+      strategy: "rebase",
+    });
+    expect(runGitRead(workspacePath, ["rev-parse", "main"])).toBe(baseTip);
+  });
+
+  it("rebases the cell onto its start point and moves the base", async () => {
+    runGit(workspacePath, ["checkout", "main"]);
+    await writeFile(join(workspacePath, "upstream.txt"), "main\n", "utf8");
+    runGit(workspacePath, ["add", "."]);
+    runGit(workspacePath, ["commit", "-m", "Upstream change"]);
+    const upstreamTip = runGitRead(workspacePath, ["rev-parse", "HEAD"]);
+    runGit(workspacePath, ["checkout", BRANCH_NAME]);
+    await writeFile(join(workspacePath, "feature.txt"), "hello\n", "utf8");
+    const app = createApp({});
+    await post(app, "git/commit", { message: "Add feature" });
+
+    const response = await post(app, "sync", {});
+    expect(response.status).toBe(HTTP_OK);
+    const payload = (await response.json()) as {
+      status: string;
+      upstream: string;
+      baseCommit: string;
+      headCommit: string;
+    };
+
+    expect(payload).toMatchObject({
+      status: "synced",
+      upstream: "main",
+      baseCommit: upstreamTip,
+    });
+    expect(runGitRead(workspacePath, ["rev-parse", "HEAD~1"])).toBe(
+      upstreamTip
+    );
+    const [cell] = await testDb
+      .select()
+      .from(cells)
+      .where(eq(cells.id, cellId));
+    expect(cell?.baseCommit).toBe(upstreamTip);
+  });
+
+  it("returns sync conflicts for the agent to resolve", async () => {
+    const originalBase = runGitRead(workspacePath, ["rev-parse", "HEAD"]);
+    await writeFile(join(workspacePath, "README.md"), "# cell\n", "utf8");
+    runGit(workspacePath, ["commit", "-am", "Cell readme"]);
+    runGit(workspacePath, ["checkout", "main"]);
+    await writeFile(join(workspacePath, "README.md"), "# main\n", "utf8");
+    runGit(workspacePath, ["commit", "-am", "Main readme"]);
+    const upstreamTip = runGitRead(workspacePath, ["rev-parse", "HEAD"]);
+    runGit(workspacePath, ["checkout", BRANCH_NAME]);
+    const app = createApp({});
+
+    const response = await post(app, "sync", {});
+    expect(response.status).toBe(HTTP_OK);
+    const payload = (await response.json()) as {
+      status: string;
+      conflicts: Array<{ path: string }>;
+      message: string | null;
+    };
+
+    expect(payload.status).toBe("conflicted");
+    expect(payload.conflicts.map((file) => file.path)).toEqual(["README.md"]);
+    expect(payload.message).toContain("git rebase --continue");
+    const [conflicted] = await testDb
+      .select()
+      .from(cells)
+      .where(eq(cells.id, cellId));
+    expect(conflicted?.baseCommit).toBe(originalBase);
+
+    await writeFile(join(workspacePath, "README.md"), "# both\n", "utf8");
+    runGit(workspacePath, ["add", "README.md"]);
+    runGit(workspacePath, ["-c", "core.editor=true", "rebase", "--continue"]);
+
+    const retry = await post(app, "sync", {});
+    expect(retry.status).toBe(HTTP_OK);
+    const retryPayload = (await retry.json()) as { status: string };
+    expect(retryPayload.status).toBe("up_to_date");
+    const [synced] = await testDb
+      .select()
+      .from(cells)
+      .where(eq(cells.id, cellId));
+    expect(synced?.baseCommit).toBe(upstreamTip);
+  });
+
+  it("sends conflicts from a UI sync to the cell's agent", async () => {
+    await testDb
+      .update(cells)
+      .set({ opencodeSessionId: "session-sync" })
+      .where(eq(cells.id, cellId));
+    await writeFile(join(workspacePath, "README.md"), "# cell\n", "utf8");
+    runGit(workspacePath, ["commit", "-am", "Cell readme"]);
+    runGit(workspacePath, ["checkout", "main"]);
+    await writeFile(join(workspacePath, "README.md"), "# main\n", "utf8");
+    runGit(workspacePath, ["commit", "-am", "Main readme"]);
+    runGit(workspacePath, ["checkout", BRANCH_NAME]);
+    const messages: Array<{ sessionId: string; content: string }> = [];
+    const app = createApp({}, undefined, {
+      sendAgentMessage: (sessionId, content) => {
+        messages.push({ sessionId, content });
+        return Promise.resolve();
+      },
+    });
+
+    const response = await post(app, "sync", {});
+    expect(response.status).toBe(HTTP_OK);
+    const payload = (await response.json()) as { message: string | null };
+
+    expect(messages).toEqual([
+      { sessionId: "session-sync", content: payload.message },
+    ]);
+    expect(messages[0]?.content).toContain("- README.md");
+
+    runGit(workspacePath, ["rebase", "--abort"]);
+    const toolResponse = await post(
+      app,
+      "sync",
+      {},
+      { "x-hive-tool": "hive_sync_with_base" }
+    );
+    expect(toolResponse.status).toBe(HTTP_OK);
+    expect(messages).toHaveLength(1);
+  });
+
+  function createApp(
+    git: GitConfig,
+    env: Record<string, string | undefined> = { GITHUB_TOKEN: "test-token" },
+    dependencies: CellGitRouteDependencies = {}
+  ) {
+    const resolveWorkspaceContext: ResolveWorkspaceContext = () =>
+      Promise.resolve({
+        workspace: {
+          id: "workspace-git",
+          label: "Git Workspace",
+          path: workspacePath,
+          addedAt: new Date().toISOString(),
+        },
+        loadConfig: () =>
+          Promise.resolve({ promptSources: [], templates: {}, git }),
+        createWorktreeManager: () =>
+          Promise.reject(new Error("Not implemented in cell git tests")),
+        createWorktree: () =>
+          Promise.reject(new Error("Not implemented in cell git tests")),
+        removeWorktree: () => Promise.resolve(),
+      } satisfies WorkspaceRuntimeContext);
+
+    return new Elysia().use(
+      createCellGitRoutes({
+        db: testDb,
+        resolveWorkspaceContext,
+        env,
+        ...dependencies,
+      })
+    );
+  }
+
+  function post(
+    app: ReturnType<typeof createApp>,
+    path: string,
+    body: Record<string, unknown>,
+    headers: Record<string, string> = {}
+  ) {
+    return app.handle(
+      new Request(`http://localhost/api/cells/${cellId}/${path}`, {
+        method: "POST",
+        headers: { ...JSON_HEADERS, ...headers },
+        body: JSON.stringify(body),
+      })
+    );
+  }
+
+  function startForgeServer(responseBody: Record<string, unknown>) {
+    const server = createServer((request, response) => {
+      let raw = "";
+      request.on("data", (chunk) => {
+        raw += chunk;
+      });
+      request.on("end", () => {
+        forgeRequests.push({
+          method: request.method ?? "",
+          url: request.url ?? "",
+          authorization: request.headers.authorization,
+          body: JSON.parse(raw || "{}"),
+        });
+        response.statusCode = 201;
+        response.setHeader("content-type", "application/json");
+        response.end(JSON.stringify(responseBody));
+      });
+    });
+    forgeServer = server;
+
+    return new Promise<string>((resolveUrl, rejectUrl) => {
+      server.once("error", rejectUrl);
+      server.listen(0, "127.0.0.1", () => {
+        const address = server.address();
+        const port = address && typeof address === "object" ? address.port : 0;
+        resolveUrl(`http://127.0.0.1:${port}`);
+      });
+    });
+  }
+});
+
+function runGit(cwd: string, args: string[]) {
+  runGitRead(cwd, args);
+}
+
+function runGitRead(cwd: string, args: string[]): string {
+  const child = Bun.spawnSync({
+    cmd: ["git", ...args],
+    cwd,
+    stdout: "pipe",
+    stderr: "pipe",
+  });
+
+  if (child.exitCode !== 0) {
+    const stderr = child.stderr.toString().trim();
+    throw new Error(
+      `git ${args.join(" ")} failed with code ${child.exitCode}${stderr ? `: ${stderr}` : ""}`
+    );
+  }
+
+  return child.stdout.toString().trim();
+}
@@ -0,0 +1,291 @@
+import { randomUUID } from "node:crypto";
+import { mkdir, mkdtemp, readFile, rm, writeFile } from "node:fs/promises";
+import { tmpdir } from "node:os";
+import { join } from "node:path";
+import { eq } from "drizzle-orm";
+import { Elysia } from "elysia";
+import { afterEach, beforeAll, beforeEach, describe, expect, it } from "vitest";
+import { createCellSnapshotRoutes } from "../../routes/cell-snapshots";
+import { cellActivityEvents } from "../../schema/activity-events";
+import { cellSnapshots } from "../../schema/cell-snapshots";
+import { cells } from "../../schema/cells";
+import { captureAgentTurnSnapshot } from "../../services/cell-snapshots";
+import type {
+  ResolveWorkspaceContext,
+  WorkspaceRuntimeContext,
+} from "../../workspaces/context";
+import { setupTestDb, testDb } from "../test-db";
+
+const HTTP_OK = 200;
+const HTTP_CREATED = 201;
+const HTTP_NOT_FOUND = 404;
+const BRANCH_NAME = "hive/cell-snapshot-test";
+const JSON_HEADERS = { "content-type": "application/json" };
+
+type SnapshotPayload = {
+  id: string;
+  name: string;
+  gitRef: string;
+  headCommit: string;
+  hasArchive: boolean;
+  agentMessageId: string | null;
+};
+
+describe("cell snapshot routes", () => {
+  let tempRoot = "";
+  let workspacePath = "";
+  let cellId = "";
+
+  beforeAll(async () => {
+    await setupTestDb();
+  });
+
+  beforeEach(async () => {
+    await testDb.delete(cellSnapshots);
+    await testDb.delete(cellActivityEvents);
+    await testDb.delete(cells);
+
+    tempRoot = await mkdtemp(join(tmpdir(), "hive-cell-snapshots-"));
+    workspacePath = join(tempRoot, "workspace");
+    await mkdir(workspacePath, { recursive: true });
+
+    runGit(workspacePath, ["init", "--initial-branch", "main"]);
+    runGit(workspacePath, ["config", "user.email", "test@example.com"]);
+    runGit(workspacePath, ["config", "user.name", "Test User"]);
+    await writeFile(join(workspacePath, ".gitignore"), ".env\n", "utf8");
+    await writeFile(join(workspacePath, "README.md"), "# test\n", "utf8");
+    runGit(workspacePath, ["add", "."]);
+    runGit(workspacePath, ["commit", "-m", "initial"]);
+    runGit(workspacePath, ["checkout", "-b", BRANCH_NAME]);
+
+    cellId = randomUUID();
+    await testDb.insert(cells).values({
+      id: cellId,
+      name: "Snapshot cell",
+      templateId: "basic",
+      workspacePath,
+      workspaceId: "workspace-snapshots",
+      workspaceRootPath: workspacePath,
+      createdAt: new Date(),
+      status: "ready",
+      branchName: BRANCH_NAME,
+      baseCommit: runGitRead(workspacePath, ["rev-parse", "HEAD"]),
+    });
+  });
+
+  afterEach(async () => {
+    await rm(tempRoot, { recursive: true, force: true });
+  });
+
+  it("restores the worktree, untracked files and included files", async () => {
+    await writeFile(join(workspacePath, "README.md"), "# draft\n", "utf8");
+    await writeFile(join(workspacePath, "notes.txt"), "keep me\n", "utf8");
+    await writeFile(join(workspacePath, ".env"), "TOKEN=one\n", "utf8");
+    const headBefore = runGitRead(workspacePath, ["rev-parse", "HEAD"]);
+    const app = createApp();
+
+    const created = await request(app, "POST", "snapshots", {
+      name: "Before refactor",
+      agentMessageId: "msg-1",
+    });
+    expect(created.status).toBe(HTTP_CREATED);
+    const snapshot = (await created.json()) as SnapshotPayload;
+    expect(snapshot).toMatchObject({
+      name: "Before refactor",
+      headCommit: headBefore,
+      hasArchive: true,
+      agentMessageId: "msg-1",
+    });
+
+    await writeFile(join(workspacePath, "README.md"), "# broken\n", "utf8");
+    await rm(join(workspacePath, "notes.txt"));
+    await writeFile(join(workspacePath, ".env"), "TOKEN=two\n", "utf8");
+    await writeFile(join(workspacePath, "junk.txt"), "junk\n", "utf8");
+    runGit(workspacePath, ["add", "."]);
+    runGit(workspacePath, ["commit", "-m", "Agent mess"]);
+
+    const restored = await request(
+      app,
+      "POST",
+      `snapshots/${snapshot.id}/restore`,
+      {}
+    );
+    expect(restored.status).toBe(HTTP_OK);
+    const payload = (await restored.json()) as {
+      restored: SnapshotPayload;
+      backup: SnapshotPayload | null;
+    };
+    expect(payload.restored.id).toBe(snapshot.id);
+    expect(payload.backup).not.toBeNull();
+
+    expect(runGitRead(workspacePath, ["rev-parse", "HEAD"])).toBe(headBefore);
+    expect(await readText("README.md")).toBe("# draft\n");
+    expect(await readText("notes.txt")).toBe("keep me\n");
+    expect(await readText(".env")).toBe("TOKEN=one\n");
+    expect(await Bun.file(join(workspacePath, "junk.txt")).exists()).toBe(
+      false
+    );
+
+    const events = await testDb
+      .select()
+      .from(cellActivityEvents)
+      .where(eq(cellActivityEvents.cellId, cellId));
+    expect(events.map((event) => event.type).sort()).toEqual([
+      "cell.restore",
+      "cell.snapshot",
+    ]);
+  });
+
+  it("lists snapshots by agent message and deletes their refs", async () => {
+    const app = createApp();
+    const first = (await (
+      await request(app, "POST", "snapshots", { agentMessageId: "msg-1" })
+    ).json()) as SnapshotPayload;
+    await request(app, "POST", "snapshots", { agentMessageId: "msg-2" });
+
+    const listed = await request(app, "GET", "snapshots?agentMessageId=msg-1");
+    expect(listed.status).toBe(HTTP_OK);
+    const { snapshots } = (await listed.json()) as {
+      snapshots: SnapshotPayload[];
+    };
+    expect(snapshots.map((snapshot) => snapshot.id)).toEqual([first.id]);
+
+    const deleted = await request(app, "DELETE", `snapshots/${first.id}`);
+    expect(deleted.status).toBe(HTTP_OK);
+    expect(
+      runGitRead(workspacePath, ["for-each-ref", "--format=%(refname)"])
+    ).not.toContain(first.gitRef);
+
+    const missing = await request(
+      app,
+      "POST",
+      `snapshots/${first.id}/restore`,
+      {}
+    );
+    expect(missing.status).toBe(HTTP_NOT_FOUND);
+  });
+
+  it("keeps only the newest agent-turn snapshots", async () => {
+    const app = createApp();
+    const manual = (await (
+      await request(app, "POST", "snapshots", { name: "Checkpoint" })
+    ).json()) as SnapshotPayload;
+    const cell = await testDb.query.cells.findFirst({
+      where: eq(cells.id, cellId),
+    });
+    if (!cell) {
+      throw new Error("Expected the snapshot cell");
+    }
+    const captureTurn = (agentMessageId: string) =>
+      captureAgentTurnSnapshot({
+        database: testDb,
+        loadHiveConfig: () =>
+          Promise.resolve({ promptSources: [], templates: {} }),
+        cell,
+        agentMessageId,
+        retain: 2,
+      });
+
+    const turns = [];
+    for (const [index, messageId] of ["msg-1", "msg-2"].entries()) {
+      const turn = await captureTurn(messageId);
+      if (!turn) {
+        throw new Error("Expected an agent-turn snapshot");
+      }
+      turns.push(turn);
+      await testDb
+        .update(cellSnapshots)
+        .set({ createdAt: new Date(Date.UTC(2026, 0, index + 1)) })
+        .where(eq(cellSnapshots.id, turn.id));
+    }
+    const latest = await captureTurn("msg-3");
+
+    const remaining = await testDb
+      .select({ id: cellSnapshots.id })
+      .from(cellSnapshots)
+      .where(eq(cellSnapshots.cellId, cellId));
+    expect(remaining.map((snapshot) => snapshot.id).sort()).toEqual(
+      [manual.id, turns[1]?.id, latest?.id].sort()
+    );
+    expect(
+      runGitRead(workspacePath, ["for-each-ref", "--format=%(refname)"])
+    ).not.toContain(turns[0]?.gitRef);
+  });
+
+  function createApp() {
+    const resolveWorkspaceContext: ResolveWorkspaceContext = () =>
+      Promise.resolve({
+        workspace: {
+          id: "workspace-snapshots",
+          label: "Snapshot Workspace",
+          path: workspacePath,
+          addedAt: new Date().toISOString(),
+        },
+        loadConfig: () =>
+          Promise.resolve({
+            promptSources: [],
+            templates: {
+              basic: {
+                id: "basic",
+                label: "Basic",
+                type: "manual",
+                includePatterns: [".env"],
+              },
+            },
+          }),
+        createWorktreeManager: () =>
+          Promise.reject(new Error("Not implemented in snapshot tests")),
+        createWorktree: () =>
+          Promise.reject(new Error("Not implemented in snapshot tests")),
+        removeWorktree: () => Promise.resolve(),
+      } satisfies WorkspaceRuntimeContext);
+
+    return new Elysia().use(
+      createCellSnapshotRoutes({
+        db: testDb,
+        resolveWorkspaceContext,
+        archiveRoot: (id) => join(tempRoot, "archives", id),
+      })
+    );
+  }
+
+  function request(
+    app: ReturnType<typeof createApp>,
+    method: string,
+    path: string,
+    body?: Record<string, unknown>
+  ) {
+    return app.handle(
+      new Request(`http://localhost/api/cells/${cellId}/${path}`, {
+        method,
+        ...(body ? { headers: JSON_HEADERS, body: JSON.stringify(body) } : {}),
+      })
+    );
+  }
+
+  function readText(relativePath: string) {
+    return readFile(join(workspacePath, relativePath), "utf8");
+  }
+});
+
+function runGit(cwd: string, args: string[]) {
+  runGitRead(cwd, args);
+}
+
+function runGitRead(cwd: string, args: string[]): string {
+  const child = Bun.spawnSync({
+    cmd: ["git", ...args],
+    cwd,
+    stdout: "pipe",
+    stderr: "pipe",
+  });
+
+  if (child.exitCode !== 0) {
+    const stderr = child.stderr.toString().trim();
+    throw new Error(
+      `git ${args.join(" ")} failed with code ${child.exitCode}${stderr ? `: ${stderr}` : ""}`
+    );
+  }
+
+  return child.stdout.toString().trim();
+}
@@ -0,0 +1,500 @@
+import { randomUUID } from "node:crypto";
+import {
+  mkdir,
+  mkdtemp,
+  readdir,
+  readFile,
+  rm,
+  writeFile,
+} from "node:fs/promises";
+import { tmpdir } from "node:os";
+import { join } from "node:path";
+import { eq } from "drizzle-orm";
+import { Elysia } from "elysia";
+import {
+  afterEach,
+  beforeAll,
+  beforeEach,
+  describe,
+  expect,
+  it,
+  vi,
+} from "vitest";
+import type { AgentMessageRecord } from "../../agents/types";
+import { createCellTransferRoutes } from "../../routes/cell-transfer";
+import { cellActivityEvents } from "../../schema/activity-events";
+import { cells } from "../../schema/cells";
+import { cellServices } from "../../schema/services";
+import { cellTimingEvents } from "../../schema/timing-events";
+import type {
+  ResolveWorkspaceContext,
+  WorkspaceRuntimeContext,
+} from "../../workspaces/context";
+import { createWorktreeManager } from "../../worktree/manager";
+import { setupTestDb, testDb } from "../test-db";
+
+const HTTP_OK = 200;
+const HTTP_CREATED = 201;
+const HTTP_BAD_REQUEST = 400;
+const SOURCE_WORKSPACE_ID = "workspace-source";
+const TARGET_WORKSPACE_ID = "workspace-target";
+const BRANCH_NAME = "hive/cell-transfer-test";
+const SERVICE_PORT = 4100;
+
+type EditableManifest = {
+  git: { ref: string };
+  services: Record<string, unknown>[];
+};
+
+type ImportPayload = {
+  id: string;
+  name: string;
+  workspaceId: string;
+  workspacePath: string;
+  sourceCellId: string;
+  services: number;
+  activityEvents: number;
+  timingEvents: number;
+  transcriptMessages: number;
+  transcriptRestored: boolean;
+};
+
+const TEMPLATES = {
+  basic: {
+    id: "basic",
+    label: "Basic",
+    type: "manual" as const,
+    includePatterns: [".env"],
+  },
+};
+
+describe("cell transfer routes", () => {
+  const originalHiveHome = process.env.HIVE_HOME;
+  let tempRoot = "";
+  let sourcePath = "";
+  let targetPath = "";
+  let cellId = "";
+
+  beforeAll(async () => {
+    await setupTestDb();
+  });
+
+  beforeEach(async () => {
+    await testDb.delete(cellActivityEvents);
+    await testDb.delete(cellTimingEvents);
+    await testDb.delete(cellServices);
+    await testDb.delete(cells);
+
+    tempRoot = await mkdtemp(join(tmpdir(), "hive-cell-transfer-"));
+    process.env.HIVE_HOME = join(tempRoot, "hive-home");
+    sourcePath = join(tempRoot, "source");
+    targetPath = join(tempRoot, "target");
+    await initRepository(sourcePath, "# source\n");
+    await initRepository(targetPath, "# target\n");
+
+    runGit(sourcePath, ["checkout", "-b", BRANCH_NAME]);
+    await writeFile(join(sourcePath, "feature.txt"), "feature\n", "utf8");
+    runGit(sourcePath, ["add", "feature.txt"]);
+    runGit(sourcePath, ["commit", "-m", "Add feature"]);
+    await writeFile(join(sourcePath, "README.md"), "# draft\n", "utf8");
+    await writeFile(join(sourcePath, "notes.txt"), "keep me\n", "utf8");
+    await writeFile(join(sourcePath, ".env"), "TOKEN=one\n", "utf8");
+
+    cellId = randomUUID();
+    const now = new Date();
+    await testDb.insert(cells).values({
+      id: cellId,
+      name: "Login fix",
+      description: "Fix the login redirect",
+      templateId: "basic",
+      workspacePath: sourcePath,
+      workspaceId: SOURCE_WORKSPACE_ID,
+      workspaceRootPath: sourcePath,
+      opencodeSessionId: "session-source",
+      createdAt: now,
+      status: "ready",
+      branchName: BRANCH_NAME,
+      baseCommit: runGitRead(sourcePath, ["rev-parse", "main"]),
+    });
+    const serviceId = randomUUID();
+    await testDb.insert(cellServices).values({
+      id: serviceId,
+      cellId,
+      name: "web",
+      type: "process",
+      command: "bun run dev",
+      cwd: join(sourcePath, "web"),
+      env: { APP_DIR: join(sourcePath, "web"), MODE: "dev" },
+      status: "running",
+      port: SERVICE_PORT,
+      pid: 1234,
+      readyTimeoutMs: null,
+      definition: { type: "process", run: "bun run dev", cwd: "web", env: {} },
+      lastKnownError: null,
+      createdAt: now,
+      updatedAt: now,
+    });
+    await testDb.insert(cellActivityEvents).values({
+      id: randomUUID(),
+      cellId,
+      serviceId,
+      type: "service.start",
+      source: null,
+      toolName: null,
+      userId: null,
+      metadata: {},
+      createdAt: now,
+    });
+    await testDb.insert(cellTimingEvents).values({
+      id: randomUUID(),
+      cellId,
+      cellName: "Login fix",
+      workspaceId: SOURCE_WORKSPACE_ID,
+      templateId: "basic",
+      workflow: "create",
+      runId: randomUUID(),
+      step: "create_worktree",
+      status: "ok",
+      durationMs: 42,
+      metadata: {},
+      createdAt: now,
+    });
+  });
+
+  afterEach(async () => {
+    process.env.HIVE_HOME = originalHiveHome;
+    await rm(tempRoot, { recursive: true, force: true });
+  });
+
+  it("moves a cell into another workspace with rewritten paths", async () => {
+    const appendAgentContext = vi.fn(() => Promise.resolve());
+    const app = createApp({ appendAgentContext });
+
+    const exported = await app.handle(
+      new Request(`http://localhost/api/cells/${cellId}/export`)
+    );
+    expect(exported.status).toBe(HTTP_OK);
+    expect(exported.headers.get("content-type")).toBe("application/x-tar");
+    const archive = new Uint8Array(await exported.arrayBuffer());
+
+    const imported = await importArchive(app, archive, "Login fix (copy)");
+    expect(imported.status).toBe(HTTP_CREATED);
+    const payload = (await imported.json()) as ImportPayload;
+    expect(payload).toMatchObject({
+      name: "Login fix (copy)",
+      workspaceId: TARGET_WORKSPACE_ID,
+      sourceCellId: cellId,
+      services: 1,
+      activityEvents: 1,
+      timingEvents: 1,
+      transcriptMessages: 2,
+      transcriptRestored: true,
+    });
+    expect(payload.id).not.toBe(cellId);
+
+    const cell = await testDb.query.cells.findFirst({
+      where: eq(cells.id, payload.id),
+    });
+    expect(cell).toMatchObject({
+      description: "Fix the login redirect",
+      workspaceRootPath: targetPath,
+      workspacePath: payload.workspacePath,
+      status: "ready",
+      opencodeSessionId: null,
+    });
+    expect(payload.workspacePath).not.toBe(sourcePath);
+
+    const readImported = (file: string) =>
+      readFile(join(payload.workspacePath, file), "utf8");
+    expect(await readImported("feature.txt")).toBe("feature\n");
+    expect(await readImported("README.md")).toBe("# draft\n");
+    expect(await readImported("notes.txt")).toBe("keep me\n");
+    expect(await readImported(".env")).toBe("TOKEN=one\n");
+
+    const [service] = await testDb
+      .select()
+      .from(cellServices)
+      .where(eq(cellServices.cellId, payload.id));
+    expect(service).toMatchObject({
+      name: "web",
+      status: "stopped",
+      port: null,
+      pid: null,
+      cwd: join(payload.workspacePath, "web"),
+      env: { APP_DIR: join(payload.workspacePath, "web"), MODE: "dev" },
+    });
+
+    const events = await testDb
+      .select()
+      .from(cellActivityEvents)
+      .where(eq(cellActivityEvents.cellId, payload.id));
+    expect(events.map((event) => event.type).sort()).toEqual([
+      "cell.import",
+      "service.start",
+    ]);
+    expect(
+      events.find((event) => event.type === "service.start")?.serviceId
+    ).toBe(service?.id);
+
+    const [timing] = await testDb
+      .select()
+      .from(cellTimingEvents)
+      .where(eq(cellTimingEvents.cellId, payload.id));
+    expect(timing).toMatchObject({
+      cellName: "Login fix (copy)",
+      workspaceId: TARGET_WORKSPACE_ID,
+      step: "create_worktree",
+    });
+
+    const sourceEvents = await testDb
+      .select()
+      .from(cellActivityEvents)
+      .where(eq(cellActivityEvents.cellId, cellId));
+    expect(sourceEvents.map((event) => event.type)).toContain("cell.export");
+
+    expect(appendAgentContext).toHaveBeenCalledWith(
+      `session-${payload.id}`,
+      expect.stringContaining("Please fix the login redirect")
+    );
+    expect(runGitRead(targetPath, ["for-each-ref", "refs/hive"])).toBe("");
+  });
+
+  it("rejects archives that are not cell exports", async () => {
+    const app = createApp({ appendAgentContext: () => Promise.resolve() });
+
+    const response = await importArchive(
+      app,
+      new TextEncoder().encode("not a tar archive")
+    );
+
+    expect(response.status).toBe(HTTP_BAD_REQUEST);
+    expect(await testDb.select().from(cells)).toHaveLength(1);
+  });
+
+  it("rejects manifests with unexpected refs or rows", async () => {
+    const app = createApp({ appendAgentContext: () => Promise.resolve() });
+    const exported = await app.handle(
+      new Request(`http://localhost/api/cells/${cellId}/export`)
+    );
+    const archive = new Uint8Array(await exported.arrayBuffer());
+
+    const edits: [string, (manifest: EditableManifest) => void][] = [
+      [
+        "git.ref",
+        (manifest) => {
+          manifest.git.ref = "refs/heads/main";
+        },
+      ],
+      [
+        "git.ref",
+        (manifest) => {
+          manifest.git.ref = "refs/hive/exports/bad..ref";
+        },
+      ],
+      [
+        "services.0.status",
+        (manifest) => {
+          manifest.services[0] = { ...manifest.services[0], status: "lost" };
+        },
+      ],
+    ];
+
+    for (const [path, edit] of edits) {
+      const response = await importArchive(
+        app,
+        await rewriteManifest(archive, edit)
+      );
+      expect(response.status).toBe(HTTP_BAD_REQUEST);
+      expect(await response.text()).toContain(
+        `Invalid cell export manifest: ${path}`
+      );
+    }
+    expect(await testDb.select().from(cells)).toHaveLength(1);
+    expect(runGitRead(targetPath, ["for-each-ref", "refs/hive"])).toBe("");
+  });
+
+  it("removes the imported rows when recording them fails", async () => {
+    const app = createApp({ appendAgentContext: () => Promise.resolve() });
+    const exported = await app.handle(
+      new Request(`http://localhost/api/cells/${cellId}/export`)
+    );
+    const archive = new Uint8Array(await exported.arrayBuffer());
+
+    const insert = testDb.insert.bind(testDb);
+    const insertSpy = vi
+      .spyOn(testDb, "insert")
+      .mockImplementation(((table: Parameters<typeof insert>[0]) => {
+        if (table === cellTimingEvents) {
+          throw new Error("disk full");
+        }
+        return insert(table);
+      }) as typeof testDb.insert);
+    try {
+      const response = await importArchive(app, archive);
+      expect(response.ok).toBe(false);
+    } finally {
+      insertSpy.mockRestore();
+    }
+
+    const remaining = await testDb.select({ id: cells.id }).from(cells);
+    expect(remaining).toEqual([{ id: cellId }]);
+    const services = await testDb.select().from(cellServices);
+    expect(services.every((service) => service.cellId === cellId)).toBe(true);
+    expect(runGitRead(targetPath, ["for-each-ref", "refs/hive"])).toBe("");
+  });
+
+  async function rewriteManifest(
+    archive: Uint8Array,
+    edit: (manifest: EditableManifest) => void
+  ): Promise<Uint8Array> {
+    const directory = await mkdtemp(join(tempRoot, "archive-"));
+    const archivePath = join(directory, "cell.tar");
+    await writeFile(archivePath, archive);
+    runTar(["-xf", archivePath, "-C", directory]);
+
+    const manifestPath = join(directory, "manifest.json");
+    const manifest = JSON.parse(
+      await readFile(manifestPath, "utf8")
+    ) as EditableManifest;
+    edit(manifest);
+    await writeFile(manifestPath, JSON.stringify(manifest), "utf8");
+
+    const entries = (await readdir(directory)).filter(
+      (entry) => entry !== "cell.tar"
+    );
+    runTar(["-cf", archivePath, "-C", directory, ...entries]);
+    return new Uint8Array(await readFile(archivePath));
+  }
+
+  function createApp(options: {
+    appendAgentContext: (sessionId: string, content: string) => Promise<void>;
+  }) {
+    const resolveWorkspaceContext: ResolveWorkspaceContext = (workspaceId) =>
+      Promise.resolve(
+        createWorkspaceContext(
+          workspaceId === TARGET_WORKSPACE_ID
+            ? { id: TARGET_WORKSPACE_ID, path: targetPath }
+            : { id: SOURCE_WORKSPACE_ID, path: sourcePath }
+        )
+      );
+
+    return new Elysia().use(
+      createCellTransferRoutes({
+        db: testDb,
+        resolveWorkspaceContext,
+        fetchAgentMessages: () => Promise.resolve(buildTranscript()),
+        ensureAgentSession: (id) =>
+          Promise.resolve({ id: `session-${id}` } as never),
+        appendAgentContext: options.appendAgentContext,
+      })
+    );
+  }
+
+  function importArchive(
+    app: ReturnType<typeof createApp>,
+    archive: Uint8Array,
+    name?: string
+  ) {
+    const query = new URLSearchParams({ workspaceId: TARGET_WORKSPACE_ID });
+    if (name) {
+      query.set("name", name);
+    }
+    return app.handle(
+      new Request(`http://localhost/api/cells/import?${query}`, {
+        method: "POST",
+        headers: { "content-type": "application/octet-stream" },
+        body: archive,
+      })
+    );
+  }
+});
+
+function createWorkspaceContext(workspace: {
+  id: string;
+  path: string;
+}): WorkspaceRuntimeContext {
+  const manager = createWorktreeManager(workspace.path, {
+    templates: TEMPLATES,
+  });
+  return {
+    workspace: {
+      id: workspace.id,
+      label: workspace.id,
+      path: workspace.path,
+      addedAt: new Date().toISOString(),
+    },
+    loadConfig: () =>
+      Promise.resolve({ promptSources: [], templates: TEMPLATES }),
+    createWorktreeManager: () => Promise.resolve(manager),
+    createWorktree: (id, options) => manager.createWorktree(id, options),
+    removeWorktree: (id) => manager.removeWorktree(id),
+  };
+}
+
+function buildTranscript(): AgentMessageRecord[] {
+  const createdAt = new Date().toISOString();
+  return [
+    {
+      id: "msg-1",
+      sessionId: "session-source",
+      role: "user",
+      content: "Please fix the login redirect",
+      state: "completed",
+      createdAt,
+      parts: [],
+    },
+    {
+      id: "msg-2",
+      sessionId: "session-source",
+      role: "assistant",
+      content: "Updated the redirect handler.",
+      state: "completed",
+      createdAt,
+      parts: [],
+    },
+  ];
+}
+
+async function initRepository(path: string, readme: string) {
+  await mkdir(path, { recursive: true });
+  runGit(path, ["init", "--initial-branch", "main"]);
+  runGit(path, ["config", "user.email", "test@example.com"]);
+  runGit(path, ["config", "user.name", "Test User"]);
+  await writeFile(join(path, ".gitignore"), ".env\n", "utf8");
+  await writeFile(join(path, "README.md"), readme, "utf8");
+  runGit(path, ["add", "."]);
+  runGit(path, ["commit", "-m", "initial"]);
+}
+
+function runTar(args: string[]) {
+  const child = Bun.spawnSync({
+    cmd: ["tar", ...args],
+    stdout: "pipe",
+    stderr: "pipe",
+  });
+  if (child.exitCode !== 0) {
+    throw new Error(`tar ${args.join(" ")} failed: ${child.stderr.toString()}`);
+  }
+}
+
+function runGit(cwd: string, args: string[]) {
+  runGitRead(cwd, args);
+}
+
+function runGitRead(cwd: string, args: string[]): string {
+  const child = Bun.spawnSync({
+    cmd: ["git", ...args],
+    cwd,
+    stdout: "pipe",
+    stderr: "pipe",
+  });
+
+  if (child.exitCode !== 0) {
+    const stderr = child.stderr.toString().trim();
+    throw new Error(
+      `git ${args.join(" ")} failed with code ${child.exitCode}${stderr ? `: ${stderr}` : ""}`
+    );
+  }
+
+  return child.stdout.toString().trim();
+}
@@ -0,0 +1,180 @@
+import { eq } from "drizzle-orm";
+import { Elysia } from "elysia";
+import { beforeAll, beforeEach, describe, expect, it, vi } from "vitest";
+import { createCellsRoutes } from "../../routes/cells";
+import { cells } from "../../schema/cells";
+import { setupTestDb, testDb } from "../test-db";
+
+const TEST_WORKSPACE_ID = "test-workspace";
+const TEMPLATE_ID = "basic";
+const HTTP_ACCEPTED = 202;
+const HTTP_BAD_REQUEST = 400;
+const HTTP_NOT_FOUND = 404;
+const BATCHES_URL = "http://localhost/api/cells/batches";
+
+function createDependencies(sendAgentMessage = vi.fn()): any {
+  const workspaceRecord = {
+    id: TEST_WORKSPACE_ID,
+    label: "Test Workspace",
+    path: "/tmp/test-workspace-root",
+    addedAt: new Date().toISOString(),
+  };
+  const createWorktree = async (cellId: string) => ({
+    path: `/tmp/mock-worktrees/${cellId}`,
+    branch: `cell-${cellId}`,
+    baseCommit: "abc123",
+  });
+
+  return {
+    db: testDb,
+    resolveWorkspaceContext: (async () => ({
+      workspace: workspaceRecord,
+      loadConfig: async () => ({
+        opencode: { defaultProvider: "opencode", defaultModel: "mock" },
+        promptSources: [],
+        templates: {
+          [TEMPLATE_ID]: { id: TEMPLATE_ID, label: "Basic", type: "manual" },
+        },
+        defaults: {},
+      }),
+      createWorktreeManager: async () => ({
+        createWorktree,
+        removeWorktree: async () => Promise.resolve(),
+      }),
+      createWorktree,
+      removeWorktree: async () => Promise.resolve(),
+    })) as any,
+    ensureAgentSession: async (cellId: string) => ({
+      id: `session-${cellId}`,
+      cellId,
+    }),
+    closeAgentSession: async () => Promise.resolve(),
+    ensureServicesForCell: async () => Promise.resolve(),
+    startServiceById: async () => Promise.resolve(),
+    startServicesForCell: async () => Promise.resolve(),
+    stopServiceById: async () => Promise.resolve(),
+    stopServicesForCell: async () => Promise.resolve(),
+    sendAgentMessage: async (sessionId: string, content: string) =>
+      sendAgentMessage(sessionId, content),
+    ensureTerminalSession: () => null,
+    readTerminalOutput: () => "",
+    subscribeToTerminal: () => () => 0,
+    writeTerminalInput: () => 0,
+    resizeTerminal: () => 0,
+    closeTerminalSession: () => 0,
+    getServiceTerminalSession: () => null,
+    readServiceTerminalOutput: () => "",
+    subscribeToServiceTerminal: () => () => 0,
+    writeServiceTerminalInput: () => 0,
+    resizeServiceTerminal: () => 0,
+    clearServiceTerminal: () => 0,
+    getSetupTerminalSession: () => null,
+    readSetupTerminalOutput: () => "",
+    subscribeToSetupTerminal: () => () => 0,
+    writeSetupTerminalInput: () => 0,
+    resizeSetupTerminal: () => 0,
+    clearSetupTerminal: () => 0,
+  };
+}
+
+const postBatch = (
+  app: { handle: (request: Request) => Promise<Response> },
+  body: Record<string, unknown>
+) =>
+  app.handle(
+    new Request(BATCHES_URL, {
+      method: "POST",
+      headers: { "content-type": "application/json" },
+      body: JSON.stringify({ workspaceId: TEST_WORKSPACE_ID, ...body }),
+    })
+  );
+
+async function readUntilCompleted(response: Response): Promise<string> {
+  const reader = response.body?.getReader();
+  if (!reader) {
+    throw new Error("Expected SSE reader");
+  }
+  const decoder = new TextDecoder();
+  let text = "";
+  while (!text.includes("event: completed")) {
+    const { value, done } = await reader.read();
+    if (done) {
+      break;
+    }
+    text += typeof value === "string" ? value : decoder.decode(value);
+  }
+  await reader.cancel();
+  return text;
+}
+
+describe("Cell batch routes", () => {
+  beforeAll(async () => {
+    await setupTestDb();
+  });
+
+  beforeEach(async () => {
+    await testDb.delete(cells);
+  });
+
+  it("creates every cell in the manifest and prompts each agent", async () => {
+    const sendAgentMessage = vi.fn().mockResolvedValue(undefined);
+    const app = new Elysia().use(
+      createCellsRoutes(createDependencies(sendAgentMessage))
+    );
+
+    const response = await postBatch(app, {
+      manifest: [
+        "concurrency: 1",
+        `templateId: ${TEMPLATE_ID}`,
+        "cells:",
+        "  - name: refactor-api",
+        "    initialPrompt: Move the API client to fetch",
+        "  - name: refactor-web",
+      ].join("\n"),
+    });
+    expect(response.status).toBe(HTTP_ACCEPTED);
+    const batch = (await response.json()) as { id: string; total: number };
+    expect(batch.total).toBe(2);
+
+    const events = await readUntilCompleted(
+      await app.handle(new Request(`${BATCHES_URL}/${batch.id}/events`))
+    );
+    expect(events).toContain("event: snapshot");
+    expect(events).toContain('"ready":2');
+
+    const created = await testDb
+      .select()
+      .from(cells)
+      .where(eq(cells.workspaceId, TEST_WORKSPACE_ID));
+    expect(created.map((cell) => cell.name).sort()).toEqual([
+      "refactor-api",
+      "refactor-web",
+    ]);
+    expect(created.every((cell) => cell.status === "ready")).toBe(true);
+
+    const apiCell = created.find((cell) => cell.name === "refactor-api");
+    expect(sendAgentMessage).toHaveBeenCalledWith(
+      `session-${apiCell?.id}`,
+      "Move the API client to fetch"
+    );
+  });
+
+  it("rejects manifests that reference unknown templates", async () => {
+    const app = new Elysia().use(createCellsRoutes(createDependencies()));
+
+    const response = await postBatch(app, {
+      manifest: [{ name: "one", templateId: "missing" }],
+    });
+
+    expect(response.status).toBe(HTTP_BAD_REQUEST);
+    expect(await response.json()).toEqual({
+      message: "Template not found for one: missing",
+    });
+  });
+
+  it("returns 404 for unknown batches", async () => {
+    const app = new Elysia().use(createCellsRoutes(createDependencies()));
+    const response = await app.handle(new Request(`${BATCHES_URL}/missing`));
+    expect(response.status).toBe(HTTP_NOT_FOUND);
+  });
+});
@@ -1,8 +1,14 @@
 // @ts-nocheck
+import { mkdtemp, readFile, rm, writeFile } from "node:fs/promises";
+import { tmpdir } from "node:os";
+import { join } from "node:path";
 import { eq } from "drizzle-orm";
 import { Elysia } from "elysia";
 import { beforeAll, beforeEach, describe, expect, it, vi } from "vitest";
 import type { AgentSessionRecord } from "../../agents/types";
+import { createApiAuthPlugin } from "../../auth/plugin";
+import { createApiToken } from "../../auth/tokens";
+import { createUser } from "../../auth/users";
 import type { HiveConfig } from "../../config/schema";
 import { createCellsRoutes, resumeSpawningCells } from "../../routes/cells";
 
@@ -21,6 +27,7 @@ const workspacePath = "/tmp/mock-worktree";
 const OK_STATUS = 200;
 const CREATED_STATUS = 201;
 const BAD_REQUEST_STATUS = 400;
+const NOT_FOUND_STATUS = 404;
 const CONFLICT_STATUS = 409;
 const WAIT_TIMEOUT_MS = 500;
 const WAIT_INTERVAL_MS = 10;
@@ -83,6 +90,14 @@ async function waitForTimingStep(cellId: string, step: string) {
   return found;
 }
 
+async function readInitialPromptSentAt(cellId: string) {
+  const [state] = await testDb
+    .select({ sentAt: cellProvisioningStates.initialPromptSentAt })
+    .from(cellProvisioningStates)
+    .where(eq(cellProvisioningStates.cellId, cellId));
+  return state?.sentAt ?? null;
+}
+
 function makeJsonPostRequest(url: string, body: Record<string, unknown>) {
   return new Request(url, {
     method: "POST",
@@ -167,7 +182,31 @@ const hiveConfig: HiveConfig = {
   defaults: {},
 };
 
-type SendAgentMessageFn = (sessionId: string, content: string) => Promise<void>;
+const parameterizedTemplateId = "seeded-template";
+
+const parameterizedHiveConfig: HiveConfig = {
+  ...hiveConfig,
+  templates: {
+    [parameterizedTemplateId]: {
+      id: parameterizedTemplateId,
+      label: "Seeded Template",
+      type: "manual",
+      parameters: [
+        { name: "dbSeed", type: "string", enum: ["demo", "full"] },
+        { name: "workers", type: "number", default: 2 },
+      ],
+      setup: ["bun run seed ${params.dbSeed} --workers ${params.workers}"],
+    },
+  },
+};
+
+const DELIVERY_OPTIONS = { onDelivered: expect.any(Function) };
+
+type SendAgentMessageFn = (
+  sessionId: string,
+  content: string,
+  options?: { onDelivered?: () => void }
+) => Promise<void>;
 type EnsureServicesForCellFn = (args: unknown) => Promise<void>;
 type CreateWorktreeFn = (
   cellId: string,
@@ -177,7 +216,8 @@ type CreateWorktreeFn = (
     startPoint?:
       | { mode: "head" }
       | { mode: "branch"; value: string }
-      | { mode: "pr"; value: string };
+      | { mode: "pr"; value: string }
+      | { mode: "cell"; value: string; commit?: string };
     onTimingEvent?: (event: {
       step: string;
       durationMs: number;
@@ -448,7 +488,8 @@ describe("POST /api/cells", () => {
     expect(capturedSessionId).toBeTruthy();
     expect(sendAgentMessage).toHaveBeenCalledWith(
       capturedSessionId,
-      "Autostart Cell\n\nFix the failing specs in apps/web"
+      "Autostart Cell\n\nFix the failing specs in apps/web",
+      DELIVERY_OPTIONS
     );
   });
 
@@ -586,6 +627,144 @@ describe("POST /api/cells", () => {
     });
   });
 
+  it("forks another cell with its uncommitted changes and model overrides", async () => {
+    const tempRoot = await mkdtemp(join(tmpdir(), "hive-cell-fork-"));
+    const sourcePath = join(tempRoot, "source");
+    const forkPath = join(tempRoot, "fork");
+    try {
+      runGit(tempRoot, ["init", "--initial-branch", "main", sourcePath]);
+      runGit(sourcePath, ["config", "user.email", "test@example.com"]);
+      runGit(sourcePath, ["config", "user.name", "Test User"]);
+      await writeFile(join(sourcePath, "app.ts"), "export {};\n", "utf8");
+      runGit(sourcePath, ["add", "."]);
+      runGit(sourcePath, ["commit", "-m", "initial"]);
+      await writeFile(join(sourcePath, "app.ts"), "export const a = 1;\n");
+      await writeFile(join(sourcePath, "notes.md"), "plan\n", "utf8");
+
+      await insertCellRow({
+        id: "source-cell",
+        name: "Source",
+        workspacePath: sourcePath,
+        opencodeSessionId: "session-source",
+      });
+      await insertProvisioningStateRow("source-cell", {
+        modelIdOverride: "source-model",
+        providerIdOverride: "zen",
+      });
+
+      let capturedWorktreeOptions: Parameters<CreateWorktreeFn>[1] | undefined;
+      let capturedOverrides: Record<string, unknown> | undefined;
+      const app = createTestApp({
+        createWorktree: (_cellId, createOptions) => {
+          capturedWorktreeOptions = createOptions;
+          const startPoint = createOptions?.startPoint;
+          const commit =
+            startPoint?.mode === "cell" ? startPoint.commit : "HEAD";
+          runGit(sourcePath, ["worktree", "add", "--detach", forkPath, commit]);
+          return Promise.resolve({
+            path: forkPath,
+            branch: "cell-branch",
+            baseCommit: commit,
+            startPoint: { mode: "cell", ref: "source-cell" },
+          });
+        },
+        onEnsureAgentSession: (_cellId, _sessionId, overrides) => {
+          capturedOverrides = overrides;
+        },
+      });
+
+      const payload = await createCellAndExpectSpawning({
+        app,
+        body: {
+          name: "Fork",
+          templateId,
+          workspaceId: "test-workspace",
+          spawnFromMode: "cell",
+          spawnFromValue: "source-cell",
+          forkSession: true,
+        },
+      });
+
+      const forkedRow = await waitForCellStatus(payload.id, "ready");
+      expect(forkedRow.startPointMode).toBe("cell");
+      expect(forkedRow.startPointRef).toBe("source-cell");
+      expect(capturedWorktreeOptions?.startPoint).toEqual({
+        mode: "cell",
+        value: "source-cell",
+        commit: runGitRead(sourcePath, ["rev-parse", "HEAD"]),
+      });
+      expect(await readFile(join(forkPath, "app.ts"), "utf8")).toBe(
+        "export const a = 1;\n"
+      );
+      expect(await readFile(join(forkPath, "notes.md"), "utf8")).toBe(
+        "plan\n"
+      );
+
+      const [provisioningState] = await testDb
+        .select()
+        .from(cellProvisioningStates)
+        .where(eq(cellProvisioningStates.cellId, payload.id));
+      expect(provisioningState?.modelIdOverride).toBe("source-model");
+      expect(provisioningState?.providerIdOverride).toBe("zen");
+      expect(capturedOverrides).toMatchObject({
+        modelId: "source-model",
+        providerId: "zen",
+        forkFromSessionId: "session-source",
+      });
+    } finally {
+      await rm(tempRoot, { recursive: true, force: true });
+    }
+  });
+
+  it("returns 404 when the cell to fork does not exist", async () => {
+    const app = createTestApp();
+
+    const response = await postCreateCell(app, {
+      name: "Missing Source",
+      templateId,
+      workspaceId: "test-workspace",
+      spawnFromMode: "cell",
+      spawnFromValue: "missing-cell",
+    });
+
+    expect(response.status).toBe(NOT_FOUND_STATUS);
+    expect((await response.json()) as { message: string }).toEqual({
+      message: "Source cell not found",
+    });
+  });
+
+  it("records the creating user as the cell owner", async () => {
+    const owner = await createUser(testDb, {
+      username: `owner-${Date.now()}`,
+    });
+    const { token } = await createApiToken(testDb, {
+      name: "owner",
+      scope: "operate",
+      userId: owner.id,
+    });
+    const app = new Elysia()
+      .use(createApiAuthPlugin({ db: testDb, mode: "required" }))
+      .use(createCellsRoutes(createDependencies()));
+
+    const response = await app.handle(
+      new Request(CELLS_API_URL, {
+        method: "POST",
+        headers: { ...JSON_HEADERS, authorization: `Bearer ${token}` },
+        body: JSON.stringify({
+          name: "Owned Cell",
+          templateId,
+          workspaceId: "test-workspace",
+        }),
+      })
+    );
+    expect(response.status).toBe(CREATED_STATUS);
+    const payload = (await response.json()) as { id: string; ownerId?: string };
+    expect(payload.ownerId).toBe(owner.id);
+
+    const row = await waitForCellStatus(payload.id, "ready");
+    expect(row.ownerId).toBe(owner.id);
+  });
+
   it("returns 400 when branch spawn source is missing value", async () => {
     const app = createTestApp();
 
@@ -688,6 +867,222 @@ describe("POST /api/cells", () => {
     expect(sendAgentMessage).not.toHaveBeenCalled();
   });
 
+  it("sends initialPrompt with its attachments instead of the description", async () => {
+    const worktreePath = await mkdtemp(join(tmpdir(), "hive-prompt-"));
+    try {
+      const sendAgentMessage = vi
+        .fn<SendAgentMessageFn>()
+        .mockResolvedValue(undefined);
+      const app = createTestApp({
+        sendAgentMessage,
+        createWorktree: async () => ({
+          path: worktreePath,
+          branch: "cell-branch",
+          baseCommit: "abc123",
+        }),
+      });
+
+      const payload = await createCellAndExpectSpawning({
+        app,
+        body: {
+          name: "Prompted Cell",
+          templateId,
+          workspaceId: "test-workspace",
+          description: "Shown in the UI only",
+          initialPrompt: "Implement the spec in the attached file",
+          attachments: [
+            {
+              name: "spec.md",
+              content: Buffer.from("# Spec\n").toString("base64"),
+              mimeType: "text/markdown",
+            },
+          ],
+        },
+      });
+
+      await waitForCellStatus(payload.id, "ready");
+      await waitForCondition(() => sendAgentMessage.mock.calls.length === 1);
+
+      expect(sendAgentMessage).toHaveBeenCalledWith(
+        `session-${payload.id}`,
+        "Implement the spec in the attached file\n\nAttached files (in this worktree):\n- .hive/attachments/spec.md",
+        DELIVERY_OPTIONS
+      );
+      expect(
+        await readFile(join(worktreePath, ".hive/attachments/spec.md"), "utf8")
+      ).toBe("# Spec\n");
+
+      await waitForCondition(
+        async () => (await readInitialPromptSentAt(payload.id)) !== null
+      );
+      const [provisioningState] = await testDb
+        .select()
+        .from(cellProvisioningStates)
+        .where(eq(cellProvisioningStates.cellId, payload.id));
+      expect(provisioningState?.initialPrompt).toBe(
+        "Implement the spec in the attached file"
+      );
+      expect(provisioningState?.initialPromptAttachments).toHaveLength(1);
+      expect(provisioningState?.initialPromptSentAt).toBeInstanceOf(Date);
+    } finally {
+      await rm(worktreePath, { recursive: true, force: true });
+    }
+  });
+
+  it("resends an undelivered initial prompt when setup is retried", async () => {
+    const sendAgentMessage = vi
+      .fn<SendAgentMessageFn>()
+      .mockRejectedValueOnce(new Error("agent unavailable"))
+      .mockResolvedValue(undefined);
+    const app = createTestApp({ sendAgentMessage });
+
+    const payload = await createCellAndExpectSpawning({
+      app,
+      body: {
+        name: "Retried Prompt",
+        templateId,
+        workspaceId: "test-workspace",
+        initialPrompt: "Fix the flaky test",
+      },
+    });
+    await waitForCellStatus(payload.id, "ready");
+    await waitForCondition(() => sendAgentMessage.mock.calls.length === 1);
+    expect(await readInitialPromptSentAt(payload.id)).toBeNull();
+
+    await testDb
+      .update(cells)
+      .set({ status: "error" })
+      .where(eq(cells.id, payload.id));
+    await waitForCondition(
+      async () => (await postSetupRetry(app, payload.id)).status === OK_STATUS
+    );
+    await waitForCellStatus(payload.id, "ready");
+    await waitForCondition(() => sendAgentMessage.mock.calls.length === 2);
+    await waitForCondition(
+      async () => (await readInitialPromptSentAt(payload.id)) !== null
+    );
+
+    expect(sendAgentMessage).toHaveBeenLastCalledWith(
+      `session-${payload.id}`,
+      "Fix the flaky test",
+      DELIVERY_OPTIONS
+    );
+  });
+
+  it("records a delivered prompt while its turn is still running", async () => {
+    // The agent has the prompt but its first turn never finishes.
+    const sendAgentMessage = vi
+      .fn<SendAgentMessageFn>()
+      .mockImplementation((_sessionId, _content, options) => {
+        options?.onDelivered?.();
+        return new Promise<void>(() => undefined);
+      });
+    const app = createTestApp({ sendAgentMessage });
+
+    const payload = await createCellAndExpectSpawning({
+      app,
+      body: {
+        name: "Long Turn",
+        templateId,
+        workspaceId: "test-workspace",
+        initialPrompt: "Refactor the billing module",
+      },
+    });
+    await waitForCellStatus(payload.id, "ready");
+    await waitForCondition(
+      async () => (await readInitialPromptSentAt(payload.id)) !== null
+    );
+
+    await testDb
+      .update(cells)
+      .set({ status: "error" })
+      .where(eq(cells.id, payload.id));
+    await waitForCondition(
+      async () => (await postSetupRetry(app, payload.id)).status === OK_STATUS
+    );
+    await waitForCellStatus(payload.id, "ready");
+
+    expect(sendAgentMessage).toHaveBeenCalledTimes(1);
+  });
+
+  it("returns 400 for attachments that are not plain file names", async () => {
+    const app = createTestApp();
+
+    const response = await postCreateCell(app, {
+      name: "Bad Attachment",
+      templateId,
+      workspaceId: "test-workspace",
+      initialPrompt: "Read the file",
+      attachments: [{ name: "../escape.sh", content: "ZWNobw==" }],
+    });
+
+    expect(response.status).toBe(BAD_REQUEST_STATUS);
+    expect(await response.json()).toEqual({
+      message: 'Attachment name "../escape.sh" must be a plain file name',
+    });
+  });
+
+  it("interpolates template parameters and reuses them on setup retry", async () => {
+    const setupRuns: string[][] = [];
+    const app = createTestApp({
+      hiveConfigOverride: parameterizedHiveConfig,
+      ensureServicesForCell: (args: { template: { setup?: string[] } }) => {
+        setupRuns.push(args.template.setup ?? []);
+        return Promise.resolve();
+      },
+    });
+
+    const payload = await createCellAndExpectSpawning({
+      app,
+      body: {
+        name: "Seeded Cell",
+        templateId: parameterizedTemplateId,
+        workspaceId: "test-workspace",
+        parameters: { dbSeed: "full" },
+      },
+    });
+    await waitForCellStatus(payload.id, "ready");
+
+    const [provisioningState] = await testDb
+      .select()
+      .from(cellProvisioningStates)
+      .where(eq(cellProvisioningStates.cellId, payload.id));
+    expect(provisioningState?.templateParameters).toEqual({
+      dbSeed: "full",
+      workers: 2,
+    });
+
+    await testDb
+      .update(cells)
+      .set({ status: "error" })
+      .where(eq(cells.id, payload.id));
+    await waitForCondition(
+      async () => (await postSetupRetry(app, payload.id)).status === OK_STATUS
+    );
+    await waitForCellStatus(payload.id, "ready");
+
+    expect(setupRuns).toEqual([
+      ["bun run seed full --workers 2"],
+      ["bun run seed full --workers 2"],
+    ]);
+  });
+
+  it("returns 400 for parameter values the template does not accept", async () => {
+    const app = createTestApp({ hiveConfigOverride: parameterizedHiveConfig });
+
+    const response = await postCreateCell(app, {
+      name: "Bad Seed",
+      templateId: parameterizedTemplateId,
+      workspaceId: "test-workspace",
+      parameters: { dbSeed: "everything" },
+    });
+
+    expect(response.status).toBe(BAD_REQUEST_STATUS);
+    expect(await response.json()).toEqual({
+      message: `Template "${parameterizedTemplateId}": Parameter "dbSeed" must be one of: demo, full`,
+    });
+  });
+
   it("persists create_worktree timing sub-steps while provisioning is still running", async () => {
     let releaseWorktree = () => {
       // replaced when deferred worktree promise is created
@@ -857,8 +1252,50 @@ describe("POST /api/cells/:id/setup/retry", () => {
     expect(sendAgentMessage).toHaveBeenCalledTimes(1);
     expect(sendAgentMessage).toHaveBeenCalledWith(
       `session-${cellId}`,
-      "Retry No Session\n\nSend this after retry"
+      "Retry No Session\n\nSend this after retry",
+      DELIVERY_OPTIONS
+    );
+  });
+
+  it("sends the persisted initial prompt on retry until it has been sent", async () => {
+    const sendAgentMessage = vi
+      .fn<SendAgentMessageFn>()
+      .mockResolvedValue(undefined);
+    const app = createTestApp({ sendAgentMessage });
+    const cellId = "retry-persisted-prompt-cell";
+
+    await insertCellRow({
+      id: cellId,
+      name: "Retry Persisted Prompt",
+      description: "Not the prompt",
+      opencodeSessionId: null,
+      status: "error",
+      lastSetupError: "setup failed",
+    });
+    await insertProvisioningStateRow(cellId, {
+      attemptCount: 1,
+      initialPrompt: "Upgrade the lockfile",
+    });
+
+    expect((await postSetupRetry(app, cellId)).status).toBe(OK_STATUS);
+    await waitForCellStatus(cellId, "ready");
+    expect(sendAgentMessage).toHaveBeenCalledTimes(1);
+    expect(sendAgentMessage).toHaveBeenCalledWith(
+      `session-${cellId}`,
+      "Upgrade the lockfile",
+      DELIVERY_OPTIONS
     );
+
+    await testDb
+      .update(cells)
+      .set({ status: "error", opencodeSessionId: null })
+      .where(eq(cells.id, cellId));
+    // The first workflow releases its slot just after marking the cell ready.
+    await waitForCondition(
+      async () => (await postSetupRetry(app, cellId)).status === OK_STATUS
+    );
+    await waitForCellStatus(cellId, "ready");
+    expect(sendAgentMessage).toHaveBeenCalledTimes(1);
   });
 
   it("returns 409 when the cell is being deleted", async () => {
@@ -1011,3 +1448,25 @@ describe("resumeSpawningCells", () => {
     expect(removeWorktreeCalls).toBe(1);
   });
 });
+
+function runGit(cwd: string, args: string[]) {
+  runGitRead(cwd, args);
+}
+
+function runGitRead(cwd: string, args: string[]): string {
+  const child = Bun.spawnSync({
+    cmd: ["git", ...args],
+    cwd,
+    stdout: "pipe",
+    stderr: "pipe",
+  });
+
+  if (child.exitCode !== 0) {
+    const stderr = child.stderr.toString().trim();
+    throw new Error(
+      `git ${args.join(" ")} failed with code ${child.exitCode}${stderr ? `: ${stderr}` : ""}`
+    );
+  }
+
+  return child.stdout.toString().trim();
+}
@@ -1,9 +1,4 @@
-import { Database } from "bun:sqlite";
-import { join } from "node:path";
-import { fileURLToPath } from "node:url";
 import { eq } from "drizzle-orm";
-import { drizzle } from "drizzle-orm/bun-sqlite";
-import { migrate } from "drizzle-orm/bun-sqlite/migrator";
 import { Elysia } from "elysia";
 import {
   afterEach,
@@ -21,12 +16,12 @@ import {
   storeLinearSecret,
 } from "../../linear/token-crypto";
 import { createLinearRoutes } from "../../routes/linear";
-import { schema } from "../../schema";
 import { linearIntegrations } from "../../schema/linear-integrations";
 import type {
   ResolveWorkspaceContext,
   WorkspaceRuntimeContext,
 } from "../../workspaces/context";
+import { setupTestDb, testDb } from "../test-db";
 
 const JSON_HEADERS = {
   "content-type": "application/json",
@@ -46,8 +41,6 @@ const HTTP_UNAUTHORIZED = 401;
 const HTTP_FORBIDDEN = 403;
 const HTTP_NO_CONTENT = 204;
 const HTTP_INTERNAL_ERROR = 500;
-const sqlite = new Database(":memory:");
-const linearTestDb = drizzle(sqlite, { schema });
 
 const resolveWorkspaceContext: ResolveWorkspaceContext = (workspaceId) => {
   if (workspaceId && workspaceId !== WORKSPACE.id) {
@@ -68,35 +61,19 @@ const resolveWorkspaceContext: ResolveWorkspaceContext = (workspaceId) => {
 const createApp = () =>
   new Elysia().use(
     createLinearRoutes({
-      db: linearTestDb,
+      db: testDb,
       resolveWorkspaceContext,
     })
   );
 
-async function setupLinearTestDb() {
-  sqlite.exec("DROP TABLE IF EXISTS cell_timing_events;");
-  sqlite.exec("DROP TABLE IF EXISTS cell_activity_events;");
-  sqlite.exec("DROP TABLE IF EXISTS cell_resource_rollups;");
-  sqlite.exec("DROP TABLE IF EXISTS cell_resource_history;");
-  sqlite.exec("DROP TABLE IF EXISTS cell_services;");
-  sqlite.exec("DROP TABLE IF EXISTS cell_provisioning_state;");
-  sqlite.exec("DROP TABLE IF EXISTS cells;");
-  sqlite.exec("DROP TABLE IF EXISTS workspace_linear_integrations;");
-  sqlite.exec("DROP TABLE IF EXISTS __drizzle_migrations;");
-
-  const packageRoot = fileURLToPath(new URL("../../..", import.meta.url));
-  const migrationsFolder = join(packageRoot, "src", "migrations");
-  await migrate(linearTestDb, { migrationsFolder });
-}
-
 describe("createLinearRoutes", () => {
   beforeAll(async () => {
-    await setupLinearTestDb();
+    await setupTestDb();
   });
 
   beforeEach(async () => {
     vi.restoreAllMocks();
-    await linearTestDb.delete(linearIntegrations);
+    await testDb.delete(linearIntegrations);
     process.env.LINEAR_TOKEN_ENCRYPTION_SECRET = "";
   });
 
@@ -174,7 +151,7 @@ describe("createLinearRoutes", () => {
       team: null,
     });
 
-    const [integration] = await linearTestDb
+    const [integration] = await testDb
       .select()
       .from(linearIntegrations)
       .where(eq(linearIntegrations.workspaceId, WORKSPACE.id));
@@ -412,7 +389,7 @@ describe("createLinearRoutes", () => {
 
     expect(response.status).toBe(HTTP_NO_CONTENT);
 
-    const remaining = await linearTestDb
+    const remaining = await testDb
       .select()
       .from(linearIntegrations)
       .where(eq(linearIntegrations.workspaceId, WORKSPACE.id));
@@ -423,7 +400,7 @@ describe("createLinearRoutes", () => {
 async function insertConnectedIntegration(
   overrides: Partial<typeof linearIntegrations.$inferInsert> = {}
 ) {
-  await linearTestDb.insert(linearIntegrations).values({
+  await testDb.insert(linearIntegrations).values({
     workspaceId: WORKSPACE.id,
     accessToken: storeLinearSecret("linear-access-token", null),
     refreshToken: null,
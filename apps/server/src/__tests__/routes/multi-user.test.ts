@@ -0,0 +1,381 @@
+import { randomUUID } from "node:crypto";
+import { Elysia } from "elysia";
+import { beforeAll, beforeEach, describe, expect, it } from "vitest";
+import { createApiAuthPlugin } from "../../auth/plugin";
+import { createApiToken, issueCellApiToken } from "../../auth/tokens";
+import { createUser } from "../../auth/users";
+import { createCellShareRoutes } from "../../routes/cell-shares";
+import { createCellsRoutes } from "../../routes/cells";
+import { cellActivityEvents } from "../../schema/activity-events";
+import { apiTokens } from "../../schema/api-tokens";
+import { cellShares } from "../../schema/cell-shares";
+import { cells } from "../../schema/cells";
+import { cellServices } from "../../schema/services";
+import { cellTimingEvents } from "../../schema/timing-events";
+import { users } from "../../schema/users";
+import { setupTestDb, testDb } from "../test-db";
+
+const TEST_WORKSPACE_ID = "multi-user-workspace";
+const HTTP_OK = 200;
+const HTTP_CREATED = 201;
+const HTTP_FORBIDDEN = 403;
+const HTTP_NOT_FOUND = 404;
+
+type CellListPayload = { cells: Array<{ id: string; ownerId?: string }> };
+type TimingPayload = { steps: Array<{ cellId: string }> };
+type ActivityPayload = {
+  events: Array<{ type: string; userId: string | null; source: string | null }>;
+};
+
+function createMinimalDependencies(): any {
+  const workspaceRecord = {
+    id: TEST_WORKSPACE_ID,
+    label: "Shared Workspace",
+    path: "/tmp/multi-user-workspace",
+    addedAt: new Date().toISOString(),
+  };
+
+  return {
+    db: testDb,
+    resolveWorkspaceContext: (async () => ({
+      workspace: workspaceRecord,
+      loadConfig: async () => ({
+        opencode: { defaultProvider: "opencode", defaultModel: "mock" },
+        promptSources: [],
+        templates: {
+          basic: { id: "basic", label: "Basic", type: "manual" },
+        },
+        defaults: {},
+      }),
+      createWorktreeManager: async () => ({
+        createWorktree: async () => ({
+          path: "/tmp",
+          branch: "b",
+          baseCommit: "c",
+        }),
+        removeWorktree: () => Promise.resolve(),
+      }),
+      createWorktree: async () => ({
+        path: "/tmp",
+        branch: "b",
+        baseCommit: "c",
+      }),
+      removeWorktree: () => Promise.resolve(),
+    })) as any,
+    ensureAgentSession: async () => ({ id: "session" }),
+    closeAgentSession: () => Promise.resolve(),
+    ensureServicesForCell: () => Promise.resolve(),
+    startServicesForCell: () => Promise.resolve(),
+    stopServicesForCell: () => Promise.resolve(),
+    startServiceById: () => Promise.resolve(),
+    stopServiceById: () => Promise.resolve(),
+    sendAgentMessage: () => Promise.resolve(),
+    readSetupTerminalOutput: () => "",
+  };
+}
+
+describe("multi-user cells", () => {
+  let adaId = "";
+  let bobId = "";
+  let adaToken = "";
+  let bobToken = "";
+  let carolToken = "";
+  let adminToken = "";
+
+  beforeAll(async () => {
+    await setupTestDb();
+  });
+
+  beforeEach(async () => {
+    await testDb.delete(cellActivityEvents);
+    await testDb.delete(cellTimingEvents);
+    await testDb.delete(cellServices);
+    await testDb.delete(cellShares);
+    await testDb.delete(cells);
+    await testDb.delete(apiTokens);
+    await testDb.delete(users);
+
+    const ada = await createUser(testDb, { username: "ada" });
+    const bob = await createUser(testDb, { username: "bob" });
+    const carol = await createUser(testDb, { username: "carol" });
+    adaId = ada.id;
+    bobId = bob.id;
+
+    adaToken = await mintToken("ada", "operate", ada.id);
+    bobToken = await mintToken("bob", "operate", bob.id);
+    carolToken = await mintToken("carol", "operate", carol.id);
+    adminToken = await mintToken("admin", "admin", null);
+
+    await seedCell("ada-cell", ada.id);
+    await seedCell("bob-cell", bob.id);
+  });
+
+  it("lists only owned and shared cells for members", async () => {
+    const app = createApp();
+
+    expect(await listCellIds(app, adaToken)).toEqual(["ada-cell"]);
+    expect(await listCellIds(app, carolToken)).toEqual([]);
+
+    const shared = await app.handle(
+      request("POST", "/api/cells/ada-cell/shares", adaToken, { user: "bob" })
+    );
+    expect(shared.status).toBe(HTTP_CREATED);
+    expect(await listCellIds(app, bobToken)).toEqual(["ada-cell", "bob-cell"]);
+
+    expect(await listCellIds(app, adminToken)).toEqual([
+      "ada-cell",
+      "bob-cell",
+    ]);
+    expect(await listCellIds(app, adminToken, `?userId=${bobId}`)).toEqual([
+      "bob-cell",
+    ]);
+  });
+
+  it("keeps shared cells read-only and hides unshared ones", async () => {
+    const app = createApp();
+    await app.handle(
+      request("POST", "/api/cells/ada-cell/shares", adaToken, { user: "bob" })
+    );
+
+    const read = await app.handle(
+      request("GET", "/api/cells/ada-cell", bobToken)
+    );
+    expect(read.status).toBe(HTTP_OK);
+
+    const write = await app.handle(
+      request("POST", "/api/cells/ada-cell/services/stop", bobToken)
+    );
+    expect(write.status).toBe(HTTP_FORBIDDEN);
+
+    const reshare = await app.handle(
+      request("POST", "/api/cells/ada-cell/shares", bobToken, { user: "carol" })
+    );
+    expect(reshare.status).toBe(HTTP_FORBIDDEN);
+
+    const hidden = await app.handle(
+      request("GET", "/api/cells/ada-cell", carolToken)
+    );
+    expect(hidden.status).toBe(HTTP_NOT_FOUND);
+
+    const unshared = await app.handle(
+      request("DELETE", "/api/cells/ada-cell/shares/bob", adaToken)
+    );
+    expect(unshared.status).toBe(HTTP_OK);
+    const afterUnshare = await app.handle(
+      request("GET", "/api/cells/ada-cell", bobToken)
+    );
+    expect(afterUnshare.status).toBe(HTTP_NOT_FOUND);
+  });
+
+  it("stamps activity with the acting user and filters by user", async () => {
+    const app = createApp();
+
+    const stopped = await app.handle(
+      request("POST", "/api/cells/ada-cell/services/stop", adaToken)
+    );
+    expect(stopped.status).toBe(HTTP_OK);
+
+    const agentToken = await issueCellApiToken(testDb, "ada-cell");
+    const byAgent = await app.handle(
+      new Request("http://localhost/api/cells/ada-cell/services/start", {
+        method: "POST",
+        headers: {
+          authorization: `Bearer ${agentToken}`,
+          "x-hive-source": "opencode",
+        },
+      })
+    );
+    expect(byAgent.status).toBe(HTTP_OK);
+
+    const byAdmin = await app.handle(
+      request("POST", "/api/cells/ada-cell/services/restart", adminToken)
+    );
+    expect(byAdmin.status).toBe(HTTP_OK);
+
+    const all = (await (
+      await app.handle(request("GET", "/api/cells/ada-cell/activity", adaToken))
+    ).json()) as ActivityPayload;
+    const byType = Object.fromEntries(
+      all.events.map((event) => [event.type, event])
+    );
+    expect(byType["services.stop"]?.userId).toBe(adaId);
+    expect(byType["services.start"]).toMatchObject({
+      userId: adaId,
+      source: "opencode",
+    });
+    expect(byType["services.restart"]?.userId).toBeNull();
+
+    const filtered = (await (
+      await app.handle(
+        request(
+          "GET",
+          `/api/cells/ada-cell/activity?userId=${adaId}`,
+          adminToken
+        )
+      )
+    ).json()) as ActivityPayload;
+    expect(filtered.events.map((event) => event.type).sort()).toEqual([
+      "services.start",
+      "services.stop",
+    ]);
+  });
+
+  it("confines agent tokens to their own cell", async () => {
+    const app = createApp();
+    const agentToken = await issueCellApiToken(testDb, "ada-cell");
+    await app.handle(
+      request("POST", "/api/cells/bob-cell/shares", bobToken, { user: "ada" })
+    );
+
+    const own = await app.handle(
+      request("GET", "/api/cells/ada-cell", agentToken)
+    );
+    expect(own.status).toBe(HTTP_OK);
+
+    const other = await app.handle(
+      request("POST", "/api/cells/bob-cell/services/stop", agentToken)
+    );
+    expect(other.status).toBe(HTTP_NOT_FOUND);
+    const otherRead = await app.handle(
+      request("GET", "/api/cells/bob-cell", agentToken)
+    );
+    expect(otherRead.status).toBe(HTTP_NOT_FOUND);
+
+    const list = await app.handle(request("GET", "/api/cells", agentToken));
+    expect(list.status).toBe(HTTP_FORBIDDEN);
+  });
+
+  it("hides unshared cells from forks and bulk deletes", async () => {
+    const app = createApp();
+
+    const forked = await app.handle(
+      request("POST", "/api/cells", carolToken, {
+        name: "Sneaky fork",
+        templateId: "basic",
+        workspaceId: TEST_WORKSPACE_ID,
+        spawnFromMode: "cell",
+        spawnFromValue: "ada-cell",
+      })
+    );
+    expect(forked.status).toBe(HTTP_NOT_FOUND);
+
+    const bulkDelete = await app.handle(
+      request("DELETE", "/api/cells", carolToken, { ids: ["ada-cell"] })
+    );
+    expect(bulkDelete.status).toBe(HTTP_NOT_FOUND);
+    expect(await listCellIds(app, adaToken)).toEqual(["ada-cell"]);
+  });
+
+  it("limits global timings to cells the caller can read", async () => {
+    const app = createApp();
+    await seedTiming("ada-cell");
+    await seedTiming("bob-cell");
+
+    expect(await listTimingCellIds(app, adaToken)).toEqual(["ada-cell"]);
+    expect(
+      await listTimingCellIds(app, carolToken, "?cellId=ada-cell")
+    ).toEqual([]);
+    expect(await listTimingCellIds(app, adminToken)).toEqual([
+      "ada-cell",
+      "bob-cell",
+    ]);
+
+    await app.handle(
+      request("POST", "/api/cells/ada-cell/shares", adaToken, { user: "bob" })
+    );
+    expect(await listTimingCellIds(app, bobToken)).toEqual([
+      "ada-cell",
+      "bob-cell",
+    ]);
+  });
+
+  function createApp() {
+    return new Elysia()
+      .use(createApiAuthPlugin({ db: testDb, mode: "required" }))
+      .use(createCellsRoutes(createMinimalDependencies()))
+      .use(createCellShareRoutes({ db: testDb }));
+  }
+});
+
+async function mintToken(
+  name: string,
+  scope: "operate" | "admin",
+  userId: string | null
+) {
+  const { token } = await createApiToken(testDb, { name, scope, userId });
+  return token;
+}
+
+async function seedCell(id: string, ownerId: string) {
+  await testDb.insert(cells).values({
+    id,
+    name: id,
+    templateId: "template",
+    workspacePath: `/tmp/${id}`,
+    workspaceId: TEST_WORKSPACE_ID,
+    workspaceRootPath: "/tmp/multi-user-workspace",
+    createdAt: new Date(),
+    status: "ready",
+    ownerId,
+  });
+}
+
+async function seedTiming(cellId: string) {
+  await testDb.insert(cellTimingEvents).values({
+    id: randomUUID(),
+    cellId,
+    cellName: cellId,
+    workspaceId: TEST_WORKSPACE_ID,
+    templateId: "template",
+    workflow: "create",
+    runId: randomUUID(),
+    step: "create_worktree",
+    status: "error",
+    durationMs: 10,
+    error: `setup failed for ${cellId}`,
+    metadata: {},
+    createdAt: new Date(),
+  });
+}
+
+async function listTimingCellIds(
+  app: { handle: (request: Request) => Promise<Response> },
+  token: string,
+  query = ""
+) {
+  const response = await app.handle(
+    request("GET", `/api/cells/timings/global${query}`, token)
+  );
+  expect(response.status).toBe(HTTP_OK);
+  const payload = (await response.json()) as TimingPayload;
+  return [...new Set(payload.steps.map((step) => step.cellId))].sort();
+}
+
+async function listCellIds(
+  app: { handle: (request: Request) => Promise<Response> },
+  token: string,
+  query = ""
+) {
+  const response = await app.handle(
+    request("GET", `/api/cells${query}`, token)
+  );
+  expect(response.status).toBe(HTTP_OK);
+  const payload = (await response.json()) as CellListPayload;
+  return payload.cells.map((cell) => cell.id).sort();
+}
+
+function request(
+  method: string,
+  path: string,
+  token: string,
+  body?: Record<string, unknown>
+) {
+  return new Request(`http://localhost${path}`, {
+    method,
+    headers: {
+      authorization: `Bearer ${token}`,
+      ...(body ? { "content-type": "application/json" } : {}),
+    },
+    ...(body ? { body: JSON.stringify(body) } : {}),
+  });
+}
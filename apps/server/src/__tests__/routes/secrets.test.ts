@@ -0,0 +1,148 @@
+import { randomBytes } from "node:crypto";
+import { Elysia } from "elysia";
+import { beforeAll, beforeEach, describe, expect, it } from "vitest";
+import { createSecretRoutes } from "../../routes/secrets";
+import { workspaceSecrets } from "../../schema/workspace-secrets";
+import { createWorkspaceSecretStore } from "../../services/workspace-secrets";
+import { WorkspaceContextError } from "../../workspaces/context";
+import { setupTestDb, testDb } from "../test-db";
+
+const TEST_WORKSPACE_ID = "test-workspace";
+const HTTP_OK = 200;
+const HTTP_BAD_REQUEST = 400;
+const HTTP_NOT_FOUND = 404;
+const SECRETS_URL = "http://localhost/api/secrets";
+
+const key = randomBytes(32);
+const store = createWorkspaceSecretStore({
+  db: testDb,
+  loadKey: () => Promise.resolve(key),
+});
+
+function createApp() {
+  return new Elysia().use(
+    createSecretRoutes({
+      db: testDb,
+      store,
+      resolveWorkspaceContext: (async (workspaceId?: string) => {
+        if (workspaceId !== TEST_WORKSPACE_ID) {
+          throw new WorkspaceContextError(
+            `Workspace '${workspaceId}' not found`
+          );
+        }
+        return {
+          workspace: {
+            id: TEST_WORKSPACE_ID,
+            label: "Test Workspace",
+            path: "/tmp/test-workspace-root",
+            addedAt: new Date().toISOString(),
+          },
+        };
+      }) as any,
+    })
+  );
+}
+
+const jsonRequest = (method: string, url: string, body: unknown) =>
+  new Request(url, {
+    method,
+    headers: { "content-type": "application/json" },
+    body: JSON.stringify(body),
+  });
+
+describe("Secret routes", () => {
+  beforeAll(async () => {
+    await setupTestDb();
+  });
+
+  beforeEach(async () => {
+    await testDb.delete(workspaceSecrets);
+  });
+
+  it("stores values encrypted and only lists names", async () => {
+    const app = createApp();
+
+    const saved = await app.handle(
+      jsonRequest("PUT", `${SECRETS_URL}/STRIPE_KEY`, {
+        workspaceId: TEST_WORKSPACE_ID,
+        value: "sk_test_123456",
+      })
+    );
+    expect(saved.status).toBe(HTTP_OK);
+    expect(await saved.json()).toMatchObject({
+      name: "STRIPE_KEY",
+      workspaceId: TEST_WORKSPACE_ID,
+    });
+
+    const [row] = await testDb.select().from(workspaceSecrets);
+    expect(row?.encryptedValue).not.toContain("sk_test_123456");
+
+    const listed = await app.handle(
+      new Request(`${SECRETS_URL}?workspaceId=${TEST_WORKSPACE_ID}`)
+    );
+    const body = await listed.json();
+    expect(body.secrets.map((secret: { name: string }) => secret.name)).toEqual(
+      ["STRIPE_KEY"]
+    );
+    expect(JSON.stringify(body)).not.toContain("sk_test_123456");
+
+    const resolved = await store.resolveEnv(TEST_WORKSPACE_ID, {
+      STRIPE_API_KEY: "${secret:STRIPE_KEY}",
+    });
+    expect(resolved.env.STRIPE_API_KEY).toBe("sk_test_123456");
+  });
+
+  it("overwrites existing secrets and deletes them", async () => {
+    const app = createApp();
+    for (const value of ["first-value", "second-value"]) {
+      await app.handle(
+        jsonRequest("PUT", `${SECRETS_URL}/DB_PASSWORD`, {
+          workspaceId: TEST_WORKSPACE_ID,
+          value,
+        })
+      );
+    }
+
+    const resolved = await store.resolveEnv(TEST_WORKSPACE_ID, {
+      PGPASSWORD: "${secret:DB_PASSWORD}",
+    });
+    expect(resolved.env.PGPASSWORD).toBe("second-value");
+    expect(await testDb.select().from(workspaceSecrets)).toHaveLength(1);
+
+    const deleted = await app.handle(
+      new Request(
+        `${SECRETS_URL}/DB_PASSWORD?workspaceId=${TEST_WORKSPACE_ID}`,
+        { method: "DELETE" }
+      )
+    );
+    expect(deleted.status).toBe(HTTP_OK);
+
+    const missing = await app.handle(
+      new Request(
+        `${SECRETS_URL}/DB_PASSWORD?workspaceId=${TEST_WORKSPACE_ID}`,
+        { method: "DELETE" }
+      )
+    );
+    expect(missing.status).toBe(HTTP_NOT_FOUND);
+  });
+
+  it("rejects invalid names and unknown workspaces", async () => {
+    const app = createApp();
+
+    const badName = await app.handle(
+      jsonRequest("PUT", `${SECRETS_URL}/not-valid`, {
+        workspaceId: TEST_WORKSPACE_ID,
+        value: "value",
+      })
+    );
+    expect(badName.status).toBe(HTTP_BAD_REQUEST);
+
+    const unknownWorkspace = await app.handle(
+      jsonRequest("PUT", `${SECRETS_URL}/TOKEN`, {
+        workspaceId: "missing",
+        value: "value",
+      })
+    );
+    expect(unknownWorkspace.status).toBe(HTTP_NOT_FOUND);
+  });
+});
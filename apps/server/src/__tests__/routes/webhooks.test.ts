@@ -0,0 +1,170 @@
+import { Elysia } from "elysia";
+import { beforeAll, beforeEach, describe, expect, it, vi } from "vitest";
+import { createWebhookRoutes } from "../../routes/webhooks";
+import { webhookDeliveries, workspaceWebhooks } from "../../schema/webhooks";
+import { WorkspaceContextError } from "../../workspaces/context";
+import { setupTestDb, testDb } from "../test-db";
+
+const TEST_WORKSPACE_ID = "test-workspace";
+const HTTP_OK = 200;
+const HTTP_CREATED = 201;
+const HTTP_BAD_REQUEST = 400;
+const HTTP_NOT_FOUND = 404;
+const WEBHOOKS_URL = "http://localhost/api/webhooks";
+
+function createApp(ping = vi.fn()) {
+  return new Elysia().use(
+    createWebhookRoutes({
+      db: testDb,
+      resolveWorkspaceContext: (async (workspaceId?: string) => {
+        if (workspaceId !== TEST_WORKSPACE_ID) {
+          throw new WorkspaceContextError(
+            `Workspace '${workspaceId}' not found`
+          );
+        }
+        return {
+          workspace: {
+            id: TEST_WORKSPACE_ID,
+            label: "Test Workspace",
+            path: "/tmp/test-workspace-root",
+            addedAt: new Date().toISOString(),
+          },
+        };
+      }) as any,
+      dispatcher: { ping },
+    })
+  );
+}
+
+const jsonRequest = (method: string, url: string, body: unknown) =>
+  new Request(url, {
+    method,
+    headers: { "content-type": "application/json" },
+    body: JSON.stringify(body),
+  });
+
+describe("Webhook routes", () => {
+  beforeAll(async () => {
+    await setupTestDb();
+  });
+
+  beforeEach(async () => {
+    await testDb.delete(webhookDeliveries);
+    await testDb.delete(workspaceWebhooks);
+  });
+
+  it("creates a webhook and only reveals its secret once", async () => {
+    const app = createApp();
+
+    const created = await app.handle(
+      jsonRequest("POST", WEBHOOKS_URL, {
+        workspaceId: TEST_WORKSPACE_ID,
+        url: "https://hooks.example.test/hive",
+      })
+    );
+    expect(created.status).toBe(HTTP_CREATED);
+    const { secret, webhook } = (await created.json()) as {
+      secret: string;
+      webhook: { id: string; events: string[]; enabled: boolean };
+    };
+    expect(secret.startsWith("whsec_")).toBe(true);
+    expect(webhook.enabled).toBe(true);
+    expect(webhook.events).toEqual([
+      "cell.status",
+      "cell.setup_failed",
+      "service.crashed",
+      "agent.status",
+    ]);
+
+    const listed = await app.handle(
+      new Request(`${WEBHOOKS_URL}?workspaceId=${TEST_WORKSPACE_ID}`)
+    );
+    const body = await listed.json();
+    expect(body.webhooks).toHaveLength(1);
+    expect(JSON.stringify(body)).not.toContain(secret);
+  });
+
+  it("rejects unknown workspaces and non-http URLs", async () => {
+    const app = createApp();
+
+    const unknownWorkspace = await app.handle(
+      jsonRequest("POST", WEBHOOKS_URL, {
+        workspaceId: "missing",
+        url: "https://hooks.example.test/hive",
+      })
+    );
+    expect(unknownWorkspace.status).toBe(HTTP_NOT_FOUND);
+
+    const badUrl = await app.handle(
+      jsonRequest("POST", WEBHOOKS_URL, {
+        workspaceId: TEST_WORKSPACE_ID,
+        url: "file:///etc/passwd",
+      })
+    );
+    expect(badUrl.status).toBe(HTTP_BAD_REQUEST);
+  });
+
+  it("updates the event filter, pings and lists deliveries", async () => {
+    const ping = vi.fn(async (webhook: { id: string }) => {
+      const delivery = {
+        id: "delivery-1",
+        webhookId: webhook.id,
+        event: "ping",
+        payload: { id: "delivery-1", event: "ping" },
+        status: "succeeded" as const,
+        attempts: 1,
+        responseStatus: HTTP_OK,
+        error: null,
+        createdAt: new Date(),
+        lastAttemptAt: new Date(),
+        nextAttemptAt: null,
+      };
+      await testDb.insert(webhookDeliveries).values(delivery);
+      return delivery;
+    });
+    const app = createApp(ping);
+    const created = await app.handle(
+      jsonRequest("POST", WEBHOOKS_URL, {
+        workspaceId: TEST_WORKSPACE_ID,
+        url: "https://hooks.example.test/hive",
+      })
+    );
+    const { webhook } = (await created.json()) as { webhook: { id: string } };
+
+    const updated = await app.handle(
+      jsonRequest("PATCH", `${WEBHOOKS_URL}/${webhook.id}`, {
+        events: ["agent.status"],
+        enabled: false,
+      })
+    );
+    expect(await updated.json()).toMatchObject({
+      events: ["agent.status"],
+      enabled: false,
+    });
+
+    const pinged = await app.handle(
+      new Request(`${WEBHOOKS_URL}/${webhook.id}/ping`, { method: "POST" })
+    );
+    expect(await pinged.json()).toMatchObject({
+      event: "ping",
+      status: "succeeded",
+    });
+    expect(ping).toHaveBeenCalledWith(
+      expect.objectContaining({ id: webhook.id })
+    );
+
+    const deliveries = await app.handle(
+      new Request(`${WEBHOOKS_URL}/${webhook.id}/deliveries`)
+    );
+    expect((await deliveries.json()).deliveries).toHaveLength(1);
+
+    const deleted = await app.handle(
+      new Request(`${WEBHOOKS_URL}/${webhook.id}`, { method: "DELETE" })
+    );
+    expect(deleted.status).toBe(HTTP_OK);
+    const missing = await app.handle(
+      new Request(`${WEBHOOKS_URL}/${webhook.id}/deliveries`)
+    );
+    expect(missing.status).toBe(HTTP_NOT_FOUND);
+  });
+});
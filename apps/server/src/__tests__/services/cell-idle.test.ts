@@ -0,0 +1,194 @@
+import { setTimeout as delay } from "node:timers/promises";
+import { eq } from "drizzle-orm";
+import { Elysia } from "elysia";
+import { beforeAll, beforeEach, describe, expect, it, vi } from "vitest";
+import type { Template } from "../../config/schema";
+import { cellActivityEvents } from "../../schema/activity-events";
+import { type Cell, cells } from "../../schema/cells";
+import { cellServices } from "../../schema/services";
+import {
+  type CellIdleMonitorDependencies,
+  createCellIdleMonitor,
+  createCellIdlePlugin,
+} from "../../services/cell-idle";
+import { setupTestDb, testDb } from "../test-db";
+
+const MINUTE_MS = 60_000;
+const SERVICE_PORT = 41_234;
+
+const idleTemplate: Template = {
+  id: "idle-template",
+  label: "Idle",
+  type: "manual",
+  idle: { suspendAfterMinutes: 10 },
+};
+
+async function insertCell(overrides: Partial<Cell> = {}): Promise<Cell> {
+  const [cell] = await testDb
+    .insert(cells)
+    .values({
+      id: crypto.randomUUID(),
+      name: "Idle cell",
+      templateId: idleTemplate.id,
+      workspacePath: "/tmp/idle-cell",
+      workspaceId: "workspace-1",
+      workspaceRootPath: "/tmp",
+      createdAt: new Date(),
+      status: "ready",
+      ...overrides,
+    })
+    .returning();
+  if (!cell) {
+    throw new Error("Failed to insert cell");
+  }
+  return cell;
+}
+
+async function insertRunningService(cellId: string) {
+  const now = new Date();
+  await testDb.insert(cellServices).values({
+    id: crypto.randomUUID(),
+    cellId,
+    name: "web",
+    type: "process",
+    command: "bun run dev",
+    cwd: "/tmp/idle-cell",
+    env: {},
+    status: "running",
+    port: SERVICE_PORT,
+    pid: null,
+    readyTimeoutMs: null,
+    definition: { type: "process", run: "bun run dev" },
+    lastKnownError: null,
+    createdAt: now,
+    updatedAt: now,
+  });
+}
+
+async function readStatus(cellId: string) {
+  const cell = await testDb.query.cells.findFirst({
+    where: eq(cells.id, cellId),
+  });
+  return cell?.status;
+}
+
+function createHarness(overrides: CellIdleMonitorDependencies = {}) {
+  let clock = Date.now();
+  const deps = {
+    db: testDb,
+    now: () => clock,
+    loadTemplate: (cell: Cell) =>
+      Promise.resolve(
+        cell.templateId === idleTemplate.id ? idleTemplate : undefined
+      ),
+    probeConnections: vi.fn(() => Promise.resolve([] as string[])),
+    isAgentWorking: vi.fn(() => false),
+    suspendAgent: vi.fn(() => Promise.resolve()),
+    resumeAgent: vi.fn(() => Promise.resolve()),
+    stopServices: vi.fn(() => Promise.resolve()),
+    resumeServices: vi.fn(() => Promise.resolve()),
+    ...overrides,
+  };
+  const monitor = createCellIdleMonitor(deps);
+  return {
+    monitor,
+    deps,
+    advance(ms: number) {
+      clock += ms;
+    },
+  };
+}
+
+describe("cell idle monitor", () => {
+  beforeAll(async () => {
+    await setupTestDb();
+  });
+
+  beforeEach(async () => {
+    await testDb.delete(cellActivityEvents);
+    await testDb.delete(cellServices);
+    await testDb.delete(cells);
+  });
+
+  it("suspends cells once their template's idle window passes", async () => {
+    const cell = await insertCell();
+    const untracked = await insertCell({ templateId: "no-policy" });
+    const { monitor, deps, advance } = createHarness();
+
+    expect(await monitor.sweep()).toEqual([]);
+    advance(9 * MINUTE_MS);
+    expect(await monitor.sweep()).toEqual([]);
+    advance(MINUTE_MS);
+    expect(await monitor.sweep()).toEqual([cell.id]);
+
+    expect(await readStatus(cell.id)).toBe("suspended");
+    expect(await readStatus(untracked.id)).toBe("ready");
+    expect(monitor.isSuspended(cell.id)).toBe(true);
+    expect(deps.suspendAgent).toHaveBeenCalledWith(cell.id);
+    expect(deps.stopServices).toHaveBeenCalledWith(cell.id, {
+      suspend: true,
+    });
+    const [event] = await testDb
+      .select()
+      .from(cellActivityEvents)
+      .where(eq(cellActivityEvents.cellId, cell.id));
+    expect(event).toMatchObject({
+      type: "cell.suspend",
+      metadata: { idleMinutes: 10 },
+    });
+  });
+
+  it("treats terminal input, agent work and new connections as activity", async () => {
+    const cell = await insertCell();
+    await insertRunningService(cell.id);
+    let connections: string[] = [];
+    let agentWorking = false;
+    const { monitor, advance } = createHarness({
+      probeConnections: () => Promise.resolve(connections),
+      isAgentWorking: () => agentWorking,
+    });
+
+    await monitor.sweep();
+    advance(8 * MINUTE_MS);
+    monitor.recordActivity(cell.id);
+
+    advance(8 * MINUTE_MS);
+    connections = ["127.0.0.1:41234->127.0.0.1:50000"];
+    expect(await monitor.sweep()).toEqual([]);
+
+    advance(8 * MINUTE_MS);
+    agentWorking = true;
+    expect(await monitor.sweep()).toEqual([]);
+
+    // An open but silent connection does not keep the cell awake.
+    agentWorking = false;
+    advance(10 * MINUTE_MS);
+    expect(await monitor.sweep()).toEqual([cell.id]);
+  });
+
+  it("resumes suspended cells when a request targets them", async () => {
+    const cell = await insertCell({ resumeAgentSessionOnStartup: true });
+    const { monitor, deps, advance } = createHarness();
+    await monitor.sweep();
+    advance(10 * MINUTE_MS);
+    await monitor.sweep();
+
+    const app = new Elysia()
+      .use(createCellIdlePlugin({ db: testDb, monitor }))
+      .get("/api/cells/:id", ({ params }) => readStatus(params.id));
+    const response = await app.handle(
+      new Request(`http://localhost/api/cells/${cell.id}`)
+    );
+
+    expect(await response.text()).toBe("ready");
+    expect(monitor.isSuspended(cell.id)).toBe(false);
+    for (let attempt = 0; attempt < 100; attempt += 1) {
+      if (deps.resumeAgent.mock.calls.length > 0) {
+        break;
+      }
+      await delay(10);
+    }
+    expect(deps.resumeServices).toHaveBeenCalledWith(cell.id);
+    expect(deps.resumeAgent).toHaveBeenCalledWith(cell.id);
+  });
+});
@@ -0,0 +1,131 @@
+import { setTimeout as delay } from "node:timers/promises";
+import { beforeAll, beforeEach, describe, expect, it, vi } from "vitest";
+import { cells } from "../../schema/cells";
+import type { CellConfigImpact } from "../../services/config-impact";
+import {
+  type ConfigWatcherDependencies,
+  createConfigWatcher,
+} from "../../services/config-watcher";
+import type { ConfigReloadEvent } from "../../services/events";
+import { setupTestDb, testDb } from "../test-db";
+
+const WORKSPACE = { id: "workspace-watch", path: "/tmp/workspace-watch" };
+const CELL_ID = "cell-watch";
+
+describe("config watcher", () => {
+  beforeAll(async () => {
+    await setupTestDb();
+  });
+
+  beforeEach(async () => {
+    await testDb.delete(cells);
+    await testDb.insert(cells).values({
+      id: CELL_ID,
+      name: "Watched cell",
+      templateId: "basic",
+      workspacePath: "/tmp/workspace-watch/cell",
+      workspaceId: WORKSPACE.id,
+      workspaceRootPath: WORKSPACE.path,
+      createdAt: new Date(),
+      status: "ready",
+    });
+  });
+
+  it("describes a workspace for new subscribers without dropping caches", async () => {
+    const { watcher, clearConfig, reloadTemplates, previewCellConfig } =
+      createHarness();
+
+    const event = await watcher.describe(WORKSPACE.id);
+
+    expect(event).toMatchObject({
+      workspaceId: WORKSPACE.id,
+      status: "valid",
+      cells: [{ cellId: CELL_ID }],
+    });
+    expect(previewCellConfig).toHaveBeenCalledWith(CELL_ID);
+    expect(clearConfig).not.toHaveBeenCalled();
+    expect(reloadTemplates).not.toHaveBeenCalled();
+  });
+
+  it("drops cached config on a file change and reports invalid configs", async () => {
+    let notifyChange: (filename: string | null) => void = () => undefined;
+    const published: ConfigReloadEvent[] = [];
+    const { watcher, clearConfig, reloadTemplates } = createHarness({
+      watchDirectory: (_directory, onChange) => {
+        notifyChange = onChange;
+        return () => undefined;
+      },
+      loadConfig: () => Promise.reject(new Error("templates.web: Required")),
+      publish: (event) => published.push(event),
+    });
+
+    await watcher.sync();
+    notifyChange("hive.config.json");
+    await delay(20);
+    watcher.stop();
+
+    expect(clearConfig).toHaveBeenCalledWith(WORKSPACE.path);
+    expect(reloadTemplates).not.toHaveBeenCalled();
+    expect(published).toEqual([
+      expect.objectContaining({
+        status: "invalid",
+        error: "templates.web: Required",
+        cells: [],
+      }),
+    ]);
+  });
+
+  it("reloads when a file the config imports changes", async () => {
+    const notifiers = new Map<string, (filename: string | null) => void>();
+    const { watcher, clearConfig } = createHarness({
+      listImports: () => ["/tmp/shared/templates.ts"],
+      watchDirectory: (directory, onChange) => {
+        notifiers.set(directory, onChange);
+        return () => undefined;
+      },
+    });
+
+    await watcher.sync();
+    notifiers.get("/tmp/shared")?.("other.ts");
+    await delay(20);
+    expect(clearConfig).not.toHaveBeenCalled();
+
+    notifiers.get("/tmp/shared")?.("templates.ts");
+    await delay(20);
+    watcher.stop();
+
+    expect([...notifiers.keys()]).toEqual([WORKSPACE.path, "/tmp/shared"]);
+    expect(clearConfig).toHaveBeenCalledWith(WORKSPACE.path);
+  });
+});
+
+function createHarness(overrides: ConfigWatcherDependencies = {}) {
+  const clearConfig = vi.fn();
+  const reloadTemplates = vi.fn();
+  const previewCellConfig = vi.fn(
+    (cellId: string): Promise<CellConfigImpact | null> =>
+      Promise.resolve({
+        cellId,
+        cellName: "Watched cell",
+        workspaceId: WORKSPACE.id,
+        templateId: "basic",
+        services: [
+          { serviceId: null, name: "web", change: "added", fields: [] },
+        ],
+      })
+  );
+  const watcher = createConfigWatcher({
+    db: testDb,
+    listWorkspaces: () => Promise.resolve([WORKSPACE]),
+    watchDirectory: () => () => undefined,
+    loadConfig: () => Promise.resolve({}),
+    listImports: () => [],
+    clearConfig,
+    reloadTemplates,
+    previewCellConfig,
+    publish: () => undefined,
+    debounceMs: 0,
+    ...overrides,
+  });
+  return { watcher, clearConfig, reloadTemplates, previewCellConfig };
+}
@@ -1,21 +1,28 @@
-import { randomUUID } from "node:crypto";
+import { randomBytes, randomUUID } from "node:crypto";
 import { mkdtemp, rm } from "node:fs/promises";
 import { createServer } from "node:net";
 import { tmpdir } from "node:os";
 import { join } from "node:path";
-import { eq } from "drizzle-orm";
+import { setTimeout as delay } from "node:timers/promises";
+import { and, eq } from "drizzle-orm";
 import { afterEach, beforeAll, beforeEach, describe, expect, it } from "vitest";
 import { resolveWorkspaceRoot } from "../../config/context";
+import type { HiveConfig, Template } from "../../config/schema";
+import { cellActivityEvents } from "../../schema/activity-events";
 import { cells } from "../../schema/cells";
 import { cellServices } from "../../schema/services";
+import { RESTART_STABLE_WINDOW_MS } from "../../services/restart-policy";
 import { createServiceTerminalRuntime } from "../../services/service-terminal";
 import type {
+  EnsureCellServicesTimingEvent,
   ProcessHandle,
   RunCommand,
   SpawnProcess,
   SpawnProcessOptions,
+  SupervisorDependencies,
 } from "../../services/supervisor";
 import { createServiceSupervisor } from "../../services/supervisor";
+import { createWorkspaceSecretStore } from "../../services/workspace-secrets";
 import { setupTestDb, testDb } from "../test-db";
 
 const silentLogger = {
@@ -100,6 +107,524 @@ describe("service supervisor", () => {
     await Promise.all(harness.processes.map((proc) => proc.handle.exited));
   });
 
+  it("runs docker services in a cell-scoped container with mapped ports", async () => {
+    const workspace = await createWorkspaceDir();
+    const cell = await insertCell(workspace, "template-docker");
+
+    const harness = createHarness();
+
+    await harness.supervisor.ensureCellServices({
+      cell,
+      template: {
+        id: "template-docker",
+        label: "Template",
+        type: "manual",
+        services: {
+          api: {
+            type: "process",
+            run: "bun run dev",
+            env: {
+              DATABASE_URL: "postgres://postgres@localhost:$PORT:db/app",
+            },
+          },
+          db: {
+            type: "docker",
+            image: "postgres:16",
+            ports: ["$PORT:5432"],
+            env: {
+              POSTGRES_PASSWORD: "secret",
+              API_ORIGIN: "http://localhost:${PORT:api}",
+            },
+            volumes: ["./.hive/pgdata:/var/lib/postgresql/data"],
+          },
+        },
+      },
+    });
+
+    const rows = await testDb
+      .select()
+      .from(cellServices)
+      .where(eq(cellServices.cellId, cell.id));
+    const dbService = rows.find((row) => row.name === "db");
+    const apiService = rows.find((row) => row.name === "api");
+    if (!(dbService?.port && apiService?.port)) {
+      throw new Error("Expected docker and process service records");
+    }
+
+    expect(dbService.type).toBe("docker");
+    expect(dbService.status).toBe("running");
+    expect(dbService.command).toBe("docker run postgres:16");
+
+    const containerName = `hive-${cell.id}-db`;
+    const dockerCall = harness.processes.find((proc) =>
+      proc.options.command.startsWith("docker run")
+    );
+    if (!dockerCall) {
+      throw new Error("Expected docker run to be spawned");
+    }
+
+    const { command, env } = dockerCall.options;
+    expect(command).toContain(`--name ${containerName}`);
+    expect(command).toContain(`-p ${dbService.port}:5432`);
+    expect(command).toContain("-e POSTGRES_PASSWORD");
+    expect(command).toContain(
+      `-v ${join(workspace, ".hive/pgdata")}:/var/lib/postgresql/data`
+    );
+    expect(command.endsWith("postgres:16")).toBe(true);
+    expect(env.POSTGRES_PASSWORD).toBe("secret");
+    expect(env.API_ORIGIN).toBe(`http://localhost:${apiService.port}`);
+    expect(harness.runCommandCalls).toContain(
+      `docker rm --force ${containerName}`
+    );
+
+    const apiCall = harness.processes.find(
+      (proc) => proc.options.command === "bun run dev"
+    );
+    expect(apiCall?.options.env.DATABASE_URL).toBe(
+      `postgres://postgres@localhost:${dbService.port}/app`
+    );
+
+    const output = harness.terminalRuntime.readServiceOutput(dbService.id);
+    expect(output).toContain("[mock] started docker run");
+
+    await harness.supervisor.stopCellService(dbService.id);
+    expect(harness.runCommandCalls).toContain(
+      `docker stop --time 5 ${containerName}`
+    );
+
+    const [stopped] = await testDb
+      .select()
+      .from(cellServices)
+      .where(eq(cellServices.id, dbService.id));
+    expect(stopped?.status).toBe("stopped");
+
+    await harness.supervisor.startCellService(dbService.id);
+    const [restarted] = await testDb
+      .select()
+      .from(cellServices)
+      .where(eq(cellServices.id, dbService.id));
+    expect(restarted?.status).toBe("running");
+
+    await harness.supervisor.stopCellServices(cell.id, {
+      releasePorts: true,
+    });
+    await Promise.all(harness.processes.map((proc) => proc.handle.exited));
+  });
+
+  it("tracks compose services as rows under a cell-scoped project", async () => {
+    const workspace = await createWorkspaceDir();
+    const cell = await insertCell(workspace, "template-compose");
+
+    const harness = createHarness();
+
+    await harness.supervisor.ensureCellServices({
+      cell,
+      template: {
+        id: "template-compose",
+        label: "Template",
+        type: "manual",
+        services: {
+          stack: {
+            type: "compose",
+            file: "docker-compose.yml",
+            services: ["db", "cache"],
+            env: { POSTGRES_PASSWORD: "secret" },
+          },
+        },
+      },
+    });
+
+    const rows = await testDb
+      .select()
+      .from(cellServices)
+      .where(eq(cellServices.cellId, cell.id));
+    expect(rows.map((row) => row.name).sort()).toEqual(["cache", "db"]);
+    expect(rows.every((row) => row.type === "compose")).toBe(true);
+    expect(rows.every((row) => row.status === "running")).toBe(true);
+
+    const project = `hive-${cell.id}`;
+    const composeFile = join(workspace, "docker-compose.yml");
+    const prefix = `docker compose --project-name ${project} --file ${composeFile}`;
+    expect(harness.runCommandCalls).toContain(`${prefix} up --detach db`);
+    expect(harness.runCommandCalls).toContain(`${prefix} up --detach cache`);
+
+    const logFollowers = harness.processes.map((proc) => proc.options.command);
+    expect(logFollowers).toContain(
+      `${prefix} logs --follow --no-log-prefix db`
+    );
+    const dbCall = harness.processes.find((proc) =>
+      proc.options.command.endsWith("db")
+    );
+    expect(dbCall?.options.cwd).toBe(workspace);
+    expect(dbCall?.options.env.POSTGRES_PASSWORD).toBe("secret");
+    expect(dbCall?.options.env.CACHE_PORT).toBeDefined();
+
+    await harness.supervisor.teardownCellServices(cell.id);
+
+    expect(harness.runCommandCalls).toContain(`${prefix} stop --timeout 5 db`);
+    expect(harness.runCommandCalls).toContain(
+      `${prefix} down --volumes --remove-orphans`
+    );
+
+    const stopped = await testDb
+      .select()
+      .from(cellServices)
+      .where(eq(cellServices.cellId, cell.id));
+    expect(stopped.every((row) => row.status === "stopped")).toBe(true);
+    await Promise.all(harness.processes.map((proc) => proc.handle.exited));
+  });
+
+  it("marks services running only after readiness passes", async () => {
+    const workspace = await createWorkspaceDir();
+    const cell = await insertCell(workspace, "template-ready");
+
+    const harness = createHarness();
+    const timingEvents: EnsureCellServicesTimingEvent[] = [];
+
+    await harness.supervisor.ensureCellServices({
+      cell,
+      template: {
+        id: "template-ready",
+        label: "Template",
+        type: "manual",
+        services: {
+          web: {
+            type: "process",
+            run: "bun run dev",
+            readiness: {
+              type: "log",
+              pattern: "started bun run dev",
+              intervalMs: 10,
+            },
+          },
+        },
+      },
+      onTimingEvent: (event) => timingEvents.push(event),
+    });
+
+    const [service] = await testDb
+      .select()
+      .from(cellServices)
+      .where(eq(cellServices.cellId, cell.id));
+    expect(service?.status).toBe("running");
+    expect(service?.lastKnownError).toBeNull();
+
+    const readyEvent = timingEvents.find(
+      (event) => event.step === "service_ready:web"
+    );
+    expect(readyEvent?.status).toBe("ok");
+    expect(readyEvent?.metadata?.probe).toBe("log");
+
+    await harness.supervisor.stopCellServices(cell.id, {
+      releasePorts: true,
+    });
+    await Promise.all(harness.processes.map((proc) => proc.handle.exited));
+  });
+
+  it("surfaces readiness failures in lastKnownError and timing events", async () => {
+    const workspace = await createWorkspaceDir();
+    const cell = await insertCell(workspace, "template-not-ready");
+
+    const harness = createHarness();
+    const timingEvents: EnsureCellServicesTimingEvent[] = [];
+
+    await expect(
+      harness.supervisor.ensureCellServices({
+        cell,
+        template: {
+          id: "template-not-ready",
+          label: "Template",
+          type: "manual",
+          services: {
+            web: {
+              type: "process",
+              run: "bun run dev",
+              readiness: {
+                type: "log",
+                pattern: "listening",
+                intervalMs: 10,
+                timeoutMs: 50,
+              },
+            },
+          },
+        },
+        onTimingEvent: (event) => timingEvents.push(event),
+      })
+    ).rejects.toThrow("timed out after 50ms");
+
+    const [service] = await testDb
+      .select()
+      .from(cellServices)
+      .where(eq(cellServices.cellId, cell.id));
+    expect(service?.status).toBe("error");
+    expect(service?.pid).toBeNull();
+    expect(service?.lastKnownError).toContain(
+      "Readiness check (output matching /listening/) timed out after 50ms"
+    );
+
+    const readyEvent = timingEvents.find(
+      (event) => event.step === "service_ready:web"
+    );
+    expect(readyEvent?.status).toBe("error");
+    expect(readyEvent?.error).toContain("timed out after 50ms");
+    expect(
+      timingEvents.find((event) => event.step === "service_start:web")?.status
+    ).toBe("error");
+
+    await Promise.all(harness.processes.map((proc) => proc.handle.exited));
+  });
+
+  it("starts services after their dependencies and stops them in reverse", async () => {
+    const workspace = await createWorkspaceDir();
+    const cell = await insertCell(workspace, "template-deps");
+    const template = {
+      id: "template-deps",
+      label: "Template",
+      type: "manual" as const,
+      services: {
+        web: {
+          type: "process" as const,
+          run: "bun run web",
+          stop: "stop-web",
+          dependsOn: ["api"],
+        },
+        api: {
+          type: "process" as const,
+          run: "bun run api",
+          stop: "stop-api",
+          dependsOn: ["db"],
+        },
+        db: { type: "process" as const, run: "bun run db", stop: "stop-db" },
+      },
+    };
+
+    const harness = createHarness({
+      loadHiveConfig: () =>
+        Promise.resolve({
+          promptSources: [],
+          templates: { [template.id]: template },
+        }),
+    });
+
+    await harness.supervisor.ensureCellServices({ cell, template });
+
+    expect(harness.processes.map((proc) => proc.options.command)).toEqual([
+      "bun run db",
+      "bun run api",
+      "bun run web",
+    ]);
+
+    await harness.supervisor.stopCellServices(cell.id);
+    expect(harness.runCommandCalls).toEqual([
+      "stop-web",
+      "stop-api",
+      "stop-db",
+    ]);
+
+    const [web] = await testDb
+      .select()
+      .from(cellServices)
+      .where(eq(cellServices.name, "web"));
+    if (!web) {
+      throw new Error("Missing web service record");
+    }
+
+    await harness.supervisor.startCellService(web.id);
+    expect(
+      harness.processes.slice(3).map((proc) => proc.options.command)
+    ).toEqual(["bun run db", "bun run api", "bun run web"]);
+
+    await harness.supervisor.stopCellServices(cell.id, {
+      releasePorts: true,
+    });
+    await Promise.all(harness.processes.map((proc) => proc.handle.exited));
+  });
+
+  it("restarts crashed services with backoff until retries run out", async () => {
+    const workspace = await createWorkspaceDir();
+    const cell = await insertCell(workspace, "template-restart-policy");
+
+    const harness = createHarness();
+
+    await harness.supervisor.ensureCellServices({
+      cell,
+      template: {
+        id: "template-restart-policy",
+        label: "Template",
+        type: "manual",
+        services: {
+          worker: {
+            type: "process",
+            run: "bun run worker",
+            restart: { policy: "on-failure", maxRetries: 1, backoffMs: 5 },
+          },
+        },
+      },
+    });
+
+    harness.processes[0]?.exit(1);
+    await waitFor(async () => {
+      const [service] = await testDb
+        .select()
+        .from(cellServices)
+        .where(eq(cellServices.cellId, cell.id));
+      return (
+        service?.status === "running" &&
+        service.pid === harness.processes[1]?.handle.pid
+      );
+    });
+
+    harness.processes[1]?.exit(2);
+    await waitFor(async () => {
+      const [service] = await testDb
+        .select()
+        .from(cellServices)
+        .where(eq(cellServices.cellId, cell.id));
+      return service?.status === "error";
+    });
+
+    const [failed] = await testDb
+      .select()
+      .from(cellServices)
+      .where(eq(cellServices.cellId, cell.id));
+    expect(failed?.lastKnownError).toBe(
+      "Exited with code 2; gave up after 1 restart attempts"
+    );
+    expect(harness.processes).toHaveLength(2);
+
+    const events = await testDb
+      .select()
+      .from(cellActivityEvents)
+      .where(eq(cellActivityEvents.cellId, cell.id));
+    expect(events).toHaveLength(1);
+    expect(events[0]?.type).toBe("service.restart");
+    expect(events[0]?.metadata).toMatchObject({
+      serviceName: "worker",
+      exitCode: 1,
+      attempt: 1,
+    });
+  });
+
+  it("keeps retrying when a restart fails to spawn", async () => {
+    const workspace = await createWorkspaceDir();
+    const cell = await insertCell(workspace, "template-restart-spawn");
+
+    const harness = createHarness();
+
+    await harness.supervisor.ensureCellServices({
+      cell,
+      template: {
+        id: "template-restart-spawn",
+        label: "Template",
+        type: "manual",
+        services: {
+          worker: {
+            type: "process",
+            run: "bun run worker",
+            restart: { policy: "on-failure", maxRetries: 3, backoffMs: 5 },
+          },
+        },
+      },
+    });
+
+    harness.failNextSpawns(1);
+    harness.processes[0]?.exit(1);
+    await waitFor(async () => {
+      const [service] = await testDb
+        .select()
+        .from(cellServices)
+        .where(eq(cellServices.cellId, cell.id));
+      return (
+        service?.status === "running" &&
+        service.pid === harness.processes[1]?.handle.pid
+      );
+    });
+
+    const events = await testDb
+      .select()
+      .from(cellActivityEvents)
+      .where(eq(cellActivityEvents.cellId, cell.id));
+    expect(
+      events.map((event) => (event.metadata as { attempt: number }).attempt)
+    ).toEqual([1, 2]);
+  });
+
+  it("resets restart attempts once a service has stayed up", async () => {
+    const workspace = await createWorkspaceDir();
+    const cell = await insertCell(workspace, "template-restart-stable");
+
+    let clock = Date.now();
+    const harness = createHarness({ now: () => new Date(clock) });
+
+    await harness.supervisor.ensureCellServices({
+      cell,
+      template: {
+        id: "template-restart-stable",
+        label: "Template",
+        type: "manual",
+        services: {
+          worker: {
+            type: "process",
+            run: "bun run worker",
+            restart: { policy: "on-failure", maxRetries: 1, backoffMs: 5 },
+          },
+        },
+      },
+    });
+
+    for (const index of [0, 1]) {
+      clock += RESTART_STABLE_WINDOW_MS;
+      harness.processes[index]?.exit(1);
+      await waitFor(async () => {
+        const [service] = await testDb
+          .select()
+          .from(cellServices)
+          .where(eq(cellServices.cellId, cell.id));
+        return (
+          service?.status === "running" &&
+          service.pid === harness.processes[index + 1]?.handle.pid
+        );
+      });
+    }
+
+    expect(harness.processes).toHaveLength(3);
+  });
+
+  it("does not restart services that exit cleanly under on-failure", async () => {
+    const workspace = await createWorkspaceDir();
+    const cell = await insertCell(workspace, "template-restart-clean");
+
+    const harness = createHarness();
+
+    await harness.supervisor.ensureCellServices({
+      cell,
+      template: {
+        id: "template-restart-clean",
+        label: "Template",
+        type: "manual",
+        services: {
+          job: {
+            type: "process",
+            run: "bun run job",
+            restart: { policy: "on-failure", backoffMs: 5 },
+          },
+        },
+      },
+    });
+
+    harness.processes[0]?.exit(0);
+    await waitFor(async () => {
+      const [service] = await testDb
+        .select()
+        .from(cellServices)
+        .where(eq(cellServices.cellId, cell.id));
+      return service?.status === "stopped";
+    });
+    await delay(20);
+
+    expect(harness.processes).toHaveLength(1);
+  });
+
   it("captures runtime output in service terminal buffers", async () => {
     const workspace = await createWorkspaceDir();
     const cell = await insertCell(workspace, "template-web");
@@ -134,6 +659,181 @@ describe("service supervisor", () => {
     expect(output).toContain("bun run dev");
   });
 
+  it("injects workspace secrets at spawn without persisting them", async () => {
+    const workspace = await createWorkspaceDir();
+    const cell = await insertCell(workspace, "template-secrets");
+    const key = randomBytes(32);
+    const secrets = createWorkspaceSecretStore({
+      db: testDb,
+      loadKey: () => Promise.resolve(key),
+    });
+    await secrets.set(cell.workspaceId, "DB_PASSWORD", "hunter2-secret");
+
+    const harness = createHarness({ resolveSecretEnv: secrets.resolveEnv });
+
+    await harness.supervisor.ensureCellServices({
+      cell,
+      template: {
+        id: "template-secrets",
+        label: "Template",
+        type: "manual",
+        services: {
+          web: {
+            type: "process",
+            run: "bun run dev",
+            cwd: ".",
+            env: { DATABASE_URL: "postgres://app:${secret:DB_PASSWORD}@db" },
+          },
+        },
+      },
+    });
+
+    const call = harness.processes[0];
+    expect(call?.options.env.DATABASE_URL).toBe(
+      "postgres://app:hunter2-secret@db"
+    );
+    const [service] = await testDb
+      .select()
+      .from(cellServices)
+      .where(eq(cellServices.cellId, cell.id));
+    if (!service) {
+      throw new Error("Expected service record");
+    }
+    expect(service.env.DATABASE_URL).toBe(
+      "postgres://app:${secret:DB_PASSWORD}@db"
+    );
+
+    call?.options.onData?.("connecting with hunter2-secret\n");
+    call?.options.onData?.("retrying with hunt");
+    call?.options.onData?.("er2-secret\n");
+    const output = harness.terminalRuntime.readServiceOutput(service.id);
+    expect(output).toContain("connecting with [redacted]");
+    expect(output).toContain("retrying with [redacted]");
+    expect(output).not.toContain("hunt");
+
+    await harness.supervisor.stopCellServices(cell.id, {
+      releasePorts: true,
+    });
+    await Promise.all(harness.processes.map((proc) => proc.handle.exited));
+  });
+
+  it("fails services that reference a missing secret", async () => {
+    const workspace = await createWorkspaceDir();
+    const cell = await insertCell(workspace, "template-missing-secret");
+    const secrets = createWorkspaceSecretStore({
+      db: testDb,
+      loadKey: () => Promise.resolve(randomBytes(32)),
+    });
+    const harness = createHarness({ resolveSecretEnv: secrets.resolveEnv });
+
+    await expect(
+      harness.supervisor.ensureCellServices({
+        cell,
+        template: {
+          id: "template-missing-secret",
+          label: "Template",
+          type: "manual",
+          services: {
+            web: {
+              type: "process",
+              run: "bun run dev",
+              env: { API_KEY: "${secret:API_KEY}" },
+            },
+          },
+        },
+      })
+    ).rejects.toThrow("Missing workspace secret: API_KEY");
+
+    expect(harness.processes).toHaveLength(0);
+    const [service] = await testDb
+      .select()
+      .from(cellServices)
+      .where(eq(cellServices.cellId, cell.id));
+    expect(service?.status).toBe("error");
+    expect(service?.lastKnownError).toBe("Missing workspace secret: API_KEY");
+  });
+
+  it("applies parameter defaults for cells without stored values", async () => {
+    const workspace = await createWorkspaceDir();
+    const cell = await insertCell(workspace, "template-param-defaults");
+    const template = {
+      id: "template-param-defaults",
+      label: "Template",
+      type: "manual" as const,
+      parameters: [
+        { name: "dataset", type: "string" as const, default: "small" },
+      ],
+      services: {
+        web: {
+          type: "process" as const,
+          run: "bun run seed ${params.dataset}",
+        },
+      },
+    };
+    const harness = createHarness({
+      loadHiveConfig: () =>
+        Promise.resolve({
+          promptSources: [],
+          templates: { [template.id]: template },
+        }),
+    });
+
+    await harness.supervisor.ensureCellServices({ cell });
+
+    expect(harness.processes.map((proc) => proc.options.command)).toEqual([
+      "bun run seed small",
+    ]);
+    await harness.supervisor.stopCellServices(cell.id, { releasePorts: true });
+  });
+
+  it("fails services whose template parameters cannot be resolved", async () => {
+    const workspace = await createWorkspaceDir();
+    const cell = await insertCell(workspace, "template-param-required");
+    const services = {
+      web: { type: "process" as const, run: "bun run seed" },
+    };
+    const initialHarness = createHarness();
+    await initialHarness.supervisor.ensureCellServices({
+      cell,
+      template: {
+        id: "template-param-required",
+        label: "Template",
+        type: "manual",
+        services,
+      },
+    });
+    await initialHarness.supervisor.stopCellServices(cell.id);
+
+    const harness = createHarness({
+      loadHiveConfig: () =>
+        Promise.resolve({
+          promptSources: [],
+          templates: {
+            "template-param-required": {
+              id: "template-param-required",
+              label: "Template",
+              type: "manual",
+              parameters: [{ name: "dataset", type: "string" }],
+              env: { DATASET: "${params.dataset}" },
+              services,
+            },
+          },
+        }),
+    });
+
+    await expect(harness.supervisor.startCellServices(cell.id)).rejects.toThrow(
+      'Parameter "dataset" is required'
+    );
+
+    expect(harness.processes).toHaveLength(0);
+    const [service] = await testDb
+      .select()
+      .from(cellServices)
+      .where(eq(cellServices.cellId, cell.id));
+    expect(service?.status).toBe("error");
+    expect(service?.lastKnownError).toBe('Parameter "dataset" is required');
+  });
+
   it("does not start duplicate services when pid is alive", async () => {
     const workspace = await createWorkspaceDir();
     const cell = await insertCell(workspace, "template-dup");
@@ -438,6 +1138,99 @@ describe("service supervisor", () => {
     );
   });
 
+  it("leaves dependents down when a dependency fails to resume", async () => {
+    const workspace = await createWorkspaceDir();
+    const cell = await insertCell(workspace, "template-resume-deps");
+    const template = {
+      id: "template-resume-deps",
+      label: "Template",
+      type: "manual" as const,
+      services: {
+        web: {
+          type: "process" as const,
+          run: "bun run web",
+          dependsOn: ["db"],
+        },
+        db: { type: "process" as const, run: "bun run db" },
+      },
+    };
+    const loadHiveConfig = () =>
+      Promise.resolve({
+        promptSources: [],
+        templates: { [template.id]: template },
+      });
+
+    const initialHarness = createHarness({ loadHiveConfig });
+    await initialHarness.supervisor.ensureCellServices({ cell, template });
+    await initialHarness.supervisor.stopAll();
+    await Promise.all(
+      initialHarness.processes.map((proc) => proc.handle.exited)
+    );
+
+    const restartHarness = createHarness({ loadHiveConfig });
+    restartHarness.failNextSpawns(1);
+    await restartHarness.supervisor.bootstrap();
+
+    expect(restartHarness.processes).toHaveLength(0);
+    const rows = await testDb
+      .select()
+      .from(cellServices)
+      .where(eq(cellServices.cellId, cell.id));
+    const byName = Object.fromEntries(rows.map((row) => [row.name, row]));
+    expect(byName.db?.status).toBe("error");
+    expect(byName.web?.status).toBe("error");
+    expect(byName.web?.lastKnownError).toBe("Dependency db failed to start");
+  });
+
+  it("leaves dependents down when a dependency was stopped", async () => {
+    const workspace = await createWorkspaceDir();
+    const cell = await insertCell(workspace, "template-stopped-deps");
+    const template = {
+      id: "template-stopped-deps",
+      label: "Template",
+      type: "manual" as const,
+      services: {
+        web: {
+          type: "process" as const,
+          run: "bun run web",
+          dependsOn: ["db"],
+        },
+        db: { type: "process" as const, run: "bun run db" },
+      },
+    };
+    const loadHiveConfig = () =>
+      Promise.resolve({
+        promptSources: [],
+        templates: { [template.id]: template },
+      });
+
+    const initialHarness = createHarness({ loadHiveConfig });
+    await initialHarness.supervisor.ensureCellServices({ cell, template });
+    await initialHarness.supervisor.stopAll();
+    await Promise.all(
+      initialHarness.processes.map((proc) => proc.handle.exited)
+    );
+    await testDb
+      .update(cellServices)
+      .set({ status: "stopped" })
+      .where(
+        and(eq(cellServices.cellId, cell.id), eq(cellServices.name, "db"))
+      );
+
+    const restartHarness = createHarness({ loadHiveConfig });
+    await restartHarness.supervisor.bootstrap();
+
+    expect(restartHarness.processes).toHaveLength(0);
+    const rows = await testDb
+      .select()
+      .from(cellServices)
+      .where(eq(cellServices.cellId, cell.id));
+    const byName = Object.fromEntries(rows.map((row) => [row.name, row]));
+    expect(byName.db?.status).toBe("stopped");
+    expect(byName.web?.status).toBe("error");
+    expect(byName.web?.lastKnownError).toBe("Dependency db is not running");
+  });
+
   it("does not restart manually stopped services during bootstrap", async () => {
     const workspace = await createWorkspaceDir();
     const cell = await insertCell(workspace, "template-manual-stop");
@@ -568,6 +1361,155 @@ describe("service supervisor", () => {
     );
   });
 
+  it("resumes only the services a suspend stopped", async () => {
+    const workspace = await createWorkspaceDir();
+    const cell = await insertCell(workspace, "template-suspend");
+
+    const harness = createHarness();
+
+    await harness.supervisor.ensureCellServices({
+      cell,
+      template: {
+        id: "template-suspend",
+        label: "Template",
+        type: "manual",
+        services: {
+          web: {
+            type: "process",
+            run: "bun run dev",
+            cwd: ".",
+          },
+          worker: {
+            type: "process",
+            run: "bun run worker",
+            cwd: ".",
+          },
+        },
+      },
+    });
+
+    const initialRows = await testDb
+      .select()
+      .from(cellServices)
+      .where(eq(cellServices.cellId, cell.id));
+    const workerService = initialRows.find(
+      (service) => service.name === "worker"
+    );
+    if (!workerService) {
+      throw new Error("Expected worker service to exist");
+    }
+
+    await harness.supervisor.stopCellService(workerService.id);
+    await harness.supervisor.stopCellServices(cell.id, { suspend: true });
+    await Promise.all(harness.processes.map((proc) => proc.handle.exited));
+
+    const suspended = await testDb
+      .select()
+      .from(cellServices)
+      .where(eq(cellServices.cellId, cell.id));
+    expect(
+      Object.fromEntries(suspended.map((row) => [row.name, row.status]))
+    ).toEqual({ web: "needs_resume", worker: "stopped" });
+
+    await harness.supervisor.resumeCellServices(cell.id);
+
+    expect(harness.processes).toHaveLength(3);
+    const resumed = await testDb
+      .select()
+      .from(cellServices)
+      .where(eq(cellServices.cellId, cell.id));
+    expect(
+      Object.fromEntries(resumed.map((row) => [row.name, row.status]))
+    ).toEqual({ web: "running", worker: "stopped" });
+
+    await harness.supervisor.stopAll();
+    await Promise.all(harness.processes.map((proc) => proc.handle.exited));
+  });
+
+  it("previews config changes and restarts only the changed services", async () => {
+    const workspace = await createWorkspaceDir();
+    const cell = await insertCell(workspace, "template-reload");
+    const template: Template = {
+      id: "template-reload",
+      label: "Template",
+      type: "manual",
+      services: {
+        web: { type: "process", run: "bun run dev", cwd: "." },
+        worker: { type: "process", run: "bun run worker", cwd: "." },
+      },
+    };
+    let config: HiveConfig = {
+      promptSources: [],
+      templates: { [template.id]: template },
+    };
+
+    const harness = createHarness({
+      loadHiveConfig: () => Promise.resolve(config),
+    });
+    await harness.supervisor.ensureCellServices({ cell });
+    expect(harness.processes).toHaveLength(2);
+
+    config = {
+      promptSources: [],
+      templates: {
+        [template.id]: {
+          ...template,
+          services: {
+            web: {
+              type: "process",
+              run: "bun run dev",
+              cwd: ".",
+              env: { LOG_LEVEL: "debug" },
+            },
+            worker: { type: "process", run: "bun run worker", cwd: "." },
+            docs: { type: "process", run: "bun run docs", cwd: "." },
+          },
+        },
+      },
+    };
+    harness.supervisor.reloadTemplates();
+
+    const preview = await harness.supervisor.previewCellConfig(cell.id);
+    expect(
+      preview?.services.map(({ name, change, fields }) => ({
+        name,
+        change,
+        fields,
+      }))
+    ).toEqual([
+      {
+        name: "web",
+        change: "modified",
+        fields: [{ path: "env.LOG_LEVEL", after: "debug" }],
+      },
+      { name: "docs", change: "added", fields: [] },
+    ]);
+
+    const workerBefore = await testDb
+      .select()
+      .from(cellServices)
+      .where(eq(cellServices.name, "worker"));
+
+    await harness.supervisor.applyCellConfig(cell.id);
+
+    expect(
+      harness.processes.slice(2).map((proc) => proc.options.command)
+    ).toEqual(["bun run dev", "bun run docs"]);
+    expect(harness.processes[2]?.options.env.LOG_LEVEL).toBe("debug");
+
+    const workerAfter = await testDb
+      .select()
+      .from(cellServices)
+      .where(eq(cellServices.name, "worker"));
+    expect(workerAfter[0]?.pid).toBe(workerBefore[0]?.pid);
+
+    const settled = await harness.supervisor.previewCellConfig(cell.id);
+    expect(settled?.services).toEqual([]);
+
+    await harness.supervisor.stopAll();
+    await Promise.all(harness.processes.map((proc) => proc.handle.exited));
+  });
+
   it("stops running services and clears pid", async () => {
     const workspace = await createWorkspaceDir();
     const cell = await insertCell(workspace, "template-stop");
@@ -742,14 +1684,20 @@ describe("service supervisor", () => {
     return cell;
   }
 
-  function createHarness() {
+  function createHarness(overrides: Partial<SupervisorDependencies> = {}) {
     const processes: FakeProcess[] = [];
     const runCommandCalls: string[] = [];
     let pidCounter = 10_000;
     let clock = Date.now();
+    let pendingSpawnFailures = 0;
     const terminalRuntime = createServiceTerminalRuntime();
 
     const spawnProcess: SpawnProcess = (options) => {
+      if (pendingSpawnFailures > 0) {
+        pendingSpawnFailures -= 1;
+        throw new Error(`spawn failed: ${options.command}`);
+      }
+
       let exit!: (code: number) => void;
       const exited = new Promise<number>((resolveExit) => {
         exit = resolveExit;
@@ -788,9 +1736,31 @@ describe("service supervisor", () => {
       now: () => new Date(clock++),
       logger: silentLogger,
       terminalRuntime,
+      ...overrides,
     });
 
-    return { supervisor, processes, runCommandCalls, terminalRuntime };
+    return {
+      supervisor,
+      processes,
+      runCommandCalls,
+      terminalRuntime,
+      failNextSpawns(count: number) {
+        pendingSpawnFailures = count;
+      },
+    };
+  }
+
+  async function waitFor(
+    condition: () => boolean | Promise<boolean>,
+    timeoutMs = 1000
+  ) {
+    const deadline = Date.now() + timeoutMs;
+    while (!(await condition())) {
+      if (Date.now() > deadline) {
+        throw new Error("Timed out waiting for condition");
+      }
+      await delay(5);
+    }
   }
 
   async function createWorkspaceDir() {
@@ -0,0 +1,318 @@
+import { setTimeout as delay } from "node:timers/promises";
+import { eq } from "drizzle-orm";
+import {
+  afterEach,
+  beforeAll,
+  beforeEach,
+  describe,
+  expect,
+  it,
+  vi,
+} from "vitest";
+import {
+  type WorkspaceWebhook,
+  webhookDeliveries,
+  workspaceWebhooks,
+} from "../../schema/webhooks";
+import {
+  emitAgentStatusUpdate,
+  emitCellStatusUpdate,
+  emitServiceCrash,
+} from "../../services/events";
+import {
+  createWebhookDispatcher,
+  signWebhookPayload,
+  toWebhookNotifications,
+  type WebhookDispatcher,
+} from "../../services/webhooks";
+import { setupTestDb, testDb } from "../test-db";
+
+const WORKSPACE_ID = "workspace-1";
+const SECRET = "test-secret-0123456789";
+const HTTP_OK = 200;
+const HTTP_UNAVAILABLE = 503;
+
+async function insertWebhook(
+  overrides: Partial<WorkspaceWebhook> = {}
+): Promise<WorkspaceWebhook> {
+  const now = new Date();
+  const webhook: WorkspaceWebhook = {
+    id: crypto.randomUUID(),
+    workspaceId: WORKSPACE_ID,
+    url: "https://hooks.example.test/hive",
+    secret: SECRET,
+    events: ["cell.setup_failed", "service.crashed", "agent.status"],
+    enabled: true,
+    createdAt: now,
+    updatedAt: now,
+    ...overrides,
+  };
+  await testDb.insert(workspaceWebhooks).values(webhook);
+  return webhook;
+}
+
+async function waitForDeliveries(
+  webhookId: string,
+  predicate: (rows: (typeof webhookDeliveries.$inferSelect)[]) => boolean
+) {
+  for (let attempt = 0; attempt < 100; attempt += 1) {
+    const rows = await testDb
+      .select()
+      .from(webhookDeliveries)
+      .where(eq(webhookDeliveries.webhookId, webhookId));
+    if (predicate(rows)) {
+      return rows;
+    }
+    await delay(10);
+  }
+  throw new Error("Timed out waiting for webhook deliveries");
+}
+
+describe("toWebhookNotifications", () => {
+  it("reports failed setups as a status change and a setup failure", () => {
+    const notifications = toWebhookNotifications({
+      type: "cell.status",
+      event: {
+        workspaceId: WORKSPACE_ID,
+        cellId: "cell-1",
+        status: "error",
+        lastSetupError: "bun install exited with 1",
+      },
+    });
+    expect(notifications.map((notification) => notification.type)).toEqual([
+      "cell.status",
+      "cell.setup_failed",
+    ]);
+  });
+
+  it("only forwards agent statuses someone may act on", () => {
+    const agentEvent = (status: "working" | "awaiting_input") =>
+      toWebhookNotifications({
+        type: "agent.status",
+        event: {
+          workspaceId: WORKSPACE_ID,
+          cellId: "cell-1",
+          sessionId: "session-1",
+          status,
+          previousStatus: "starting",
+        },
+      });
+    expect(agentEvent("working")).toEqual([]);
+    expect(agentEvent("awaiting_input")).toHaveLength(1);
+  });
+});
+
+describe("webhook dispatcher", () => {
+  let dispatcher: WebhookDispatcher | null = null;
+
+  beforeAll(async () => {
+    await setupTestDb();
+  });
+
+  beforeEach(async () => {
+    await testDb.delete(webhookDeliveries);
+    await testDb.delete(workspaceWebhooks);
+  });
+
+  afterEach(() => {
+    dispatcher?.stop();
+    dispatcher = null;
+  });
+
+  it("signs and delivers subscribed events for the webhook's workspace", async () => {
+    const fetchMock = vi.fn(() =>
+      Promise.resolve(new Response(null, { status: HTTP_OK }))
+    );
+    const webhook = await insertWebhook();
+    await insertWebhook({ workspaceId: "workspace-2" });
+    dispatcher = createWebhookDispatcher({
+      db: testDb,
+      fetch: fetchMock as unknown as typeof fetch,
+    });
+    await dispatcher.start();
+
+    emitAgentStatusUpdate({
+      workspaceId: WORKSPACE_ID,
+      cellId: "cell-1",
+      sessionId: "session-1",
+      status: "awaiting_input",
+      previousStatus: "working",
+    });
+    // Not in the webhook's event filter.
+    emitCellStatusUpdate({
+      workspaceId: WORKSPACE_ID,
+      cellId: "cell-1",
+      status: "ready",
+    });
+
+    const [delivery] = await waitForDeliveries(
+      webhook.id,
+      (rows) => rows[0]?.status === "succeeded"
+    );
+    expect(fetchMock).toHaveBeenCalledTimes(1);
+    expect(delivery).toMatchObject({
+      event: "agent.status",
+      attempts: 1,
+      responseStatus: HTTP_OK,
+      payload: {
+        event: "agent.status",
+        workspaceId: WORKSPACE_ID,
+        cell: { id: "cell-1" },
+        data: { status: "awaiting_input", sessionId: "session-1" },
+      },
+    });
+
+    const [url, init] = fetchMock.mock.calls[0] as unknown as [
+      string,
+      RequestInit & { headers: Record<string, string> },
+    ];
+    expect(url).toBe(webhook.url);
+    const timestamp = init.headers["x-hive-timestamp"] ?? "";
+    expect(init.headers["x-hive-event"]).toBe("agent.status");
+    expect(init.headers["x-hive-signature"]).toBe(
+      signWebhookPayload(SECRET, timestamp, String(init.body))
+    );
+  });
+
+  it("retries failed deliveries and records every attempt", async () => {
+    const fetchMock = vi
+      .fn()
+      .mockResolvedValueOnce(new Response("busy", { status: HTTP_UNAVAILABLE }))
+      .mockRejectedValueOnce(new Error("connect ECONNREFUSED"))
+      .mockResolvedValue(new Response(null, { status: HTTP_OK }));
+    const webhook = await insertWebhook();
+    dispatcher = createWebhookDispatcher({
+      db: testDb,
+      fetch: fetchMock as unknown as typeof fetch,
+      retryDelaysMs: [5, 5],
+    });
+    await dispatcher.start();
+
+    emitServiceCrash({
+      workspaceId: WORKSPACE_ID,
+      cellId: "cell-1",
+      serviceId: "service-1",
+      serviceName: "web",
+      exitCode: 1,
+      willRestart: false,
+    });
+
+    const [delivery] = await waitForDeliveries(
+      webhook.id,
+      (rows) => rows[0]?.status === "succeeded"
+    );
+    expect(fetchMock).toHaveBeenCalledTimes(3);
+    expect(delivery).toMatchObject({
+      event: "service.crashed",
+      attempts: 3,
+      error: null,
+      nextAttemptAt: null,
+    });
+  });
+
+  it("gives up once the retries are used up", async () => {
+    const fetchMock = vi.fn(() =>
+      Promise.resolve(new Response("nope", { status: HTTP_UNAVAILABLE }))
+    );
+    const webhook = await insertWebhook();
+    dispatcher = createWebhookDispatcher({
+      db: testDb,
+      fetch: fetchMock as unknown as typeof fetch,
+      retryDelaysMs: [5],
+    });
+
+    const delivery = await dispatcher.ping(webhook);
+    expect(delivery.status).toBe("pending");
+
+    const [failed] = await waitForDeliveries(
+      webhook.id,
+      (rows) => rows[0]?.status === "failed"
+    );
+    expect(fetchMock).toHaveBeenCalledTimes(2);
+    expect(failed).toMatchObject({
+      event: "ping",
+      attempts: 2,
+      responseStatus: HTTP_UNAVAILABLE,
+      error: "HTTP 503: nope",
+    });
+  });
+
+  it("does not hold up webhooks behind a slow receiver", async () => {
+    let releaseSlow: (() => void) | null = null;
+    const fetchMock = vi.fn((url: string) =>
+      url.includes("slow")
+        ? new Promise<Response>((resolve) => {
+            releaseSlow = () =>
+              resolve(new Response(null, { status: HTTP_OK }));
+          })
+        : Promise.resolve(new Response(null, { status: HTTP_OK }))
+    );
+    const slow = await insertWebhook({ url: "https://slow.example.test/hive" });
+    const fast = await insertWebhook();
+    dispatcher = createWebhookDispatcher({
+      db: testDb,
+      fetch: fetchMock as unknown as typeof fetch,
+    });
+    await dispatcher.start();
+
+    emitServiceCrash({
+      workspaceId: WORKSPACE_ID,
+      cellId: "cell-1",
+      serviceId: "service-1",
+      serviceName: "web",
+      exitCode: 1,
+      willRestart: false,
+    });
+
+    await waitForDeliveries(
+      fast.id,
+      (rows) => rows[0]?.status === "succeeded"
+    );
+    while (!releaseSlow) {
+      await delay(10);
+    }
+    (releaseSlow as () => void)();
+    await waitForDeliveries(
+      slow.id,
+      (rows) => rows[0]?.status === "succeeded"
+    );
+  });
+
+  it("drops pending retries once the webhook is disabled", async () => {
+    const fetchMock = vi.fn(() =>
+      Promise.resolve(new Response("busy", { status: HTTP_UNAVAILABLE }))
+    );
+    const webhook = await insertWebhook();
+    dispatcher = createWebhookDispatcher({
+      db: testDb,
+      fetch: fetchMock as unknown as typeof fetch,
+      retryDelaysMs: [50],
+    });
+    await dispatcher.start();
+
+    emitServiceCrash({
+      workspaceId: WORKSPACE_ID,
+      cellId: "cell-1",
+      serviceId: "service-1",
+      serviceName: "web",
+      exitCode: 1,
+      willRestart: false,
+    });
+    await waitForDeliveries(webhook.id, (rows) => rows[0]?.attempts === 1);
+    await testDb
+      .update(workspaceWebhooks)
+      .set({ enabled: false })
+      .where(eq(workspaceWebhooks.id, webhook.id));
+
+    const [dropped] = await waitForDeliveries(
+      webhook.id,
+      (rows) => rows[0]?.status === "failed"
+    );
+    expect(fetchMock).toHaveBeenCalledTimes(1);
+    expect(dropped).toMatchObject({
+      attempts: 1,
+      error: "Webhook is disabled",
+      nextAttemptAt: null,
+    });
+  });
+});
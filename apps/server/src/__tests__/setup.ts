@@ -2,7 +2,8 @@ import { afterAll, beforeAll } from "vitest";
 
 // Set up test environment variables
 beforeAll(() => {
-  process.env.DATABASE_URL = "file::memory:";
+  // `test:pg` points DATABASE_URL at Postgres; keep it when set.
+  process.env.DATABASE_URL ??= "file::memory:";
 });
 
 afterAll(() => {
@@ -1,32 +1,67 @@
-import { Database } from "bun:sqlite";
 import { join } from "node:path";
 import { fileURLToPath } from "node:url";
-import { drizzle } from "drizzle-orm/bun-sqlite";
-import { migrate } from "drizzle-orm/bun-sqlite/migrator";
-import { schema } from "../schema";
+import type { Database } from "bun:sqlite";
+import type { SQL } from "bun";
+import { databaseEngine, databaseUrl } from "../config/database";
+import {
+  createEngineDatabase,
+  migrateDatabase,
+  resolveMigrationsDirectoryName,
+  toHiveDatabase,
+} from "../db";
 
-// Use in-memory SQLite database for tests (same as production, but in-memory)
-const sqlite = new Database(":memory:");
+// Tests use in-memory SQLite unless DATABASE_URL points at Postgres (see
+// `bun run test:pg`), in which case the schema is rebuilt in that database.
 let setupPromise: Promise<void> | null = null;
 
-export const testDb = drizzle(sqlite, { schema });
+const testEngineDb = createEngineDatabase(
+  databaseEngine,
+  databaseEngine === "postgres" ? databaseUrl : ":memory:"
+);
+
+export const testDb = toHiveDatabase(testEngineDb);
+
+function resetSqliteDatabase(sqlite: Database) {
+  sqlite.exec("DROP TABLE IF EXISTS workspace_secrets;");
+  sqlite.exec("DROP TABLE IF EXISTS webhook_deliveries;");
+  sqlite.exec("DROP TABLE IF EXISTS workspace_webhooks;");
+  sqlite.exec("DROP TABLE IF EXISTS cell_shares;");
+  sqlite.exec("DROP TABLE IF EXISTS api_tokens;");
+  sqlite.exec("DROP TABLE IF EXISTS cell_snapshots;");
+  sqlite.exec("DROP TABLE IF EXISTS cell_timing_events;");
+  sqlite.exec("DROP TABLE IF EXISTS cell_activity_events;");
+  sqlite.exec("DROP TABLE IF EXISTS cell_resource_rollups;");
+  sqlite.exec("DROP TABLE IF EXISTS cell_resource_history;");
+  sqlite.exec("DROP TABLE IF EXISTS cell_services;");
+  sqlite.exec("DROP TABLE IF EXISTS cell_provisioning_state;");
+  sqlite.exec("DROP TABLE IF EXISTS cells;");
+  sqlite.exec("DROP TABLE IF EXISTS users;");
+  sqlite.exec("DROP TABLE IF EXISTS workspace_linear_integrations;");
+  sqlite.exec("DROP TABLE IF EXISTS __drizzle_migrations;");
+}
+
+async function resetPgDatabase(client: SQL) {
+  await client.unsafe("DROP SCHEMA IF EXISTS drizzle CASCADE;");
+  await client.unsafe("DROP SCHEMA IF EXISTS public CASCADE;");
+  await client.unsafe("CREATE SCHEMA public;");
+}
 
 export async function setupTestDb() {
   if (!setupPromise) {
     setupPromise = (async () => {
-      sqlite.exec("DROP TABLE IF EXISTS cell_timing_events;");
-      sqlite.exec("DROP TABLE IF EXISTS cell_activity_events;");
-      sqlite.exec("DROP TABLE IF EXISTS cell_resource_rollups;");
-      sqlite.exec("DROP TABLE IF EXISTS cell_resource_history;");
-      sqlite.exec("DROP TABLE IF EXISTS cell_services;");
-      sqlite.exec("DROP TABLE IF EXISTS cell_provisioning_state;");
-      sqlite.exec("DROP TABLE IF EXISTS cells;");
-      sqlite.exec("DROP TABLE IF EXISTS workspace_linear_integrations;");
-      sqlite.exec("DROP TABLE IF EXISTS __drizzle_migrations;");
+      if (testEngineDb.engine === "postgres") {
+        await resetPgDatabase(testEngineDb.client.$client);
+      } else {
+        resetSqliteDatabase(testEngineDb.client.$client);
+      }
 
       const packageRoot = fileURLToPath(new URL("../..", import.meta.url));
-      const migrationsFolder = join(packageRoot, "src", "migrations");
-      await migrate(testDb, { migrationsFolder });
+      const migrationsFolder = join(
+        packageRoot,
+        "src",
+        resolveMigrationsDirectoryName(databaseEngine)
+      );
+      await migrateDatabase(testEngineDb, migrationsFolder);
     })();
   }
 
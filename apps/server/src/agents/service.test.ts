@@ -1,3 +1,5 @@
+import { readFile } from "node:fs/promises";
+import { join } from "node:path";
 import type { OpencodeClient, Event as OpencodeEvent } from "@opencode-ai/sdk";
 import { eq } from "drizzle-orm";
 
@@ -12,6 +14,7 @@ import {
   vi,
 } from "vitest";
 import { setupTestDb, testDb } from "../__tests__/test-db";
+import { verifyApiToken } from "../auth/tokens";
 import type { HiveConfig } from "../config/schema";
 import { cellProvisioningStates } from "../schema/cell-provisioning";
 import { cells } from "../schema/cells";
@@ -24,6 +27,7 @@ type ClientStub = {
   session: {
     create: ReturnType<typeof vi.fn>;
     delete: ReturnType<typeof vi.fn>;
+    fork: ReturnType<typeof vi.fn>;
     get: ReturnType<typeof vi.fn>;
     messages: ReturnType<typeof vi.fn>;
     prompt: ReturnType<typeof vi.fn>;
@@ -81,6 +85,8 @@ describe("agent model selection", () => {
   let loadHiveConfigMock: Mock;
   let loadEffectiveOpencodeDefaultsSpy: Mock;
   let acquireOpencodeClientMock: Mock;
+  let snapshotAgentTurnMock: Mock;
+  let linkAgentTurnSnapshotMock: Mock;
 
   beforeAll(async () => {
     await setupTestDb();
@@ -95,6 +101,8 @@ describe("agent model selection", () => {
     );
 
     loadHiveConfigMock = vi.fn(async () => mockHiveConfig);
+    snapshotAgentTurnMock = vi.fn(async () => null);
+    linkAgentTurnSnapshotMock = vi.fn(async () => undefined);
     loadEffectiveOpencodeDefaultsSpy = vi
       .spyOn(OpencodeConfig, "loadEffectiveOpencodeDefaults")
       .mockResolvedValue({});
@@ -104,6 +112,8 @@ describe("agent model selection", () => {
       loadHiveConfig: loadHiveConfigMock,
       loadEffectiveOpencodeDefaults: loadEffectiveOpencodeDefaultsSpy,
       acquireOpencodeClient: acquireOpencodeClientMock,
+      snapshotAgentTurn: snapshotAgentTurnMock,
+      linkAgentTurnSnapshot: linkAgentTurnSnapshotMock,
     });
 
     await closeAllAgentSessions();
@@ -130,6 +140,20 @@ describe("agent model selection", () => {
     vi.restoreAllMocks();
   });
 
+  it("writes a token scoped to the cell for the Hive tools", async () => {
+    await ensureAgentSession(cellId);
+
+    const hiveDirectory = join("/tmp/model-test", ".hive");
+    const token = (
+      await readFile(join(hiveDirectory, "api-token"), "utf8")
+    ).trim();
+    const record = await verifyApiToken(testDb as unknown as AppDb, token);
+    expect(record).toMatchObject({ cellId, scope: "operate", userId: null });
+    expect(await readFile(join(hiveDirectory, ".gitignore"), "utf8")).toContain(
+      "api-token"
+    );
+  });
+
   it("hydrates runtime model from the last user message", async () => {
     sessionMessagesMock.mockResolvedValueOnce({
       data: [
@@ -779,6 +803,119 @@ describe("agent model selection", () => {
     ).toBe(true);
   });
 
+  it("snapshots messages sent outside Hive once per message", async () => {
+    const userMessage = (id: string) =>
+      ({
+        type: "message.updated",
+        properties: {
+          info: { id, sessionID: "session-runtime", role: "user" },
+        },
+      }) as unknown as OpencodeEvent;
+    const clientStubWithEvents = buildClientStubWithEvents([
+      userMessage("msg-1"),
+      userMessage("msg-1"),
+      userMessage("msg-2"),
+    ]);
+    acquireOpencodeClientMock = vi.fn(
+      async () => clientStubWithEvents as unknown as OpencodeClient
+    );
+    setAgentRuntimeDependencies({
+      acquireOpencodeClient: acquireOpencodeClientMock,
+    });
+
+    await ensureAgentSession(cellId);
+    await new Promise((resolve) => setTimeout(resolve, 0));
+
+    expect(
+      snapshotAgentTurnMock.mock.calls.map(([cell, messageId]) => [
+        (cell as { id: string }).id,
+        messageId,
+      ])
+    ).toEqual([
+      [cellId, "msg-1"],
+      [cellId, "msg-2"],
+    ]);
+  });
+
+  it("snapshots the worktree before sending a prompt", async () => {
+    const userMessage = (id: string) =>
+      ({
+        type: "message.updated",
+        properties: {
+          info: { id, sessionID: "session-runtime", role: "user" },
+        },
+      }) as unknown as OpencodeEvent;
+    const steps: string[] = [];
+    let releaseEvents: () => void = () => undefined;
+    const promptSent = new Promise<void>((resolve) => {
+      releaseEvents = resolve;
+    });
+    clientStub.event.subscribe = vi.fn(async () => ({
+      stream: (async function* () {
+        await promptSent;
+        yield userMessage("msg-1");
+        yield userMessage("msg-1");
+      })(),
+    }));
+    clientStub.session.prompt = vi.fn(async () => {
+      steps.push("prompt");
+      releaseEvents();
+      return { error: null };
+    });
+    snapshotAgentTurnMock.mockImplementation(async () => {
+      steps.push("snapshot");
+      return { id: "snapshot-1" };
+    });
+
+    const session = await ensureAgentSession(cellId);
+    await sendAgentMessage(session.id, "Edit the readme");
+    await new Promise((resolve) => setTimeout(resolve, 0));
+
+    expect(steps).toEqual(["snapshot", "prompt"]);
+    expect(snapshotAgentTurnMock.mock.calls).toEqual([
+      [expect.objectContaining({ id: cellId })],
+    ]);
+    expect(linkAgentTurnSnapshotMock.mock.calls).toEqual([
+      ["snapshot-1", "msg-1"],
+    ]);
+  });
+
+  it("reports a prompt as delivered before its turn ends", async () => {
+    let releaseEvents: () => void = () => undefined;
+    const promptStarted = new Promise<void>((resolve) => {
+      releaseEvents = resolve;
+    });
+    let finishTurn: () => void = () => undefined;
+    clientStub.event.subscribe = vi.fn(async () => ({
+      stream: (async function* () {
+        await promptStarted;
+        yield {
+          type: "message.updated",
+          properties: {
+            info: { id: "msg-1", sessionID: "session-runtime", role: "user" },
+          },
+        } as unknown as OpencodeEvent;
+      })(),
+    }));
+    clientStub.session.prompt = vi.fn(() => {
+      releaseEvents();
+      return new Promise((resolve) => {
+        finishTurn = () => resolve({ error: null });
+      });
+    });
+    const onDelivered = vi.fn();
+
+    const session = await ensureAgentSession(cellId);
+    const turn = sendAgentMessage(session.id, "Start the long task", {
+      onDelivered,
+    });
+    await vi.waitFor(() => expect(onDelivered).toHaveBeenCalledTimes(1));
+
+    finishTurn();
+    await turn;
+    expect(onDelivered).toHaveBeenCalledTimes(1);
+  });
+
   it("tracks mode transitions from plan to build", async () => {
     const modeEvent = {
       type: "message.updated",
@@ -1023,6 +1160,21 @@ describe("agent model selection", () => {
     expect(cell?.resumeAgentSessionOnStartup).toBe(true);
   });
 
+  it("forks the requested session instead of creating an empty one", async () => {
+    const session = await ensureAgentSession(cellId, {
+      forkFromSessionId: "session-source",
+    });
+
+    expect(session.id).toBe("session-forked");
+    expect(clientStub.session.create).not.toHaveBeenCalled();
+    expect(clientStub.session.fork).toHaveBeenCalledWith({
+      path: { id: "session-source" },
+      query: { directory: "/tmp/model-test" },
+    });
+    const [row] = await testDb.select().from(cells).where(eq(cells.id, cellId));
+    expect(row?.opencodeSessionId).toBe("session-forked");
+  });
+
   it("deletes remote opencode session when runtime stops", async () => {
     const session = await ensureAgentSession(cellId);
 
@@ -1074,6 +1226,9 @@ function buildClientStub(): ClientStub {
   const session = {
     create: vi.fn(async () => ({ data: createMockSession() })),
     delete: vi.fn(async () => ({ error: null })),
+    fork: vi.fn(async () => ({
+      data: { ...createMockSession(), id: "session-forked" },
+    })),
     get: vi.fn(async () => ({ data: createMockSession() })),
     messages: sessionMessagesMock,
     prompt: vi.fn(async () => ({ error: null })),
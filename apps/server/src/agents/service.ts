@@ -9,13 +9,20 @@ import type {
   Part,
   Session,
 } from "@opencode-ai/sdk";
-import { eq, inArray } from "drizzle-orm";
+import { and, eq, inArray, ne } from "drizzle-orm";
+import { issueCellApiToken } from "../auth/tokens";
 import { loadHiveConfig } from "../config/context";
 import type { HiveConfig, Template } from "../config/schema";
 import { db } from "../db";
 import { cellProvisioningStates } from "../schema/cell-provisioning";
 import { type Cell, cells } from "../schema/cells";
 import { type CellService, cellServices } from "../schema/services";
+import {
+  captureAgentTurnSnapshot,
+  linkAgentTurnSnapshot,
+} from "../services/cell-snapshots";
+import { emitAgentStatusUpdate } from "../services/events";
+import { collectSecretReferences } from "../services/workspace-secrets";
 import { publishAgentEvent } from "./events";
 import {
   loadEffectiveOpencodeDefaults,
@@ -37,6 +44,8 @@ const cellSessionMap = new Map<string, string>();
 const DEFAULT_SERVICE_HOST = process.env.SERVICE_HOST ?? "localhost";
 const DEFAULT_SERVICE_PROTOCOL = process.env.SERVICE_PROTOCOL ?? "http";
 const HIVE_INSTRUCTIONS_RELATIVE_PATH = ".hive/instructions.md";
+const HIVE_API_TOKEN_RELATIVE_PATH = ".hive/api-token";
+const HIVE_DIRECTORY_GITIGNORE = "api-token\n";
 
 type DirectoryQuery = {
   directory?: string;
@@ -204,9 +213,38 @@ function buildEnvironmentVariableLines(
     lines.push("- Service ports will populate once services start.");
   }
 
+  const secretVariables = collectSecretEnvNames(context.template);
+  if (secretVariables.length > 0) {
+    lines.push("", "### Secret Variables");
+    for (const name of secretVariables) {
+      lines.push(`- ${name}=[redacted]`);
+    }
+    lines.push(
+      "- Filled from workspace secrets in service and setup processes only; never print or commit their values."
+    );
+  }
+
   return lines;
 }
 
+function collectSecretEnvNames(template: Template): string[] {
+  const envs = [
+    template.env ?? {},
+    ...Object.values(template.services ?? {}).map(
+      (service) => service.env ?? {}
+    ),
+  ];
+  const names = new Set<string>();
+  for (const env of envs) {
+    for (const [name, value] of Object.entries(env)) {
+      if (collectSecretReferences({ [name]: value }).length > 0) {
+        names.add(name);
+      }
+    }
+  }
+  return [...names].sort();
+}
+
 function buildToolLines(): string[] {
   const lines = [
     "## Hive Tools",
@@ -219,6 +257,9 @@ function buildToolLines(): string[] {
     "- `hive_restart_service` - Restart ONE service (recommended default). Requires confirm=true.",
     "- `hive_restart_services` - Restart ALL services (higher blast radius). Requires confirm=true.",
     "- `hive_rerun_setup` - Re-run setup/provisioning commands if initialization failed. Requires confirm=true.",
+    "- `hive_commit_changes` - Commit worktree changes on the cell branch. Pushing requires push=true and confirm=true.",
+    "- `hive_open_pull_request` - Push the cell branch and open a pull request. Requires confirm=true.",
+    "- `hive_sync_with_base` - Rebase (or merge) the cell branch onto the latest base. Conflicts are left in the worktree for you to resolve.",
     "",
     "WHEN TO USE:",
     "- Something not working? → Call hive_services to see service status and errors",
@@ -227,6 +268,8 @@ function buildToolLines(): string[] {
     "- One service stuck/crashed? → Call hive_restart_service (confirm=true) then re-check with hive_services",
     "- Whole cell wedged? → Call hive_restart_services (confirm=true) then re-check with hive_services",
     "- Setup failed / dependencies broken? → Fix workspace then call hive_rerun_setup (confirm=true)",
+    "- Work ready for review? → Call hive_commit_changes, then hive_open_pull_request (confirm=true)",
+    "- Base branch moved on? → Commit, call hive_sync_with_base, resolve any conflicts, then call it again",
     "",
     "DO NOT ask the user for logs - use these tools to get them yourself.",
   ];
@@ -263,6 +306,34 @@ async function writeHiveSessionInstructions(
   await writeFile(instructionsPath, content, "utf8");
 }
 
+/**
+ * Hands the Hive tools in this worktree a token that only works for this
+ * cell. It lives in the worktree rather than the environment so services and
+ * terminals in other cells cannot pick it up, and is kept out of git.
+ */
+async function writeCellApiToken(
+  deps: AgentRuntimeDependencies,
+  cell: Cell
+): Promise<void> {
+  const hiveDirectory = join(cell.workspacePath, ".hive");
+  await mkdir(hiveDirectory, { recursive: true });
+  await writeFile(
+    join(hiveDirectory, ".gitignore"),
+    HIVE_DIRECTORY_GITIGNORE,
+    { encoding: "utf8", flag: "wx" }
+  ).catch((error: NodeJS.ErrnoException) => {
+    if (error.code !== "EEXIST") {
+      throw error;
+    }
+  });
+  const token = await deps.issueCellApiToken(cell.id);
+  await writeFile(
+    join(cell.workspacePath, HIVE_API_TOKEN_RELATIVE_PATH),
+    `${token}\n`,
+    { encoding: "utf8", mode: 0o600 }
+  );
+}
+
 type RuntimeCompactionState = {
   count: number;
   lastCompactionAt: string | null;
@@ -280,19 +351,43 @@ type RuntimeHandle = {
   status: AgentSessionStatus;
   pendingInterrupt: boolean;
   compaction: RuntimeCompactionState;
+  snapshottedMessageIds: Set<string>;
+  pendingTurnSnapshots: PendingTurnSnapshot[];
   startMode: AgentMode;
   currentMode: AgentMode;
   modeUpdatedAt: string;
-  sendMessage: (content: string) => Promise<void>;
+  sendMessage: (
+    content: string,
+    options?: SendAgentMessageOptions
+  ) => Promise<void>;
   stop: (options?: StopRuntimeOptions) => Promise<void>;
 };
 
+export type SendAgentMessageOptions = {
+  /**
+   * Called once the agent has the prompt, which is when its user message
+   * exists. The promise only settles when the whole turn ends.
+   */
+  onDelivered?: () => void;
+};
+
+/**
+ * A restore point taken before a prompt was sent, waiting for the user
+ * message it belongs to. `null` marks prompts that start no turn.
+ */
+type PendingTurnSnapshot = {
+  snapshotId: string | null;
+  onDelivered?: () => void;
+};
+
 type EnsureAgentSessionOptions = {
   force?: boolean;
   modelId?: string;
   providerId?: string;
   variant?: string;
   startMode?: AgentMode;
+  /** Copy this session's history instead of starting an empty session. */
+  forkFromSessionId?: string;
 };
 
 type StopRuntimeOptions = {
@@ -333,6 +428,15 @@ type AgentRuntimeDependencies = {
   loadEffectiveOpencodeDefaults: typeof loadEffectiveOpencodeDefaults;
   publishAgentEvent: typeof publishAgentEvent;
   acquireOpencodeClient: () => Promise<OpencodeClient>;
+  snapshotAgentTurn: (
+    cell: Cell,
+    messageId?: string
+  ) => Promise<{ id: string } | null>;
+  linkAgentTurnSnapshot: (
+    snapshotId: string,
+    messageId: string
+  ) => Promise<unknown>;
+  issueCellApiToken: (cellId: string) => Promise<string>;
 };
 
 const agentRuntimeOverrides: Partial<AgentRuntimeDependencies> = {};
@@ -361,6 +465,26 @@ const getAgentRuntimeDependencies = (): AgentRuntimeDependencies => ({
     agentRuntimeOverrides.publishAgentEvent ?? publishAgentEvent,
   acquireOpencodeClient:
     agentRuntimeOverrides.acquireOpencodeClient ?? acquireSharedOpencodeClient,
+  snapshotAgentTurn:
+    agentRuntimeOverrides.snapshotAgentTurn ??
+    ((cell, messageId) =>
+      captureAgentTurnSnapshot({
+        database: agentRuntimeOverrides.db ?? db,
+        loadHiveConfig: agentRuntimeOverrides.loadHiveConfig ?? loadHiveConfig,
+        cell,
+        ...(messageId ? { agentMessageId: messageId } : {}),
+      })),
+  linkAgentTurnSnapshot:
+    agentRuntimeOverrides.linkAgentTurnSnapshot ??
+    ((snapshotId, messageId) =>
+      linkAgentTurnSnapshot({
+        database: agentRuntimeOverrides.db ?? db,
+        snapshotId,
+        agentMessageId: messageId,
+      })),
+  issueCellApiToken:
+    agentRuntimeOverrides.issueCellApiToken ??
+    ((cellId) => issueCellApiToken(agentRuntimeOverrides.db ?? db, cellId)),
 });
 
 async function readProviderCredentials(): Promise<ProviderCredentialsStore> {
@@ -1231,11 +1355,38 @@ export async function updateAgentSessionModel(
 }
 
 export async function sendAgentMessage(
+  sessionId: string,
+  content: string,
+  options?: SendAgentMessageOptions
+): Promise<void> {
+  const runtime = await ensureRuntimeForSession(sessionId);
+  await runtime.sendMessage(content, options);
+}
+
+/**
+ * Adds a user message without asking the agent to reply, so later turns see
+ * it as context.
+ */
+export async function appendAgentContext(
   sessionId: string,
   content: string
 ): Promise<void> {
   const runtime = await ensureRuntimeForSession(sessionId);
-  await runtime.sendMessage(content);
+  const pending = enqueueTurnSnapshot(runtime, null);
+  const response = await runtime.client.session.prompt({
+    path: { id: runtime.session.id },
+    query: runtime.directoryQuery,
+    body: {
+      noReply: true,
+      parts: [{ type: "text", text: content }],
+    },
+  });
+  if (response.error) {
+    dropPendingTurnSnapshot(runtime, pending);
+    throw new Error(
+      getRpcErrorMessage(response.error, "Failed to add agent context")
+    );
+  }
 }
 
 export async function interruptAgentSession(sessionId: string): Promise<void> {
@@ -1325,34 +1476,77 @@ export async function resumeAgentSessionsOnStartup(): Promise<void> {
   const cellsToResume = await runtimeDb
     .select()
     .from(cells)
-    .where(eq(cells.resumeAgentSessionOnStartup, true));
+    .where(
+      and(
+        eq(cells.resumeAgentSessionOnStartup, true),
+        ne(cells.status, "suspended")
+      )
+    );
 
   if (cellsToResume.length === 0) {
     return;
   }
 
   for (const cell of cellsToResume) {
-    try {
-      const runtime = await ensureRuntimeForCell(cell.id, { force: false });
-      const shouldResume = await shouldResumeRuntime(runtime);
-      if (shouldResume) {
-        await runtime.sendMessage(RESUME_SESSION_PROMPT);
-        continue;
-      }
-      await runtimeDb
-        .update(cells)
-        .set({ resumeAgentSessionOnStartup: false })
-        .where(eq(cells.id, cell.id));
-      runtime.cell.resumeAgentSessionOnStartup = false;
-    } catch (error) {
-      const message = error instanceof Error ? error.message : String(error);
-      process.stderr.write(
-        `[agent] Failed to resume agent session for ${cell.id}: ${message}\n`
-      );
+    await resumeAgentSessionForCell(cell.id);
+  }
+}
+
+/**
+ * Recreates the runtime of a cell flagged with `resumeAgentSessionOnStartup`
+ * and asks the agent to continue when its last turn was cut short.
+ */
+export async function resumeAgentSessionForCell(cellId: string): Promise<void> {
+  const { db: runtimeDb } = getAgentRuntimeDependencies();
+  try {
+    const runtime = await ensureRuntimeForCell(cellId, { force: false });
+    const shouldResume = await shouldResumeRuntime(runtime);
+    if (shouldResume) {
+      await runtime.sendMessage(RESUME_SESSION_PROMPT);
+      return;
     }
+    await runtimeDb
+      .update(cells)
+      .set({ resumeAgentSessionOnStartup: false })
+      .where(eq(cells.id, cellId));
+    runtime.cell.resumeAgentSessionOnStartup = false;
+  } catch (error) {
+    const message = error instanceof Error ? error.message : String(error);
+    process.stderr.write(
+      `[agent] Failed to resume agent session for ${cellId}: ${message}\n`
+    );
   }
 }
 
+export function isAgentSessionWorking(cellId: string): boolean {
+  const sessionId = cellSessionMap.get(cellId);
+  const runtime = sessionId ? runtimeRegistry.get(sessionId) : undefined;
+  return runtime?.status === "working" && !runtime.pendingInterrupt;
+}
+
+/**
+ * Stops the runtime of a cell without deleting its remote session, so the
+ * next message recreates it. A turn still in progress is flagged to be
+ * continued by `resumeAgentSessionForCell`.
+ */
+export async function suspendAgentSessionForCell(
+  cellId: string
+): Promise<void> {
+  const sessionId = cellSessionMap.get(cellId);
+  if (!sessionId) {
+    return;
+  }
+
+  if (isAgentSessionWorking(cellId)) {
+    const { db: runtimeDb } = getAgentRuntimeDependencies();
+    await runtimeDb
+      .update(cells)
+      .set({ resumeAgentSessionOnStartup: true })
+      .where(eq(cells.id, cellId));
+  }
+  await stopAgentSession(sessionId, { deleteRemote: false });
+}
+
 async function shouldResumeRuntime(runtime: RuntimeHandle): Promise<boolean> {
   const query = runtime.directoryQuery.directory
     ? { directory: runtime.directoryQuery.directory, limit: 100 }
@@ -1441,6 +1635,11 @@ export type AgentRuntimeService = {
     model: { modelId: string; providerId?: string; variant?: string }
   ) => Promise<AgentSessionRecord>;
   readonly sendAgentMessage: (
+    sessionId: string,
+    content: string,
+    options?: SendAgentMessageOptions
+  ) => Promise<void>;
+  readonly appendAgentContext: (
     sessionId: string,
     content: string
   ) => Promise<void>;
@@ -1476,8 +1675,10 @@ const makeAgentRuntimeService = (): AgentRuntimeService => ({
     wrapAgentRuntime(fetchCompactionStats)(sessionId),
   updateAgentSessionModel: (sessionId, model) =>
     wrapAgentRuntime(updateAgentSessionModel)(sessionId, model),
-  sendAgentMessage: (sessionId, content) =>
-    wrapAgentRuntime(sendAgentMessage)(sessionId, content),
+  sendAgentMessage: (sessionId, content, options) =>
+    wrapAgentRuntime(sendAgentMessage)(sessionId, content, options),
+  appendAgentContext: (sessionId, content) =>
+    wrapAgentRuntime(appendAgentContext)(sessionId, content),
   interruptAgentSession: (sessionId) =>
     wrapAgentRuntime(interruptAgentSession)(sessionId),
   stopAgentSession: (sessionId, options) =>
@@ -1609,6 +1810,7 @@ async function ensureRuntimeForCell(
   const workspaceRootPath = cell.workspaceRootPath || cell.workspacePath;
 
   const { hiveConfig, template } = await hydrateInstructionsForCell(deps, cell);
+  await writeCellApiToken(deps, cell);
 
   const agentConfig = resolveTemplateAgentConfig(template);
   const effectiveOpencodeDefaults =
@@ -1669,6 +1871,7 @@ async function ensureRuntimeForCell(
     variant: requestedVariant,
     startMode,
     force: options?.force ?? false,
+    forkFromSessionId: options?.forkFromSessionId,
     deps,
   });
 
@@ -1806,6 +2009,7 @@ type StartRuntimeArgs = {
   variant?: string;
   startMode: AgentMode;
   force: boolean;
+  forkFromSessionId?: string;
   deps: AgentRuntimeDependencies;
 };
 
@@ -1861,6 +2065,7 @@ async function startOpencodeRuntime({
   variant,
   startMode,
   force,
+  forkFromSessionId,
   deps,
 }: StartRuntimeArgs): Promise<{ runtime: RuntimeHandle; created: boolean }> {
   const client = await deps.acquireOpencodeClient();
@@ -1870,6 +2075,7 @@ async function startOpencodeRuntime({
     cell,
     directoryQuery,
     force,
+    forkFromSessionId,
   });
 
   if (created) {
@@ -1907,11 +2113,18 @@ async function startOpencodeRuntime({
     status: "awaiting_input",
     pendingInterrupt: false,
     compaction: { count: 0, lastCompactionAt: null },
+    snapshottedMessageIds: new Set(),
+    pendingTurnSnapshots: [],
     startMode,
     currentMode: startMode,
     modeUpdatedAt: new Date().toISOString(),
-    async sendMessage(content) {
+    async sendMessage(content, options) {
       await applyRuntimeStatus(runtime, "working");
+      const pending = enqueueTurnSnapshot(
+        runtime,
+        await snapshotBeforeAgentTurn(runtime),
+        options?.onDelivered
+      );
 
       const activeModelId = runtime.modelId;
       const parts = [{ type: "text" as const, text: content }];
@@ -1927,16 +2140,22 @@ async function startOpencodeRuntime({
             }
           : { parts };
 
-      const response = await client.session.prompt({
-        path: { id: session.id },
-        query: directoryQuery,
-        body: {
-          ...promptBody,
-          agent: runtime.currentMode,
-        },
-      });
+      const response = await client.session
+        .prompt({
+          path: { id: session.id },
+          query: directoryQuery,
+          body: {
+            ...promptBody,
+            agent: runtime.currentMode,
+          },
+        })
+        .catch((error: unknown) => {
+          dropPendingTurnSnapshot(runtime, pending);
+          throw error;
+        });
 
       if (response.error) {
+        dropPendingTurnSnapshot(runtime, pending);
         if (runtime.pendingInterrupt && isMessageAbortedError(response.error)) {
           runtime.pendingInterrupt = false;
           await applyRuntimeStatus(runtime, "awaiting_input");
@@ -1951,6 +2170,8 @@ async function startOpencodeRuntime({
         throw new Error(errorMessage);
       }
 
+      // A finished turn had its prompt, even if the event stream missed it.
+      markPromptDelivered(pending);
       runtime.pendingInterrupt = false;
     },
     async stop(options = { deleteRemote: false }) {
@@ -1985,6 +2206,7 @@ type ResolveSessionArgs = {
   cell: Cell;
   directoryQuery: DirectoryQuery;
   force: boolean;
+  forkFromSessionId?: string;
 };
 
 async function resolveOpencodeSession({
@@ -1992,6 +2214,7 @@ async function resolveOpencodeSession({
   cell,
   directoryQuery,
   force,
+  forkFromSessionId,
 }: ResolveSessionArgs): Promise<{ session: Session; created: boolean }> {
   if (!force && cell.opencodeSessionId) {
     const existing = await getRemoteSession(
@@ -2004,6 +2227,21 @@ async function resolveOpencodeSession({
     }
   }
 
+  if (forkFromSessionId) {
+    const forked = await client.session.fork({
+      path: { id: forkFromSessionId },
+      query: directoryQuery,
+    });
+
+    if (forked.error || !forked.data) {
+      throw new Error(
+        getRpcErrorMessage(forked.error, "Failed to fork OpenCode session")
+      );
+    }
+
+    return { session: forked.data, created: true };
+  }
+
   const created = await client.session.create({
     body: {
       title: cell.name,
@@ -2063,6 +2301,7 @@ async function startEventStream({
 
       updateRuntimeModeFromEvent(runtime, event);
       recordCompactionEvent(runtime, event);
+      linkTurnSnapshot(runtime, event);
       publish(runtime.session.id, event);
       await updateRuntimeStatusFromEvent(runtime, event);
     }
@@ -2337,6 +2576,97 @@ function getFallbackEventSessionId(event: Event): string | null {
   return typeof sessionId === "string" ? sessionId : null;
 }
 
+/**
+ * Snapshots the worktree before a prompt is sent, so the turn it starts can
+ * be undone from the chat view without the agent's first edits leaking in.
+ */
+async function snapshotBeforeAgentTurn(
+  runtime: RuntimeHandle
+): Promise<string | null> {
+  const { snapshotAgentTurn } = getAgentRuntimeDependencies();
+  try {
+    const snapshot = await snapshotAgentTurn(runtime.cell);
+    return snapshot?.id ?? null;
+  } catch (error) {
+    // biome-ignore lint/suspicious/noConsole: non-fatal snapshot failures should not interrupt the agent turn
+    console.warn("[agent] Failed to snapshot worktree before agent turn", {
+      cellId: runtime.cell.id,
+      error,
+    });
+    return null;
+  }
+}
+
+function enqueueTurnSnapshot(
+  runtime: RuntimeHandle,
+  snapshotId: string | null,
+  onDelivered?: () => void
+): PendingTurnSnapshot {
+  const pending = { snapshotId, onDelivered };
+  runtime.pendingTurnSnapshots.push(pending);
+  return pending;
+}
+
+function markPromptDelivered(pending: PendingTurnSnapshot) {
+  const { onDelivered } = pending;
+  pending.onDelivered = undefined;
+  onDelivered?.();
+}
+
+/** A prompt that failed before creating its message leaves nothing to link. */
+function dropPendingTurnSnapshot(
+  runtime: RuntimeHandle,
+  pending: PendingTurnSnapshot
+) {
+  const index = runtime.pendingTurnSnapshots.indexOf(pending);
+  if (index !== -1) {
+    runtime.pendingTurnSnapshots.splice(index, 1);
+  }
+}
+
+/**
+ * Prompts are created in the order they were sent, so each new user message
+ * takes the oldest pending snapshot as its restore point and confirms that
+ * prompt was delivered. Messages typed in the opencode TUI never pass through
+ * `sendMessage`; they are snapshotted here, as early as Hive learns about
+ * them.
+ */
+function linkTurnSnapshot(runtime: RuntimeHandle, event: Event) {
+  if (event.type !== "message.updated") {
+    return;
+  }
+  const info = event.properties.info;
+  if (info.role !== "user" || runtime.snapshottedMessageIds.has(info.id)) {
+    return;
+  }
+  runtime.snapshottedMessageIds.add(info.id);
+
+  recordTurnSnapshot(runtime, info.id).catch((error: unknown) => {
+    // biome-ignore lint/suspicious/noConsole: non-fatal snapshot failures should not interrupt the agent turn
+    console.warn("[agent] Failed to record snapshot for agent turn", {
+      cellId: runtime.cell.id,
+      messageId: info.id,
+      error,
+    });
+  });
+}
+
+function recordTurnSnapshot(
+  runtime: RuntimeHandle,
+  messageId: string
+): Promise<unknown> {
+  const dependencies = getAgentRuntimeDependencies();
+  const pending = runtime.pendingTurnSnapshots.shift();
+  if (!pending) {
+    return dependencies.snapshotAgentTurn(runtime.cell, messageId);
+  }
+  markPromptDelivered(pending);
+  if (!pending.snapshotId) {
+    return Promise.resolve();
+  }
+  return dependencies.linkAgentTurnSnapshot(pending.snapshotId, messageId);
+}
+
 async function updateRuntimeStatusFromEvent(
   runtime: RuntimeHandle,
   event: Event
@@ -2520,6 +2850,7 @@ function setRuntimeStatus(
   status: AgentSessionStatus,
   error?: string
 ) {
+  const previousStatus = runtime.status;
   runtime.status = status;
   const statusEvent =
     error === undefined
@@ -2527,6 +2858,17 @@ function setRuntimeStatus(
       : { type: "status" as const, status, error };
   const { publishAgentEvent: publish } = getAgentRuntimeDependencies();
   publish(runtime.session.id, statusEvent);
+
+  if (previousStatus !== status) {
+    emitAgentStatusUpdate({
+      workspaceId: runtime.cell.workspaceId,
+      cellId: runtime.cell.id,
+      sessionId: runtime.session.id,
+      status,
+      previousStatus,
+      ...(error === undefined ? {} : { error }),
+    });
+  }
 }
 
 async function applyRuntimeStatus(
@@ -10,7 +10,7 @@
  * server codebase - only use Node.js built-ins and @opencode-ai/plugin.
  */
 
-import { readFileSync } from "node:fs";
+import { existsSync, readFileSync } from "node:fs";
 import { join } from "node:path";
 import { type ToolDefinition, tool } from "@opencode-ai/plugin";
 
@@ -59,6 +59,7 @@ function buildLogQueryParams(lines?: number, offset?: number): string {
 type HiveConfig = {
   cellId: string;
   hiveUrl: string;
+  apiToken: string | null;
 };
 
 /**
@@ -87,7 +88,11 @@ function readHiveConfig(worktreePath: string): HiveConfig | Error {
       );
     }
 
-    return { cellId: config.cellId, hiveUrl: config.hiveUrl };
+    return {
+      cellId: config.cellId,
+      hiveUrl: config.hiveUrl,
+      apiToken: readApiToken(worktreePath),
+    };
   } catch (error) {
     if ((error as NodeJS.ErrnoException).code === "ENOENT") {
       return new Error(
@@ -125,8 +130,37 @@ function resolveWorktreePath(context: {
   );
 }
 
-async function fetchJson<T>(url: string, signal: AbortSignal): Promise<T> {
-  const response = await fetch(url, { signal });
+/**
+ * Hive writes a token scoped to this cell to .hive/api-token when the agent
+ * session starts. It only grants access to this cell.
+ */
+function readApiToken(worktreePath: string): string | null {
+  const tokenPath = join(worktreePath, ".hive", "api-token");
+  if (!existsSync(tokenPath)) {
+    return null;
+  }
+  return readFileSync(tokenPath, "utf-8").trim() || null;
+}
+
+function withApiToken(
+  config: HiveConfig,
+  headers?: Record<string, string>
+): Record<string, string> | undefined {
+  if (!config.apiToken) {
+    return headers;
+  }
+  return { ...(headers ?? {}), authorization: `Bearer ${config.apiToken}` };
+}
+
+async function fetchJson<T>(
+  config: HiveConfig,
+  url: string,
+  signal: AbortSignal
+): Promise<T> {
+  const response = await fetch(url, {
+    signal,
+    headers: withApiToken(config),
+  });
   if (!response.ok) {
     const body = await response.text().catch(() => "");
     const details = body ? ` ${body}` : "";
@@ -138,11 +172,19 @@ async function fetchJson<T>(url: string, signal: AbortSignal): Promise<T> {
 }
 
 async function fetchJsonWithInit<T>(
+  config: HiveConfig,
   url: string,
   init: RequestInit,
   signal: AbortSignal
 ): Promise<T> {
-  const response = await fetch(url, { ...init, signal });
+  const response = await fetch(url, {
+    ...init,
+    headers: withApiToken(
+      config,
+      init.headers as Record<string, string> | undefined
+    ),
+    signal,
+  });
   if (!response.ok) {
     const body = await response.text().catch(() => "");
     const details = body ? ` ${body}` : "";
@@ -170,6 +212,7 @@ async function restartAllCellServices(args: {
   headers: Record<string, string>;
 }) {
   await fetchJsonWithInit<ServiceListResponse>(
+    args.config,
     `${args.config.hiveUrl}/api/cells/${args.config.cellId}/services/restart`,
     { method: "POST", headers: args.headers },
     args.signal
@@ -182,6 +225,7 @@ async function resolveServiceIdByName(args: {
   serviceName: string;
 }): Promise<string> {
   const list = await fetchJson<ServiceListResponse>(
+    args.config,
     `${args.config.hiveUrl}/api/cells/${args.config.cellId}/services`,
     args.signal
   );
@@ -208,6 +252,7 @@ async function restartSingleService(args: {
   const serviceId = await resolveServiceIdByName(args);
 
   await fetchJsonWithInit<HiveService>(
+    args.config,
     `${args.config.hiveUrl}/api/cells/${args.config.cellId}/services/${serviceId}/restart`,
     { method: "POST", headers: args.headers },
     args.signal
@@ -360,6 +405,7 @@ PAGINATION: By default returns last 200 log lines per service. Use logLines/logO
 
     try {
       const payload = await fetchJson<ServiceListResponse>(
+        config,
         `${config.hiveUrl}/api/cells/${config.cellId}/services${queryParams}`,
         context.abort
       );
@@ -437,6 +483,7 @@ TIP: If you don't know the service name, call hive_services first to see availab
 
     try {
       const payload = await fetchJsonWithInit<ServiceListResponse>(
+        config,
         `${config.hiveUrl}/api/cells/${config.cellId}/services${queryParams}`,
         { method: "GET", headers },
         context.abort
@@ -511,6 +558,7 @@ NOTE: This is different from service logs - setup runs once when the cell is cre
 
     try {
       const payload = await fetchJsonWithInit<CellResponse>(
+        config,
         `${config.hiveUrl}/api/cells/${config.cellId}`,
         { method: "GET", headers },
         context.abort
@@ -609,6 +657,7 @@ TIP: Call hive_services after restarting to confirm everything is healthy.`,
       await restartAllCellServices({ config, signal: context.abort, headers });
 
       const final = await fetchJson<ServiceListResponse>(
+        config,
         `${config.hiveUrl}/api/cells/${config.cellId}/services${queryParams}`,
         context.abort
       );
@@ -716,6 +765,7 @@ TIP: Call hive_services after restarting to confirm everything is healthy.`,
       });
 
       const final = await fetchJson<ServiceListResponse>(
+        config,
         `${config.hiveUrl}/api/cells/${config.cellId}/services${queryParams}`,
         context.abort
       );
@@ -788,6 +838,7 @@ SAFETY: You must pass confirm=true or the tool will refuse to run.`,
 
     try {
       const payload = await fetchJsonWithInit<Record<string, unknown>>(
+        config,
         `${config.hiveUrl}/api/cells/${config.cellId}/setup/retry`,
         { method: "POST", headers },
         context.abort
@@ -817,3 +868,289 @@ SAFETY: You must pass confirm=true or the tool will refuse to run.`,
     }
   },
 });
+
+type CommitResponse = {
+  commit: string;
+  branchName: string | null;
+  files: string[];
+};
+
+type PushResponse = {
+  remote: string;
+  branchName: string;
+  commit: string;
+};
+
+type PullRequestResponse = {
+  number: number;
+  url: string;
+  head: string;
+  base: string;
+  commit: string;
+};
+
+type SyncResponse = {
+  status: "synced" | "conflicted" | "up_to_date";
+  strategy: "merge" | "rebase";
+  branchName: string;
+  upstream: string;
+  baseCommit: string;
+  headCommit: string;
+  conflicts: Array<{ path: string; status: string }>;
+  message: string | null;
+};
+
+function buildJsonInit(
+  body: Record<string, unknown>,
+  headers: Record<string, string>
+): RequestInit {
+  return {
+    method: "POST",
+    headers: { ...headers, "content-type": "application/json" },
+    body: JSON.stringify(body),
+  };
+}
+
+export const commit_changes: ToolDefinition = tool({
+  description: `Commit changes in this cell's worktree, optionally pushing the cell branch.
+
+USE THIS TOOL WHEN:
+- Your work is in a good state and should be recorded on the cell branch
+- You are about to open a pull request with hive_open_pull_request
+
+Stages every change (including new files) unless specific files are listed.
+
+SAFETY: Pushing publishes the branch to the configured remote. Pass push=true together with confirm=true to push.`,
+  args: {
+    message: tool.schema.string().describe("Commit message."),
+    files: tool.schema
+      .array(tool.schema.string())
+      .optional()
+      .describe(
+        "Only stage these paths (relative to the worktree). Default: all changes."
+      ),
+    push: tool.schema
+      .boolean()
+      .optional()
+      .describe("Push the cell branch after committing. Default: false."),
+    confirm: tool.schema
+      .boolean()
+      .optional()
+      .describe("Required when push=true. Prevents accidental pushes."),
+    format: tool.schema
+      .enum(["text", "json"])
+      .optional()
+      .describe(
+        "Output format. Use 'json' for programmatic parsing. Default: text."
+      ),
+  },
+  async execute(args, context) {
+    if (args.push === true && args.confirm !== true) {
+      return "Refusing to push without confirm=true.";
+    }
+
+    const worktreePath = resolveWorktreePath(context);
+    if (worktreePath instanceof Error) {
+      return `Error: ${worktreePath.message}`;
+    }
+
+    const config = readHiveConfig(worktreePath);
+    if (config instanceof Error) {
+      return `Error: ${config.message}`;
+    }
+
+    const format = args.format ?? "text";
+    const headers = buildHiveToolHeaders("hive_commit_changes");
+    const cellUrl = `${config.hiveUrl}/api/cells/${config.cellId}`;
+
+    try {
+      const commit = await fetchJsonWithInit<CommitResponse>(
+        config,
+        `${cellUrl}/git/commit`,
+        buildJsonInit(
+          {
+            message: args.message,
+            ...(args.files ? { files: args.files } : {}),
+          },
+          headers
+        ),
+        context.abort
+      );
+
+      const push = args.push
+        ? await fetchJsonWithInit<PushResponse>(
+            config,
+            `${cellUrl}/git/push`,
+            buildJsonInit({}, headers),
+            context.abort
+          )
+        : null;
+
+      if (format === "json") {
+        return JSON.stringify({ commit, push }, null, 2);
+      }
+
+      const lines = [
+        `Committed ${commit.files.length} file(s) as ${commit.commit.slice(0, 12)} on ${commit.branchName ?? "(detached)"}.`,
+        ...commit.files.map((file) => `- ${file}`),
+      ];
+      if (push) {
+        lines.push(`Pushed ${push.branchName} to ${push.remote}.`);
+      }
+      return lines.join("\n");
+    } catch (error) {
+      return `Error: ${error instanceof Error ? error.message : String(error)}`;
+    }
+  },
+});
+
+export const open_pull_request: ToolDefinition = tool({
+  description: `Push the cell branch and open a pull request for it on the configured forge.
+
+USE THIS TOOL WHEN:
+- The work is committed (see hive_commit_changes) and ready for review
+
+Uncommitted changes are NOT included - commit them first.
+
+SAFETY: This publishes the branch and creates a pull request. You must pass confirm=true or the tool will refuse to run.`,
+  args: {
+    title: tool.schema.string().describe("Pull request title."),
+    body: tool.schema
+      .string()
+      .optional()
+      .describe("Pull request description (markdown)."),
+    base: tool.schema
+      .string()
+      .optional()
+      .describe(
+        "Branch to merge into. Default: the configured base branch or the remote's default branch."
+      ),
+    draft: tool.schema
+      .boolean()
+      .optional()
+      .describe("Open the pull request as a draft. Default: false."),
+    confirm: tool.schema
+      .boolean()
+      .optional()
+      .describe(
+        "Required. Set true to actually open the pull request. This prevents accidental publishing."
+      ),
+    format: tool.schema
+      .enum(["text", "json"])
+      .optional()
+      .describe(
+        "Output format. Use 'json' for programmatic parsing. Default: text."
+      ),
+  },
+  async execute(args, context) {
+    if (args.confirm !== true) {
+      return "Refusing to open a pull request without confirm=true.";
+    }
+
+    const worktreePath = resolveWorktreePath(context);
+    if (worktreePath instanceof Error) {
+      return `Error: ${worktreePath.message}`;
+    }
+
+    const config = readHiveConfig(worktreePath);
+    if (config instanceof Error) {
+      return `Error: ${config.message}`;
+    }
+
+    const format = args.format ?? "text";
+    const headers = buildHiveToolHeaders("hive_open_pull_request");
+
+    try {
+      const pullRequest = await fetchJsonWithInit<PullRequestResponse>(
+        config,
+        `${config.hiveUrl}/api/cells/${config.cellId}/pull-request`,
+        buildJsonInit(
+          {
+            title: args.title,
+            ...(args.body ? { body: args.body } : {}),
+            ...(args.base ? { base: args.base } : {}),
+            ...(args.draft != null ? { draft: args.draft } : {}),
+          },
+          headers
+        ),
+        context.abort
+      );
+
+      if (format === "json") {
+        return JSON.stringify(pullRequest, null, 2);
+      }
+
+      return [
+        `Opened pull request #${pullRequest.number}: ${pullRequest.url}`,
+        `${pullRequest.head} -> ${pullRequest.base} at ${pullRequest.commit.slice(0, 12)}`,
+      ].join("\n");
+    } catch (error) {
+      return `Error: ${error instanceof Error ? error.message : String(error)}`;
+    }
+  },
+});
+
+export const sync_with_base: ToolDefinition = tool({
+  description: `Bring the cell branch up to date with the latest tip of the branch or PR it was started from.
+
+USE THIS TOOL WHEN:
+- The base branch has moved on and your work needs the newer changes
+- A merge or pull request reports conflicts with the base branch
+
+Commit your changes first - syncing refuses to run with uncommitted changes.
+
+If the rebase or merge stops with conflicts, they are left in the worktree. Resolve them, finish the rebase/merge as instructed, then call this tool again to record the new base commit.`,
+  args: {
+    strategy: tool.schema
+      .enum(["rebase", "merge"])
+      .optional()
+      .describe(
+        "Rebase the cell commits or merge the base in. Default: rebase."
+      ),
+    format: tool.schema
+      .enum(["text", "json"])
+      .optional()
+      .describe(
+        "Output format. Use 'json' for programmatic parsing. Default: text."
+      ),
+  },
+  async execute(args, context) {
+    const worktreePath = resolveWorktreePath(context);
+    if (worktreePath instanceof Error) {
+      return `Error: ${worktreePath.message}`;
+    }
+
+    const config = readHiveConfig(worktreePath);
+    if (config instanceof Error) {
+      return `Error: ${config.message}`;
+    }
+
+    const format = args.format ?? "text";
+
+    try {
+      const sync = await fetchJsonWithInit<SyncResponse>(
+        config,
+        `${config.hiveUrl}/api/cells/${config.cellId}/sync`,
+        buildJsonInit(
+          { ...(args.strategy ? { strategy: args.strategy } : {}) },
+          buildHiveToolHeaders("hive_sync_with_base")
+        ),
+        context.abort
+      );
+
+      if (format === "json") {
+        return JSON.stringify(sync, null, 2);
+      }
+
+      if (sync.status === "conflicted") {
+        return sync.message ?? "Sync stopped with conflicts.";
+      }
+      if (sync.status === "up_to_date") {
+        return `${sync.branchName} already contains ${sync.upstream} (${sync.baseCommit.slice(0, 12)}).`;
+      }
+      return `Synced ${sync.branchName} onto ${sync.upstream} (${sync.baseCommit.slice(0, 12)}) using ${sync.strategy}.`;
+    } catch (error) {
+      return `Error: ${error instanceof Error ? error.message : String(error)}`;
+    }
+  },
+});
@@ -0,0 +1,181 @@
+import { and, eq, inArray, or, type SQL } from "drizzle-orm";
+import type { DatabaseService as DatabaseServiceType } from "../db";
+import { cellShares } from "../schema/cell-shares";
+import { type Cell, cells } from "../schema/cells";
+import type { ApiPrincipal } from "./plugin";
+
+type DatabaseClient = DatabaseServiceType["db"];
+
+/** Owners and admins can change a cell; shared users can only look at it. */
+export type CellAccessLevel = "read" | "write";
+
+const CELL_PATH_PATTERN = /^\/api\/cells\/([^/]+)/;
+const CELL_COLLECTION_SEGMENTS = new Set([
+  "workspace",
+  "timings",
+  "import",
+  "batches",
+]);
+const AGENT_CELL_PATH_PATTERN = /^\/api\/agents\/sessions\/byCell\/([^/]+)/;
+const AGENT_SESSION_PATH_PATTERN = /^\/api\/agents\/sessions\/([^/]+)/;
+
+const HTTP_STATUS = {
+  FORBIDDEN: 403,
+  NOT_FOUND: 404,
+} as const;
+
+/**
+ * Admin tokens and principals that are not tied to a user (local mode and
+ * service tokens) see every cell. So do requests that never went through the
+ * auth plugin. Agent tokens are bound to a single cell and never are.
+ */
+export function hasUnrestrictedCellAccess(
+  principal: ApiPrincipal | null
+): boolean {
+  if (principal?.kind === "internal") {
+    return false;
+  }
+  return (
+    !principal || principal.userId === null || principal.scope === "admin"
+  );
+}
+
+export async function canAccessCell(args: {
+  database: DatabaseClient;
+  principal: ApiPrincipal | null;
+  cell: Pick<Cell, "id" | "ownerId">;
+  level: CellAccessLevel;
+}): Promise<boolean> {
+  const { principal, cell } = args;
+  if (!principal || hasUnrestrictedCellAccess(principal)) {
+    return true;
+  }
+  if (principal.kind === "internal") {
+    return cell.id === principal.cellId;
+  }
+  if (cell.ownerId === principal.userId) {
+    return true;
+  }
+  if (args.level === "write" || !principal.userId) {
+    return false;
+  }
+
+  const share = await args.database.query.cellShares.findFirst({
+    where: and(
+      eq(cellShares.cellId, cell.id),
+      eq(cellShares.userId, principal.userId)
+    ),
+  });
+  return Boolean(share);
+}
+
+/** A `cells` filter matching what the principal may access, if restricted. */
+export function cellAccessFilter(
+  database: DatabaseClient,
+  principal: ApiPrincipal | null,
+  level: CellAccessLevel
+): SQL | undefined {
+  if (principal?.kind === "internal") {
+    return eq(cells.id, principal.cellId ?? "");
+  }
+  if (!principal?.userId || hasUnrestrictedCellAccess(principal)) {
+    return;
+  }
+  if (level === "write") {
+    return eq(cells.ownerId, principal.userId);
+  }
+  return or(
+    eq(cells.ownerId, principal.userId),
+    inArray(
+      cells.id,
+      database
+        .select({ cellId: cellShares.cellId })
+        .from(cellShares)
+        .where(eq(cellShares.userId, principal.userId))
+    )
+  );
+}
+
+/** The cell a request path targets, by cell id or agent session id. */
+export async function resolveRequestCell(
+  database: DatabaseClient,
+  pathname: string
+): Promise<Cell | null> {
+  const cellId =
+    pathname.match(AGENT_CELL_PATH_PATTERN)?.[1] ??
+    pathname.match(CELL_PATH_PATTERN)?.[1];
+  if (cellId) {
+    if (CELL_COLLECTION_SEGMENTS.has(cellId)) {
+      return null;
+    }
+    const cell = await database.query.cells.findFirst({
+      where: eq(cells.id, decodeURIComponent(cellId)),
+    });
+    return cell ?? null;
+  }
+
+  const sessionId = pathname.match(AGENT_SESSION_PATH_PATTERN)?.[1];
+  if (!sessionId) {
+    return null;
+  }
+  const cell = await database.query.cells.findFirst({
+    where: eq(cells.opencodeSessionId, decodeURIComponent(sessionId)),
+  });
+  return cell ?? null;
+}
+
+/**
+ * Checks the cell a request targets against the caller. Cells the caller
+ * cannot see answer 404 so their existence does not leak. An agent token
+ * only reaches routes of its own cell, where it acts on behalf of the cell
+ * owner so its activity is attributed to them.
+ */
+export async function authorizeCellRequest(args: {
+  database: DatabaseClient;
+  principal: ApiPrincipal;
+  pathname: string;
+  level: CellAccessLevel;
+}): Promise<
+  | { ok: true; principal: ApiPrincipal }
+  | { ok: false; status: number; message: string }
+> {
+  const { database, principal } = args;
+  if (principal.kind !== "internal" && hasUnrestrictedCellAccess(principal)) {
+    return { ok: true, principal };
+  }
+
+  const cell = await resolveRequestCell(database, args.pathname);
+  if (!cell) {
+    if (principal.kind === "internal") {
+      return {
+        ok: false,
+        status: HTTP_STATUS.FORBIDDEN,
+        message: "Agent tokens can only access their own cell",
+      };
+    }
+    return { ok: true, principal };
+  }
+
+  if (!(await canAccessCell({ database, principal, cell, level: "read" }))) {
+    return {
+      ok: false,
+      status: HTTP_STATUS.NOT_FOUND,
+      message: "Cell not found",
+    };
+  }
+  if (
+    args.level === "write" &&
+    !(await canAccessCell({ database, principal, cell, level: "write" }))
+  ) {
+    return {
+      ok: false,
+      status: HTTP_STATUS.FORBIDDEN,
+      message: "This cell is shared with you read-only",
+    };
+  }
+
+  if (principal.kind === "internal") {
+    return { ok: true, principal: { ...principal, userId: cell.ownerId } };
+  }
+  return { ok: true, principal };
+}
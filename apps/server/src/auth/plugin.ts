@@ -0,0 +1,273 @@
+import { Elysia } from "elysia";
+import {
+  DatabaseService,
+  type DatabaseService as DatabaseServiceType,
+} from "../db";
+import type { ApiTokenScope } from "../schema/api-tokens";
+import { authorizeCellRequest } from "./access";
+import { scopeAllows, verifyApiToken } from "./tokens";
+
+type DatabaseClient = DatabaseServiceType["db"];
+
+const HTTP_STATUS = {
+  UNAUTHORIZED: 401,
+  FORBIDDEN: 403,
+} as const;
+
+export const API_TOKEN_COOKIE = "hive_token";
+const ACCESS_TOKEN_QUERY_PARAM = "access_token";
+const LOGIN_TOKEN_QUERY_PARAM = "token";
+const LOGIN_PATH = "/api/auth/login";
+/** WebSocket and SSE endpoints, whose browser clients cannot set headers. */
+const QUERY_TOKEN_PATH_PATTERN = /\/(ws|stream|events)$/;
+const BEARER_PATTERN = /^Bearer\s+(.+)$/i;
+const LOOPBACK_HOSTNAMES = new Set(["localhost", "127.0.0.1", "::1", "[::1]"]);
+const PUBLIC_API_PATHS = new Set([LOGIN_PATH, "/api/auth/logout"]);
+const READ_METHODS = new Set(["GET", "HEAD"]);
+const ADMIN_WRITE_PREFIXES = [
+  "/api/workspaces",
+  "/api/users",
+  "/api/webhooks",
+  "/api/secrets",
+];
+
+export type ApiAuthMode = "required" | "off";
+
+export type ApiPrincipal = {
+  kind: "token" | "internal" | "local";
+  scope: ApiTokenScope;
+  tokenId: string | null;
+  name: string;
+  /** The user the token belongs to; null for service and local access. */
+  userId: string | null;
+  /** The only cell an internal agent token may touch. */
+  cellId: string | null;
+};
+
+const LOCAL_PRINCIPAL: ApiPrincipal = {
+  kind: "local",
+  scope: "admin",
+  tokenId: null,
+  name: "local",
+  userId: null,
+  cellId: null,
+};
+
+const requestPrincipals = new WeakMap<Request, ApiPrincipal>();
+
+/** The caller the auth plugin resolved for this request, if any. */
+export const getRequestPrincipal = (request: Request): ApiPrincipal | null =>
+  requestPrincipals.get(request) ?? null;
+
+/** The user to stamp on activity recorded while handling this request. */
+export const getRequestUserId = (request: Request): string | null =>
+  getRequestPrincipal(request)?.userId ?? null;
+
+/**
+ * `HIVE_AUTH=required|off` wins; otherwise tokens are only required when the
+ * server listens on something other than a loopback address.
+ */
+export function resolveApiAuthMode(
+  hostname: string,
+  value: string | undefined = process.env.HIVE_AUTH
+): ApiAuthMode {
+  const normalized = value?.trim().toLowerCase();
+  if (normalized === "required" || normalized === "off") {
+    return normalized;
+  }
+  return LOOPBACK_HOSTNAMES.has(hostname) ? "off" : "required";
+}
+
+/**
+ * Maps a request to the scope it needs, or null for public endpoints. Reads
+ * (including SSE streams) need `read`; anything that changes state, and
+ * terminal WebSockets since they accept input, need `operate`. Managing
+ * tokens, users, workspaces, webhooks and secrets needs `admin`.
+ */
+export function resolveRequiredScope(
+  method: string,
+  pathname: string
+): ApiTokenScope | null {
+  const upperMethod = method.toUpperCase();
+  if (upperMethod === "OPTIONS") {
+    return null;
+  }
+  if (!pathname.startsWith("/api/") || PUBLIC_API_PATHS.has(pathname)) {
+    return null;
+  }
+  if (pathname.startsWith("/api/auth/tokens")) {
+    return "admin";
+  }
+
+  const isRead = READ_METHODS.has(upperMethod) && !pathname.endsWith("/ws");
+  if (
+    !isRead &&
+    ADMIN_WRITE_PREFIXES.some((prefix) => pathname.startsWith(prefix))
+  ) {
+    return "admin";
+  }
+  return isRead ? "read" : "operate";
+}
+
+export function extractRequestToken(request: Request): string | null {
+  const authorization = request.headers.get("authorization");
+  const bearer = authorization?.match(BEARER_PATTERN)?.[1]?.trim();
+  if (bearer) {
+    return bearer;
+  }
+
+  const cookieToken = readCookie(
+    request.headers.get("cookie"),
+    API_TOKEN_COOKIE
+  );
+  if (cookieToken) {
+    return cookieToken;
+  }
+
+  // EventSource and WebSocket clients cannot set headers. Everything else
+  // must not put tokens in URLs, where they end up in logs and history.
+  try {
+    const url = new URL(request.url);
+    if (!QUERY_TOKEN_PATH_PATTERN.test(url.pathname)) {
+      return null;
+    }
+    return url.searchParams.get(ACCESS_TOKEN_QUERY_PARAM)?.trim() || null;
+  } catch {
+    return null;
+  }
+}
+
+/** The request URL with any token in the query string masked, for logging. */
+export function redactRequestUrl(rawUrl: string): string {
+  let url: URL;
+  try {
+    url = new URL(rawUrl);
+  } catch {
+    return rawUrl;
+  }
+  const params =
+    url.pathname === LOGIN_PATH
+      ? [ACCESS_TOKEN_QUERY_PARAM, LOGIN_TOKEN_QUERY_PARAM]
+      : [ACCESS_TOKEN_QUERY_PARAM];
+  let redacted = false;
+  for (const param of params) {
+    if (url.searchParams.has(param)) {
+      url.searchParams.set(param, "[redacted]");
+      redacted = true;
+    }
+  }
+  return redacted ? url.toString() : rawUrl;
+}
+
+export async function authenticateRequest(args: {
+  database: DatabaseClient;
+  request: Request;
+}): Promise<ApiPrincipal | null> {
+  const token = extractRequestToken(args.request);
+  if (!token) {
+    return null;
+  }
+
+  const record = await verifyApiToken(args.database, token);
+  if (!record) {
+    return null;
+  }
+  if (record.cellId) {
+    return {
+      kind: "internal",
+      scope: "operate",
+      tokenId: record.id,
+      name: "hive",
+      userId: null,
+      cellId: record.cellId,
+    };
+  }
+  return {
+    kind: "token",
+    scope: record.scope,
+    tokenId: record.id,
+    name: record.name,
+    userId: record.userId,
+    cellId: null,
+  };
+}
+
+export type ApiAuthPluginOptions = {
+  db?: DatabaseClient;
+  mode?: ApiAuthMode;
+};
+
+/**
+ * Enforces API tokens on every REST, SSE and WebSocket request, including
+ * per-cell ownership. Runs in `onRequest` so WebSocket upgrades are checked
+ * before the socket opens.
+ */
+export function createApiAuthPlugin({
+  db = DatabaseService.db,
+  mode = "off",
+}: ApiAuthPluginOptions = {}) {
+  return new Elysia({ name: "api-auth" }).onRequest(
+    async ({ request, set }) => {
+      if (mode === "off") {
+        requestPrincipals.set(request, LOCAL_PRINCIPAL);
+        return;
+      }
+
+      const pathname = readPathname(request.url);
+      const requiredScope = resolveRequiredScope(request.method, pathname);
+      if (!requiredScope) {
+        return;
+      }
+
+      const principal = await authenticateRequest({ database: db, request });
+      if (!principal) {
+        set.status = HTTP_STATUS.UNAUTHORIZED;
+        set.headers["www-authenticate"] = 'Bearer realm="hive"';
+        return { message: "Authentication required" };
+      }
+      if (!scopeAllows(principal.scope, requiredScope)) {
+        set.status = HTTP_STATUS.FORBIDDEN;
+        return {
+          message: `Token "${principal.name}" lacks the "${requiredScope}" scope`,
+        };
+      }
+
+      const cellAccess = await authorizeCellRequest({
+        database: db,
+        principal,
+        pathname,
+        level: requiredScope === "read" ? "read" : "write",
+      });
+      if (!cellAccess.ok) {
+        set.status = cellAccess.status;
+        return { message: cellAccess.message };
+      }
+
+      requestPrincipals.set(request, cellAccess.principal);
+    }
+  );
+}
+
+function readPathname(url: string): string {
+  try {
+    return new URL(url).pathname;
+  } catch {
+    return url;
+  }
+}
+
+function readCookie(header: string | null, name: string): string | null {
+  if (!header) {
+    return null;
+  }
+  for (const part of header.split(";")) {
+    const separator = part.indexOf("=");
+    if (separator === -1) {
+      continue;
+    }
+    if (part.slice(0, separator).trim() === name) {
+      return part.slice(separator + 1).trim() || null;
+    }
+  }
+  return null;
+}
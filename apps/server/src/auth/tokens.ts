@@ -0,0 +1,171 @@
+import { createHash, randomBytes } from "node:crypto";
+import { and, desc, eq, isNull, or } from "drizzle-orm";
+import type { DatabaseService as DatabaseServiceType } from "../db";
+import {
+  API_TOKEN_SCOPES,
+  type ApiToken,
+  type ApiTokenScope,
+  apiTokens,
+} from "../schema/api-tokens";
+
+type DatabaseClient = DatabaseServiceType["db"];
+
+const TOKEN_PREFIX = "hive_";
+const TOKEN_BYTES = 32;
+const DISPLAY_PREFIX_LENGTH = TOKEN_PREFIX.length + 8;
+const LAST_USED_WRITE_INTERVAL_MS = 60_000;
+
+export class ApiTokenError extends Error {
+  constructor(message: string) {
+    super(message);
+    this.name = "ApiTokenError";
+  }
+}
+
+export const isApiTokenScope = (value: unknown): value is ApiTokenScope =>
+  typeof value === "string" &&
+  (API_TOKEN_SCOPES as readonly string[]).includes(value);
+
+export function scopeAllows(
+  granted: ApiTokenScope,
+  required: ApiTokenScope
+): boolean {
+  return (
+    API_TOKEN_SCOPES.indexOf(granted) >= API_TOKEN_SCOPES.indexOf(required)
+  );
+}
+
+export function hashApiToken(token: string): string {
+  return createHash("sha256").update(token).digest("hex");
+}
+
+export function generateApiTokenSecret(): string {
+  return `${TOKEN_PREFIX}${randomBytes(TOKEN_BYTES).toString("base64url")}`;
+}
+
+/**
+ * Mints a token and stores only its hash. The plaintext is returned once so
+ * the caller can hand it to the user.
+ */
+export async function createApiToken(
+  database: DatabaseClient,
+  input: { name: string; scope: ApiTokenScope; userId?: string | null }
+): Promise<{ token: string; record: ApiToken }> {
+  const name = input.name.trim();
+  if (!name) {
+    throw new ApiTokenError("Token name is required");
+  }
+  if (!isApiTokenScope(input.scope)) {
+    throw new ApiTokenError(
+      `Unknown scope "${input.scope}". Use one of: ${API_TOKEN_SCOPES.join(", ")}`
+    );
+  }
+
+  const token = generateApiTokenSecret();
+  const [record] = await database
+    .insert(apiTokens)
+    .values({
+      id: crypto.randomUUID(),
+      name,
+      tokenHash: hashApiToken(token),
+      tokenPrefix: token.slice(0, DISPLAY_PREFIX_LENGTH),
+      scope: input.scope,
+      userId: input.userId ?? null,
+      createdAt: new Date(),
+      lastUsedAt: null,
+      revokedAt: null,
+    })
+    .returning();
+  if (!record) {
+    throw new ApiTokenError("Failed to store API token");
+  }
+
+  return { token, record };
+}
+
+/**
+ * Mints the token an agent uses to call back into Hive for one cell. Earlier
+ * tokens for the cell stop working, so only the current agent session holds
+ * a valid one.
+ */
+export async function issueCellApiToken(
+  database: DatabaseClient,
+  cellId: string
+): Promise<string> {
+  await database.delete(apiTokens).where(eq(apiTokens.cellId, cellId));
+
+  const token = generateApiTokenSecret();
+  await database.insert(apiTokens).values({
+    id: crypto.randomUUID(),
+    name: `cell:${cellId}`,
+    tokenHash: hashApiToken(token),
+    tokenPrefix: token.slice(0, DISPLAY_PREFIX_LENGTH),
+    scope: "operate",
+    userId: null,
+    cellId,
+    createdAt: new Date(),
+    lastUsedAt: null,
+    revokedAt: null,
+  });
+  return token;
+}
+
+/** Tokens people created; agent tokens are managed by Hive and not listed. */
+export async function listApiTokens(
+  database: DatabaseClient
+): Promise<ApiToken[]> {
+  return await database
+    .select()
+    .from(apiTokens)
+    .where(isNull(apiTokens.cellId))
+    .orderBy(desc(apiTokens.createdAt));
+}
+
+/** Revokes a token by id or by its display prefix. */
+export async function revokeApiToken(
+  database: DatabaseClient,
+  idOrPrefix: string
+): Promise<ApiToken | null> {
+  const [revoked] = await database
+    .update(apiTokens)
+    .set({ revokedAt: new Date() })
+    .where(
+      and(
+        isNull(apiTokens.revokedAt),
+        isNull(apiTokens.cellId),
+        or(eq(apiTokens.id, idOrPrefix), eq(apiTokens.tokenPrefix, idOrPrefix))
+      )
+    )
+    .returning();
+  return revoked ?? null;
+}
+
+export async function verifyApiToken(
+  database: DatabaseClient,
+  token: string
+): Promise<ApiToken | null> {
+  if (!token.startsWith(TOKEN_PREFIX)) {
+    return null;
+  }
+
+  const record = await database.query.apiTokens.findFirst({
+    where: and(
+      eq(apiTokens.tokenHash, hashApiToken(token)),
+      isNull(apiTokens.revokedAt)
+    ),
+  });
+  if (!record) {
+    return null;
+  }
+
+  const now = new Date();
+  const lastUsedAt = record.lastUsedAt?.getTime() ?? 0;
+  if (now.getTime() - lastUsedAt > LAST_USED_WRITE_INTERVAL_MS) {
+    await database
+      .update(apiTokens)
+      .set({ lastUsedAt: now })
+      .where(eq(apiTokens.id, record.id));
+  }
+
+  return record;
+}
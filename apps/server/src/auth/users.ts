@@ -0,0 +1,94 @@
+import { asc, eq, or } from "drizzle-orm";
+import type { DatabaseService as DatabaseServiceType } from "../db";
+import { cellActivityEvents } from "../schema/activity-events";
+import { apiTokens } from "../schema/api-tokens";
+import { cellShares } from "../schema/cell-shares";
+import { cells } from "../schema/cells";
+import { type User, users } from "../schema/users";
+
+type DatabaseClient = DatabaseServiceType["db"];
+
+const USERNAME_PATTERN = /^[a-z0-9][a-z0-9._-]{0,63}$/;
+
+export class UserAccountError extends Error {
+  constructor(message: string) {
+    super(message);
+    this.name = "UserAccountError";
+  }
+}
+
+export async function createUser(
+  database: DatabaseClient,
+  input: { username: string; displayName?: string | null }
+): Promise<User> {
+  const username = input.username.trim().toLowerCase();
+  if (!USERNAME_PATTERN.test(username)) {
+    throw new UserAccountError(
+      "Usernames must start with a letter or digit and only contain letters, digits, '.', '_' or '-'"
+    );
+  }
+  if (await findUser(database, username)) {
+    throw new UserAccountError(`User "${username}" already exists`);
+  }
+
+  const [user] = await database
+    .insert(users)
+    .values({
+      id: crypto.randomUUID(),
+      username,
+      displayName: input.displayName?.trim() || null,
+      createdAt: new Date(),
+    })
+    .returning();
+  if (!user) {
+    throw new UserAccountError("Failed to store user");
+  }
+  return user;
+}
+
+export async function listUsers(database: DatabaseClient): Promise<User[]> {
+  return await database.select().from(users).orderBy(asc(users.username));
+}
+
+/** Looks a user up by id or username. */
+export async function findUser(
+  database: DatabaseClient,
+  idOrUsername: string
+): Promise<User | null> {
+  const user = await database.query.users.findFirst({
+    where: or(
+      eq(users.id, idOrUsername),
+      eq(users.username, idOrUsername.trim().toLowerCase())
+    ),
+  });
+  return user ?? null;
+}
+
+/**
+ * Removes a user along with their tokens and shares. Cells they own stay in
+ * place without an owner so admins can still reach them.
+ */
+export async function deleteUser(
+  database: DatabaseClient,
+  idOrUsername: string
+): Promise<User | null> {
+  const user = await findUser(database, idOrUsername);
+  if (!user) {
+    return null;
+  }
+
+  // SQLite only enforces the foreign key actions when the pragma is on, so
+  // detach everything explicitly.
+  await database.delete(apiTokens).where(eq(apiTokens.userId, user.id));
+  await database.delete(cellShares).where(eq(cellShares.userId, user.id));
+  await database
+    .update(cells)
+    .set({ ownerId: null })
+    .where(eq(cells.ownerId, user.id));
+  await database
+    .update(cellActivityEvents)
+    .set({ userId: null })
+    .where(eq(cellActivityEvents.userId, user.id));
+  await database.delete(users).where(eq(users.id, user.id));
+  return user;
+}
@@ -2,7 +2,7 @@ import { stat } from "node:fs/promises";
 import { resolve as resolvePath } from "node:path";
 import type { WorkspaceRegistryError } from "../workspaces/registry";
 import { getWorkspaceRegistry } from "../workspaces/registry";
-import { hasConfigFile } from "./files";
+import { findConfigPath, hasConfigFile } from "./files";
 import { loadConfig } from "./loader";
 import type { HiveConfig } from "./schema";
 
@@ -13,7 +13,6 @@ type ConfigCacheEntry = {
 };
 
 const configCache = new Map<string, ConfigCacheEntry>();
-const CONFIG_FILENAME = "hive.config.json";
 
 export function resolveWorkspaceRoot(): string {
   const baseRoot = resolveBaseWorkspaceRoot();
@@ -56,7 +55,10 @@ async function readConfigMtimeMs(
   workspaceRoot: string
 ): Promise<number | null> {
   try {
-    const configPath = resolvePath(workspaceRoot, CONFIG_FILENAME);
+    const configPath = findConfigPath(workspaceRoot);
+    if (!configPath) {
+      return null;
+    }
     const stats = await stat(configPath);
     return stats.mtimeMs;
   } catch {
@@ -33,6 +33,13 @@ if (!envDatabaseUrl) {
   throw new Error("DATABASE_URL environment variable is required");
 }
 
+export type DatabaseEngine = "sqlite" | "postgres";
+
+const POSTGRES_URL_PATTERN = /^postgres(?:ql)?:\/\//i;
+
+export const resolveDatabaseEngine = (url: string): DatabaseEngine =>
+  POSTGRES_URL_PATTERN.test(url) ? "postgres" : "sqlite";
+
 const SQLITE_MEMORY_URLS = new Set([":memory:", "file::memory:?cache=shared"]);
 
 const stripFilePrefix = (value: string) => {
@@ -49,6 +56,7 @@ const stripFilePrefix = (value: string) => {
 };
 
 export const databaseUrl = envDatabaseUrl;
+export const databaseEngine = resolveDatabaseEngine(envDatabaseUrl);
 export const sqliteDatabasePath = SQLITE_MEMORY_URLS.has(envDatabaseUrl)
   ? ":memory:"
   : stripFilePrefix(envDatabaseUrl);
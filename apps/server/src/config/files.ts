@@ -1,11 +1,19 @@
 import { existsSync } from "node:fs";
 import { join } from "node:path";
 
-export const CONFIG_FILENAMES = ["hive.config.json"] as const;
+/** Supported config files, in the order they are looked up. */
+export const CONFIG_FILENAMES = [
+  "hive.config.ts",
+  "hive.config.js",
+  "hive.config.json",
+  "hive.config.jsonc",
+  "hive.config.yaml",
+  "hive.config.yml",
+] as const;
 
 export type ConfigFilename = (typeof CONFIG_FILENAMES)[number];
 
-export const PREFERRED_CONFIG_FILENAME = CONFIG_FILENAMES[0];
+export const PREFERRED_CONFIG_FILENAME: ConfigFilename = "hive.config.json";
 
 export const findConfigPath = (directory: string): string | null => {
   for (const filename of CONFIG_FILENAMES) {
@@ -0,0 +1,271 @@
+import { mkdtempSync, rmSync, writeFileSync } from "node:fs";
+import { tmpdir } from "node:os";
+import { join } from "node:path";
+import { fileURLToPath } from "node:url";
+import { afterEach, describe, expect, it } from "vitest";
+
+import { listConfigImports, loadConfig, stripJsonComments } from "./loader";
+
+const SCHEMA_MODULE_PATH = fileURLToPath(
+  new URL("./schema.ts", import.meta.url)
+);
+
+describe("loadConfig formats", () => {
+  const createdDirs: string[] = [];
+
+  const writeWorkspace = (filename: string, contents: string) => {
+    const workspaceRoot = mkdtempSync(join(tmpdir(), "hive-loader-"));
+    createdDirs.push(workspaceRoot);
+    writeFileSync(join(workspaceRoot, filename), contents);
+    return workspaceRoot;
+  };
+
+  afterEach(() => {
+    for (const dir of createdDirs.splice(0)) {
+      rmSync(dir, { recursive: true, force: true });
+    }
+  });
+
+  it("loads JSONC with comments and trailing commas", async () => {
+    const workspaceRoot = writeWorkspace(
+      "hive.config.jsonc",
+      `{
+  // Shared by every template
+  "templates": {
+    "basic": { "id": "basic", "label": "Basic", "type": "manual", },
+  },
+}
+`
+    );
+
+    const config = await loadConfig(workspaceRoot);
+    expect(config.templates.basic?.label).toBe("Basic");
+  });
+
+  it("loads YAML configs", async () => {
+    const workspaceRoot = writeWorkspace(
+      "hive.config.yaml",
+      `templates:
+  basic:
+    id: basic
+    label: Basic
+    type: manual
+    ignorePatterns: [node_modules/**]
+`
+    );
+
+    const config = await loadConfig(workspaceRoot);
+    expect(config.templates.basic?.ignorePatterns).toEqual([
+      "node_modules/**",
+    ]);
+  });
+
+  it("evaluates hive.config.ts default-exported factories", async () => {
+    const workspaceRoot = writeWorkspace(
+      "hive.config.ts",
+      `import { defineHiveConfig } from ${JSON.stringify(SCHEMA_MODULE_PATH)};
+
+const ignorePatterns = ["node_modules/**", ".turbo/**"];
+
+export default defineHiveConfig(({ workspaceRoot }) => ({
+  promptSources: [],
+  templates: {
+    api: { id: "api", label: workspaceRoot, type: "manual", ignorePatterns },
+    web: { id: "web", label: "Web", type: "manual", ignorePatterns },
+  },
+}));
+`
+    );
+
+    const config = await loadConfig(workspaceRoot);
+    expect(config.templates.api?.label).toBe(workspaceRoot);
+    expect(config.templates.web?.ignorePatterns).toEqual([
+      "node_modules/**",
+      ".turbo/**",
+    ]);
+  });
+
+  it("re-evaluates files hive.config.ts imports on every load", async () => {
+    const writeTemplates = (label: string) =>
+      `export const templates = {
+  web: { id: "web", label: ${JSON.stringify(label)}, type: "manual" },
+};
+`;
+    const workspaceRoot = writeWorkspace(
+      "hive.config.ts",
+      `import { templates } from "./templates";
+
+export default { promptSources: [], templates };
+`
+    );
+    const templatesPath = join(workspaceRoot, "templates.ts");
+    writeFileSync(templatesPath, writeTemplates("Before"));
+
+    expect(listConfigImports(workspaceRoot)).toEqual([templatesPath]);
+    expect((await loadConfig(workspaceRoot)).templates.web?.label).toBe(
+      "Before"
+    );
+
+    writeFileSync(templatesPath, writeTemplates("After"));
+    expect((await loadConfig(workspaceRoot)).templates.web?.label).toBe(
+      "After"
+    );
+  });
+
+  it("hides credentials from code configs", async () => {
+    process.env.HIVE_LOADER_TEST_TOKEN = "secret";
+    process.env.HIVE_LOADER_TEST_REGION = "eu";
+    try {
+      const workspaceRoot = writeWorkspace(
+        "hive.config.ts",
+        `export default ({ env }) => ({
+  promptSources: [],
+  templates: {
+    web: {
+      id: "web",
+      label: [
+        env.HIVE_LOADER_TEST_REGION,
+        env.HIVE_LOADER_TEST_TOKEN,
+        process.env.HIVE_LOADER_TEST_TOKEN,
+      ].join(","),
+      type: "manual",
+    },
+  },
+});
+`
+      );
+
+      const config = await loadConfig(workspaceRoot);
+      expect(config.templates.web?.label).toBe("eu,,");
+    } finally {
+      delete process.env.HIVE_LOADER_TEST_TOKEN;
+      delete process.env.HIVE_LOADER_TEST_REGION;
+    }
+  });
+
+  it("points validation errors at the file and line", async () => {
+    const workspaceRoot = writeWorkspace(
+      "hive.config.yaml",
+      `templates:
+  basic:
+    id: basic
+    label: Basic
+    type: manual
+    idle:
+      suspendAfterMinutes: 0
+`
+    );
+
+    const configPath = join(workspaceRoot, "hive.config.yaml");
+    await expect(loadConfig(workspaceRoot)).rejects.toThrow(
+      `${configPath}:7:28 templates.basic.idle.suspendAfterMinutes:`
+    );
+  });
+
+  it("lets a template override part of an inherited service", async () => {
+    const workspaceRoot = writeWorkspace(
+      "hive.config.yaml",
+      `templates:
+  base:
+    id: base
+    label: Base
+    type: manual
+    services:
+      api:
+        type: process
+        run: bun run dev
+  web:
+    id: web
+    label: Web
+    type: manual
+    extends: base
+    services:
+      api:
+        env:
+          LOG_LEVEL: debug
+`
+    );
+
+    const config = await loadConfig(workspaceRoot);
+    expect(config.templates.web?.services?.api).toEqual({
+      type: "process",
+      run: "bun run dev",
+      env: { LOG_LEVEL: "debug" },
+    });
+  });
+
+  it("reports services that stay incomplete after inheritance", async () => {
+    const workspaceRoot = writeWorkspace(
+      "hive.config.yaml",
+      `templates:
+  web:
+    id: web
+    label: Web
+    type: manual
+    services:
+      api:
+        type: process
+        env:
+          LOG_LEVEL: debug
+`
+    );
+
+    await expect(loadConfig(workspaceRoot)).rejects.toThrow(
+      `${join(workspaceRoot, "hive.config.yaml")}:8:9 templates.web.services.api.run:`
+    );
+  });
+
+  it("points unknown extends targets at the extends key", async () => {
+    const workspaceRoot = writeWorkspace(
+      "hive.config.yaml",
+      `templates:
+  web:
+    id: web
+    label: Web
+    type: manual
+    extends: base
+`
+    );
+
+    await expect(loadConfig(workspaceRoot)).rejects.toThrow(
+      `${join(workspaceRoot, "hive.config.yaml")}:6:14: Template "web": Extends unknown template "base"`
+    );
+  });
+
+  it("locates errors in code configs by their keys", async () => {
+    const workspaceRoot = writeWorkspace(
+      "hive.config.js",
+      `export default {
+  templates: {
+    basic: {
+      id: "basic",
+      label: 42,
+      type: "manual",
+    },
+  },
+};
+`
+    );
+
+    await expect(loadConfig(workspaceRoot)).rejects.toThrow(
+      `${join(workspaceRoot, "hive.config.js")}:5:7 templates.basic.label:`
+    );
+  });
+});
+
+describe("stripJsonComments", () => {
+  it("keeps string contents and line numbers intact", () => {
+    const source = `{
+  "url": "http://example.test//path", // trailing
+  /* block
+     comment */ "list": [1, 2,],
+}`;
+
+    const stripped = stripJsonComments(source);
+    expect(stripped.split("\n")).toHaveLength(source.split("\n").length);
+    expect(JSON.parse(stripped)).toEqual({
+      url: "http://example.test//path",
+      list: [1, 2],
+    });
+  });
+});
@@ -1,11 +1,67 @@
+import { readFileSync } from "node:fs";
 import { readFile } from "node:fs/promises";
-import { basename, extname } from "node:path";
+import { basename, dirname, extname } from "node:path";
+import { isNode, LineCounter, parseDocument } from "yaml";
+import { ZodError } from "zod";
 
 import { findConfigPath, PREFERRED_CONFIG_FILENAME } from "./files";
 import type { HiveConfig } from "./schema";
-import { hiveConfigSchema } from "./schema";
+import { unresolvedHiveConfigSchema } from "./schema";
+import { validateTemplateServiceDependencies } from "./service-dependencies";
+import {
+  resolveTemplateInheritance,
+  TemplateInheritanceError,
+} from "./template-inheritance";
 
-const JSON_EXTENSIONS = new Set([".json"]);
+const JSON_EXTENSIONS = new Set([".json", ".jsonc"]);
+const YAML_EXTENSIONS = new Set([".yaml", ".yml"]);
+const MODULE_EXTENSIONS = new Set([".ts", ".js"]);
+const REGEX_SPECIAL_CHARS = /[.*+?^${}()|[\]\\]/g;
+const WHITESPACE = /\s/;
+const MODULE_CONFIG_TIMEOUT_MS = 15_000;
+const MODULE_CONFIG_RESULT_MARKER = "\u0000hive-config-result\u0000";
+const isCompiledRuntime = !basename(process.execPath)
+  .toLowerCase()
+  .startsWith("bun");
+const SENSITIVE_ENV_PATTERN =
+  /TOKEN|SECRET|PASSWORD|PASSWD|CREDENTIAL|PRIVATE_KEY|API_KEY|DATABASE_URL/i;
+
+/**
+ * Evaluated by a fresh `bun -e` for every load, so the config and the local
+ * files it imports are always read from disk and nothing stays cached in
+ * the server. The result goes to stdout after a marker, which keeps
+ * `console.log` calls in the config out of it.
+ */
+const MODULE_CONFIG_SCRIPT = `
+const [configPath, workspaceRoot] = process.argv.slice(-2);
+let result;
+try {
+  let value = (await import(configPath)).default;
+  if (typeof value === "function") {
+    value = await value({
+      workspaceRoot,
+      platform: process.platform,
+      env: { ...process.env },
+    });
+  }
+  result = { value };
+} catch (error) {
+  result =
+    error?.name === "ZodError" && Array.isArray(error.issues)
+      ? { issues: error.issues }
+      : { error: error instanceof Error ? error.message : String(error) };
+}
+const marker = ${JSON.stringify(MODULE_CONFIG_RESULT_MARKER)};
+process.stdout.write(marker + JSON.stringify(result));
+`;
+
+type SourcePosition = { line: number; col: number };
+
+type LoadedConfig = {
+  value: unknown;
+  /** Maps a config path to where it is written, when that can be told. */
+  locate: (path: PropertyKey[]) => SourcePosition | null;
+};
 
 export async function loadConfig(workspaceRoot: string): Promise<HiveConfig> {
   const configPath = findConfigPath(workspaceRoot);
@@ -17,45 +73,387 @@ export async function loadConfig(workspaceRoot: string): Promise<HiveConfig> {
   }
 
   const extension = extname(configPath).toLowerCase();
-  if (!JSON_EXTENSIONS.has(extension)) {
+  let loaded: LoadedConfig;
+  if (JSON_EXTENSIONS.has(extension)) {
+    loaded = await loadJsonConfig(configPath);
+  } else if (YAML_EXTENSIONS.has(extension)) {
+    loaded = await loadYamlConfig(configPath);
+  } else if (MODULE_EXTENSIONS.has(extension)) {
+    loaded = await loadModuleConfig(configPath, workspaceRoot);
+  } else {
     throw new Error(
       `Unsupported config format at ${basename(configPath)}. Use ${PREFERRED_CONFIG_FILENAME}.`
     );
   }
 
-  const config = await loadJsonConfig(configPath);
+  const config = loaded.value;
+  if (!config || typeof config !== "object" || Array.isArray(config)) {
+    throw new Error(
+      `Invalid config format in ${basename(configPath)}: expected an object`
+    );
+  }
+  (config as Record<string, unknown>).$schema = undefined;
 
-  if (config && typeof config === "object" && !Array.isArray(config)) {
-    (config as Record<string, unknown>).$schema = undefined;
+  // Templates are validated after inheritance is resolved, so an override
+  // only needs the fields it changes.
+  const result = unresolvedHiveConfigSchema.safeParse(config);
+  if (!result.success) {
+    throw new Error(formatConfigIssues(configPath, result.error, loaded));
+  }
+  let parsed: HiveConfig;
+  try {
+    parsed = resolveTemplateInheritance(result.data);
+  } catch (error) {
+    if (error instanceof ZodError) {
+      throw new Error(formatConfigIssues(configPath, error, loaded));
+    }
+    if (!(error instanceof TemplateInheritanceError)) {
+      throw error;
+    }
+    const position = loaded.locate(["templates", error.templateId, "extends"]);
+    const location = position
+      ? `${configPath}:${position.line}:${position.col}`
+      : configPath;
+    throw new Error(
+      `Invalid template inheritance in ${basename(configPath)}:\n  ${location}: ${error.message}`
+    );
   }
 
-  return hiveConfigSchema.parse(config);
+  try {
+    validateTemplateServiceDependencies(parsed.templates);
+  } catch (error) {
+    throw new Error(
+      `Invalid service dependencies in ${basename(configPath)}: ${(error as Error).message}`
+    );
+  }
+
+  return parsed;
+}
+
+/**
+ * Lists every schema violation as `<file>:<line>:<col> <path>: <message>` so
+ * editors and terminals can jump straight to it.
+ */
+export function formatConfigIssues(
+  configPath: string,
+  error: ZodError,
+  loaded: Pick<LoadedConfig, "locate">
+): string {
+  const lines = error.issues.map((issue) => {
+    const path = [...issue.path];
+    if (issue.code === "unrecognized_keys" && issue.keys[0]) {
+      path.push(issue.keys[0]);
+    }
+    const position = loaded.locate(path);
+    const location = position
+      ? `${configPath}:${position.line}:${position.col}`
+      : configPath;
+    const field = path.length > 0 ? ` ${path.map(String).join(".")}` : "";
+    return `  ${location}${field}: ${issue.message}`;
+  });
+  return `Invalid config in ${basename(configPath)}:\n${lines.join("\n")}`;
 }
 
-const loadJsonConfig = async (configPath: string): Promise<unknown> => {
-  let contents: string;
+const readConfigFile = async (configPath: string): Promise<string> => {
   try {
-    contents = await readFile(configPath, "utf8");
+    return await readFile(configPath, "utf8");
   } catch (error) {
     throw new Error(
       `Could not read ${basename(configPath)}: ${(error as Error).message}`
     );
   }
+};
+
+/**
+ * Positions come from parsing the text as YAML, which is a superset of JSON,
+ * so YAML and JSON configs share one locator.
+ */
+const createDocumentLocator = (contents: string) => {
+  const lineCounter = new LineCounter();
+  const document = parseDocument(contents, { lineCounter });
+  const locate = (path: PropertyKey[]): SourcePosition | null => {
+    for (let depth = path.length; depth >= 0; depth--) {
+      const node =
+        depth === 0
+          ? document.contents
+          : document.getIn(path.slice(0, depth) as unknown[], true);
+      if (isNode(node) && node.range) {
+        return lineCounter.linePos(node.range[0]);
+      }
+    }
+    return null;
+  };
+  return { document, locate };
+};
+
+const loadJsonConfig = async (configPath: string): Promise<LoadedConfig> => {
+  const raw = await readConfigFile(configPath);
+  const contents =
+    extname(configPath).toLowerCase() === ".jsonc"
+      ? stripJsonComments(raw)
+      : raw;
+  const { document, locate } = createDocumentLocator(contents);
 
-  let parsed: unknown;
   try {
-    parsed = JSON.parse(contents);
+    return { value: JSON.parse(contents), locate };
   } catch (error) {
+    const position = document.errors[0]?.linePos?.[0];
+    const location = position ? `:${position.line}:${position.col}` : "";
     throw new Error(
-      `Could not parse ${basename(configPath)}: ${(error as Error).message}`
+      `Could not parse ${basename(configPath)}${location}: ${(error as Error).message}`
     );
   }
+};
+
+const loadYamlConfig = async (configPath: string): Promise<LoadedConfig> => {
+  const contents = await readConfigFile(configPath);
+  const { document, locate } = createDocumentLocator(contents);
 
-  if (!parsed || typeof parsed !== "object" || Array.isArray(parsed)) {
+  const [parseError] = document.errors;
+  if (parseError) {
+    const position = parseError.linePos?.[0];
+    const location = position ? `:${position.line}:${position.col}` : "";
     throw new Error(
-      `Invalid config format in ${basename(configPath)}: expected a JSON object`
+      `Could not parse ${basename(configPath)}${location}: ${parseError.message}`
     );
   }
 
-  return parsed;
+  return { value: document.toJS(), locate };
 };
+
+/**
+ * Evaluates `hive.config.ts`/`.js` in a short-lived subprocess. The default
+ * export is either the config or a (possibly async) function that receives
+ * the environment it is loaded in; either way the result must be plain
+ * JSON data. Secrets are stripped from the environment the config sees.
+ */
+const loadModuleConfig = async (
+  configPath: string,
+  workspaceRoot: string
+): Promise<LoadedConfig> => {
+  const source = await readConfigFile(configPath);
+  const locate = (path: PropertyKey[]) => locateInSource(source, path);
+
+  let result: { value?: unknown; issues?: unknown; error?: string };
+  try {
+    result = await evaluateModuleConfig(configPath, workspaceRoot);
+  } catch (error) {
+    throw new Error(
+      `Could not load ${basename(configPath)}: ${(error as Error).message}`
+    );
+  }
+
+  if (Array.isArray(result.issues)) {
+    // defineHiveConfig validates object configs while the module loads.
+    const error = new ZodError(result.issues as ZodError["issues"]);
+    throw new Error(formatConfigIssues(configPath, error, { locate }));
+  }
+  if (result.error !== undefined) {
+    throw new Error(`Could not load ${basename(configPath)}: ${result.error}`);
+  }
+  if (result.value === undefined) {
+    throw new Error(
+      `${basename(configPath)} must default-export a config or a function returning one`
+    );
+  }
+  return { value: result.value, locate };
+};
+
+const evaluateModuleConfig = async (
+  configPath: string,
+  workspaceRoot: string
+) => {
+  const env = configEnvironment();
+  const child = Bun.spawn({
+    cmd: [
+      process.execPath,
+      "-e",
+      MODULE_CONFIG_SCRIPT,
+      configPath,
+      workspaceRoot,
+    ],
+    cwd: workspaceRoot,
+    stdout: "pipe",
+    stderr: "pipe",
+    // A compiled hive binary only acts as the bun CLI when asked to.
+    env: isCompiledRuntime ? { ...env, BUN_BE_BUN: "1" } : env,
+    timeout: MODULE_CONFIG_TIMEOUT_MS,
+  });
+
+  const stdoutPromise = new Response(child.stdout).text();
+  const stderrPromise = new Response(child.stderr).text();
+  const exitCode = await child.exited;
+  const stdout = await stdoutPromise;
+  const stderr = await stderrPromise;
+
+  const markerIndex = stdout.lastIndexOf(MODULE_CONFIG_RESULT_MARKER);
+  if (markerIndex === -1) {
+    const reason = child.signalCode
+      ? `timed out after ${MODULE_CONFIG_TIMEOUT_MS / 1000}s`
+      : stderr.trim() || `exited with code ${exitCode}`;
+    throw new Error(reason);
+  }
+  return JSON.parse(
+    stdout.slice(markerIndex + MODULE_CONFIG_RESULT_MARKER.length)
+  ) as { value?: unknown; issues?: unknown; error?: string };
+};
+
+/** The server environment minus anything that looks like a credential. */
+export function configEnvironment(
+  env: NodeJS.ProcessEnv = process.env
+): Record<string, string> {
+  const filtered: Record<string, string> = {};
+  for (const [name, value] of Object.entries(env)) {
+    if (value !== undefined && !SENSITIVE_ENV_PATTERN.test(name)) {
+      filtered[name] = value;
+    }
+  }
+  return filtered;
+}
+
+/**
+ * Local files a workspace's code config imports, directly or through each
+ * other, so edits to them can trigger a reload. Package imports and files
+ * that cannot be parsed are left out.
+ */
+export function listConfigImports(workspaceRoot: string): string[] {
+  const configPath = findConfigPath(workspaceRoot);
+  if (
+    !(configPath && MODULE_EXTENSIONS.has(extname(configPath).toLowerCase()))
+  ) {
+    return [];
+  }
+
+  const found = new Set<string>([configPath]);
+  const visit = (file: string) => {
+    let imports: { path: string }[];
+    try {
+      const loader = extname(file).toLowerCase() === ".ts" ? "ts" : "js";
+      imports = new Bun.Transpiler({ loader }).scanImports(
+        readFileSync(file, "utf8")
+      );
+    } catch {
+      return;
+    }
+    for (const { path } of imports) {
+      if (!path.startsWith(".")) {
+        continue;
+      }
+      let resolved: string;
+      try {
+        resolved = Bun.resolveSync(path, dirname(file));
+      } catch {
+        continue;
+      }
+      if (!found.has(resolved)) {
+        found.add(resolved);
+        visit(resolved);
+      }
+    }
+  };
+  visit(configPath);
+  found.delete(configPath);
+  return [...found];
+}
+
+/**
+ * Best-effort position for code configs: finds each key of the path in
+ * order, so computed or spread keys fall back to the closest parent found.
+ */
+export function locateInSource(
+  source: string,
+  path: PropertyKey[]
+): SourcePosition | null {
+  let offset = -1;
+  let searchFrom = 0;
+  for (const segment of path) {
+    if (typeof segment !== "string") {
+      continue;
+    }
+    const escaped = segment.replace(REGEX_SPECIAL_CHARS, "\\$&");
+    const pattern = new RegExp(`(["'\`]?)${escaped}\\1\\s*:`, "g");
+    pattern.lastIndex = searchFrom;
+    const match = pattern.exec(source);
+    if (!match) {
+      break;
+    }
+    offset = match.index;
+    searchFrom = match.index + match[0].length;
+  }
+  if (offset < 0) {
+    return null;
+  }
+
+  const before = source.slice(0, offset);
+  const line = before.split("\n").length;
+  return { line, col: offset - before.lastIndexOf("\n") };
+}
+
+/**
+ * Blanks out comments and trailing commas in JSONC. Replaced characters
+ * become spaces and newlines are kept, so line and column numbers still
+ * match the original file.
+ */
+export function stripJsonComments(contents: string): string {
+  const output = contents.split("");
+  let index = 0;
+  let inString = false;
+  let lastSignificant = -1;
+
+  const blank = (from: number, to: number) => {
+    for (let position = from; position < to; position += 1) {
+      if (output[position] !== "\n" && output[position] !== "\r") {
+        output[position] = " ";
+      }
+    }
+  };
+
+  while (index < contents.length) {
+    const char = contents[index];
+    if (inString) {
+      if (char === "\\") {
+        index += 2;
+        continue;
+      }
+      if (char === '"') {
+        inString = false;
+        lastSignificant = index;
+      }
+      index += 1;
+      continue;
+    }
+
+    if (char === '"') {
+      inString = true;
+      index += 1;
+      continue;
+    }
+    if (char === "/" && contents[index + 1] === "/") {
+      const end = contents.indexOf("\n", index);
+      const stop = end === -1 ? contents.length : end;
+      blank(index, stop);
+      index = stop;
+      continue;
+    }
+    if (char === "/" && contents[index + 1] === "*") {
+      const end = contents.indexOf("*/", index + 2);
+      const stop = end === -1 ? contents.length : end + 2;
+      blank(index, stop);
+      index = stop;
+      continue;
+    }
+    if (
+      (char === "}" || char === "]") &&
+      lastSignificant >= 0 &&
+      output[lastSignificant] === ","
+    ) {
+      output[lastSignificant] = " ";
+    }
+    if (char !== undefined && !WHITESPACE.test(char)) {
+      lastSignificant = index;
+    }
+    index += 1;
+  }
+
+  return output.join("");
+}
@@ -1,5 +1,128 @@
 import { z } from "zod";
 
+const readinessTimingSchema = {
+  intervalMs: z
+    .number()
+    .int()
+    .positive()
+    .optional()
+    .describe("Milliseconds between readiness attempts"),
+  timeoutMs: z
+    .number()
+    .int()
+    .positive()
+    .optional()
+    .describe(
+      "Milliseconds to wait for readiness (defaults to the service readyTimeoutMs)"
+    ),
+};
+
+const httpReadinessSchema = z.object({
+  type: z.literal("http").describe("Probe type"),
+  path: z
+    .string()
+    .optional()
+    .describe("Request path for the HTTP GET probe (defaults to '/')"),
+  host: z
+    .string()
+    .optional()
+    .describe("Host to probe (defaults to 127.0.0.1)"),
+  port: z
+    .number()
+    .int()
+    .positive()
+    .optional()
+    .describe("Port to probe (defaults to the service's assigned port)"),
+  expectedStatus: z
+    .number()
+    .int()
+    .optional()
+    .describe(
+      "HTTP status code that marks the service ready (defaults to 200)"
+    ),
+  ...readinessTimingSchema,
+});
+
+const tcpReadinessSchema = z.object({
+  type: z.literal("tcp").describe("Probe type"),
+  host: z
+    .string()
+    .optional()
+    .describe("Host to connect to (defaults to 127.0.0.1)"),
+  port: z
+    .number()
+    .int()
+    .positive()
+    .optional()
+    .describe("Port to connect to (defaults to the service's assigned port)"),
+  ...readinessTimingSchema,
+});
+
+const logReadinessSchema = z.object({
+  type: z.literal("log").describe("Probe type"),
+  pattern: z
+    .string()
+    .min(1)
+    .refine(
+      (value) => {
+        try {
+          new RegExp(value);
+          return true;
+        } catch {
+          return false;
+        }
+      },
+      { message: "Readiness pattern must be a valid regular expression" }
+    )
+    .describe("Regular expression matched against service output"),
+  ...readinessTimingSchema,
+});
+
+const commandReadinessSchema = z.object({
+  type: z.literal("command").describe("Probe type"),
+  run: z
+    .string()
+    .describe("Shell command that exits 0 once the service is ready"),
+  ...readinessTimingSchema,
+});
+
+export const readinessSchema = z
+  .discriminatedUnion("type", [
+    httpReadinessSchema,
+    tcpReadinessSchema,
+    logReadinessSchema,
+    commandReadinessSchema,
+  ])
+  .describe("Probe that must pass before a service is marked running");
+
+const dependsOnSchema = z
+  .array(z.string())
+  .optional()
+  .describe("Template services that must be ready before this service starts");
+
+export const restartPolicySchema = z
+  .object({
+    policy: z
+      .enum(["never", "on-failure", "always"])
+      .optional()
+      .describe("When to restart the service after it exits (default: never)"),
+    maxRetries: z
+      .number()
+      .int()
+      .min(0)
+      .optional()
+      .describe("Restarts attempted before giving up (default: 5)"),
+    backoffMs: z
+      .number()
+      .int()
+      .positive()
+      .optional()
+      .describe(
+        "Delay before the first restart, doubled for each retry (default: 1000)"
+      ),
+  })
+  .describe("Automatic restart behaviour for crashed services");
+
 export const processServiceSchema = z.object({
   type: z.literal("process").default("process").describe("Service type"),
   run: z.string().describe("Command to run service"),
@@ -16,17 +139,46 @@ export const processServiceSchema = z.object({
     .number()
     .optional()
     .describe("Milliseconds to wait for service to be ready"),
+  readiness: readinessSchema.optional(),
+  dependsOn: dependsOnSchema,
+  restart: restartPolicySchema.optional(),
   stop: z.string().optional().describe("Command to gracefully stop service"),
 });
 
+const CONTAINER_PORT_ONLY = /^\d+(?:\/(?:tcp|udp|sctp))?$/;
+const OWN_PORT_MAPPING = /^\$(?:PORT|\{PORT\}):(\d+(?:\/(?:tcp|udp|sctp))?)$/;
+
+/**
+ * The container port a docker port entry publishes on the service's
+ * assigned port (`5432`, `$PORT:5432`), or null for other mappings.
+ */
+export function assignedPortTarget(mapping: string): string | null {
+  const trimmed = mapping.trim();
+  if (CONTAINER_PORT_ONLY.test(trimmed)) {
+    return trimmed;
+  }
+  return OWN_PORT_MAPPING.exec(trimmed)?.[1] ?? null;
+}
+
 export const dockerServiceSchema = z.object({
   type: z.literal("docker").describe("Service type"),
   image: z.string().describe("Docker image to use"),
   command: z.string().optional().describe("Command to override default"),
   ports: z
     .array(z.string())
+    .refine(
+      (ports) =>
+        ports.filter((mapping) => assignedPortTarget(mapping) !== null)
+          .length <= 1,
+      {
+        message:
+          "Only one port can be published on the assigned port; give the others a host port, e.g. '6380:6379'",
+      }
+    )
     .optional()
-    .describe("Port mappings (e.g., '3000:3000')"),
+    .describe(
+      "Port mappings (e.g., '5432' or '$PORT:5432' to publish on the assigned port)"
+    ),
   env: z
     .record(z.string(), z.string())
     .optional()
@@ -36,6 +188,9 @@ export const dockerServiceSchema = z.object({
     .number()
     .optional()
     .describe("Milliseconds to wait for service to be ready"),
+  readiness: readinessSchema.optional(),
+  dependsOn: dependsOnSchema,
+  restart: restartPolicySchema.optional(),
 });
 
 export const composeServiceSchema = z.object({
@@ -46,6 +201,13 @@ export const composeServiceSchema = z.object({
     .record(z.string(), z.string())
     .optional()
     .describe("Environment variables"),
+  readyTimeoutMs: z
+    .number()
+    .optional()
+    .describe("Milliseconds to wait for each compose service to be ready"),
+  readiness: readinessSchema.optional(),
+  dependsOn: dependsOnSchema,
+  restart: restartPolicySchema.optional(),
 });
 
 export const serviceSchema = z
@@ -81,10 +243,65 @@ const templateAgentSchema = z.object({
   agentId: z.string().optional().describe("Agent preset identifier"),
 });
 
+export const idlePolicySchema = z
+  .object({
+    suspendAfterMinutes: z
+      .number()
+      .int()
+      .positive()
+      .describe(
+        "Minutes without agent activity, terminal input or service traffic before the cell is suspended"
+      ),
+  })
+  .describe(
+    "Stops services and the agent runtime of idle cells until they are opened again"
+  );
+
+const PARAMETER_NAME_PATTERN = /^[A-Za-z_][A-Za-z0-9_]*$/;
+
+export const templateParameterSchema = z
+  .object({
+    name: z
+      .string()
+      .regex(PARAMETER_NAME_PATTERN, {
+        message: "Parameter names must be valid identifiers",
+      })
+      .describe("Name referenced as ${params.<name>}"),
+    type: z
+      .enum(["string", "number", "boolean"])
+      .default("string")
+      .describe("Value type (default: string)"),
+    default: z
+      .union([z.string(), z.number(), z.boolean()])
+      .optional()
+      .describe("Value used when none is supplied at cell creation"),
+    enum: z
+      .array(z.union([z.string(), z.number()]))
+      .optional()
+      .describe("Allowed values"),
+    description: z
+      .string()
+      .optional()
+      .describe("Help text shown next to the input"),
+  })
+  .describe("Input supplied when creating a cell from this template");
+
 export const templateSchema = z.object({
   id: z.string().describe("Unique template identifier"),
   label: z.string().describe("Display name for template"),
   type: z.literal("manual").describe("Template type"),
+  extends: z
+    .string()
+    .optional()
+    .describe(
+      "Template whose services, env, setup, prompts and patterns are inherited"
+    ),
+  parameters: z
+    .array(templateParameterSchema)
+    .optional()
+    .describe(
+      "Inputs interpolated into env, setup, service commands and prompts as ${params.<name>}"
+    ),
   services: z
     .record(z.string(), serviceSchema)
     .optional()
@@ -92,7 +309,9 @@ export const templateSchema = z.object({
   env: z
     .record(z.string(), z.string())
     .optional()
-    .describe("Global environment variables"),
+    .describe(
+      "Global environment variables; ${secret:NAME} reads a workspace secret"
+    ),
   setup: z
     .array(z.string())
     .optional()
@@ -122,8 +341,22 @@ export const templateSchema = z.object({
     .describe(
       "Glob patterns to skip when copying included files into worktrees"
     ),
+  idle: idlePolicySchema.optional(),
 });
 
+export const templateDefaultsSchema = templateSchema
+  .omit({ id: true, label: true, type: true, extends: true })
+  .describe("Fields every template inherits before its own extends chain");
+
+/**
+ * A template as written. Only the fields `extends` resolution needs are
+ * checked here; the rest is validated once inherited fields are merged in, so
+ * a child can override part of an inherited service.
+ */
+const templateLayerSchema = templateSchema
+  .pick({ id: true, label: true, type: true, extends: true })
+  .passthrough();
+
 const opencodeConfigSchema = z
   .object({
     token: z
@@ -159,6 +392,48 @@ export const defaultsSchema = z
   })
   .describe("Default values for cell creation");
 
+export const forgeConfigSchema = z
+  .object({
+    type: z
+      .enum(["github", "gitlab"])
+      .describe("Hosting service used to open pull requests"),
+    apiUrl: z
+      .string()
+      .optional()
+      .describe(
+        "API base URL (defaults to https://api.github.com or https://gitlab.com/api/v4)"
+      ),
+    repository: z
+      .string()
+      .optional()
+      .describe(
+        "Repository path such as 'owner/repo' (defaults to the remote URL path)"
+      ),
+    tokenEnv: z
+      .string()
+      .optional()
+      .describe(
+        "Environment variable holding the API token (defaults to GITHUB_TOKEN or GITLAB_TOKEN)"
+      ),
+  })
+  .describe("Forge used to open pull requests for cell branches");
+
+export const gitConfigSchema = z
+  .object({
+    remote: z
+      .string()
+      .optional()
+      .describe("Remote that cell branches are pushed to (default: origin)"),
+    baseBranch: z
+      .string()
+      .optional()
+      .describe(
+        "Branch pull requests target (defaults to the remote's default branch)"
+      ),
+    forge: forgeConfigSchema.optional(),
+  })
+  .describe("Git settings for committing, pushing and opening pull requests");
+
 export const hiveConfigSchema = z
   .object({
     opencode: opencodeConfigSchema.optional(),
@@ -171,20 +446,50 @@ export const hiveConfigSchema = z
     templates: z
       .record(z.string(), templateSchema)
       .describe("Available cell templates"),
+    templateDefaults: templateDefaultsSchema.optional(),
     defaults: defaultsSchema.optional(),
+    git: gitConfigSchema.optional(),
   })
   .describe("Hive workspace configuration");
 
+/** The config as written, before template inheritance is resolved. */
+export const unresolvedHiveConfigSchema = hiveConfigSchema.extend({
+  templates: z.record(z.string(), templateLayerSchema),
+});
+
+export type Readiness = z.infer<typeof readinessSchema>;
+export type RestartPolicy = z.infer<typeof restartPolicySchema>;
+export type IdlePolicy = z.infer<typeof idlePolicySchema>;
 export type ProcessService = z.infer<typeof processServiceSchema>;
 export type DockerService = z.infer<typeof dockerServiceSchema>;
 export type ComposeService = z.infer<typeof composeServiceSchema>;
 export type Service = z.infer<typeof serviceSchema>;
 export type TemplateAgent = z.infer<typeof templateAgentSchema>;
+export type TemplateParameter = z.infer<typeof templateParameterSchema>;
 export type Template = z.infer<typeof templateSchema>;
+export type TemplateDefaults = z.infer<typeof templateDefaultsSchema>;
 export type OpencodeConfig = z.infer<typeof opencodeConfigSchema>;
 export type Defaults = z.infer<typeof defaultsSchema>;
+export type GitConfig = z.infer<typeof gitConfigSchema>;
+export type ForgeConfig = z.infer<typeof forgeConfigSchema>;
 export type HiveConfig = z.infer<typeof hiveConfigSchema>;
+export type UnresolvedHiveConfig = z.infer<typeof unresolvedHiveConfigSchema>;
+
+/** Passed to a `hive.config.ts` whose default export is a function. */
+export type HiveConfigEnvironment = {
+  workspaceRoot: string;
+  platform: NodeJS.Platform;
+  env: Record<string, string | undefined>;
+};
+
+export type HiveConfigFactory = (
+  environment: HiveConfigEnvironment
+) => HiveConfig | Promise<HiveConfig>;
 
-export function defineHiveConfig(config: HiveConfig): HiveConfig {
-  return hiveConfigSchema.parse(config);
+export function defineHiveConfig(config: HiveConfig): HiveConfig;
+export function defineHiveConfig(config: HiveConfigFactory): HiveConfigFactory;
+export function defineHiveConfig(
+  config: HiveConfig | HiveConfigFactory
+): HiveConfig | HiveConfigFactory {
+  return typeof config === "function" ? config : hiveConfigSchema.parse(config);
 }
@@ -0,0 +1,91 @@
+import { mkdtempSync, rmSync, writeFileSync } from "node:fs";
+import { tmpdir } from "node:os";
+import { join } from "node:path";
+import { afterEach, describe, expect, it } from "vitest";
+
+import { PREFERRED_CONFIG_FILENAME } from "./files";
+import { loadConfig } from "./loader";
+import {
+  collectServiceDependencies,
+  resolveServiceStartOrder,
+} from "./service-dependencies";
+
+describe("resolveServiceStartOrder", () => {
+  it("places dependencies before their dependents", () => {
+    const order = resolveServiceStartOrder({
+      web: { dependsOn: ["api"] },
+      api: { dependsOn: ["db", "cache"] },
+      db: {},
+      cache: {},
+    });
+
+    expect(order).toEqual(["db", "cache", "api", "web"]);
+  });
+
+  it("reports the cycle path", () => {
+    expect(() =>
+      resolveServiceStartOrder(
+        {
+          api: { dependsOn: ["worker"] },
+          worker: { dependsOn: ["api"] },
+        },
+        "web-app"
+      )
+    ).toThrow(
+      'Template "web-app": Service dependency cycle detected: api -> worker -> api'
+    );
+  });
+
+  it("rejects unknown dependencies", () => {
+    expect(() =>
+      resolveServiceStartOrder({ api: { dependsOn: ["db"] } })
+    ).toThrow('Service "api" depends on unknown service "db"');
+  });
+
+  it("collects transitive dependencies in start order", () => {
+    const services = {
+      web: { dependsOn: ["api"] },
+      api: { dependsOn: ["db"] },
+      db: {},
+      docs: {},
+    };
+
+    expect(collectServiceDependencies(services, "web")).toEqual(["db", "api"]);
+    expect(collectServiceDependencies(services, "docs")).toEqual([]);
+  });
+});
+
+describe("loadConfig service dependencies", () => {
+  const createdDirs: string[] = [];
+
+  afterEach(() => {
+    for (const dir of createdDirs.splice(0)) {
+      rmSync(dir, { recursive: true, force: true });
+    }
+  });
+
+  it("fails to load configs with dependency cycles", async () => {
+    const workspaceRoot = mkdtempSync(join(tmpdir(), "hive-deps-"));
+    createdDirs.push(workspaceRoot);
+    writeFileSync(
+      join(workspaceRoot, PREFERRED_CONFIG_FILENAME),
+      JSON.stringify({
+        templates: {
+          basic: {
+            id: "basic",
+            label: "Basic",
+            type: "manual",
+            services: {
+              api: { run: "bun run api", dependsOn: ["db"] },
+              db: { run: "bun run db", dependsOn: ["api"] },
+            },
+          },
+        },
+      })
+    );
+
+    await expect(loadConfig(workspaceRoot)).rejects.toThrow(
+      "Service dependency cycle detected: api -> db -> api"
+    );
+  });
+});
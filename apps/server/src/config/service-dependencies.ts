@@ -0,0 +1,93 @@
+import type { Service, Template } from "./schema";
+
+export class ServiceDependencyError extends Error {
+  readonly templateId?: string;
+
+  constructor(message: string, templateId?: string) {
+    super(templateId ? `Template "${templateId}": ${message}` : message);
+    this.name = "ServiceDependencyError";
+    if (templateId) {
+      this.templateId = templateId;
+    }
+  }
+}
+
+/**
+ * Orders template services so every service comes after the services listed
+ * in its `dependsOn`. Services without dependencies keep their declaration
+ * order. Throws on unknown dependencies and cycles.
+ */
+export function resolveServiceStartOrder(
+  services: Record<string, Pick<Service, "dependsOn">> = {},
+  templateId?: string
+): string[] {
+  const names = Object.keys(services);
+  const ordered: string[] = [];
+  const state = new Map<string, "visiting" | "done">();
+
+  const visit = (name: string, path: string[]) => {
+    const current = state.get(name);
+    if (current === "done") {
+      return;
+    }
+    if (current === "visiting") {
+      const cycleStart = path.indexOf(name);
+      const cycle = [...path.slice(cycleStart), name].join(" -> ");
+      throw new ServiceDependencyError(
+        `Service dependency cycle detected: ${cycle}`,
+        templateId
+      );
+    }
+
+    state.set(name, "visiting");
+    for (const dependency of services[name]?.dependsOn ?? []) {
+      if (!(dependency in services)) {
+        throw new ServiceDependencyError(
+          `Service "${name}" depends on unknown service "${dependency}"`,
+          templateId
+        );
+      }
+      visit(dependency, [...path, name]);
+    }
+    state.set(name, "done");
+    ordered.push(name);
+  };
+
+  for (const name of names) {
+    visit(name, []);
+  }
+
+  return ordered;
+}
+
+/**
+ * Returns the transitive dependencies of `name` in start order, excluding the
+ * service itself.
+ */
+export function collectServiceDependencies(
+  services: Record<string, Pick<Service, "dependsOn">>,
+  name: string
+): string[] {
+  const required = new Set<string>();
+  const pending = [...(services[name]?.dependsOn ?? [])];
+  while (pending.length) {
+    const next = pending.pop();
+    if (!next || required.has(next) || next === name) {
+      continue;
+    }
+    required.add(next);
+    pending.push(...(services[next]?.dependsOn ?? []));
+  }
+
+  return resolveServiceStartOrder(services).filter((candidate) =>
+    required.has(candidate)
+  );
+}
+
+export function validateTemplateServiceDependencies(
+  templates: Record<string, Template>
+): void {
+  for (const [templateId, template] of Object.entries(templates)) {
+    resolveServiceStartOrder(template.services, templateId);
+  }
+}
@@ -0,0 +1,184 @@
+import { describe, expect, it } from "vitest";
+import { ZodError } from "zod";
+
+import type { HiveConfig, Template, UnresolvedHiveConfig } from "./schema";
+import {
+  getTemplateFieldOrigins,
+  resolveTemplateInheritance,
+  TEMPLATE_DEFAULTS_ORIGIN,
+} from "./template-inheritance";
+
+const buildConfig = (
+  templates: Record<string, Record<string, unknown>>,
+  templateDefaults?: HiveConfig["templateDefaults"]
+): UnresolvedHiveConfig => ({
+  promptSources: [],
+  templates: Object.fromEntries(
+    Object.entries(templates).map(([id, template]) => [
+      id,
+      { id, label: id, type: "manual" as const, ...template },
+    ])
+  ),
+  ...(templateDefaults ? { templateDefaults } : {}),
+});
+
+describe("resolveTemplateInheritance", () => {
+  it("layers templateDefaults, ancestors and the template itself", () => {
+    const config = resolveTemplateInheritance(
+      buildConfig(
+        {
+          base: {
+            env: { NODE_ENV: "development", LOG_LEVEL: "info" },
+            setup: ["bun install"],
+            services: {
+              api: {
+                type: "process",
+                run: "bun run dev",
+                env: { PORT: "$PORT" },
+              },
+            },
+          },
+          web: {
+            extends: "base",
+            env: { LOG_LEVEL: "debug" },
+            setup: ["bun run build"],
+            services: {
+              api: { type: "process", run: "bun run dev:web" },
+            },
+          },
+        },
+        { ignorePatterns: ["node_modules/**"], includePatterns: [".env"] }
+      )
+    );
+
+    const web = config.templates.web;
+    expect(web?.label).toBe("web");
+    expect(web?.env).toEqual({ NODE_ENV: "development", LOG_LEVEL: "debug" });
+    expect(web?.setup).toEqual(["bun install", "bun run build"]);
+    expect(web?.ignorePatterns).toEqual(["node_modules/**"]);
+    expect(web?.services?.api).toEqual({
+      type: "process",
+      run: "bun run dev:web",
+      env: { PORT: "$PORT" },
+    });
+    expect(config.templates.base?.env?.LOG_LEVEL).toBe("info");
+  });
+
+  it("replaces services whose type changes", () => {
+    const config = resolveTemplateInheritance(
+      buildConfig({
+        base: {
+          services: {
+            db: { type: "process", run: "postgres", env: { PGPORT: "5432" } },
+          },
+        },
+        child: {
+          extends: "base",
+          services: { db: { type: "docker", image: "postgres:16" } },
+        },
+      })
+    );
+
+    expect(config.templates.child?.services?.db).toEqual({
+      type: "docker",
+      image: "postgres:16",
+    });
+  });
+
+  it("merges service overrides that only change some fields", () => {
+    const config = resolveTemplateInheritance(
+      buildConfig({
+        base: {
+          services: {
+            db: { type: "docker", image: "postgres:16", env: { A: "1" } },
+            api: { type: "process", run: "bun run dev" },
+          },
+        },
+        child: {
+          extends: "base",
+          services: {
+            db: { env: { B: "2" } },
+            api: { env: { PORT: "$PORT" } },
+          },
+        },
+      })
+    );
+
+    expect(config.templates.child?.services).toEqual({
+      db: { type: "docker", image: "postgres:16", env: { A: "1", B: "2" } },
+      api: { type: "process", run: "bun run dev", env: { PORT: "$PORT" } },
+    });
+  });
+
+  it("validates templates once inherited fields are merged", () => {
+    expect(() =>
+      resolveTemplateInheritance(
+        buildConfig({
+          web: { services: { api: { type: "process", env: { A: "1" } } } },
+        })
+      )
+    ).toThrow(ZodError);
+  });
+
+  it("replaces inherited parameters that share a name", () => {
+    const config = resolveTemplateInheritance(
+      buildConfig({
+        base: {
+          parameters: [
+            { name: "dbSeed", type: "string", default: "demo" },
+            { name: "workers", type: "number", default: 2 },
+          ],
+        },
+        child: {
+          extends: "base",
+          parameters: [{ name: "dbSeed", type: "string", default: "full" }],
+        },
+      })
+    );
+
+    expect(config.templates.child?.parameters).toEqual([
+      { name: "dbSeed", type: "string", default: "full" },
+      { name: "workers", type: "number", default: 2 },
+    ]);
+  });
+
+  it("reports the origin of each resolved field", () => {
+    const config = resolveTemplateInheritance(
+      buildConfig(
+        {
+          base: { env: { A: "1" } },
+          child: { extends: "base", env: { B: "2" } },
+        },
+        { ignorePatterns: ["dist/**"] }
+      )
+    );
+    const child = config.templates.child as Template;
+
+    expect(getTemplateFieldOrigins("child", child)).toEqual({
+      id: "child",
+      label: "child",
+      type: "child",
+      extends: "child",
+      "env.A": "base",
+      "env.B": "child",
+      "ignorePatterns.0": TEMPLATE_DEFAULTS_ORIGIN,
+    });
+  });
+
+  it("rejects unknown parents", () => {
+    expect(() =>
+      resolveTemplateInheritance(buildConfig({ web: { extends: "missing" } }))
+    ).toThrow('Template "web": Extends unknown template "missing"');
+  });
+
+  it("reports the cycle path", () => {
+    expect(() =>
+      resolveTemplateInheritance(
+        buildConfig({
+          a: { extends: "b" },
+          b: { extends: "a" },
+        })
+      )
+    ).toThrow('Template "a": Inheritance cycle detected: a -> b -> a');
+  });
+});
@@ -0,0 +1,255 @@
+import { isDeepStrictEqual } from "node:util";
+import {
+  type HiveConfig,
+  hiveConfigSchema,
+  type Template,
+  type UnresolvedHiveConfig,
+} from "./schema";
+
+/** Origin reported for fields inherited from `templateDefaults`. */
+export const TEMPLATE_DEFAULTS_ORIGIN = "templateDefaults";
+
+/**
+ * Maps each resolved leaf field, such as `services.web.env.PORT` or
+ * `ignorePatterns.3`, to the template id (or `templateDefaults`) that set it.
+ */
+export type TemplateFieldOrigins = Record<string, string>;
+
+type Layer = { origin: string; values: Record<string, unknown> };
+type OriginMap = Map<string, string>;
+type TemplateLayer = UnresolvedHiveConfig["templates"][string];
+
+/** Services written without a `type` are process services. */
+const DEFAULT_SERVICE_TYPE = "process";
+
+const OWN_FIELDS = new Set(["id", "label", "type", "extends"]);
+
+const resolvedOrigins = new WeakMap<Template, TemplateFieldOrigins>();
+
+export class TemplateInheritanceError extends Error {
+  readonly templateId: string;
+
+  constructor(message: string, templateId: string) {
+    super(`Template "${templateId}": ${message}`);
+    this.name = "TemplateInheritanceError";
+    this.templateId = templateId;
+  }
+}
+
+const isPlainObject = (value: unknown): value is Record<string, unknown> =>
+  typeof value === "object" && value !== null && !Array.isArray(value);
+
+const clearOrigins = (origins: OriginMap, path: string) => {
+  for (const key of [...origins.keys()]) {
+    if (key === path || key.startsWith(`${path}.`)) {
+      origins.delete(key);
+    }
+  }
+};
+
+const recordOrigins = (
+  origins: OriginMap,
+  value: unknown,
+  origin: string,
+  path: string
+) => {
+  if (Array.isArray(value)) {
+    value.forEach((entry, index) => {
+      recordOrigins(origins, entry, origin, `${path}.${index}`);
+    });
+    return;
+  }
+  if (isPlainObject(value)) {
+    for (const [key, entry] of Object.entries(value)) {
+      recordOrigins(origins, entry, origin, path ? `${path}.${key}` : key);
+    }
+    return;
+  }
+  if (value !== undefined) {
+    origins.set(path, origin);
+  }
+};
+
+const findNamedEntry = (entries: unknown[], entry: unknown) => {
+  if (!(isPlainObject(entry) && typeof entry.name === "string")) {
+    return -1;
+  }
+  return entries.findIndex(
+    (existing) => isPlainObject(existing) && existing.name === entry.name
+  );
+};
+
+/**
+ * Objects merge unless the layer sets a different `type`; leaving `type` out
+ * keeps the inherited one, so `{ env }` alone can extend a docker service.
+ */
+const canMergeObjects = (
+  current: Record<string, unknown>,
+  value: Record<string, unknown>,
+  path: string
+) => {
+  if (value.type === undefined) {
+    return true;
+  }
+  const currentType =
+    current.type ?? (path === "services" ? DEFAULT_SERVICE_TYPE : undefined);
+  return currentType === value.type;
+};
+
+/**
+ * Applies one layer on top of `target`. Objects merge key by key unless their
+ * `type` differs, arrays append entries that are not already present (named
+ * entries such as parameters replace the one with the same `name`), and
+ * anything else is replaced.
+ */
+const mergeLayer = (
+  target: Record<string, unknown>,
+  layer: Record<string, unknown>,
+  origin: string,
+  origins: OriginMap,
+  path = ""
+) => {
+  for (const [key, value] of Object.entries(layer)) {
+    if (value === undefined || (!path && OWN_FIELDS.has(key))) {
+      continue;
+    }
+    const fieldPath = path ? `${path}.${key}` : key;
+    const current = target[key];
+
+    if (Array.isArray(value) && Array.isArray(current)) {
+      const merged = [...current];
+      for (const entry of value) {
+        if (merged.some((existing) => isDeepStrictEqual(existing, entry))) {
+          continue;
+        }
+        const namedIndex = findNamedEntry(merged, entry);
+        const index = namedIndex === -1 ? merged.length : namedIndex;
+        const entryPath = `${fieldPath}.${index}`;
+        clearOrigins(origins, entryPath);
+        recordOrigins(origins, entry, origin, entryPath);
+        merged[index] = structuredClone(entry);
+      }
+      target[key] = merged;
+      continue;
+    }
+
+    if (
+      isPlainObject(value) &&
+      isPlainObject(current) &&
+      canMergeObjects(current, value, path)
+    ) {
+      const merged = { ...current };
+      mergeLayer(merged, value, origin, origins, fieldPath);
+      target[key] = merged;
+      continue;
+    }
+
+    clearOrigins(origins, fieldPath);
+    target[key] = structuredClone(value);
+    recordOrigins(origins, value, origin, fieldPath);
+  }
+};
+
+const collectLayers = (
+  templates: Record<string, TemplateLayer>,
+  templateId: string
+): Layer[] => {
+  const chain: Layer[] = [];
+  const visited: string[] = [];
+  let currentId: string | undefined = templateId;
+
+  while (currentId) {
+    if (visited.includes(currentId)) {
+      const cycle = [...visited, currentId].join(" -> ");
+      throw new TemplateInheritanceError(
+        `Inheritance cycle detected: ${cycle}`,
+        templateId
+      );
+    }
+    const template: TemplateLayer | undefined = templates[currentId];
+    const parentOf = visited.at(-1);
+    if (!template) {
+      throw new TemplateInheritanceError(
+        `Extends unknown template "${currentId}"`,
+        parentOf ?? templateId
+      );
+    }
+    visited.push(currentId);
+    chain.unshift({ origin: currentId, values: template });
+    currentId = template.extends;
+  }
+
+  return chain;
+};
+
+/**
+ * Resolves `extends` and `templateDefaults` for every template. Layers apply
+ * from least to most specific: `templateDefaults`, then the farthest
+ * ancestor down to the template itself. `id`, `label`, `type` and `extends`
+ * always come from the template itself. The merged config is validated as a
+ * whole and a failure is thrown as the `ZodError`.
+ */
+export function resolveTemplateInheritance(
+  config: UnresolvedHiveConfig
+): HiveConfig {
+  const templates: Record<string, Record<string, unknown>> = {};
+  const templateOrigins = new Map<string, TemplateFieldOrigins>();
+
+  for (const [templateId, template] of Object.entries(config.templates)) {
+    const layers = collectLayers(config.templates, templateId);
+    if (config.templateDefaults) {
+      layers.unshift({
+        origin: TEMPLATE_DEFAULTS_ORIGIN,
+        values: config.templateDefaults,
+      });
+    }
+
+    const origins: OriginMap = new Map();
+    const resolved: Record<string, unknown> = {
+      id: template.id,
+      label: template.label,
+      type: template.type,
+      ...(template.extends ? { extends: template.extends } : {}),
+    };
+    for (const layer of layers) {
+      mergeLayer(resolved, layer.values, layer.origin, origins);
+    }
+    for (const field of OWN_FIELDS) {
+      if (field in resolved) {
+        origins.set(field, templateId);
+      }
+    }
+
+    templateOrigins.set(templateId, Object.fromEntries(origins));
+    templates[templateId] = resolved;
+  }
+
+  const result = hiveConfigSchema.safeParse({ ...config, templates });
+  if (!result.success) {
+    throw result.error;
+  }
+  for (const [templateId, template] of Object.entries(result.data.templates)) {
+    const origins = templateOrigins.get(templateId);
+    if (origins) {
+      resolvedOrigins.set(template, origins);
+    }
+  }
+  return result.data;
+}
+
+/**
+ * Returns where each field of a resolved template came from. Templates that
+ * did not go through {@link resolveTemplateInheritance} own all their fields.
+ */
+export function getTemplateFieldOrigins(
+  templateId: string,
+  template: Template
+): TemplateFieldOrigins {
+  const origins = resolvedOrigins.get(template);
+  if (origins) {
+    return { ...origins };
+  }
+  const own: OriginMap = new Map();
+  recordOrigins(own, template, templateId, "");
+  return Object.fromEntries(own);
+}
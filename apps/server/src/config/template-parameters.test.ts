@@ -0,0 +1,90 @@
+import { describe, expect, it } from "vitest";
+
+import type { Template } from "./schema";
+import {
+  applyTemplateParameters,
+  resolveTemplateParameterValues,
+} from "./template-parameters";
+
+const template: Template = {
+  id: "api",
+  label: "API",
+  type: "manual",
+  parameters: [
+    { name: "dbSeed", type: "string", enum: ["demo", "full"], default: "demo" },
+    { name: "workers", type: "number", default: 2 },
+    { name: "verbose", type: "boolean", default: false },
+  ],
+  env: { SEED: "${params.dbSeed}" },
+  setup: ["bun run seed ${params.dbSeed}"],
+  prompts: ["docs/prompts/${params.dbSeed}.md"],
+  services: {
+    api: {
+      type: "process",
+      run: "bun run dev --workers ${params.workers}",
+      env: { VERBOSE: "${params.verbose}" },
+    },
+    db: { type: "docker", image: "postgres:16" },
+  },
+};
+
+describe("resolveTemplateParameterValues", () => {
+  it("fills defaults and coerces string inputs", () => {
+    expect(
+      resolveTemplateParameterValues(template, {
+        workers: "4",
+        verbose: "true",
+      })
+    ).toEqual({ dbSeed: "demo", workers: 4, verbose: true });
+  });
+
+  it("rejects values outside the enum", () => {
+    expect(() =>
+      resolveTemplateParameterValues(template, { dbSeed: "prod" })
+    ).toThrow('Template "api": Parameter "dbSeed" must be one of: demo, full');
+  });
+
+  it("rejects unknown and missing parameters", () => {
+    expect(() =>
+      resolveTemplateParameterValues(template, { region: "eu" })
+    ).toThrow('Template "api": Unknown parameter: region');
+    expect(() =>
+      resolveTemplateParameterValues({
+        ...template,
+        parameters: [{ name: "token", type: "string" }],
+      })
+    ).toThrow('Template "api": Parameter "token" is required');
+  });
+});
+
+describe("applyTemplateParameters", () => {
+  it("interpolates env, setup, prompts and service commands", () => {
+    const applied = applyTemplateParameters(template, {
+      dbSeed: "full",
+      workers: 4,
+      verbose: true,
+    });
+
+    expect(applied.env).toEqual({ SEED: "full" });
+    expect(applied.setup).toEqual(["bun run seed full"]);
+    expect(applied.prompts).toEqual(["docs/prompts/full.md"]);
+    expect(applied.services?.api).toEqual({
+      type: "process",
+      run: "bun run dev --workers 4",
+      env: { VERBOSE: "true" },
+    });
+    expect(applied.services?.db).toEqual({
+      type: "docker",
+      image: "postgres:16",
+    });
+  });
+
+  it("rejects references to undeclared parameters", () => {
+    expect(() =>
+      applyTemplateParameters(
+        { id: "api", label: "API", type: "manual", setup: ["${params.region}"] },
+        {}
+      )
+    ).toThrow('Template "api": References undefined parameter "region"');
+  });
+});
@@ -0,0 +1,183 @@
+import type { Template, TemplateParameter } from "./schema";
+
+export type TemplateParameterValue = string | number | boolean;
+export type TemplateParameterValues = Record<string, TemplateParameterValue>;
+
+const PARAMETER_REFERENCE = /\$\{params\.([A-Za-z_][A-Za-z0-9_]*)\}/g;
+
+export class TemplateParameterError extends Error {
+  readonly templateId: string;
+
+  constructor(message: string, templateId: string) {
+    super(`Template "${templateId}": ${message}`);
+    this.name = "TemplateParameterError";
+    this.templateId = templateId;
+  }
+}
+
+const coerceValue = (
+  parameter: TemplateParameter,
+  value: TemplateParameterValue
+): TemplateParameterValue | undefined => {
+  if (parameter.type === "string") {
+    return typeof value === "string" ? value : undefined;
+  }
+  if (parameter.type === "number") {
+    const parsed = typeof value === "string" ? Number(value) : value;
+    return typeof parsed === "number" && Number.isFinite(parsed)
+      ? parsed
+      : undefined;
+  }
+  if (typeof value === "boolean") {
+    return value;
+  }
+  if (value === "true" || value === "false") {
+    return value === "true";
+  }
+  return;
+};
+
+/**
+ * Checks supplied values against the template's parameters and fills in
+ * defaults. Form inputs arrive as strings, so numbers and booleans are
+ * accepted in their string form too.
+ */
+export function resolveTemplateParameterValues(
+  template: Template,
+  supplied: TemplateParameterValues = {}
+): TemplateParameterValues {
+  const parameters = template.parameters ?? [];
+  const known = new Set(parameters.map((parameter) => parameter.name));
+  const unknown = Object.keys(supplied).filter((name) => !known.has(name));
+  if (unknown.length > 0) {
+    throw new TemplateParameterError(
+      `Unknown parameter${unknown.length === 1 ? "" : "s"}: ${unknown.join(", ")}`,
+      template.id
+    );
+  }
+
+  const values: TemplateParameterValues = {};
+  for (const parameter of parameters) {
+    const raw = supplied[parameter.name] ?? parameter.default;
+    if (raw === undefined) {
+      throw new TemplateParameterError(
+        `Parameter "${parameter.name}" is required`,
+        template.id
+      );
+    }
+    const value = coerceValue(parameter, raw);
+    if (value === undefined) {
+      throw new TemplateParameterError(
+        `Parameter "${parameter.name}" must be a ${parameter.type}`,
+        template.id
+      );
+    }
+    if (parameter.enum && !parameter.enum.includes(value as string | number)) {
+      throw new TemplateParameterError(
+        `Parameter "${parameter.name}" must be one of: ${parameter.enum.join(", ")}`,
+        template.id
+      );
+    }
+    values[parameter.name] = value;
+  }
+
+  return values;
+}
+
+const interpolate = (
+  input: string,
+  values: TemplateParameterValues,
+  templateId: string
+) =>
+  input.replace(PARAMETER_REFERENCE, (_match, name: string) => {
+    const value = values[name];
+    if (value === undefined) {
+      throw new TemplateParameterError(
+        `References undefined parameter "${name}"`,
+        templateId
+      );
+    }
+    return String(value);
+  });
+
+const interpolateList = (
+  list: string[] | undefined,
+  values: TemplateParameterValues,
+  templateId: string
+) => list?.map((entry) => interpolate(entry, values, templateId));
+
+const interpolateRecord = (
+  record: Record<string, string> | undefined,
+  values: TemplateParameterValues,
+  templateId: string
+) =>
+  record
+    ? Object.fromEntries(
+        Object.entries(record).map(([key, value]) => [
+          key,
+          interpolate(value, values, templateId),
+        ])
+      )
+    : undefined;
+
+const withDefined = <T extends object>(target: T, updates: Partial<T>): T => {
+  const next = { ...target };
+  for (const [key, value] of Object.entries(updates)) {
+    if (value !== undefined) {
+      (next as Record<string, unknown>)[key] = value;
+    }
+  }
+  return next;
+};
+
+/**
+ * Substitutes `${params.<name>}` in the template's env, setup commands,
+ * prompts and each service's run, command, setup and env.
+ */
+export function applyTemplateParameters(
+  template: Template,
+  values: TemplateParameterValues
+): Template {
+  const id = template.id;
+  const services = template.services
+    ? Object.fromEntries(
+        Object.entries(template.services).map(([name, service]) => {
+          if (service.type === "process") {
+            return [
+              name,
+              withDefined(service, {
+                run: interpolate(service.run, values, id),
+                setup: interpolateList(service.setup, values, id),
+                env: interpolateRecord(service.env, values, id),
+              }),
+            ];
+          }
+          if (service.type === "docker") {
+            return [
+              name,
+              withDefined(service, {
+                command:
+                  service.command === undefined
+                    ? undefined
+                    : interpolate(service.command, values, id),
+                env: interpolateRecord(service.env, values, id),
+              }),
+            ];
+          }
+          return [
+            name,
+            withDefined(service, {
+              env: interpolateRecord(service.env, values, id),
+            }),
+          ];
+        })
+      )
+    : undefined;
+
+  return withDefined(template, {
+    env: interpolateRecord(template.env, values, id),
+    setup: interpolateList(template.setup, values, id),
+    prompts: interpolateList(template.prompts, values, id),
+    services,
+  });
+}
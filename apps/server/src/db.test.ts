@@ -1,4 +1,3 @@
-import type { Database } from "bun:sqlite";
 import { beforeAll, describe, expect, it } from "vitest";
 
 describe("Database", () => {
@@ -8,7 +7,7 @@ describe("Database", () => {
     }
   });
 
-  it("initializes a Bun-powered sqlite client", async () => {
+  it("initializes a Bun-powered database client", async () => {
     const { db } = await import("./db");
     expect(db).toBeDefined();
     expect(db).toHaveProperty("query");
@@ -17,11 +16,30 @@ describe("Database", () => {
   });
 
   it("executes queries end-to-end", async () => {
-    const { db } = await import("./db");
-    const client = (db as typeof db & { $client: Database }).$client;
-    const row = client.query("select 'hello world' as text").get() as {
-      text: string;
-    };
-    expect(row.text).toBe("hello world");
+    const { engineDatabase } = await import("./db");
+    const query = "select 'hello world' as text";
+    const row =
+      engineDatabase.engine === "postgres"
+        ? (
+            (await engineDatabase.client.$client.unsafe(query)) as {
+              text: string;
+            }[]
+          )[0]
+        : (engineDatabase.client.$client.query(query).get() as {
+            text: string;
+          });
+    expect(row?.text).toBe("hello world");
+  });
+
+  it("detects the engine from DATABASE_URL", async () => {
+    const { resolveDatabaseEngine } = await import("./config/database");
+    expect(resolveDatabaseEngine("postgres://hive@db:5432/hive")).toBe(
+      "postgres"
+    );
+    expect(resolveDatabaseEngine("postgresql://localhost/hive")).toBe(
+      "postgres"
+    );
+    expect(resolveDatabaseEngine("file:./hive.db")).toBe("sqlite");
+    expect(resolveDatabaseEngine(":memory:")).toBe("sqlite");
   });
 });
@@ -1,10 +1,92 @@
 import { Database } from "bun:sqlite";
-import { drizzle } from "drizzle-orm/bun-sqlite";
-import { sqliteDatabasePath } from "./config/database";
+import { SQL } from "bun";
+import { type BunSQLDatabase, drizzle as drizzlePg } from "drizzle-orm/bun-sql";
+import { migrate as migratePg } from "drizzle-orm/bun-sql/migrator";
+import { type BunSQLiteDatabase, drizzle } from "drizzle-orm/bun-sqlite";
+import { migrate as migrateSqlite } from "drizzle-orm/bun-sqlite/migrator";
+import {
+  type DatabaseEngine,
+  databaseEngine,
+  databaseUrl,
+  sqliteDatabasePath,
+} from "./config/database";
 import { schema } from "./schema";
 
-const sqlite = new Database(sqliteDatabasePath);
-export const db = drizzle(sqlite, { schema });
+type SqliteDatabase = BunSQLiteDatabase<typeof schema> & { $client: Database };
+type PgDatabase = BunSQLDatabase<typeof schema> & { $client: SQL };
+
+/** A drizzle client tagged with the engine it talks to. */
+export type EngineDatabase =
+  | { engine: "sqlite"; client: SqliteDatabase }
+  | { engine: "postgres"; client: PgDatabase };
+
+/**
+ * Raw statement helpers and the driver client differ between dialects, so
+ * they are left off `HiveDatabase`: shared code that reaches for them fails
+ * to compile instead of failing at runtime on the other engine.
+ */
+type DialectSpecificMembers =
+  | "$client"
+  | "run"
+  | "all"
+  | "get"
+  | "values"
+  | "batch";
+
+/** The query builder surface shared by both engines. */
+export type HiveDatabase = Omit<SqliteDatabase, DialectSpecificMembers>;
+
+/** `location` is a SQLite file path or a postgres:// connection URL. */
+export const createEngineDatabase = (
+  engine: DatabaseEngine,
+  location: string
+): EngineDatabase =>
+  engine === "postgres"
+    ? { engine, client: drizzlePg({ client: new SQL(location), schema }) }
+    : { engine, client: drizzle(new Database(location), { schema }) };
+
+/**
+ * The Postgres client is handed out with the SQLite builder types: `schema`
+ * already resolves to the Postgres tables on that engine, and `HiveDatabase`
+ * only exposes builder methods both dialects implement. This is the one place
+ * the two client types are bridged.
+ */
+export const toHiveDatabase = (database: EngineDatabase): HiveDatabase =>
+  database.engine === "postgres"
+    ? (database.client as unknown as HiveDatabase)
+    : database.client;
+
+/** Each engine keeps its own migration history next to the other. */
+export const resolveMigrationsDirectoryName = (engine: DatabaseEngine) =>
+  engine === "postgres" ? "migrations-pg" : "migrations";
+
+export const migrateDatabase = async (
+  database: EngineDatabase,
+  migrationsFolder: string
+): Promise<void> => {
+  if (database.engine === "postgres") {
+    await migratePg(database.client, { migrationsFolder });
+    return;
+  }
+  await migrateSqlite(database.client, { migrationsFolder });
+};
+
+export const closeDatabase = async (
+  database: EngineDatabase
+): Promise<void> => {
+  if (database.engine === "postgres") {
+    await database.client.$client.close();
+    return;
+  }
+  database.client.$client.close();
+};
+
+export const engineDatabase = createEngineDatabase(
+  databaseEngine,
+  databaseEngine === "postgres" ? databaseUrl : sqliteDatabasePath
+);
+
+export const db = toHiveDatabase(engineDatabase);
 
 export type DatabaseService = {
   readonly db: typeof db;
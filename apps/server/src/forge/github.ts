@@ -0,0 +1,55 @@
+import {
+  type ForgeAdapter,
+  type ForgeFetch,
+  readForgeResponse,
+} from "./types";
+
+export const DEFAULT_GITHUB_API_URL = "https://api.github.com";
+
+type GithubPullResponse = {
+  number: number;
+  html_url: string;
+};
+
+export function createGithubForge({
+  apiUrl = DEFAULT_GITHUB_API_URL,
+  token,
+  fetch: fetcher = fetch,
+}: {
+  apiUrl?: string;
+  token: string;
+  fetch?: ForgeFetch;
+}): ForgeAdapter {
+  const baseUrl = apiUrl.replace(/\/+$/, "");
+
+  return {
+    type: "github",
+    async openPullRequest(request) {
+      const response = await fetcher(
+        `${baseUrl}/repos/${request.repository}/pulls`,
+        {
+          method: "POST",
+          headers: {
+            accept: "application/vnd.github+json",
+            authorization: `Bearer ${token}`,
+            "content-type": "application/json",
+            "x-github-api-version": "2022-11-28",
+          },
+          body: JSON.stringify({
+            head: request.head,
+            base: request.base,
+            title: request.title,
+            body: request.body ?? "",
+            draft: request.draft ?? false,
+          }),
+        }
+      );
+
+      const pull = await readForgeResponse<GithubPullResponse>(
+        response,
+        "Opening GitHub pull request"
+      );
+      return { number: pull.number, url: pull.html_url };
+    },
+  };
+}
@@ -0,0 +1,53 @@
+import {
+  type ForgeAdapter,
+  type ForgeFetch,
+  readForgeResponse,
+} from "./types";
+
+export const DEFAULT_GITLAB_API_URL = "https://gitlab.com/api/v4";
+
+type GitlabMergeRequestResponse = {
+  iid: number;
+  web_url: string;
+};
+
+export function createGitlabForge({
+  apiUrl = DEFAULT_GITLAB_API_URL,
+  token,
+  fetch: fetcher = fetch,
+}: {
+  apiUrl?: string;
+  token: string;
+  fetch?: ForgeFetch;
+}): ForgeAdapter {
+  const baseUrl = apiUrl.replace(/\/+$/, "");
+
+  return {
+    type: "gitlab",
+    async openPullRequest(request) {
+      const project = encodeURIComponent(request.repository);
+      const response = await fetcher(
+        `${baseUrl}/projects/${project}/merge_requests`,
+        {
+          method: "POST",
+          headers: {
+            "private-token": token,
+            "content-type": "application/json",
+          },
+          body: JSON.stringify({
+            source_branch: request.head,
+            target_branch: request.base,
+            title: request.draft ? `Draft: ${request.title}` : request.title,
+            description: request.body ?? "",
+          }),
+        }
+      );
+
+      const mergeRequest = await readForgeResponse<GitlabMergeRequestResponse>(
+        response,
+        "Opening GitLab merge request"
+      );
+      return { number: mergeRequest.iid, url: mergeRequest.web_url };
+    },
+  };
+}
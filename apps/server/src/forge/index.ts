@@ -0,0 +1,83 @@
+import type { ForgeConfig } from "../config/schema";
+import { createGithubForge } from "./github";
+import { createGitlabForge } from "./gitlab";
+import type { ForgeAdapter, ForgeFetch } from "./types";
+
+export type {
+  ForgeAdapter,
+  PullRequestRequest,
+  PullRequestResult,
+} from "./types";
+export { ForgeRequestError } from "./types";
+
+const DEFAULT_TOKEN_ENV: Record<ForgeConfig["type"], string> = {
+  github: "GITHUB_TOKEN",
+  gitlab: "GITLAB_TOKEN",
+};
+
+export class ForgeConfigurationError extends Error {
+  constructor(message: string) {
+    super(message);
+    this.name = "ForgeConfigurationError";
+  }
+}
+
+export function createForgeAdapter(
+  config: ForgeConfig,
+  options: {
+    env?: Record<string, string | undefined>;
+    fetch?: ForgeFetch;
+  } = {}
+): ForgeAdapter {
+  const env = options.env ?? process.env;
+  const tokenEnv = config.tokenEnv ?? DEFAULT_TOKEN_ENV[config.type];
+  const token = env[tokenEnv]?.trim();
+  if (!token) {
+    throw new ForgeConfigurationError(
+      `Set ${tokenEnv} to open pull requests on ${config.type}`
+    );
+  }
+
+  const adapterOptions = {
+    token,
+    ...(config.apiUrl ? { apiUrl: config.apiUrl } : {}),
+    ...(options.fetch ? { fetch: options.fetch } : {}),
+  };
+
+  switch (config.type) {
+    case "github":
+      return createGithubForge(adapterOptions);
+    case "gitlab":
+      return createGitlabForge(adapterOptions);
+    default:
+      throw new ForgeConfigurationError(
+        `Unsupported forge type: ${String(config.type)}`
+      );
+  }
+}
+
+const SCP_REMOTE_PATTERN = /^[^@/]+@[^:]+:(.+)$/;
+
+/**
+ * Extracts `owner/repo` (or a nested GitLab group path) from a remote URL in
+ * either `git@host:owner/repo.git` or `https://host/owner/repo.git` form.
+ */
+export function parseRemoteRepository(remoteUrl: string): string | null {
+  const trimmed = remoteUrl.trim();
+  const scpMatch = SCP_REMOTE_PATTERN.exec(trimmed);
+  let path: string | undefined = scpMatch?.[1];
+
+  if (!path) {
+    try {
+      path = new URL(trimmed).pathname;
+    } catch {
+      return null;
+    }
+  }
+
+  const normalized = path
+    .replace(/^\/+/, "")
+    .replace(/\/+$/, "")
+    .replace(/\.git$/, "");
+  return normalized.includes("/") ? normalized : null;
+}
@@ -0,0 +1,51 @@
+export type PullRequestRequest = {
+  repository: string;
+  head: string;
+  base: string;
+  title: string;
+  body?: string;
+  draft?: boolean;
+};
+
+export type PullRequestResult = {
+  number: number;
+  url: string;
+};
+
+/**
+ * Minimal surface a hosted forge must provide so Hive can turn a pushed cell
+ * branch into a reviewable change.
+ */
+export type ForgeAdapter = {
+  readonly type: string;
+  openPullRequest(request: PullRequestRequest): Promise<PullRequestResult>;
+};
+
+export class ForgeRequestError extends Error {
+  readonly status: number;
+
+  constructor(message: string, status: number) {
+    super(message);
+    this.name = "ForgeRequestError";
+    this.status = status;
+  }
+}
+
+export type ForgeFetch = (
+  input: string,
+  init: RequestInit
+) => Promise<Response>;
+
+export async function readForgeResponse<T>(
+  response: Response,
+  action: string
+): Promise<T> {
+  if (!response.ok) {
+    const body = await response.text().catch(() => "");
+    throw new ForgeRequestError(
+      `${action} failed (${response.status})${body ? `: ${body}` : ""}`,
+      response.status
+    );
+  }
+  return (await response.json()) as T;
+}
@@ -0,0 +1,203 @@
+CREATE TABLE "users" (
+	"id" text PRIMARY KEY NOT NULL,
+	"username" text NOT NULL,
+	"display_name" text,
+	"created_at" timestamp with time zone NOT NULL
+);
+--> statement-breakpoint
+CREATE TABLE "cells" (
+	"id" text PRIMARY KEY NOT NULL,
+	"name" text NOT NULL,
+	"description" text,
+	"template_id" text NOT NULL,
+	"workspace_path" text NOT NULL,
+	"workspace_id" text NOT NULL,
+	"workspace_root_path" text DEFAULT '' NOT NULL,
+	"opencode_session_id" text,
+	"resume_agent_session_on_startup" boolean DEFAULT false NOT NULL,
+	"created_at" timestamp with time zone NOT NULL,
+	"status" text DEFAULT 'ready' NOT NULL,
+	"last_setup_error" text,
+	"branch_name" text,
+	"base_commit" text,
+	"start_point_mode" text,
+	"start_point_ref" text,
+	"owner_id" text
+);
+--> statement-breakpoint
+CREATE TABLE "cell_services" (
+	"id" text PRIMARY KEY NOT NULL,
+	"cell_id" text NOT NULL,
+	"name" text NOT NULL,
+	"type" text NOT NULL,
+	"command" text NOT NULL,
+	"cwd" text NOT NULL,
+	"env" jsonb NOT NULL,
+	"status" text DEFAULT 'pending' NOT NULL,
+	"port" integer,
+	"pid" integer,
+	"ready_timeout_ms" integer,
+	"definition" jsonb NOT NULL,
+	"last_known_error" text,
+	"created_at" timestamp with time zone NOT NULL,
+	"updated_at" timestamp with time zone NOT NULL
+);
+--> statement-breakpoint
+CREATE TABLE "cell_resource_history" (
+	"id" text PRIMARY KEY NOT NULL,
+	"cell_id" text NOT NULL,
+	"sampled_at" timestamp with time zone NOT NULL,
+	"process_count" integer NOT NULL,
+	"active_process_count" integer NOT NULL,
+	"total_cpu_percent" double precision NOT NULL,
+	"total_rss_bytes" bigint NOT NULL,
+	"active_cpu_percent" double precision NOT NULL,
+	"active_rss_bytes" bigint NOT NULL,
+	"processes" jsonb NOT NULL,
+	"created_at" timestamp with time zone NOT NULL
+);
+--> statement-breakpoint
+CREATE TABLE "cell_resource_rollups" (
+	"id" text PRIMARY KEY NOT NULL,
+	"cell_id" text NOT NULL,
+	"bucket_start_at" timestamp with time zone NOT NULL,
+	"sample_count" integer NOT NULL,
+	"sum_active_cpu_percent" double precision NOT NULL,
+	"sum_active_rss_bytes" bigint NOT NULL,
+	"peak_active_cpu_percent" double precision NOT NULL,
+	"peak_active_rss_bytes" bigint NOT NULL,
+	"created_at" timestamp with time zone NOT NULL,
+	"updated_at" timestamp with time zone NOT NULL
+);
+--> statement-breakpoint
+CREATE TABLE "cell_provisioning_state" (
+	"cell_id" text PRIMARY KEY NOT NULL,
+	"model_id_override" text,
+	"provider_id_override" text,
+	"variant_override" text,
+	"start_mode" text,
+	"started_at" timestamp with time zone,
+	"finished_at" timestamp with time zone,
+	"attempt_count" integer DEFAULT 0 NOT NULL
+);
+--> statement-breakpoint
+CREATE TABLE "cell_activity_events" (
+	"id" text PRIMARY KEY NOT NULL,
+	"cell_id" text NOT NULL,
+	"service_id" text,
+	"type" text NOT NULL,
+	"source" text,
+	"tool_name" text,
+	"user_id" text,
+	"metadata" jsonb NOT NULL,
+	"created_at" timestamp with time zone NOT NULL
+);
+--> statement-breakpoint
+CREATE TABLE "cell_timing_events" (
+	"id" text PRIMARY KEY NOT NULL,
+	"cell_id" text NOT NULL,
+	"cell_name" text,
+	"workspace_id" text,
+	"template_id" text,
+	"workflow" text NOT NULL,
+	"run_id" text NOT NULL,
+	"step" text NOT NULL,
+	"status" text NOT NULL,
+	"duration_ms" integer NOT NULL,
+	"attempt" integer,
+	"error" text,
+	"metadata" jsonb NOT NULL,
+	"created_at" timestamp with time zone NOT NULL
+);
+--> statement-breakpoint
+CREATE TABLE "cell_snapshots" (
+	"id" text PRIMARY KEY NOT NULL,
+	"cell_id" text NOT NULL,
+	"name" text NOT NULL,
+	"git_ref" text NOT NULL,
+	"snapshot_commit" text NOT NULL,
+	"head_commit" text NOT NULL,
+	"archive_path" text,
+	"agent_message_id" text,
+	"created_at" timestamp with time zone NOT NULL
+);
+--> statement-breakpoint
+CREATE TABLE "workspace_linear_integrations" (
+	"workspace_id" text PRIMARY KEY NOT NULL,
+	"access_token" text NOT NULL,
+	"refresh_token" text,
+	"access_token_expires_at" timestamp with time zone,
+	"token_type" text,
+	"scope" text,
+	"linear_user_id" text NOT NULL,
+	"linear_user_name" text,
+	"linear_user_email" text,
+	"linear_organization_id" text,
+	"linear_organization_name" text,
+	"team_id" text,
+	"team_key" text,
+	"team_name" text,
+	"created_at" timestamp with time zone NOT NULL,
+	"updated_at" timestamp with time zone NOT NULL
+);
+--> statement-breakpoint
+CREATE TABLE "api_tokens" (
+	"id" text PRIMARY KEY NOT NULL,
+	"name" text NOT NULL,
+	"token_hash" text NOT NULL,
+	"token_prefix" text NOT NULL,
+	"scope" text NOT NULL,
+	"user_id" text,
+	"created_at" timestamp with time zone NOT NULL,
+	"last_used_at" timestamp with time zone,
+	"revoked_at" timestamp with time zone
+);
+--> statement-breakpoint
+CREATE TABLE "cell_shares" (
+	"id" text PRIMARY KEY NOT NULL,
+	"cell_id" text NOT NULL,
+	"user_id" text NOT NULL,
+	"created_at" timestamp with time zone NOT NULL
+);
+--> statement-breakpoint
+ALTER TABLE "cells" ADD CONSTRAINT "cells_owner_id_users_id_fk" FOREIGN KEY ("owner_id") REFERENCES "public"."users"("id") ON DELETE set null ON UPDATE no action;
+--> statement-breakpoint
+ALTER TABLE "cell_services" ADD CONSTRAINT "cell_services_cell_id_cells_id_fk" FOREIGN KEY ("cell_id") REFERENCES "public"."cells"("id") ON DELETE cascade ON UPDATE no action;
+--> statement-breakpoint
+ALTER TABLE "cell_resource_history" ADD CONSTRAINT "cell_resource_history_cell_id_cells_id_fk" FOREIGN KEY ("cell_id") REFERENCES "public"."cells"("id") ON DELETE cascade ON UPDATE no action;
+--> statement-breakpoint
+ALTER TABLE "cell_resource_rollups" ADD CONSTRAINT "cell_resource_rollups_cell_id_cells_id_fk" FOREIGN KEY ("cell_id") REFERENCES "public"."cells"("id") ON DELETE cascade ON UPDATE no action;
+--> statement-breakpoint
+ALTER TABLE "cell_provisioning_state" ADD CONSTRAINT "cell_provisioning_state_cell_id_cells_id_fk" FOREIGN KEY ("cell_id") REFERENCES "public"."cells"("id") ON DELETE cascade ON UPDATE no action;
+--> statement-breakpoint
+ALTER TABLE "cell_activity_events" ADD CONSTRAINT "cell_activity_events_cell_id_cells_id_fk" FOREIGN KEY ("cell_id") REFERENCES "public"."cells"("id") ON DELETE cascade ON UPDATE no action;
+--> statement-breakpoint
+ALTER TABLE "cell_activity_events" ADD CONSTRAINT "cell_activity_events_service_id_cell_services_id_fk" FOREIGN KEY ("service_id") REFERENCES "public"."cell_services"("id") ON DELETE cascade ON UPDATE no action;
+--> statement-breakpoint
+ALTER TABLE "cell_activity_events" ADD CONSTRAINT "cell_activity_events_user_id_users_id_fk" FOREIGN KEY ("user_id") REFERENCES "public"."users"("id") ON DELETE set null ON UPDATE no action;
+--> statement-breakpoint
+ALTER TABLE "cell_snapshots" ADD CONSTRAINT "cell_snapshots_cell_id_cells_id_fk" FOREIGN KEY ("cell_id") REFERENCES "public"."cells"("id") ON DELETE cascade ON UPDATE no action;
+--> statement-breakpoint
+ALTER TABLE "api_tokens" ADD CONSTRAINT "api_tokens_user_id_users_id_fk" FOREIGN KEY ("user_id") REFERENCES "public"."users"("id") ON DELETE cascade ON UPDATE no action;
+--> statement-breakpoint
+ALTER TABLE "cell_shares" ADD CONSTRAINT "cell_shares_cell_id_cells_id_fk" FOREIGN KEY ("cell_id") REFERENCES "public"."cells"("id") ON DELETE cascade ON UPDATE no action;
+--> statement-breakpoint
+ALTER TABLE "cell_shares" ADD CONSTRAINT "cell_shares_user_id_users_id_fk" FOREIGN KEY ("user_id") REFERENCES "public"."users"("id") ON DELETE cascade ON UPDATE no action;
+--> statement-breakpoint
+CREATE UNIQUE INDEX "users_username_idx" ON "users" USING btree ("username");
+--> statement-breakpoint
+CREATE INDEX "cell_resource_history_cell_sampled_idx" ON "cell_resource_history" USING btree ("cell_id","sampled_at");
+--> statement-breakpoint
+CREATE INDEX "cell_resource_rollups_cell_bucket_idx" ON "cell_resource_rollups" USING btree ("cell_id","bucket_start_at");
+--> statement-breakpoint
+CREATE INDEX "cell_timing_events_cell_created_idx" ON "cell_timing_events" USING btree ("cell_id","created_at");
+--> statement-breakpoint
+CREATE INDEX "cell_timing_events_run_created_idx" ON "cell_timing_events" USING btree ("run_id","created_at");
+--> statement-breakpoint
+CREATE INDEX "cell_timing_events_created_idx" ON "cell_timing_events" USING btree ("created_at");
+--> statement-breakpoint
+CREATE INDEX "cell_snapshots_cell_id_idx" ON "cell_snapshots" USING btree ("cell_id");
+--> statement-breakpoint
+CREATE UNIQUE INDEX "api_tokens_token_hash_idx" ON "api_tokens" USING btree ("token_hash");
+--> statement-breakpoint
+CREATE UNIQUE INDEX "cell_shares_cell_user_idx" ON "cell_shares" USING btree ("cell_id","user_id");
@@ -0,0 +1,5 @@
+ALTER TABLE "cell_provisioning_state" ADD COLUMN "initial_prompt" text;
+--> statement-breakpoint
+ALTER TABLE "cell_provisioning_state" ADD COLUMN "initial_prompt_attachments" jsonb;
+--> statement-breakpoint
+ALTER TABLE "cell_provisioning_state" ADD COLUMN "initial_prompt_sent_at" timestamp with time zone;
@@ -0,0 +1,10 @@
+CREATE TABLE "workspace_secrets" (
+	"id" text PRIMARY KEY NOT NULL,
+	"workspace_id" text NOT NULL,
+	"name" text NOT NULL,
+	"encrypted_value" text NOT NULL,
+	"created_at" timestamp with time zone NOT NULL,
+	"updated_at" timestamp with time zone NOT NULL
+);
+--> statement-breakpoint
+CREATE UNIQUE INDEX "workspace_secrets_workspace_name_idx" ON "workspace_secrets" USING btree ("workspace_id","name");
@@ -0,0 +1,3 @@
+ALTER TABLE "api_tokens" ADD COLUMN "cell_id" text;
+--> statement-breakpoint
+ALTER TABLE "api_tokens" ADD CONSTRAINT "api_tokens_cell_id_cells_id_fk" FOREIGN KEY ("cell_id") REFERENCES "public"."cells"("id") ON DELETE cascade ON UPDATE no action;
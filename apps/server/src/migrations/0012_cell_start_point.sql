@@ -0,0 +1,3 @@
+ALTER TABLE `cells` ADD COLUMN `start_point_mode` text;
+--> statement-breakpoint
+ALTER TABLE `cells` ADD COLUMN `start_point_ref` text;
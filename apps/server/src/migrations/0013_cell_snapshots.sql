@@ -0,0 +1,14 @@
+CREATE TABLE `cell_snapshots` (
+	`id` text PRIMARY KEY NOT NULL,
+	`cell_id` text NOT NULL,
+	`name` text NOT NULL,
+	`git_ref` text NOT NULL,
+	`snapshot_commit` text NOT NULL,
+	`head_commit` text NOT NULL,
+	`archive_path` text,
+	`agent_message_id` text,
+	`created_at` integer NOT NULL,
+	FOREIGN KEY (`cell_id`) REFERENCES `cells`(`id`) ON UPDATE no action ON DELETE cascade
+);
+--> statement-breakpoint
+CREATE INDEX `cell_snapshots_cell_id_idx` ON `cell_snapshots` (`cell_id`);
@@ -0,0 +1,25 @@
+CREATE TABLE `users` (
+	`id` text PRIMARY KEY NOT NULL,
+	`username` text NOT NULL,
+	`display_name` text,
+	`created_at` integer NOT NULL
+);
+--> statement-breakpoint
+CREATE UNIQUE INDEX `users_username_idx` ON `users` (`username`);
+--> statement-breakpoint
+CREATE TABLE `cell_shares` (
+	`id` text PRIMARY KEY NOT NULL,
+	`cell_id` text NOT NULL,
+	`user_id` text NOT NULL,
+	`created_at` integer NOT NULL,
+	FOREIGN KEY (`cell_id`) REFERENCES `cells`(`id`) ON UPDATE no action ON DELETE cascade,
+	FOREIGN KEY (`user_id`) REFERENCES `users`(`id`) ON UPDATE no action ON DELETE cascade
+);
+--> statement-breakpoint
+CREATE UNIQUE INDEX `cell_shares_cell_user_idx` ON `cell_shares` (`cell_id`,`user_id`);
+--> statement-breakpoint
+ALTER TABLE `cells` ADD COLUMN `owner_id` text REFERENCES `users`(`id`) ON DELETE set null;
+--> statement-breakpoint
+ALTER TABLE `cell_activity_events` ADD COLUMN `user_id` text REFERENCES `users`(`id`) ON DELETE set null;
+--> statement-breakpoint
+ALTER TABLE `api_tokens` ADD COLUMN `user_id` text REFERENCES `users`(`id`) ON DELETE cascade;
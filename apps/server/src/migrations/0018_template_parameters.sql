@@ -0,0 +1 @@
+ALTER TABLE `cell_provisioning_state` ADD COLUMN `template_parameters` text;
@@ -0,0 +1 @@
+ALTER TABLE `api_tokens` ADD COLUMN `cell_id` text REFERENCES `cells`(`id`) ON DELETE cascade;
@@ -1,5 +1,5 @@
 import { Elysia, sse, t } from "elysia";
-import { subscribeAgentEvents } from "../agents/events";
+import { publishAgentEvent, subscribeAgentEvents } from "../agents/events";
 import { loadOpencodeModelPreferences } from "../agents/opencode-config";
 import {
   fetchAgentMessages,
@@ -8,16 +8,21 @@ import {
   fetchProviderCatalogForWorkspace,
   type ProviderEntry,
   type ProviderModel,
+  sendAgentMessage,
 } from "../agents/service";
 import type { AgentSessionRecord, AgentStreamEvent } from "../agents/types";
 import {
+  AgentEventsQuerySchema,
   AgentMessageListResponseSchema,
   AgentSessionByCellResponseSchema,
+  SendAgentMessageBodySchema,
+  SendAgentMessageResponseSchema,
 } from "../schema/api";
 import { createWorkspaceContextPlugin } from "../workspaces/plugin";
 
 const HTTP_STATUS = {
   OK: 200,
+  ACCEPTED: 202,
   NOT_FOUND: 404,
   BAD_REQUEST: 400,
 } as const;
@@ -298,6 +303,33 @@ export const agentsRoutes = new Elysia({ prefix: "/api/agents" })
       },
     }
   )
+  .post(
+    "/sessions/:id/messages",
+    async ({ params, body, set }) => {
+      try {
+        const session = await fetchSessionOrThrow(
+          params.id,
+          "Failed to fetch session"
+        );
+        dispatchAgentMessageInBackground(session.id, body.content);
+        set.status = HTTP_STATUS.ACCEPTED;
+        return { sessionId: session.id, status: "accepted" as const };
+      } catch (error) {
+        const routeError = asAgentRouteError(error, "Failed to send message");
+        set.status = routeError.status;
+        return { message: routeError.message };
+      }
+    },
+    {
+      params: t.Object({ id: t.String() }),
+      body: SendAgentMessageBodySchema,
+      response: {
+        202: SendAgentMessageResponseSchema,
+        400: t.Object({ message: t.String() }),
+        404: t.Object({ message: t.String() }),
+      },
+    }
+  )
   .get(
     "/sessions/byCell/:cellId",
     async ({ params, set }) => {
@@ -322,7 +354,7 @@ export const agentsRoutes = new Elysia({ prefix: "/api/agents" })
   )
   .get(
     "/sessions/:id/events",
-    async ({ params, request, set }) => {
+    async ({ params, query, request, set }) => {
       let session: AgentSessionRecord;
       try {
         session = await fetchSessionOrThrow(
@@ -347,7 +379,9 @@ export const agentsRoutes = new Elysia({ prefix: "/api/agents" })
         }
 
         for await (const event of iterator) {
-          const nextEvent = formatAgentStreamSseEvent(event);
+          const nextEvent =
+            formatAgentStreamSseEvent(event) ??
+            (query.includeParts ? formatMessageSseEvent(event) : null);
           if (nextEvent) {
             yield nextEvent;
           }
@@ -358,6 +392,7 @@ export const agentsRoutes = new Elysia({ prefix: "/api/agents" })
     },
     {
       params: t.Object({ id: t.String() }),
+      query: AgentEventsQuerySchema,
       response: {
         200: t.Any(),
         400: t.Object({ message: t.String() }),
@@ -537,6 +572,61 @@ function formatAgentStreamSseEvent(event: AgentStreamEvent) {
   return null;
 }
 
+/**
+ * Message events let non-UI clients render a reply as it streams: which
+ * messages belong to the assistant, their text, and tool call progress.
+ */
+function formatMessageSseEvent(event: AgentStreamEvent) {
+  if (event.type === "message.updated") {
+    const { info } = event.properties;
+    return sse({ event: "message", data: { id: info.id, role: info.role } });
+  }
+
+  if (event.type !== "message.part.updated") {
+    return null;
+  }
+
+  const { part, delta } = event.properties;
+  const base = { messageId: part.messageID, partId: part.id, type: part.type };
+  if (part.type === "text" || part.type === "reasoning") {
+    return sse({
+      event: "part",
+      data: { ...base, text: part.text, ...(delta ? { delta } : {}) },
+    });
+  }
+
+  if (part.type === "tool") {
+    const { state } = part;
+    return sse({
+      event: "part",
+      data: {
+        ...base,
+        tool: part.tool,
+        status: state.status,
+        ...("title" in state && state.title ? { title: state.title } : {}),
+        ...(state.status === "error" ? { error: state.error } : {}),
+      },
+    });
+  }
+
+  return null;
+}
+
+/**
+ * The prompt call only returns once the agent finishes its turn, so it runs
+ * in the background and progress is reported on the event stream. Failures
+ * before the prompt reaches the agent are surfaced there as an error status.
+ */
+function dispatchAgentMessageInBackground(sessionId: string, content: string) {
+  sendAgentMessage(sessionId, content).catch((error: unknown) => {
+    publishAgentEvent(sessionId, {
+      type: "status",
+      status: "error",
+      error: formatUnknown(error, "Failed to send message"),
+    });
+  });
+}
+
 function formatSession(session: AgentSessionRecord) {
   return {
     id: session.id,
@@ -0,0 +1,187 @@
+import { Elysia, t } from "elysia";
+import {
+  API_TOKEN_COOKIE,
+  type ApiAuthMode,
+  getRequestPrincipal,
+} from "../auth/plugin";
+import {
+  ApiTokenError,
+  createApiToken,
+  listApiTokens,
+  revokeApiToken,
+  verifyApiToken,
+} from "../auth/tokens";
+import { findUser } from "../auth/users";
+import {
+  DatabaseService,
+  type DatabaseService as DatabaseServiceType,
+} from "../db";
+import {
+  ApiTokenListResponseSchema,
+  ApiTokenSchema,
+  AuthLoginQuerySchema,
+  AuthSessionResponseSchema,
+  CreateApiTokenBodySchema,
+  CreateApiTokenResponseSchema,
+} from "../schema/api";
+import type { ApiToken } from "../schema/api-tokens";
+
+const HTTP_STATUS = {
+  CREATED: 201,
+  FOUND: 302,
+  BAD_REQUEST: 400,
+  UNAUTHORIZED: 401,
+  NOT_FOUND: 404,
+} as const;
+
+const SESSION_COOKIE_MAX_AGE_SECONDS = 60 * 60 * 24 * 30;
+
+const MessageSchema = t.Object({ message: t.String() });
+
+type DatabaseClient = DatabaseServiceType["db"];
+
+export type AuthRouteDependencies = {
+  db?: DatabaseClient;
+  mode?: ApiAuthMode;
+};
+
+const toApiTokenResponse = (token: ApiToken) => ({
+  id: token.id,
+  name: token.name,
+  tokenPrefix: token.tokenPrefix,
+  scope: token.scope,
+  userId: token.userId,
+  createdAt: token.createdAt.toISOString(),
+  lastUsedAt: token.lastUsedAt?.toISOString() ?? null,
+  revokedAt: token.revokedAt?.toISOString() ?? null,
+});
+
+/**
+ * Only same-host targets are followed after login so the endpoint cannot be
+ * used as an open redirect.
+ */
+function resolveLoginRedirect(request: Request, target?: string): string {
+  if (!target) {
+    return "/";
+  }
+  if (target.startsWith("/") && !target.startsWith("//")) {
+    return target;
+  }
+  try {
+    const url = new URL(target);
+    const requestUrl = new URL(request.url);
+    if (
+      (url.protocol === "http:" || url.protocol === "https:") &&
+      url.hostname === requestUrl.hostname
+    ) {
+      return url.toString();
+    }
+  } catch {
+    // Fall through to the default target.
+  }
+  return "/";
+}
+
+export function createAuthRoutes({
+  db = DatabaseService.db,
+  mode = "off",
+}: AuthRouteDependencies = {}) {
+  return new Elysia({ prefix: "/api/auth" })
+    .get(
+      "/me",
+      ({ request }) => ({ mode, principal: getRequestPrincipal(request) }),
+      { response: { 200: AuthSessionResponseSchema } }
+    )
+    .get(
+      "/login",
+      async ({ query, request, set, cookie }) => {
+        const record = await verifyApiToken(db, query.token);
+        if (!record) {
+          set.status = HTTP_STATUS.UNAUTHORIZED;
+          return { message: "Invalid or revoked token" };
+        }
+
+        cookie[API_TOKEN_COOKIE]?.set({
+          value: query.token,
+          httpOnly: true,
+          sameSite: "strict",
+          path: "/",
+          maxAge: SESSION_COOKIE_MAX_AGE_SECONDS,
+        });
+        set.status = HTTP_STATUS.FOUND;
+        set.headers.location = resolveLoginRedirect(request, query.redirect);
+        return { message: `Signed in as ${record.name}` };
+      },
+      {
+        query: AuthLoginQuerySchema,
+        response: {
+          302: MessageSchema,
+          401: MessageSchema,
+        },
+      }
+    )
+    .post(
+      "/logout",
+      ({ cookie }) => {
+        cookie[API_TOKEN_COOKIE]?.remove();
+        return { message: "Signed out" };
+      },
+      { response: { 200: MessageSchema } }
+    )
+    .get(
+      "/tokens",
+      async () => ({
+        tokens: (await listApiTokens(db)).map(toApiTokenResponse),
+      }),
+      { response: { 200: ApiTokenListResponseSchema } }
+    )
+    .post(
+      "/tokens",
+      async ({ body, set }) => {
+        try {
+          const user = body.user ? await findUser(db, body.user) : null;
+          if (body.user && !user) {
+            throw new ApiTokenError(`User "${body.user}" not found`);
+          }
+          const { token, record } = await createApiToken(db, {
+            name: body.name,
+            scope: body.scope,
+            userId: user?.id ?? null,
+          });
+          set.status = HTTP_STATUS.CREATED;
+          return { token, record: toApiTokenResponse(record) };
+        } catch (error) {
+          if (error instanceof ApiTokenError) {
+            set.status = HTTP_STATUS.BAD_REQUEST;
+            return { message: error.message };
+          }
+          throw error;
+        }
+      },
+      {
+        body: CreateApiTokenBodySchema,
+        response: {
+          201: CreateApiTokenResponseSchema,
+          400: MessageSchema,
+        },
+      }
+    )
+    .delete(
+      "/tokens/:id",
+      async ({ params, set }) => {
+        const revoked = await revokeApiToken(db, params.id);
+        if (!revoked) {
+          set.status = HTTP_STATUS.NOT_FOUND;
+          return { message: "Token not found" };
+        }
+        return toApiTokenResponse(revoked);
+      },
+      {
+        params: t.Object({ id: t.String() }),
+        response: {
+          200: ApiTokenSchema,
+          404: MessageSchema,
+        },
+      }
+    );
+}
@@ -0,0 +1,497 @@
+import { eq } from "drizzle-orm";
+import { Elysia, t } from "elysia";
+import {
+  type AgentRuntimeService,
+  agentRuntimeService,
+} from "../agents/service";
+import { getRequestUserId } from "../auth/plugin";
+import {
+  DatabaseService,
+  type DatabaseService as DatabaseServiceType,
+} from "../db";
+import {
+  createForgeAdapter,
+  ForgeConfigurationError,
+  ForgeRequestError,
+  parseRemoteRepository,
+} from "../forge";
+import type { ForgeFetch } from "../forge/types";
+import {
+  type ActivityEventType,
+  cellActivityEvents,
+} from "../schema/activity-events";
+import {
+  CellCommitBodySchema,
+  CellCommitResponseSchema,
+  CellMergeBodySchema,
+  CellMergeResponseSchema,
+  CellPullRequestBodySchema,
+  CellPullRequestResponseSchema,
+  CellPushBodySchema,
+  CellPushResponseSchema,
+  CellSyncBodySchema,
+  CellSyncResponseSchema,
+} from "../schema/api";
+import { type Cell, cells } from "../schema/cells";
+import {
+  CellGitError,
+  type CellGitErrorKind,
+  commitCellChanges,
+  DEFAULT_GIT_REMOTE,
+  pushCellBranch,
+  resolveBaseBranch,
+  resolveRemoteUrl,
+} from "../services/cell-git";
+import { mergeCellIntoBase } from "../services/cell-merge";
+import { syncCellWithUpstream } from "../services/cell-sync";
+import type { WorktreeStartPointRef } from "../worktree/manager";
+import {
+  type ResolveWorkspaceContext,
+  resolveWorkspaceContext as defaultResolveWorkspaceContext,
+} from "../workspaces/context";
+
+const HTTP_STATUS = {
+  BAD_REQUEST: 400,
+  NOT_FOUND: 404,
+  CONFLICT: 409,
+  BAD_GATEWAY: 502,
+  INTERNAL_ERROR: 500,
+} as const;
+
+const CELL_GIT_ERROR_STATUS: Record<CellGitErrorKind, number> = {
+  nothing_to_commit: HTTP_STATUS.CONFLICT,
+  missing_branch: HTTP_STATUS.CONFLICT,
+  missing_remote: HTTP_STATUS.BAD_REQUEST,
+  uncommitted_changes: HTTP_STATUS.CONFLICT,
+  rejected: HTTP_STATUS.CONFLICT,
+};
+
+const ErrorSchema = t.Object({ message: t.String() });
+const CellParamsSchema = t.Object({ id: t.String() });
+
+type DatabaseClient = DatabaseServiceType["db"];
+
+export type CellGitRouteDependencies = {
+  db?: DatabaseClient;
+  resolveWorkspaceContext?: ResolveWorkspaceContext;
+  forgeFetch?: ForgeFetch;
+  env?: Record<string, string | undefined>;
+  sendAgentMessage?: AgentRuntimeService["sendAgentMessage"];
+};
+
+class CellGitRouteError extends Error {
+  readonly status: number;
+
+  constructor(status: number, message: string) {
+    super(message);
+    this.name = "CellGitRouteError";
+    this.status = status;
+  }
+}
+
+const toRouteError = (error: unknown, fallback: string) => {
+  if (error instanceof CellGitRouteError) {
+    return error;
+  }
+  if (error instanceof CellGitError) {
+    return new CellGitRouteError(
+      CELL_GIT_ERROR_STATUS[error.kind],
+      error.message
+    );
+  }
+  if (error instanceof ForgeConfigurationError) {
+    return new CellGitRouteError(HTTP_STATUS.BAD_REQUEST, error.message);
+  }
+  if (error instanceof ForgeRequestError) {
+    return new CellGitRouteError(HTTP_STATUS.BAD_GATEWAY, error.message);
+  }
+  return new CellGitRouteError(
+    HTTP_STATUS.INTERNAL_ERROR,
+    error instanceof Error ? error.message : fallback
+  );
+};
+
+const handleRouteFailure = (
+  set: { status?: number | string },
+  error: unknown,
+  fallback: string
+) => {
+  const routeError = toRouteError(error, fallback);
+  set.status = routeError.status;
+  return { message: routeError.message };
+};
+
+// Forked cells have no upstream of their own and follow the base branch.
+const isSyncableStartPointMode = (
+  value: string | null
+): value is WorktreeStartPointRef["mode"] =>
+  value === "head" || value === "branch" || value === "pr";
+
+async function loadReadyCell(
+  database: DatabaseClient,
+  cellId: string
+): Promise<Cell> {
+  const cell = await database.query.cells.findFirst({
+    where: eq(cells.id, cellId),
+  });
+  if (!cell) {
+    throw new CellGitRouteError(HTTP_STATUS.NOT_FOUND, "Cell not found");
+  }
+  if (cell.status !== "ready" || !cell.workspacePath.trim()) {
+    throw new CellGitRouteError(
+      HTTP_STATUS.CONFLICT,
+      "Cell workspace is not ready yet"
+    );
+  }
+  return cell;
+}
+
+async function insertGitActivityEvent(args: {
+  database: DatabaseClient;
+  request: Request;
+  cellId: string;
+  type: ActivityEventType;
+  metadata: Record<string, unknown>;
+}) {
+  await args.database.insert(cellActivityEvents).values({
+    id: crypto.randomUUID(),
+    cellId: args.cellId,
+    serviceId: null,
+    type: args.type,
+    source: args.request.headers.get("x-hive-source"),
+    toolName: args.request.headers.get("x-hive-tool"),
+    userId: getRequestUserId(args.request),
+    metadata: args.metadata,
+    createdAt: new Date(),
+  });
+}
+
+export function createCellGitRoutes({
+  db = DatabaseService.db,
+  resolveWorkspaceContext = defaultResolveWorkspaceContext,
+  forgeFetch,
+  env,
+  sendAgentMessage = agentRuntimeService.sendAgentMessage,
+}: CellGitRouteDependencies = {}) {
+  const loadGitConfig = async (cell: Cell) => {
+    const workspaceContext = await resolveWorkspaceContext(cell.workspaceId);
+    const hiveConfig = await workspaceContext.loadConfig();
+    return hiveConfig.git ?? {};
+  };
+
+  // Cells spawned from HEAD or a branch merge back into that branch; PR cells
+  // and cells created before start points were recorded use the base branch.
+  const resolveCellBaseBranch = async (cell: Cell, override?: string) => {
+    if (override) {
+      return override;
+    }
+    if (
+      cell.startPointRef &&
+      (cell.startPointMode === "head" || cell.startPointMode === "branch")
+    ) {
+      return cell.startPointRef;
+    }
+    const gitConfig = await loadGitConfig(cell);
+    return resolveBaseBranch({
+      workspacePath: cell.workspacePath,
+      remote: gitConfig.remote ?? DEFAULT_GIT_REMOTE,
+      ...(gitConfig.baseBranch ? { configured: gitConfig.baseBranch } : {}),
+    });
+  };
+
+  return new Elysia({ prefix: "/api/cells" })
+    .post(
+      "/:id/git/commit",
+      async ({ params, body, set, request }) => {
+        try {
+          const cell = await loadReadyCell(db, params.id);
+          const result = await commitCellChanges({
+            workspacePath: cell.workspacePath,
+            message: body.message,
+            ...(body.files ? { files: body.files } : {}),
+          });
+
+          await insertGitActivityEvent({
+            database: db,
+            request,
+            cellId: cell.id,
+            type: "cell.commit",
+            metadata: {
+              commit: result.commit,
+              branchName: cell.branchName,
+              fileCount: result.files.length,
+            },
+          });
+
+          return { ...result, branchName: cell.branchName };
+        } catch (error) {
+          return handleRouteFailure(set, error, "Failed to commit changes");
+        }
+      },
+      {
+        params: CellParamsSchema,
+        body: CellCommitBodySchema,
+        response: {
+          200: CellCommitResponseSchema,
+          400: ErrorSchema,
+          404: ErrorSchema,
+          409: ErrorSchema,
+          500: ErrorSchema,
+        },
+      }
+    )
+    .post(
+      "/:id/git/push",
+      async ({ params, body, set, request }) => {
+        try {
+          const cell = await loadReadyCell(db, params.id);
+          const gitConfig = await loadGitConfig(cell);
+          const result = await pushCellBranch({
+            workspacePath: cell.workspacePath,
+            branchName: cell.branchName,
+            remote: body.remote ?? gitConfig.remote ?? DEFAULT_GIT_REMOTE,
+            force: body.force ?? false,
+          });
+
+          await insertGitActivityEvent({
+            database: db,
+            request,
+            cellId: cell.id,
+            type: "cell.push",
+            metadata: { ...result, force: body.force ?? false },
+          });
+
+          return result;
+        } catch (error) {
+          return handleRouteFailure(set, error, "Failed to push branch");
+        }
+      },
+      {
+        params: CellParamsSchema,
+        body: CellPushBodySchema,
+        response: {
+          200: CellPushResponseSchema,
+          400: ErrorSchema,
+          404: ErrorSchema,
+          409: ErrorSchema,
+          500: ErrorSchema,
+        },
+      }
+    )
+    .post(
+      "/:id/pull-request",
+      async ({ params, body, set, request }) => {
+        try {
+          const cell = await loadReadyCell(db, params.id);
+          const gitConfig = await loadGitConfig(cell);
+          if (!gitConfig.forge) {
+            throw new CellGitRouteError(
+              HTTP_STATUS.BAD_REQUEST,
+              "Configure git.forge in hive.config.json to open pull requests"
+            );
+          }
+
+          const forge = createForgeAdapter(gitConfig.forge, {
+            ...(env ? { env } : {}),
+            ...(forgeFetch ? { fetch: forgeFetch } : {}),
+          });
+          const remote = gitConfig.remote ?? DEFAULT_GIT_REMOTE;
+          const repository =
+            gitConfig.forge.repository ??
+            parseRemoteRepository(
+              await resolveRemoteUrl(cell.workspacePath, remote)
+            );
+          if (!repository) {
+            throw new CellGitRouteError(
+              HTTP_STATUS.BAD_REQUEST,
+              `Could not determine the repository from remote "${remote}"; set git.forge.repository`
+            );
+          }
+
+          // The branch must exist on the remote before the forge accepts it.
+          const pushed = await pushCellBranch({
+            workspacePath: cell.workspacePath,
+            branchName: cell.branchName,
+            remote,
+          });
+          const base =
+            body.base ??
+            (await resolveBaseBranch({
+              workspacePath: cell.workspacePath,
+              remote,
+              ...(gitConfig.baseBranch
+                ? { configured: gitConfig.baseBranch }
+                : {}),
+            }));
+
+          const pullRequest = await forge.openPullRequest({
+            repository,
+            head: pushed.branchName,
+            base,
+            title: body.title,
+            ...(body.body ? { body: body.body } : {}),
+            draft: body.draft ?? false,
+          });
+
+          const result = {
+            ...pullRequest,
+            head: pushed.branchName,
+            base,
+            commit: pushed.commit,
+          };
+
+          await insertGitActivityEvent({
+            database: db,
+            request,
+            cellId: cell.id,
+            type: "cell.pull_request",
+            metadata: { ...result, forge: forge.type, repository },
+          });
+
+          return result;
+        } catch (error) {
+          return handleRouteFailure(set, error, "Failed to open pull request");
+        }
+      },
+      {
+        params: CellParamsSchema,
+        body: CellPullRequestBodySchema,
+        response: {
+          200: CellPullRequestResponseSchema,
+          400: ErrorSchema,
+          404: ErrorSchema,
+          409: ErrorSchema,
+          500: ErrorSchema,
+          502: ErrorSchema,
+        },
+      }
+    )
+    .post(
+      "/:id/merge",
+      async ({ params, body, set, request }) => {
+        try {
+          const cell = await loadReadyCell(db, params.id);
+          const baseBranch = await resolveCellBaseBranch(cell, body.base);
+          const gitConfig = await loadGitConfig(cell);
+          const result = await mergeCellIntoBase({
+            workspacePath: cell.workspacePath,
+            branchName: cell.branchName,
+            baseBranch,
+            remote: gitConfig.remote ?? DEFAULT_GIT_REMOTE,
+            strategy: body.strategy ?? "merge",
+            dryRun: body.dryRun ?? false,
+            ...(body.message ? { message: body.message } : {}),
+          });
+
+          await insertGitActivityEvent({
+            database: db,
+            request,
+            cellId: cell.id,
+            type: "cell.merge",
+            metadata: {
+              status: result.status,
+              strategy: result.strategy,
+              dryRun: result.dryRun,
+              applied: result.applied,
+              branchName: result.branchName,
+              baseBranch: result.baseBranch,
+              resultCommit: result.resultCommit,
+              conflicts: result.conflicts.map((file) => file.path),
+            },
+          });
+
+          return result;
+        } catch (error) {
+          return handleRouteFailure(set, error, "Failed to merge cell");
+        }
+      },
+      {
+        params: CellParamsSchema,
+        body: CellMergeBodySchema,
+        response: {
+          200: CellMergeResponseSchema,
+          400: ErrorSchema,
+          404: ErrorSchema,
+          409: ErrorSchema,
+          500: ErrorSchema,
+        },
+      }
+    )
+    .post(
+      "/:id/sync",
+      async ({ params, body, set, request }) => {
+        try {
+          const cell = await loadReadyCell(db, params.id);
+          const gitConfig = await loadGitConfig(cell);
+          const remote = gitConfig.remote ?? DEFAULT_GIT_REMOTE;
+          const startPoint: WorktreeStartPointRef =
+            isSyncableStartPointMode(cell.startPointMode) && cell.startPointRef
+              ? { mode: cell.startPointMode, ref: cell.startPointRef }
+              : { mode: "branch", ref: await resolveCellBaseBranch(cell) };
+
+          const result = await syncCellWithUpstream({
+            workspacePath: cell.workspacePath,
+            branchName: cell.branchName,
+            startPoint,
+            remote,
+            strategy: body.strategy ?? "rebase",
+          });
+
+          if (result.status !== "conflicted") {
+            await db
+              .update(cells)
+              .set({ baseCommit: result.baseCommit })
+              .where(eq(cells.id, cell.id));
+          }
+
+          await insertGitActivityEvent({
+            database: db,
+            request,
+            cellId: cell.id,
+            type: "cell.sync",
+            metadata: {
+              status: result.status,
+              strategy: result.strategy,
+              upstream: result.upstream,
+              previousBaseCommit: cell.baseCommit,
+              baseCommit: result.baseCommit,
+              headCommit: result.headCommit,
+              conflicts: result.conflicts.map((file) => file.path),
+            },
+          });
+
+          // The agent's own sync tool already returns the conflicts to it;
+          // syncs started from the UI or API hand them to the cell's session.
+          // The prompt only settles once the agent's turn ends, so it is not
+          // awaited, and an unreachable agent leaves the response unchanged.
+          if (
+            result.status === "conflicted" &&
+            result.message &&
+            cell.opencodeSessionId &&
+            !request.headers.get("x-hive-tool")
+          ) {
+            sendAgentMessage(cell.opencodeSessionId, result.message).catch(
+              () => undefined
+            );
+          }
+
+          return result;
+        } catch (error) {
+          return handleRouteFailure(set, error, "Failed to sync cell");
+        }
+      },
+      {
+        params: CellParamsSchema,
+        body: CellSyncBodySchema,
+        response: {
+          200: CellSyncResponseSchema,
+          400: ErrorSchema,
+          404: ErrorSchema,
+          409: ErrorSchema,
+          500: ErrorSchema,
+        },
+      }
+    );
+}
+
+export const cellGitRoutes = createCellGitRoutes();
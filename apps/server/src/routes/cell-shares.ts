@@ -0,0 +1,175 @@
+import { and, asc, eq } from "drizzle-orm";
+import { Elysia, t } from "elysia";
+import { findUser } from "../auth/users";
+import {
+  DatabaseService,
+  type DatabaseService as DatabaseServiceType,
+} from "../db";
+import {
+  CellShareListResponseSchema,
+  CellShareSchema,
+  CreateCellShareBodySchema,
+} from "../schema/api";
+import { cellShares } from "../schema/cell-shares";
+import { type Cell, cells } from "../schema/cells";
+import { users } from "../schema/users";
+
+const HTTP_STATUS = {
+  CREATED: 201,
+  BAD_REQUEST: 400,
+  NOT_FOUND: 404,
+} as const;
+
+const ErrorSchema = t.Object({ message: t.String() });
+const CellParamsSchema = t.Object({ id: t.String() });
+
+type DatabaseClient = DatabaseServiceType["db"];
+
+export type CellShareRouteDependencies = {
+  db?: DatabaseClient;
+};
+
+type CellShareRow = {
+  cellId: string;
+  userId: string;
+  username: string;
+  createdAt: Date;
+};
+
+const toShareResponse = (share: CellShareRow) => ({
+  cellId: share.cellId,
+  userId: share.userId,
+  username: share.username,
+  createdAt: share.createdAt.toISOString(),
+});
+
+function loadCell(
+  database: DatabaseClient,
+  cellId: string
+): Promise<Cell | undefined> {
+  return database.query.cells.findFirst({ where: eq(cells.id, cellId) });
+}
+
+function listShares(
+  database: DatabaseClient,
+  cellId: string
+): Promise<CellShareRow[]> {
+  return database
+    .select({
+      cellId: cellShares.cellId,
+      userId: cellShares.userId,
+      username: users.username,
+      createdAt: cellShares.createdAt,
+    })
+    .from(cellShares)
+    .innerJoin(users, eq(users.id, cellShares.userId))
+    .where(eq(cellShares.cellId, cellId))
+    .orderBy(asc(users.username));
+}
+
+/**
+ * Read-only sharing. Ownership checks happen in the auth plugin: anyone who
+ * can see a cell can list its shares, only owners and admins can change them.
+ */
+export function createCellShareRoutes({
+  db = DatabaseService.db,
+}: CellShareRouteDependencies = {}) {
+  return new Elysia({ prefix: "/api/cells" })
+    .get(
+      "/:id/shares",
+      async ({ params, set }) => {
+        const cell = await loadCell(db, params.id);
+        if (!cell) {
+          set.status = HTTP_STATUS.NOT_FOUND;
+          return { message: "Cell not found" };
+        }
+        const shares = await listShares(db, cell.id);
+        return { shares: shares.map(toShareResponse) };
+      },
+      {
+        params: CellParamsSchema,
+        response: {
+          200: CellShareListResponseSchema,
+          404: ErrorSchema,
+        },
+      }
+    )
+    .post(
+      "/:id/shares",
+      async ({ params, body, set }) => {
+        const cell = await loadCell(db, params.id);
+        if (!cell) {
+          set.status = HTTP_STATUS.NOT_FOUND;
+          return { message: "Cell not found" };
+        }
+        const user = await findUser(db, body.user);
+        if (!user) {
+          set.status = HTTP_STATUS.NOT_FOUND;
+          return { message: `User "${body.user}" not found` };
+        }
+        if (user.id === cell.ownerId) {
+          set.status = HTTP_STATUS.BAD_REQUEST;
+          return { message: "Cannot share a cell with its owner" };
+        }
+
+        const createdAt = new Date();
+        await db
+          .insert(cellShares)
+          .values({
+            id: crypto.randomUUID(),
+            cellId: cell.id,
+            userId: user.id,
+            createdAt,
+          })
+          .onConflictDoNothing();
+
+        set.status = HTTP_STATUS.CREATED;
+        return toShareResponse({
+          cellId: cell.id,
+          userId: user.id,
+          username: user.username,
+          createdAt,
+        });
+      },
+      {
+        params: CellParamsSchema,
+        body: CreateCellShareBodySchema,
+        response: {
+          201: CellShareSchema,
+          400: ErrorSchema,
+          404: ErrorSchema,
+        },
+      }
+    )
+    .delete(
+      "/:id/shares/:userId",
+      async ({ params, set }) => {
+        const user = await findUser(db, params.userId);
+        const [removed] = user
+          ? await db
+              .delete(cellShares)
+              .where(
+                and(
+                  eq(cellShares.cellId, params.id),
+                  eq(cellShares.userId, user.id)
+                )
+              )
+              .returning()
+          : [];
+        if (!removed) {
+          set.status = HTTP_STATUS.NOT_FOUND;
+          return { message: "Share not found" };
+        }
+        return { message: "Share removed" };
+      },
+      {
+        params: t.Object({ id: t.String(), userId: t.String() }),
+        response: {
+          200: t.Object({ message: t.String() }),
+          404: ErrorSchema,
+        },
+      }
+    );
+}
+
+export const cellShareRoutes = createCellShareRoutes();
@@ -0,0 +1,324 @@
+import { and, eq } from "drizzle-orm";
+import { Elysia, t } from "elysia";
+import { getRequestUserId } from "../auth/plugin";
+import {
+  DatabaseService,
+  type DatabaseService as DatabaseServiceType,
+} from "../db";
+import {
+  type ActivityEventType,
+  cellActivityEvents,
+} from "../schema/activity-events";
+import {
+  CellSnapshotListQuerySchema,
+  CellSnapshotListResponseSchema,
+  CellSnapshotSchema,
+  CreateCellSnapshotBodySchema,
+  RestoreCellSnapshotBodySchema,
+  RestoreCellSnapshotResponseSchema,
+} from "../schema/api";
+import { type CellSnapshot, cellSnapshots } from "../schema/cell-snapshots";
+import { type Cell, cells } from "../schema/cells";
+import {
+  createCellSnapshot,
+  deleteCellSnapshot,
+  listCellSnapshots,
+  resolveSnapshotPatterns,
+  restoreCellSnapshot,
+} from "../services/cell-snapshots";
+import {
+  type ResolveWorkspaceContext,
+  resolveWorkspaceContext as defaultResolveWorkspaceContext,
+} from "../workspaces/context";
+
+const HTTP_STATUS = {
+  CREATED: 201,
+  NOT_FOUND: 404,
+  CONFLICT: 409,
+  INTERNAL_ERROR: 500,
+} as const;
+
+const ErrorSchema = t.Object({ message: t.String() });
+const CellParamsSchema = t.Object({ id: t.String() });
+const SnapshotParamsSchema = t.Object({
+  id: t.String(),
+  snapshotId: t.String(),
+});
+
+type DatabaseClient = DatabaseServiceType["db"];
+
+export type CellSnapshotRouteDependencies = {
+  db?: DatabaseClient;
+  resolveWorkspaceContext?: ResolveWorkspaceContext;
+  archiveRoot?: (cellId: string) => string;
+};
+
+class CellSnapshotRouteError extends Error {
+  readonly status: number;
+
+  constructor(status: number, message: string) {
+    super(message);
+    this.name = "CellSnapshotRouteError";
+    this.status = status;
+  }
+}
+
+const handleRouteFailure = (
+  set: { status?: number | string },
+  error: unknown,
+  fallback: string
+) => {
+  if (error instanceof CellSnapshotRouteError) {
+    set.status = error.status;
+    return { message: error.message };
+  }
+  set.status = HTTP_STATUS.INTERNAL_ERROR;
+  return { message: error instanceof Error ? error.message : fallback };
+};
+
+const toSnapshotResponse = (snapshot: CellSnapshot) => ({
+  id: snapshot.id,
+  cellId: snapshot.cellId,
+  name: snapshot.name,
+  gitRef: snapshot.gitRef,
+  snapshotCommit: snapshot.snapshotCommit,
+  headCommit: snapshot.headCommit,
+  hasArchive: Boolean(snapshot.archivePath),
+  agentMessageId: snapshot.agentMessageId,
+  createdAt: snapshot.createdAt.toISOString(),
+});
+
+async function loadReadyCell(
+  database: DatabaseClient,
+  cellId: string
+): Promise<Cell> {
+  const cell = await database.query.cells.findFirst({
+    where: eq(cells.id, cellId),
+  });
+  if (!cell) {
+    throw new CellSnapshotRouteError(HTTP_STATUS.NOT_FOUND, "Cell not found");
+  }
+  if (cell.status !== "ready" || !cell.workspacePath.trim()) {
+    throw new CellSnapshotRouteError(
+      HTTP_STATUS.CONFLICT,
+      "Cell workspace is not ready yet"
+    );
+  }
+  return cell;
+}
+
+async function loadSnapshot(
+  database: DatabaseClient,
+  cellId: string,
+  snapshotId: string
+): Promise<CellSnapshot> {
+  const snapshot = await database.query.cellSnapshots.findFirst({
+    where: and(
+      eq(cellSnapshots.id, snapshotId),
+      eq(cellSnapshots.cellId, cellId)
+    ),
+  });
+  if (!snapshot) {
+    throw new CellSnapshotRouteError(
+      HTTP_STATUS.NOT_FOUND,
+      "Snapshot not found"
+    );
+  }
+  return snapshot;
+}
+
+async function insertSnapshotActivityEvent(args: {
+  database: DatabaseClient;
+  request: Request;
+  cellId: string;
+  type: ActivityEventType;
+  metadata: Record<string, unknown>;
+}) {
+  await args.database.insert(cellActivityEvents).values({
+    id: crypto.randomUUID(),
+    cellId: args.cellId,
+    serviceId: null,
+    type: args.type,
+    source: args.request.headers.get("x-hive-source"),
+    toolName: args.request.headers.get("x-hive-tool"),
+    userId: getRequestUserId(args.request),
+    metadata: args.metadata,
+    createdAt: new Date(),
+  });
+}
+
+export function createCellSnapshotRoutes({
+  db = DatabaseService.db,
+  resolveWorkspaceContext = defaultResolveWorkspaceContext,
+  archiveRoot,
+}: CellSnapshotRouteDependencies = {}) {
+  const snapshotCell = async (
+    cell: Cell,
+    options: { name?: string; agentMessageId?: string }
+  ) => {
+    const workspaceContext = await resolveWorkspaceContext(cell.workspaceId);
+    const hiveConfig = await workspaceContext.loadConfig();
+    return await createCellSnapshot({
+      database: db,
+      cell,
+      patterns: resolveSnapshotPatterns(hiveConfig, cell.templateId),
+      ...options,
+      ...(archiveRoot ? { archiveRoot: archiveRoot(cell.id) } : {}),
+    });
+  };
+
+  return new Elysia({ prefix: "/api/cells" })
+    .get(
+      "/:id/snapshots",
+      async ({ params, query, set }) => {
+        try {
+          const cell = await db.query.cells.findFirst({
+            where: eq(cells.id, params.id),
+          });
+          if (!cell) {
+            throw new CellSnapshotRouteError(
+              HTTP_STATUS.NOT_FOUND,
+              "Cell not found"
+            );
+          }
+          const snapshots = await listCellSnapshots(db, cell.id, {
+            ...(query.agentMessageId
+              ? { agentMessageId: query.agentMessageId }
+              : {}),
+          });
+          return { snapshots: snapshots.map(toSnapshotResponse) };
+        } catch (error) {
+          return handleRouteFailure(set, error, "Failed to list snapshots");
+        }
+      },
+      {
+        params: CellParamsSchema,
+        query: CellSnapshotListQuerySchema,
+        response: {
+          200: CellSnapshotListResponseSchema,
+          404: ErrorSchema,
+          500: ErrorSchema,
+        },
+      }
+    )
+    .post(
+      "/:id/snapshots",
+      async ({ params, body, set, request }) => {
+        try {
+          const cell = await loadReadyCell(db, params.id);
+          const snapshot = await snapshotCell(cell, {
+            ...(body.name ? { name: body.name } : {}),
+            ...(body.agentMessageId
+              ? { agentMessageId: body.agentMessageId }
+              : {}),
+          });
+
+          await insertSnapshotActivityEvent({
+            database: db,
+            request,
+            cellId: cell.id,
+            type: "cell.snapshot",
+            metadata: {
+              snapshotId: snapshot.id,
+              name: snapshot.name,
+              agentMessageId: snapshot.agentMessageId,
+            },
+          });
+
+          set.status = HTTP_STATUS.CREATED;
+          return toSnapshotResponse(snapshot);
+        } catch (error) {
+          return handleRouteFailure(set, error, "Failed to create snapshot");
+        }
+      },
+      {
+        params: CellParamsSchema,
+        body: CreateCellSnapshotBodySchema,
+        response: {
+          201: CellSnapshotSchema,
+          404: ErrorSchema,
+          409: ErrorSchema,
+          500: ErrorSchema,
+        },
+      }
+    )
+    .post(
+      "/:id/snapshots/:snapshotId/restore",
+      async ({ params, body, set, request }) => {
+        try {
+          const cell = await loadReadyCell(db, params.id);
+          const snapshot = await loadSnapshot(db, cell.id, params.snapshotId);
+          // Keep the state being thrown away so a restore can be undone too.
+          const backup =
+            body.backup === false
+              ? null
+              : await snapshotCell(cell, {
+                  name: `Before restoring "${snapshot.name}"`,
+                });
+
+          await restoreCellSnapshot({
+            workspacePath: cell.workspacePath,
+            snapshot,
+          });
+
+          await insertSnapshotActivityEvent({
+            database: db,
+            request,
+            cellId: cell.id,
+            type: "cell.restore",
+            metadata: {
+              snapshotId: snapshot.id,
+              name: snapshot.name,
+              agentMessageId: snapshot.agentMessageId,
+              backupSnapshotId: backup?.id ?? null,
+            },
+          });
+
+          return {
+            restored: toSnapshotResponse(snapshot),
+            backup: backup ? toSnapshotResponse(backup) : null,
+          };
+        } catch (error) {
+          return handleRouteFailure(set, error, "Failed to restore snapshot");
+        }
+      },
+      {
+        params: SnapshotParamsSchema,
+        body: RestoreCellSnapshotBodySchema,
+        response: {
+          200: RestoreCellSnapshotResponseSchema,
+          404: ErrorSchema,
+          409: ErrorSchema,
+          500: ErrorSchema,
+        },
+      }
+    )
+    .delete(
+      "/:id/snapshots/:snapshotId",
+      async ({ params, set }) => {
+        try {
+          const cell = await loadReadyCell(db, params.id);
+          const snapshot = await loadSnapshot(db, cell.id, params.snapshotId);
+          await deleteCellSnapshot({
+            database: db,
+            workspacePath: cell.workspacePath,
+            snapshot,
+          });
+          return { message: "Snapshot deleted" };
+        } catch (error) {
+          return handleRouteFailure(set, error, "Failed to delete snapshot");
+        }
+      },
+      {
+        params: SnapshotParamsSchema,
+        response: {
+          200: t.Object({ message: t.String() }),
+          404: ErrorSchema,
+          409: ErrorSchema,
+          500: ErrorSchema,
+        },
+      }
+    );
+}
+
+export const cellSnapshotRoutes = createCellSnapshotRoutes();
@@ -0,0 +1,286 @@
+import { eq } from "drizzle-orm";
+import { Elysia, t } from "elysia";
+import {
+  type AgentRuntimeService,
+  agentRuntimeService,
+} from "../agents/service";
+import { getRequestUserId } from "../auth/plugin";
+import {
+  DatabaseService,
+  type DatabaseService as DatabaseServiceType,
+} from "../db";
+import {
+  type ActivityEventType,
+  cellActivityEvents,
+} from "../schema/activity-events";
+import { ImportCellQuerySchema, ImportCellResponseSchema } from "../schema/api";
+import { type Cell, cells } from "../schema/cells";
+import { resolveSnapshotPatterns } from "../services/cell-snapshots";
+import {
+  CellTransferError,
+  exportCell,
+  formatImportedTranscript,
+  importCell,
+} from "../services/cell-transfer";
+import {
+  type ResolveWorkspaceContext,
+  resolveWorkspaceContext as defaultResolveWorkspaceContext,
+  WorkspaceContextError,
+} from "../workspaces/context";
+
+const HTTP_STATUS = {
+  CREATED: 201,
+  BAD_REQUEST: 400,
+  NOT_FOUND: 404,
+  CONFLICT: 409,
+  INTERNAL_ERROR: 500,
+} as const;
+
+const ErrorSchema = t.Object({ message: t.String() });
+const CellParamsSchema = t.Object({ id: t.String() });
+
+type DatabaseClient = DatabaseServiceType["db"];
+
+export type CellTransferRouteDependencies = {
+  db?: DatabaseClient;
+  resolveWorkspaceContext?: ResolveWorkspaceContext;
+  fetchAgentMessages?: AgentRuntimeService["fetchAgentMessages"];
+  ensureAgentSession?: AgentRuntimeService["ensureAgentSession"];
+  appendAgentContext?: AgentRuntimeService["appendAgentContext"];
+};
+
+class CellTransferRouteError extends Error {
+  readonly status: number;
+
+  constructor(status: number, message: string) {
+    super(message);
+    this.name = "CellTransferRouteError";
+    this.status = status;
+  }
+}
+
+const handleRouteFailure = (
+  set: { status?: number | string },
+  error: unknown,
+  fallback: string
+) => {
+  if (error instanceof CellTransferRouteError) {
+    set.status = error.status;
+    return { message: error.message };
+  }
+  if (
+    error instanceof CellTransferError ||
+    error instanceof WorkspaceContextError
+  ) {
+    set.status = HTTP_STATUS.BAD_REQUEST;
+    return { message: error.message };
+  }
+  set.status = HTTP_STATUS.INTERNAL_ERROR;
+  return { message: error instanceof Error ? error.message : fallback };
+};
+
+async function loadReadyCell(
+  database: DatabaseClient,
+  cellId: string
+): Promise<Cell> {
+  const cell = await database.query.cells.findFirst({
+    where: eq(cells.id, cellId),
+  });
+  if (!cell) {
+    throw new CellTransferRouteError(HTTP_STATUS.NOT_FOUND, "Cell not found");
+  }
+  if (cell.status !== "ready" || !cell.workspacePath.trim()) {
+    throw new CellTransferRouteError(
+      HTTP_STATUS.CONFLICT,
+      "Cell workspace is not ready yet"
+    );
+  }
+  return cell;
+}
+
+function toArchiveBytes(body: unknown): Uint8Array {
+  if (body instanceof Uint8Array) {
+    return body;
+  }
+  if (body instanceof ArrayBuffer) {
+    return new Uint8Array(body);
+  }
+  throw new CellTransferRouteError(
+    HTTP_STATUS.BAD_REQUEST,
+    "Expected the cell archive as an application/octet-stream body"
+  );
+}
+
+const toArchiveFileName = (cell: Cell) => {
+  const slug = cell.name
+    .toLowerCase()
+    .replace(/[^a-z0-9]+/g, "-")
+    .replace(/^-+|-+$/g, "");
+  return `${slug || cell.id}.tar`;
+};
+
+async function insertTransferActivityEvent(args: {
+  database: DatabaseClient;
+  request: Request;
+  cellId: string;
+  type: ActivityEventType;
+  metadata: Record<string, unknown>;
+}) {
+  await args.database.insert(cellActivityEvents).values({
+    id: crypto.randomUUID(),
+    cellId: args.cellId,
+    serviceId: null,
+    type: args.type,
+    source: args.request.headers.get("x-hive-source"),
+    toolName: args.request.headers.get("x-hive-tool"),
+    userId: getRequestUserId(args.request),
+    metadata: args.metadata,
+    createdAt: new Date(),
+  });
+}
+
+export function createCellTransferRoutes({
+  db = DatabaseService.db,
+  resolveWorkspaceContext = defaultResolveWorkspaceContext,
+  fetchAgentMessages = agentRuntimeService.fetchAgentMessages,
+  ensureAgentSession = agentRuntimeService.ensureAgentSession,
+  appendAgentContext = agentRuntimeService.appendAgentContext,
+}: CellTransferRouteDependencies = {}) {
+  return new Elysia({ prefix: "/api/cells" })
+    .get(
+      "/:id/export",
+      async ({ params, set, request }) => {
+        try {
+          const cell = await loadReadyCell(db, params.id);
+          const workspaceContext = await resolveWorkspaceContext(
+            cell.workspaceId
+          );
+          const hiveConfig = await workspaceContext.loadConfig();
+          // A missing or unreachable agent session should not block export.
+          const transcript = cell.opencodeSessionId
+            ? await fetchAgentMessages(cell.opencodeSessionId).catch(() => [])
+            : [];
+
+          const archive = await exportCell({
+            database: db,
+            cell,
+            patterns: resolveSnapshotPatterns(hiveConfig, cell.templateId),
+            transcript,
+          });
+
+          await insertTransferActivityEvent({
+            database: db,
+            request,
+            cellId: cell.id,
+            type: "cell.export",
+            metadata: {
+              bytes: archive.byteLength,
+              transcriptMessages: transcript.length,
+            },
+          });
+
+          return new Response(archive, {
+            headers: {
+              "content-type": "application/x-tar",
+              "content-disposition": `attachment; filename="${toArchiveFileName(cell)}"`,
+            },
+          });
+        } catch (error) {
+          return handleRouteFailure(set, error, "Failed to export cell");
+        }
+      },
+      {
+        params: CellParamsSchema,
+        response: {
+          404: ErrorSchema,
+          409: ErrorSchema,
+          500: ErrorSchema,
+        },
+      }
+    )
+    .post(
+      "/import",
+      async ({ body, query, set, request }) => {
+        try {
+          const archive = toArchiveBytes(body);
+          const workspaceContext = await resolveWorkspaceContext(
+            query.workspaceId
+          );
+          const hiveConfig = await workspaceContext.loadConfig();
+
+          const result = await importCell({
+            database: db,
+            archive,
+            workspace: {
+              id: workspaceContext.workspace.id,
+              path: workspaceContext.workspace.path,
+            },
+            worktreeManager: {
+              createWorktree: workspaceContext.createWorktree,
+              removeWorktree: workspaceContext.removeWorktree,
+            },
+            templateIds: Object.keys(hiveConfig.templates),
+            ownerId: getRequestUserId(request),
+            ...(query.name ? { name: query.name } : {}),
+          });
+          const { cell, sourceCellId } = result;
+
+          let transcriptRestored = false;
+          const context = formatImportedTranscript(
+            cell.name,
+            result.transcript
+          );
+          if (context) {
+            try {
+              const session = await ensureAgentSession(cell.id);
+              await appendAgentContext(session.id, context);
+              transcriptRestored = true;
+            } catch {
+              // The cell is usable without its history; the response says so.
+            }
+          }
+
+          await insertTransferActivityEvent({
+            database: db,
+            request,
+            cellId: cell.id,
+            type: "cell.import",
+            metadata: {
+              sourceCellId,
+              services: result.serviceCount,
+              transcriptMessages: result.transcript.length,
+              transcriptRestored,
+            },
+          });
+
+          set.status = HTTP_STATUS.CREATED;
+          return {
+            id: cell.id,
+            name: cell.name,
+            workspaceId: cell.workspaceId,
+            workspacePath: cell.workspacePath,
+            branchName: cell.branchName ?? null,
+            sourceCellId,
+            services: result.serviceCount,
+            activityEvents: result.activityCount,
+            timingEvents: result.timingCount,
+            transcriptMessages: result.transcript.length,
+            transcriptRestored,
+          };
+        } catch (error) {
+          return handleRouteFailure(set, error, "Failed to import cell");
+        }
+      },
+      {
+        parse: "arrayBuffer",
+        query: ImportCellQuerySchema,
+        response: {
+          201: ImportCellResponseSchema,
+          400: ErrorSchema,
+          500: ErrorSchema,
+        },
+      }
+    );
+}
+
+export const cellTransferRoutes = createCellTransferRoutes();
@@ -3,14 +3,36 @@ import { createConnection } from "node:net";
 import { join } from "node:path";
 
 import { logger } from "@bogeychan/elysia-logger";
-import { and, desc, eq, gte, inArray, lt, ne, sql } from "drizzle-orm";
+import {
+  type AnyColumn,
+  and,
+  desc,
+  eq,
+  gte,
+  inArray,
+  lt,
+  ne,
+  type SQL,
+  sql,
+} from "drizzle-orm";
 import { Elysia, type Static, sse, t } from "elysia";
 import { loadEffectiveOpencodeDefaults } from "../agents/opencode-config";
 import { getSharedOpencodeServerBaseUrl } from "../agents/opencode-server";
 import type { AgentRuntimeService } from "../agents/service";
 import { agentRuntimeService } from "../agents/service";
 import type { AgentMode } from "../agents/types";
-import type { Template } from "../config/schema";
+import {
+  canAccessCell,
+  cellAccessFilter,
+  hasUnrestrictedCellAccess,
+} from "../auth/access";
+import { type ApiPrincipal, getRequestPrincipal } from "../auth/plugin";
+import type { HiveConfig, Template } from "../config/schema";
+import {
+  applyTemplateParameters,
+  resolveTemplateParameterValues,
+  TemplateParameterError,
+} from "../config/template-parameters";
 import {
   DatabaseService,
   type DatabaseService as DatabaseServiceType,
@@ -21,6 +43,9 @@ import {
 } from "../schema/activity-events";
 import {
   CellActivityEventListResponseSchema,
+  CellBatchResponseSchema,
+  CellConfigApplyResponseSchema,
+  CellConfigImpactSchema,
   CellDiffResponseSchema,
   CellListResponseSchema,
   CellResourceSummaryResponseSchema,
@@ -32,6 +57,7 @@ import {
   CellTerminalResizeSchema,
   CellTerminalSessionSchema,
   CellTimingListResponseSchema,
+  CreateCellBatchSchema,
   CreateCellSchema,
   DeleteCellsSchema,
   DiffQuerySchema,
@@ -42,7 +68,12 @@ import {
   type CellProvisioningState,
   cellProvisioningStates,
 } from "../schema/cell-provisioning";
-import { type CellStatus, cells, type NewCell } from "../schema/cells";
+import {
+  type Cell,
+  type CellStatus,
+  cells,
+  type NewCell,
+} from "../schema/cells";
 import {
   cellResourceHistory,
   cellResourceRollups,
@@ -55,6 +86,13 @@ import {
   cellTimingEvents,
 } from "../schema/timing-events";
 import { createAsyncEventIterator } from "../services/async-iterator";
+import {
+  type CellBatchEvent,
+  CellBatchManifestError,
+  createCellBatchService,
+  parseCellBatchManifest,
+  resolveManifestSpawnFrom,
+} from "../services/cell-batches";
 import {
   DEFAULT_ACTIVITY_LIMIT,
   fetchCellActivityPage,
@@ -67,6 +105,18 @@ import {
   deleteCellWithLifecycle,
   removeCellWorkspace,
 } from "../services/cell-delete-lifecycle";
+import { recordCellActivity } from "../services/cell-idle";
+import {
+  appendAttachmentList,
+  validatePromptAttachments,
+  writePromptAttachments,
+} from "../services/cell-prompt-attachments";
+import {
+  applyCellSnapshot,
+  createCellSnapshot,
+  resolveSnapshotPatterns,
+  type SnapshotPatterns,
+} from "../services/cell-snapshots";
 import {
   buildTimingRuns,
   type CellTimingStepRecord,
@@ -81,6 +131,8 @@ import type {
   ChatTerminalSession,
 } from "../services/chat-terminal";
 import { chatTerminalService } from "../services/chat-terminal";
+import type { CellConfigImpact } from "../services/config-impact";
+import { configWatcher } from "../services/config-watcher";
 import {
   buildCellDiffPayload,
   parseDiffRequest,
@@ -88,10 +140,12 @@ import {
 import {
   type CellStatusEvent,
   type CellTimingEvent,
+  type ConfigReloadEvent,
   emitCellStatusUpdate,
   emitCellTimingUpdate,
   subscribeToCellStatusEvents,
   subscribeToCellTimingEvents,
+  subscribeToConfigReloadEvents,
   subscribeToServiceEvents,
 } from "../services/events";
 import {
@@ -130,6 +184,7 @@ import {
   describeWorktreeError,
   toAsyncWorktreeManager,
   type WorktreeCreateTimingEvent,
+  type WorktreeLocation,
   type WorktreeManagerError,
   type WorktreeStartPoint,
 } from "../worktree/manager";
@@ -180,6 +235,12 @@ export type CellRouteDependencies = {
   startServicesForCell: ServiceSupervisorServiceType["startCellServices"];
   stopServiceById: ServiceSupervisorServiceType["stopCellService"];
   stopServicesForCell: ServiceSupervisorServiceType["stopCellServices"];
+  teardownServicesForCell?: ServiceSupervisorServiceType["teardownCellServices"];
+  previewCellConfig?: ServiceSupervisorServiceType["previewCellConfig"];
+  applyCellConfig?: ServiceSupervisorServiceType["applyCellConfig"];
+  describeWorkspaceConfig?: (
+    workspaceId: string
+  ) => Promise<ConfigReloadEvent | null>;
   ensureTerminalSession: (args: {
     cellId: string;
     workspacePath: string;
@@ -238,6 +299,8 @@ export type CellRouteDependencies = {
   sampleServiceResources: (
     pids: number[]
   ) => Promise<Map<number, ProcessResourceSnapshot>>;
+  /** Resets the idle clock of a cell when someone types into a terminal. */
+  recordCellActivity?: (cellId: string) => void;
 };
 
 const dependencyKeys: Array<keyof CellRouteDependencies> = [
@@ -292,6 +355,10 @@ const buildDefaultCellDependencies = (): CellRouteDependencies => {
     startServicesForCell: supervisor.startCellServices,
     stopServiceById: supervisor.stopCellService,
     stopServicesForCell: supervisor.stopCellServices,
+    teardownServicesForCell: supervisor.teardownCellServices,
+    previewCellConfig: supervisor.previewCellConfig,
+    applyCellConfig: supervisor.applyCellConfig,
+    describeWorkspaceConfig: configWatcher.describe,
     ensureTerminalSession: terminal.ensureSession,
     getTerminalSession: terminal.getSession,
     readTerminalOutput: terminal.readOutput,
@@ -319,6 +386,7 @@ const buildDefaultCellDependencies = (): CellRouteDependencies => {
     resizeSetupTerminal: supervisor.resizeSetupTerminal,
     clearSetupTerminal: supervisor.clearSetupTerminal,
     sampleServiceResources: resourceSnapshot.samplePids,
+    recordCellActivity,
   } satisfies CellRouteDependencies;
 };
 
@@ -363,8 +431,10 @@ function readHiveAuditHeaders(request: Request): {
   toolName: string | null;
   auditEvent: string | null;
   serviceName: string | null;
+  userId: string | null;
 } {
   return {
+    userId: getRequestPrincipal(request)?.userId ?? null,
     source: request.headers.get("x-hive-source"),
     toolName: request.headers.get("x-hive-tool"),
     auditEvent: request.headers.get("x-hive-audit-event"),
@@ -379,6 +449,7 @@ async function insertCellActivityEvent(args: {
   type: ActivityEventType;
   source?: string | null;
   toolName?: string | null;
+  userId?: string | null;
   metadata?: Record<string, unknown>;
 }) {
   await args.database.insert(cellActivityEvents).values({
@@ -388,6 +459,7 @@ async function insertCellActivityEvent(args: {
     type: args.type,
     source: args.source ?? null,
     toolName: args.toolName ?? null,
+    userId: args.userId ?? null,
     metadata: args.metadata ?? {},
     createdAt: new Date(),
   });
@@ -470,6 +542,8 @@ async function fetchTimingSteps(args: {
   workflow?: CellTimingWorkflow | null;
   runId?: string;
   workspaceId?: string;
+  /** Limits rows to the cells a restricted principal may read. */
+  cellFilter?: SQL;
 }): Promise<CellTimingStepRecord[]> {
   const rows = await args.database
     .select()
@@ -483,6 +557,15 @@ async function fetchTimingSteps(args: {
         args.runId ? eq(cellTimingEvents.runId, args.runId) : undefined,
         args.workspaceId
           ? eq(cellTimingEvents.workspaceId, args.workspaceId)
+          : undefined,
+        args.cellFilter
+          ? inArray(
+              cellTimingEvents.cellId,
+              args.database
+                .select({ id: cells.id })
+                .from(cells)
+                .where(args.cellFilter)
+            )
           : undefined
       )
     )
@@ -512,6 +595,7 @@ type ServiceRow = {
 const HTTP_STATUS = {
   OK: 200,
   CREATED: 201,
+  ACCEPTED: 202,
   BAD_REQUEST: 400,
   NOT_FOUND: 404,
   CONFLICT: 409,
@@ -665,6 +749,7 @@ function cellToResponse(cell: typeof cells.$inferSelect) {
       : {}),
     ...(cell.branchName != null ? { branchName: cell.branchName } : {}),
     ...(cell.baseCommit != null ? { baseCommit: cell.baseCommit } : {}),
+    ...(cell.ownerId != null ? { ownerId: cell.ownerId } : {}),
   };
 }
 
@@ -855,8 +940,13 @@ function normalizeStartMode(value: string | undefined): AgentMode | undefined {
 
 function normalizeSpawnFromMode(
   value: string | undefined
-): "head" | "branch" | "pr" | undefined {
-  if (value === "head" || value === "branch" || value === "pr") {
+): WorktreeStartPoint["mode"] | undefined {
+  if (
+    value === "head" ||
+    value === "branch" ||
+    value === "pr" ||
+    value === "cell"
+  ) {
     return value;
   }
 
@@ -878,6 +968,12 @@ function normalizeSpawnFromValue(
   return trimmed;
 }
 
+const SPAWN_FROM_VALUE_REQUIRED_MESSAGES = {
+  branch: "Branch name is required when spawning from branch",
+  pr: "GitHub PR reference is required when spawning from PR",
+  cell: "Source cell id is required when forking a cell",
+} as const;
+
 function resolveWorktreeStartPoint(body: {
   spawnFromMode?: string;
   spawnFromValue?: string;
@@ -890,16 +986,105 @@ function resolveWorktreeStartPoint(body: {
   }
 
   if (!value) {
-    throw new Error(
-      mode === "branch"
-        ? "Branch name is required when spawning from branch"
-        : "GitHub PR reference is required when spawning from PR"
-    );
+    throw new Error(SPAWN_FROM_VALUE_REQUIRED_MESSAGES[mode]);
   }
 
   return { mode, value };
 }
 
+type CellForkSource = {
+  cell: Cell;
+  patterns: SnapshotPatterns;
+  provisioningState: CellProvisioningState | null;
+};
+
+async function resolveCellForkSource(args: {
+  database: DatabaseClient;
+  sourceCellId: string;
+  workspaceId: string;
+  hiveConfig: HiveConfig;
+  principal: ApiPrincipal | null;
+}): Promise<CellForkSource | CellCreationResult> {
+  const cell = await args.database.query.cells.findFirst({
+    where: eq(cells.id, args.sourceCellId),
+  });
+  // Shared users may fork a cell; the fork is theirs.
+  const visible =
+    cell &&
+    (await canAccessCell({
+      database: args.database,
+      principal: args.principal,
+      cell,
+      level: "read",
+    }));
+  if (!(cell && visible)) {
+    return {
+      status: HTTP_STATUS.NOT_FOUND,
+      payload: { message: "Source cell not found" },
+    };
+  }
+  if (cell.workspaceId !== args.workspaceId) {
+    return {
+      status: HTTP_STATUS.BAD_REQUEST,
+      payload: { message: "Source cell belongs to a different workspace" },
+    };
+  }
+  if (cell.status !== "ready" || !cell.workspacePath.trim()) {
+    return {
+      status: HTTP_STATUS.CONFLICT,
+      payload: { message: "Source cell workspace is not ready yet" },
+    };
+  }
+
+  const provisioningState =
+    (await args.database.query.cellProvisioningStates.findFirst({
+      where: eq(cellProvisioningStates.cellId, cell.id),
+    })) ?? null;
+
+  return {
+    cell,
+    patterns: resolveSnapshotPatterns(args.hiveConfig, cell.templateId),
+    provisioningState,
+  };
+}
+
+/**
+ * A fork keeps the source cell's model choices unless the request picks its
+ * own, so both cells continue the same conversation with the same agent.
+ */
+function inheritForkOverrides(
+  body: Static<typeof CreateCellSchema>,
+  source: CellForkSource
+): Static<typeof CreateCellSchema> {
+  const state = source.provisioningState;
+  if (!state) {
+    return body;
+  }
+
+  const withParameters =
+    !body.parameters &&
+    state.templateParameters &&
+    source.cell.templateId === body.templateId
+      ? { ...body, parameters: state.templateParameters }
+      : body;
+  if (body.modelId || body.providerId || body.variant) {
+    return withParameters;
+  }
+
+  return {
+    ...withParameters,
+    ...(state.modelIdOverride != null
+      ? { modelId: state.modelIdOverride }
+      : {}),
+    ...(state.providerIdOverride != null
+      ? { providerId: state.providerIdOverride }
+      : {}),
+    ...(state.variantOverride != null
+      ? { variant: state.variantOverride }
+      : {}),
+  };
+}
+
 async function resolveDefaultStartMode(args: {
   workspaceRootPath: string;
   defaultsStartMode: string | undefined;
@@ -1012,6 +1197,7 @@ type SetupTerminalWsState = {
 type ServiceTerminalWsState = {
   kind: "service";
   deps: CellRouteDependencies;
+  cellId: string;
   serviceId: string;
 };
 
@@ -1054,6 +1240,7 @@ const handleSetupTerminalWsInput = (args: {
   }
 
   deps.writeSetupTerminalInput(cellId, data);
+  deps.recordCellActivity?.(cellId);
 };
 
 const handleSetupTerminalWsResize = (args: {
@@ -1074,10 +1261,11 @@ const handleSetupTerminalWsResize = (args: {
 const handleServiceTerminalWsInput = (args: {
   deps: CellRouteDependencies;
   ws: TerminalRouteSocket;
+  cellId: string;
   serviceId: string;
   data: string;
 }) => {
-  const { deps, ws, serviceId, data } = args;
+  const { deps, ws, cellId, serviceId, data } = args;
   const session = deps.getServiceTerminalSession(serviceId);
   if (!session || session.status !== "running") {
     sendWsError(ws, "Service terminal session not available");
@@ -1085,6 +1273,7 @@ const handleServiceTerminalWsInput = (args: {
   }
 
   deps.writeServiceTerminalInput(serviceId, data);
+  deps.recordCellActivity?.(cellId);
 };
 
 const handleServiceTerminalWsResize = (args: {
@@ -1109,6 +1298,7 @@ const handleCellTerminalWsInput = (args: {
 }) => {
   const { deps, cellId, data } = args;
   deps.writeTerminalInput(cellId, data);
+  deps.recordCellActivity?.(cellId);
 };
 
 const handleCellTerminalWsResize = (args: {
@@ -1163,6 +1353,62 @@ export function createCellsRoutes(
     },
   });
 
+  const cellBatches = createCellBatchService({
+    createCell: async ({ workspaceId, entry, principal }) => {
+      const deps = await resolveDeps();
+      const workspaceContext = await resolveWorkspaceContextFromDeps(
+        deps.resolveWorkspaceContext,
+        workspaceId
+      );
+      const result = await handleCellCreationRequest({
+        body: {
+          name: entry.name,
+          ...(entry.description ? { description: entry.description } : {}),
+          templateId: entry.templateId,
+          ...(entry.startMode ? { startMode: entry.startMode } : {}),
+          ...resolveManifestSpawnFrom(entry.spawnFrom),
+          ...(entry.initialPrompt
+            ? { initialPrompt: entry.initialPrompt }
+            : {}),
+          workspaceId,
+        },
+        database: deps.db,
+        ensureSession: deps.ensureAgentSession,
+        sendAgentMessage: deps.sendAgentMessage,
+        ensureServices: deps.ensureServicesForCell,
+        stopCellServices: deps.stopServicesForCell,
+        workspaceContext,
+        log: backgroundProvisioningLogger,
+        principal,
+      });
+      if ("message" in result.payload) {
+        throw new Error(result.payload.message);
+      }
+      return result.payload;
+    },
+    waitForCellReady: async (cellId, workspaceId) => {
+      const { db: database } = await resolveDeps();
+      await waitForCellProvisioning({ database, cellId, workspaceId });
+    },
+    readInitialPromptSentAt: async (cellId) => {
+      const { db: database } = await resolveDeps();
+      const [state] = await database
+        .select({ sentAt: cellProvisioningStates.initialPromptSentAt })
+        .from(cellProvisioningStates)
+        .where(eq(cellProvisioningStates.cellId, cellId))
+        .limit(1);
+      return state?.sentAt ?? null;
+    },
+  });
+
+  const canViewBatch = (request: Request, batchId: string) => {
+    const principal = getRequestPrincipal(request);
+    return (
+      hasUnrestrictedCellAccess(principal) ||
+      cellBatches.ownerOf(batchId) === principal?.userId
+    );
+  };
+
   const wsCleanupById = new Map<string, () => void>();
   const wsStateById = new Map<string, TerminalWsState>();
 
@@ -1220,6 +1466,7 @@ export function createCellsRoutes(
           type: "setup.retry",
           source: audit.source,
           toolName: audit.toolName,
+          userId: audit.userId,
           metadata: { templateId: cell.templateId },
         });
 
@@ -1346,17 +1593,20 @@ export function createCellsRoutes(
 
     .get(
       "/",
-      async ({ query, set, getWorkspaceContext }) => {
+      async ({ query, set, getWorkspaceContext, request }) => {
         try {
           const { db: database } = await resolveDeps();
           const workspaceContext = await getWorkspaceContext(query.workspaceId);
+          const principal = getRequestPrincipal(request);
           const allCells = await database
             .select()
             .from(cells)
             .where(
               and(
                 eq(cells.workspaceId, workspaceContext.workspace.id),
-                ne(cells.status, "deleting")
+                ne(cells.status, "deleting"),
+                query.userId ? eq(cells.ownerId, query.userId) : undefined,
+                cellAccessFilter(database, principal, "read")
               )
             );
           return { cells: allCells.map(cellToResponse) };
@@ -1371,6 +1621,9 @@ export function createCellsRoutes(
       {
         query: t.Object({
           workspaceId: t.Optional(t.String()),
+          userId: t.Optional(
+            t.String({ description: "Only include cells owned by this user" })
+          ),
         }),
         response: {
           200: CellListResponseSchema,
@@ -1391,6 +1644,7 @@ export function createCellsRoutes(
 
         const workspaceId = workspaceContext.workspace.id;
         const { db: database } = await resolveDeps();
+        const principal = getRequestPrincipal(request);
 
         const { iterator, cleanup } = createAsyncEventIterator<CellStatusEvent>(
           (handler) => subscribeToCellStatusEvents(workspaceId, handler),
@@ -1407,7 +1661,8 @@ export function createCellsRoutes(
               .where(
                 and(
                   eq(cells.workspaceId, workspaceId),
-                  ne(cells.status, "deleting")
+                  ne(cells.status, "deleting"),
+                  cellAccessFilter(database, principal, "read")
                 )
               );
 
@@ -1422,6 +1677,7 @@ export function createCellsRoutes(
                 database,
                 event,
                 log,
+                principal,
               });
               if (!streamEvent) {
                 continue;
@@ -1444,6 +1700,69 @@ export function createCellsRoutes(
         },
       }
     )
+    .get(
+      "/workspace/:workspaceId/config/stream",
+      async ({ params, set, getWorkspaceContext, request }) => {
+        let workspaceContext: WorkspaceRuntimeContext;
+        try {
+          workspaceContext = await getWorkspaceContext(params.workspaceId);
+        } catch {
+          set.status = HTTP_STATUS.NOT_FOUND;
+          return { message: "Workspace not found" };
+        }
+
+        const workspaceId = workspaceContext.workspace.id;
+        const { db: database, describeWorkspaceConfig } = await resolveDeps();
+        const principal = getRequestPrincipal(request);
+
+        const { iterator, cleanup } =
+          createAsyncEventIterator<ConfigReloadEvent>(
+            (handler) => subscribeToConfigReloadEvents(workspaceId, handler),
+            request.signal
+          );
+
+        async function* stream() {
+          try {
+            yield sse({ event: "ready", data: { timestamp: Date.now() } });
+
+            // New subscribers start from the config as it is now.
+            const current = await describeWorkspaceConfig?.(workspaceId);
+            if (current) {
+              yield sse({
+                event: "config",
+                data: await filterVisibleConfigImpacts(
+                  database,
+                  principal,
+                  current
+                ),
+              });
+            }
+
+            for await (const event of iterator) {
+              yield sse({
+                event: "config",
+                data: await filterVisibleConfigImpacts(
+                  database,
+                  principal,
+                  event
+                ),
+              });
+            }
+          } finally {
+            cleanup();
+          }
+        }
+
+        return stream();
+      },
+      {
+        params: t.Object({ workspaceId: t.String() }),
+        response: {
+          200: t.Any(),
+          404: t.Object({ message: t.String() }),
+        },
+      }
+    )
     .get(
       "/:id",
       async ({ params, query, set, request }) => {
@@ -1479,6 +1798,7 @@ export function createCellsRoutes(
             type: "setup.logs.read",
             source: audit.source,
             toolName: audit.toolName,
+            userId: audit.userId,
             metadata: {},
           });
         }
@@ -1547,6 +1867,7 @@ export function createCellsRoutes(
             type: "service.logs.read",
             source: audit.source,
             toolName: audit.toolName,
+            userId: audit.userId,
             metadata: {
               serviceName: audit.serviceName,
               logLines: query.logLines,
@@ -1670,6 +1991,7 @@ export function createCellsRoutes(
           limit,
           types,
           cursor,
+          userId: query.userId ?? null,
         });
 
         return page satisfies CellActivityEventListResponse;
@@ -1692,6 +2014,9 @@ export function createCellsRoutes(
                 "Optional comma-separated list of activity types to include",
             })
           ),
+          userId: t.Optional(
+            t.String({ description: "Only include events by this user" })
+          ),
         }),
         response: {
           200: CellActivityEventListResponseSchema,
@@ -1703,17 +2028,24 @@ export function createCellsRoutes(
 
     .get(
       "/timings/global",
-      async ({ query }) => {
+      async ({ query, request }) => {
         const { db: database } = await resolveDeps();
         const workflow = normalizeTimingWorkflow(query.workflow);
         const limit = normalizeTimingLimit(query.limit);
 
+        // This path skips the per-cell access check, so restricted users
+        // only see timings for cells they own or that are shared with them.
         const steps = await fetchTimingSteps({
           database,
           workflow,
           runId: query.runId,
           workspaceId: query.workspaceId,
           cellId: query.cellId,
+          cellFilter: cellAccessFilter(
+            database,
+            getRequestPrincipal(request),
+            "read"
+          ),
         });
 
         return toTimingListResponse(steps, limit);
@@ -2223,6 +2555,7 @@ export function createCellsRoutes(
 
         try {
           deps.writeSetupTerminalInput(cell.id, body.data);
+          deps.recordCellActivity?.(cell.id);
           return { ok: true };
         } catch (error) {
           set.status = HTTP_STATUS.INTERNAL_ERROR;
@@ -2351,6 +2684,7 @@ export function createCellsRoutes(
         setWsState(ws.id, {
           kind: "service",
           deps,
+          cellId: row.cell.id,
           serviceId: row.service.id,
         });
 
@@ -2385,6 +2719,7 @@ export function createCellsRoutes(
               handleServiceTerminalWsInput({
                 deps: state.deps,
                 ws,
+                cellId: state.cellId,
                 serviceId: state.serviceId,
                 data: message.data,
               });
@@ -2444,6 +2779,7 @@ export function createCellsRoutes(
 
         try {
           deps.writeServiceTerminalInput(row.service.id, body.data);
+          deps.recordCellActivity?.(row.cell.id);
           return { ok: true };
         } catch (error) {
           set.status = HTTP_STATUS.INTERNAL_ERROR;
@@ -2728,6 +3064,7 @@ export function createCellsRoutes(
                 state.cell.id,
                 message.data
               );
+              state.deps.recordCellActivity?.(state.cell.id);
               return;
             case "resize": {
               state.chatTerminal.resizeChatTerminal(
@@ -2814,6 +3151,7 @@ export function createCellsRoutes(
             themeMode
           );
           chatTerminal.writeChatTerminalInput(cell.id, body.data);
+          deps.recordCellActivity?.(cell.id);
           return { ok: true };
         } catch (error) {
           set.status = HTTP_STATUS.INTERNAL_ERROR;
@@ -3182,6 +3520,7 @@ export function createCellsRoutes(
             workspacePath: cell.workspacePath,
           });
           deps.writeTerminalInput(cell.id, body.data);
+          deps.recordCellActivity?.(cell.id);
           return { ok: true };
         } catch (error) {
           set.status = HTTP_STATUS.INTERNAL_ERROR;
@@ -3321,6 +3660,7 @@ export function createCellsRoutes(
           type: "services.start",
           source: audit.source,
           toolName: audit.toolName,
+          userId: audit.userId,
           metadata: {},
         });
 
@@ -3360,6 +3700,7 @@ export function createCellsRoutes(
           type: "services.stop",
           source: audit.source,
           toolName: audit.toolName,
+          userId: audit.userId,
           metadata: {},
         });
 
@@ -3447,6 +3788,7 @@ export function createCellsRoutes(
           type: "service.start",
           source: audit.source,
           toolName: audit.toolName,
+          userId: audit.userId,
           metadata: {},
         });
 
@@ -3501,6 +3843,7 @@ export function createCellsRoutes(
           type: "service.stop",
           source: audit.source,
           toolName: audit.toolName,
+          userId: audit.userId,
           metadata: {},
         });
 
@@ -3552,6 +3895,7 @@ export function createCellsRoutes(
           type: "services.restart",
           source: audit.source,
           toolName: audit.toolName,
+          userId: audit.userId,
           metadata: {},
         });
 
@@ -3602,6 +3946,7 @@ export function createCellsRoutes(
           type: "service.restart",
           source: audit.source,
           toolName: audit.toolName,
+          userId: audit.userId,
           metadata: {
             serviceName: row.service.name,
           },
@@ -3632,9 +3977,219 @@ export function createCellsRoutes(
         },
       }
     )
+
+    .get(
+      "/:id/config",
+      async ({ params, set }) => {
+        const { previewCellConfig } = await resolveDeps();
+        if (!previewCellConfig) {
+          set.status = HTTP_STATUS.BAD_REQUEST;
+          return { message: "Config preview is unavailable" };
+        }
+
+        let impact: CellConfigImpact | null;
+        try {
+          impact = await previewCellConfig(params.id);
+        } catch (error) {
+          set.status = HTTP_STATUS.BAD_REQUEST;
+          return { message: describeConfigFailure(error) };
+        }
+        if (!impact) {
+          set.status = HTTP_STATUS.NOT_FOUND;
+          return { message: "Cell not found" };
+        }
+
+        return impact;
+      },
+      {
+        params: t.Object({ id: t.String() }),
+        response: {
+          200: CellConfigImpactSchema,
+          400: t.Object({ message: t.String() }),
+          404: t.Object({ message: t.String() }),
+        },
+      }
+    )
+
+    .post(
+      "/:id/config/apply",
+      async ({ params, set, request }) => {
+        const deps = await resolveDeps();
+        const { db: database, applyCellConfig } = deps;
+        if (!applyCellConfig) {
+          set.status = HTTP_STATUS.BAD_REQUEST;
+          return { message: "Config apply is unavailable" };
+        }
+        const cell = await loadCellById(database, params.id);
+        if (!cell) {
+          set.status = HTTP_STATUS.NOT_FOUND;
+          return { message: "Cell not found" };
+        }
+
+        let applied: CellConfigImpact | null;
+        try {
+          applied = await applyCellConfig(params.id);
+        } catch (error) {
+          set.status = HTTP_STATUS.BAD_REQUEST;
+          return { message: describeConfigFailure(error) };
+        }
+        if (!applied) {
+          set.status = HTTP_STATUS.NOT_FOUND;
+          return { message: "Cell not found" };
+        }
+
+        if (applied.services.length > 0) {
+          const audit = readHiveAuditHeaders(request);
+          await insertCellActivityEvent({
+            database,
+            cellId: params.id,
+            type: "cell.config.apply",
+            source: audit.source,
+            toolName: audit.toolName,
+            userId: audit.userId,
+            metadata: {
+              services: applied.services.map((service) => ({
+                name: service.name,
+                change: service.change,
+              })),
+            },
+          });
+        }
+
+        const rows = await fetchServiceRows(database, params.id);
+        const services = await Promise.all(
+          rows.map((row) => serializeService(deps, database, row))
+        );
+        return { applied, services };
+      },
+      {
+        params: t.Object({ id: t.String() }),
+        response: {
+          200: CellConfigApplyResponseSchema,
+          400: t.Object({ message: t.String() }),
+          404: t.Object({ message: t.String() }),
+        },
+      }
+    )
+    .post(
+      "/batches",
+      async ({ body, set, getWorkspaceContext, request }) => {
+        let manifest: ReturnType<typeof parseCellBatchManifest>;
+        try {
+          manifest = parseCellBatchManifest(body.manifest);
+        } catch (error) {
+          if (error instanceof CellBatchManifestError) {
+            set.status = HTTP_STATUS.BAD_REQUEST;
+            return { message: error.message };
+          }
+          throw error;
+        }
+
+        let workspaceContext: WorkspaceRuntimeContext;
+        try {
+          workspaceContext = await getWorkspaceContext(body.workspaceId);
+        } catch {
+          set.status = HTTP_STATUS.NOT_FOUND;
+          return { message: "Workspace not found" };
+        }
+
+        const hiveConfig = await workspaceContext.loadConfig();
+        const unknownTemplate = manifest.cells.find(
+          (entry) => !hiveConfig.templates[entry.templateId]
+        );
+        if (unknownTemplate) {
+          set.status = HTTP_STATUS.BAD_REQUEST;
+          return {
+            message: `Template not found for ${unknownTemplate.name}: ${unknownTemplate.templateId}`,
+          };
+        }
+
+        set.status = HTTP_STATUS.ACCEPTED;
+        return cellBatches.start({
+          workspaceId: workspaceContext.workspace.id,
+          manifest,
+          ...(body.concurrency ? { concurrency: body.concurrency } : {}),
+          principal: getRequestPrincipal(request),
+        });
+      },
+      {
+        body: CreateCellBatchSchema,
+        response: {
+          202: CellBatchResponseSchema,
+          400: t.Object({ message: t.String() }),
+          404: t.Object({ message: t.String() }),
+        },
+      }
+    )
+    .get(
+      "/batches/:batchId",
+      ({ params, set, request }) => {
+        const batch = cellBatches.get(params.batchId);
+        if (!(batch && canViewBatch(request, params.batchId))) {
+          set.status = HTTP_STATUS.NOT_FOUND;
+          return { message: "Batch not found" };
+        }
+        return batch;
+      },
+      {
+        params: t.Object({ batchId: t.String() }),
+        response: {
+          200: CellBatchResponseSchema,
+          404: t.Object({ message: t.String() }),
+        },
+      }
+    )
+    .get(
+      "/batches/:batchId/events",
+      ({ params, set, request }) => {
+        const batch = cellBatches.get(params.batchId);
+        if (!(batch && canViewBatch(request, params.batchId))) {
+          set.status = HTTP_STATUS.NOT_FOUND;
+          return { message: "Batch not found" } satisfies { message: string };
+        }
+
+        const { iterator, cleanup } = createAsyncEventIterator<CellBatchEvent>(
+          (listener) => cellBatches.subscribe(params.batchId, listener),
+          request.signal
+        );
+
+        async function* stream() {
+          try {
+            // Subscribing before reading the snapshot means no update is lost;
+            // item events that arrive twice are idempotent for clients.
+            const initial = cellBatches.get(params.batchId) ?? batch;
+            yield sse({ event: "snapshot", data: initial });
+            if (initial.status === "completed") {
+              yield sse({ event: "completed", data: initial });
+              return;
+            }
+
+            for await (const event of iterator) {
+              if (event.type === "item") {
+                yield sse({ event: "item", data: event.item });
+                continue;
+              }
+              yield sse({ event: "completed", data: event.batch });
+              return;
+            }
+          } finally {
+            cleanup();
+          }
+        }
+
+        return stream();
+      },
+      {
+        params: t.Object({ batchId: t.String() }),
+        response: {
+          200: t.Any(),
+          404: t.Object({ message: t.String() }),
+        },
+      }
+    )
     .post(
       "/",
-      async ({ body, set, log, getWorkspaceContext }) => {
+      async ({ body, set, log, getWorkspaceContext, request }) => {
         try {
           const deps = await resolveDeps();
           const {
@@ -3655,6 +4210,7 @@ export function createCellsRoutes(
             stopCellServices: stopCellServicesFn,
             workspaceContext,
             log,
+            principal: getRequestPrincipal(request),
           });
 
           set.status = result.status;
@@ -3674,13 +4230,19 @@ export function createCellsRoutes(
           400: t.Object({
             message: t.String(),
           }),
+          404: t.Object({
+            message: t.String(),
+          }),
+          409: t.Object({
+            message: t.String(),
+          }),
           500: ErrorResponseSchema,
         },
       }
     )
     .delete(
       "/",
-      async ({ body, set, log }) => {
+      async ({ body, set, log, request }) => {
         try {
           const deps = await resolveDeps();
           const {
@@ -3688,6 +4250,7 @@ export function createCellsRoutes(
             resolveWorkspaceContext: resolveWorkspaceCtx,
             closeAgentSession: closeSession,
             stopServicesForCell: stopCellServicesFn,
+            teardownServicesForCell: teardownCellServicesFn,
             closeTerminalSession,
             closeChatTerminalSession,
             clearSetupTerminal,
@@ -3705,7 +4268,16 @@ export function createCellsRoutes(
               status: cells.status,
             })
             .from(cells)
-            .where(inArray(cells.id, uniqueIds));
+            .where(
+              and(
+                inArray(cells.id, uniqueIds),
+                cellAccessFilter(
+                  database,
+                  getRequestPrincipal(request),
+                  "write"
+                )
+              )
+            );
 
           if (cellsToDelete.length === 0) {
             set.status = HTTP_STATUS.NOT_FOUND;
@@ -3727,6 +4299,7 @@ export function createCellsRoutes(
                 closeChatTerminalSession,
                 clearSetupTerminal,
                 stopCellServices: stopCellServicesFn,
+                teardownCellServices: teardownCellServicesFn,
                 getWorktreeService: fetchManager,
                 log,
                 recordTimingEvent: insertCellTimingEvent,
@@ -3785,6 +4358,7 @@ export function createCellsRoutes(
             resolveWorkspaceContext: resolveWorkspaceCtx,
             closeAgentSession: closeSession,
             stopServicesForCell: stopCellServicesFn,
+            teardownServicesForCell: teardownCellServicesFn,
             closeTerminalSession,
             closeChatTerminalSession,
             clearSetupTerminal,
@@ -3812,6 +4386,7 @@ export function createCellsRoutes(
             closeChatTerminalSession,
             clearSetupTerminal,
             stopCellServices: stopCellServicesFn,
+            teardownCellServices: teardownCellServicesFn,
             getWorktreeService: async () => worktreeService,
             log,
             recordTimingEvent: insertCellTimingEvent,
@@ -3863,6 +4438,7 @@ type CellCreationArgs = {
   stopCellServices: CellRouteDependencies["stopServicesForCell"];
   workspaceContext: WorkspaceRuntimeContext;
   log: LoggerLike;
+  principal?: ApiPrincipal | null;
 };
 
 async function handleCellCreationRequest(
@@ -3886,7 +4462,7 @@ async function handleCellCreationRequest(
     configDefaultMode: hiveConfig.opencode?.defaultMode,
   });
   const worktreeStartPoint = resolveWorktreeStartPoint(rawBody);
-  const body: Static<typeof CreateCellSchema> = {
+  let body: Static<typeof CreateCellSchema> = {
     ...rawBody,
     startMode: normalizeStartMode(rawBody.startMode) ?? defaultStartMode,
     spawnFromMode: worktreeStartPoint.mode,
@@ -3902,12 +4478,54 @@ async function handleCellCreationRequest(
     };
   }
 
+  const attachmentError = validatePromptAttachments(body.attachments ?? []);
+  if (attachmentError) {
+    return {
+      status: HTTP_STATUS.BAD_REQUEST,
+      payload: { message: attachmentError },
+    };
+  }
+
+  let forkSource: CellForkSource | null = null;
+  if (worktreeStartPoint.mode === "cell") {
+    const resolved = await resolveCellForkSource({
+      database,
+      sourceCellId: worktreeStartPoint.value,
+      workspaceId: workspaceContext.workspace.id,
+      hiveConfig,
+      principal: args.principal ?? null,
+    });
+    if ("payload" in resolved) {
+      return resolved;
+    }
+    forkSource = resolved;
+    body = inheritForkOverrides(body, forkSource);
+  }
+
+  let parameterizedTemplate: Template;
+  try {
+    const parameters = resolveTemplateParameterValues(
+      template,
+      body.parameters
+    );
+    parameterizedTemplate = applyTemplateParameters(template, parameters);
+    body = { ...body, parameters };
+  } catch (error) {
+    if (!(error instanceof TemplateParameterError)) {
+      throw error;
+    }
+    return {
+      status: HTTP_STATUS.BAD_REQUEST,
+      payload: { message: error.message },
+    };
+  }
+
   const worktreeService = toAsyncWorktreeManager(
     await workspaceContext.createWorktreeManager()
   );
   const context = createProvisionContext({
     body,
-    template,
+    template: parameterizedTemplate,
     database,
     ensureSession,
     sendAgentMessage: dispatchAgentMessage,
@@ -3916,6 +4534,8 @@ async function handleCellCreationRequest(
     worktreeService,
     workspace: workspaceContext.workspace,
     log,
+    forkSource,
+    ownerId: args.principal?.userId ?? null,
   });
 
   const createRequestStartedAt = new Date();
@@ -4017,6 +4637,8 @@ type ProvisionContext = {
   worktreeService: AsyncWorktreeManager;
   workspace: WorkspaceRecord;
   log: LoggerLike;
+  forkSource?: CellForkSource | null;
+  ownerId?: string | null;
   state: CellProvisionState;
 };
 
@@ -4075,6 +4697,8 @@ function createProvisionContext(args: {
   worktreeService: AsyncWorktreeManager;
   workspace: WorkspaceRecord;
   log: LoggerLike;
+  forkSource?: CellForkSource | null;
+  ownerId?: string | null;
 }): ProvisionContext {
   return {
     ...args,
@@ -4111,9 +4735,16 @@ async function createExistingProvisionContext(args: {
     await args.workspaceContext.createWorktreeManager()
   );
 
+  // Retries re-apply the values the cell was created with so setup, env and
+  // service commands come out the same as the first attempt.
+  const parameters = resolveTemplateParameterValues(
+    args.template,
+    args.body.parameters
+  );
+
   return {
     body: args.body,
-    template: args.template,
+    template: applyTemplateParameters(args.template, parameters),
     database: args.database,
     ensureSession: args.ensureSession,
     sendAgentMessage: args.sendAgentMessage,
@@ -4171,6 +4802,7 @@ async function createCellRecord(
     createdAt: timestamp,
     status: "spawning",
     lastSetupError: null,
+    ownerId: context.ownerId ?? null,
   };
 
   const insertCellStartedAt = Date.now();
@@ -4197,6 +4829,11 @@ async function createCellRecord(
       startedAt: null,
       finishedAt: null,
       attemptCount: 0,
+      initialPrompt: body.initialPrompt ?? null,
+      initialPromptAttachments: body.attachments?.length
+        ? body.attachments
+        : null,
+      templateParameters: body.parameters ?? null,
     })
     .returning();
   insertProvisioningStateDurationMs = Date.now() - insertProvisioningStartedAt;
@@ -4228,12 +4865,31 @@ async function ensureCellWorktree(
     return;
   }
 
-  let worktree: { path: string; branch: string; baseCommit: string };
+  // Forks start from a snapshot of the source cell so its uncommitted changes
+  // come along; the snapshot doubles as a restore point on the source.
+  const forkSnapshot = context.forkSource
+    ? await createCellSnapshot({
+        database,
+        cell: context.forkSource.cell,
+        patterns: context.forkSource.patterns,
+        name: `Fork into "${body.name}"`,
+      })
+    : null;
+  const startPoint: WorktreeStartPoint =
+    context.forkSource && forkSnapshot
+      ? {
+          mode: "cell",
+          value: context.forkSource.cell.id,
+          commit: forkSnapshot.headCommit,
+        }
+      : resolveWorktreeStartPoint(body);
+
+  let worktree: WorktreeLocation;
   try {
     worktree = await worktreeService.createWorktree(state.cellId, {
       templateId: body.templateId,
       force: true,
-      startPoint: resolveWorktreeStartPoint(body),
+      startPoint,
       onTimingEvent: (event) => {
         onTimingEvent?.({
           ...event,
@@ -4256,26 +4912,35 @@ async function ensureCellWorktree(
     throw error;
   }
 
+  if (forkSnapshot) {
+    await applyCellSnapshot({
+      workspacePath: worktree.path,
+      snapshot: forkSnapshot,
+    });
+  }
+
   state.worktreeCreated = true;
   state.workspacePath = worktree.path;
   state.branchName = worktree.branch;
   state.baseCommit = worktree.baseCommit;
 
+  const worktreeFields = {
+    workspacePath: worktree.path,
+    branchName: worktree.branch,
+    baseCommit: worktree.baseCommit,
+    startPointMode: worktree.startPoint?.mode ?? null,
+    startPointRef: worktree.startPoint?.ref ?? null,
+  };
+
   await database
     .update(cells)
-    .set({
-      workspacePath: worktree.path,
-      branchName: worktree.branch,
-      baseCommit: worktree.baseCommit,
-    })
+    .set(worktreeFields)
     .where(eq(cells.id, state.cellId));
 
   if (state.createdCell) {
     state.createdCell = {
       ...state.createdCell,
-      workspacePath: worktree.path,
-      branchName: worktree.branch,
-      baseCommit: worktree.baseCommit,
+      ...worktreeFields,
     };
   }
 
@@ -4521,8 +5186,15 @@ async function finalizeCellProvisioning(
 
   state.servicesStarted = true;
 
-  const sessionOptions = buildAgentSessionOptions(body);
   const existingSessionId = state.createdCell?.opencodeSessionId ?? null;
+  const forkFromSessionId =
+    body.forkSession && !existingSessionId
+      ? (context.forkSource?.cell.opencodeSessionId ?? null)
+      : null;
+  const sessionOptions = {
+    ...buildAgentSessionOptions(body),
+    ...(forkFromSessionId ? { forkFromSessionId } : {}),
+  };
   const session = await runPhase("ensure_agent_session", async () =>
     ensureSession(
       state.cellId,
@@ -4540,23 +5212,29 @@ async function finalizeCellProvisioning(
   const initialPrompt = buildInitialPromptContent({
     title: body.name,
     description: body.description,
+    initialPrompt: body.initialPrompt,
   });
   const shouldSendInitialPrompt = shouldSendInitialPromptForAttempt({
-    attempt,
     initialPrompt,
-    existingSessionId,
+    sentAt: state.provisioningState?.initialPromptSentAt ?? null,
   });
   if (shouldSendInitialPrompt && initialPrompt) {
-    await runPhase("send_initial_prompt", () => {
+    await runPhase("send_initial_prompt", async () => {
+      const attachmentPaths = await writePromptAttachments(
+        state.workspacePath ?? createdCell.workspacePath,
+        body.attachments ?? []
+      );
       dispatchInitialPromptInBackground({
         sendAgentMessage: dispatchAgentMessage,
         sessionId: session.id,
-        content: initialPrompt,
+        content: appendAttachmentList(initialPrompt, attachmentPaths),
         timeoutMs: INITIAL_PROMPT_BACKGROUND_WARN_TIMEOUT_MS,
         cellId: state.cellId,
         log: context.log,
+        // Recorded as soon as the agent has the prompt, not when its turn
+        // ends; a prompt that never arrived is resent when setup is retried.
+        onDelivered: () => markInitialPromptSent(context),
       });
-      return Promise.resolve();
     });
   }
 
@@ -4888,29 +5566,89 @@ function resolveProvisioningParams(
     ...(provisioningState?.startMode != null
       ? { startMode: normalizeStartMode(provisioningState.startMode) }
       : {}),
+    ...(provisioningState?.initialPrompt != null
+      ? { initialPrompt: provisioningState.initialPrompt }
+      : {}),
+    ...(provisioningState?.initialPromptAttachments?.length
+      ? { attachments: provisioningState.initialPromptAttachments }
+      : {}),
+    ...(provisioningState?.templateParameters != null
+      ? { parameters: provisioningState.templateParameters }
+      : {}),
   };
 }
 
+/**
+ * Resolves once a cell finishes provisioning and rejects when setup fails or
+ * the cell goes away first. Subscribes before reading the row so a status
+ * change between the two is not missed.
+ */
+function waitForCellProvisioning(args: {
+  database: DatabaseClient;
+  cellId: string;
+  workspaceId: string;
+}): Promise<void> {
+  return new Promise<void>((resolve, reject) => {
+    let settled = false;
+    let unsubscribe: (() => void) | null = null;
+
+    const settle = (
+      status: CellStatus | null,
+      lastSetupError?: string | null
+    ) => {
+      if (settled || status === "spawning" || status === "pending") {
+        return;
+      }
+      settled = true;
+      unsubscribe?.();
+      if (status === "ready") {
+        resolve();
+        return;
+      }
+      if (status === "error") {
+        const [summary] = (lastSetupError ?? "").split("\n");
+        reject(new Error(summary?.trim() || "Cell setup failed"));
+        return;
+      }
+      reject(new Error("Cell was deleted before it became ready"));
+    };
+
+    unsubscribe = subscribeToCellStatusEvents(args.workspaceId, (event) => {
+      if (event.cellId === args.cellId) {
+        settle(event.status, event.lastSetupError);
+      }
+    });
+
+    loadCellById(args.database, args.cellId).then(
+      (cell) => settle(cell?.status ?? null, cell?.lastSetupError),
+      (error) => {
+        if (!settled) {
+          settled = true;
+          unsubscribe?.();
+          reject(error);
+        }
+      }
+    );
+  });
+}
+
 function shouldSendInitialPromptForAttempt(args: {
-  attempt: number | null;
   initialPrompt?: string;
-  existingSessionId: string | null;
+  sentAt?: Date | null;
 }): boolean {
-  if (!args.initialPrompt) {
-    return false;
-  }
-
-  if (args.attempt === 1) {
-    return true;
-  }
-
-  return !args.existingSessionId;
+  return Boolean(args.initialPrompt) && !args.sentAt;
 }
 
 function buildInitialPromptContent(args: {
   title?: string | null;
   description?: string | null;
+  initialPrompt?: string | null;
 }): string | undefined {
+  const initialPrompt = args.initialPrompt?.trim();
+  if (initialPrompt) {
+    return initialPrompt;
+  }
+
   const title = args.title?.trim();
   const description = args.description?.trim();
 
@@ -4925,6 +5663,21 @@ function buildInitialPromptContent(args: {
   return `${title}\n\n${description}`;
 }
 
+async function markInitialPromptSent(context: ProvisionContext) {
+  const sentAt = new Date();
+  await context.database
+    .update(cellProvisioningStates)
+    .set({ initialPromptSentAt: sentAt })
+    .where(eq(cellProvisioningStates.cellId, context.state.cellId));
+
+  if (context.state.provisioningState) {
+    context.state.provisioningState = {
+      ...context.state.provisioningState,
+      initialPromptSentAt: sentAt,
+    };
+  }
+}
+
 function buildAgentSessionOptions(body: Static<typeof CreateCellSchema>) {
   return {
     ...(body.modelId ? { modelId: body.modelId } : {}),
@@ -4941,9 +5694,29 @@ function dispatchInitialPromptInBackground(args: {
   timeoutMs: number;
   cellId: string;
   log: LoggerLike;
+  onDelivered?: () => Promise<void>;
 }): void {
+  let delivered = false;
+  const recordDelivery = () => {
+    if (delivered) {
+      return;
+    }
+    delivered = true;
+    args.onDelivered?.().catch((error: unknown) => {
+      args.log.warn(
+        {
+          cellId: args.cellId,
+          error: error instanceof Error ? error.message : String(error),
+        },
+        "Failed to record initial prompt delivery"
+      );
+    });
+  };
+
   const promptStartedAt = Date.now();
-  const promptDispatch = args.sendAgentMessage(args.sessionId, args.content);
+  const promptDispatch = args.sendAgentMessage(args.sessionId, args.content, {
+    onDelivered: recordDelivery,
+  });
   let timeoutHandle: ReturnType<typeof setTimeout> | null = setTimeout(() => {
     args.log.warn(
       {
@@ -4962,6 +5735,7 @@ function dispatchInitialPromptInBackground(args: {
         clearTimeout(timeoutHandle);
         timeoutHandle = null;
       }
+      recordDelivery();
     },
     (error) => {
       if (timeoutHandle) {
@@ -5109,6 +5883,7 @@ const resumeDeletingCells = async (deps: CellRouteDependencies) => {
         closeChatTerminalSession: deps.closeChatTerminalSession,
         clearSetupTerminal: deps.clearSetupTerminal,
         stopCellServices: deps.stopServicesForCell,
+        teardownCellServices: deps.teardownServicesForCell,
         getWorktreeService: fetchManager,
         log: backgroundProvisioningLogger,
         recordTimingEvent: insertCellTimingEvent,
@@ -5153,6 +5928,18 @@ const unwrapSupervisorError = (error: unknown): unknown => {
   return error;
 };
 
+const describeConfigFailure = (error: unknown): string => {
+  const cause = unwrapSupervisorError(error);
+  if (
+    cause &&
+    typeof cause === "object" &&
+    (cause as { _tag?: string })._tag === "HiveConfigError"
+  ) {
+    return describeConfigFailure((cause as { cause: unknown }).cause);
+  }
+  return cause instanceof Error ? cause.message : String(cause);
+};
+
 const reviveTemplateSetupError = (
   error: unknown
 ): TemplateSetupError | null => {
@@ -5431,10 +6218,29 @@ function shouldEmitCellRemovalEvent(
   return cell.status === "deleting";
 }
 
+async function filterVisibleConfigImpacts(
+  database: DatabaseClient,
+  principal: ApiPrincipal | null,
+  event: ConfigReloadEvent
+): Promise<ConfigReloadEvent> {
+  const visible: CellConfigImpact[] = [];
+  for (const impact of event.cells) {
+    const cell = await loadCellById(database, impact.cellId);
+    if (
+      cell &&
+      (await canAccessCell({ database, principal, cell, level: "read" }))
+    ) {
+      visible.push(impact);
+    }
+  }
+  return { ...event, cells: visible };
+}
+
 async function resolveWorkspaceCellStreamEvent(args: {
   database: DatabaseClient;
   event: CellStatusEvent;
   log: LoggerLike;
+  principal: ApiPrincipal | null;
 }): Promise<
   | { event: "cell"; data: ReturnType<typeof cellToResponse> }
   | { event: "cell_removed"; data: { id: string } }
@@ -5453,6 +6259,16 @@ async function resolveWorkspaceCellStreamEvent(args: {
       return null;
     }
 
+    const visible = await canAccessCell({
+      database: args.database,
+      principal: args.principal,
+      cell,
+      level: "read",
+    });
+    if (!visible) {
+      return null;
+    }
+
     return {
       event: "cell",
       data: cellToResponse(cell),
@@ -5793,6 +6609,11 @@ function shouldPersistResourceHistoryPoint(
   return true;
 }
 
+/** Portable across SQLite and Postgres, which spell `max(a, b)` differently. */
+function greatestOf(column: AnyColumn, value: number) {
+  return sql`case when ${column} > ${value} then ${column} else ${value} end`;
+}
+
 async function persistCellResourceHistoryPoint(
   database: DatabaseClient,
   summary: CellResourceSummaryResponse
@@ -5848,8 +6669,14 @@ async function persistCellResourceHistoryPoint(
         sampleCount: sql`${cellResourceRollups.sampleCount} + 1`,
         sumActiveCpuPercent: sql`${cellResourceRollups.sumActiveCpuPercent} + ${summary.activeCpuPercent}`,
         sumActiveRssBytes: sql`${cellResourceRollups.sumActiveRssBytes} + ${summary.activeRssBytes}`,
-        peakActiveCpuPercent: sql`max(${cellResourceRollups.peakActiveCpuPercent}, ${summary.activeCpuPercent})`,
-        peakActiveRssBytes: sql`max(${cellResourceRollups.peakActiveRssBytes}, ${summary.activeRssBytes})`,
+        peakActiveCpuPercent: greatestOf(
+          cellResourceRollups.peakActiveCpuPercent,
+          summary.activeCpuPercent
+        ),
+        peakActiveRssBytes: greatestOf(
+          cellResourceRollups.peakActiveRssBytes,
+          summary.activeRssBytes
+        ),
         updatedAt: createdAt,
       },
     });
@@ -0,0 +1,185 @@
+import { Elysia, t } from "elysia";
+import { hasUnrestrictedCellAccess } from "../auth/access";
+import { getRequestPrincipal } from "../auth/plugin";
+import {
+  DatabaseService,
+  type DatabaseService as DatabaseServiceType,
+} from "../db";
+import {
+  SetWorkspaceSecretBodySchema,
+  WorkspaceSecretListResponseSchema,
+  WorkspaceSecretQuerySchema,
+  WorkspaceSecretSchema,
+} from "../schema/api";
+import type { WorkspaceSecret } from "../schema/workspace-secrets";
+import {
+  createWorkspaceSecretStore,
+  SECRET_NAME_PATTERN,
+  type WorkspaceSecretStore,
+} from "../services/workspace-secrets";
+import {
+  type ResolveWorkspaceContext,
+  resolveWorkspaceContext as defaultResolveWorkspaceContext,
+  WorkspaceContextError,
+} from "../workspaces/context";
+
+const HTTP_STATUS = {
+  BAD_REQUEST: 400,
+  FORBIDDEN: 403,
+  NOT_FOUND: 404,
+  INTERNAL_ERROR: 500,
+} as const;
+
+const ErrorSchema = t.Object({ message: t.String() });
+const SecretParamsSchema = t.Object({ name: t.String() });
+
+type DatabaseClient = DatabaseServiceType["db"];
+
+export type SecretRouteDependencies = {
+  db?: DatabaseClient;
+  resolveWorkspaceContext?: ResolveWorkspaceContext;
+  store?: WorkspaceSecretStore;
+};
+
+class SecretRouteError extends Error {
+  readonly status: number;
+
+  constructor(status: number, message: string) {
+    super(message);
+    this.name = "SecretRouteError";
+    this.status = status;
+  }
+}
+
+const handleRouteFailure = (
+  set: { status?: number | string },
+  error: unknown,
+  fallback: string
+) => {
+  if (error instanceof SecretRouteError) {
+    set.status = error.status;
+    return { message: error.message };
+  }
+  if (error instanceof WorkspaceContextError) {
+    set.status = HTTP_STATUS.NOT_FOUND;
+    return { message: error.message };
+  }
+  set.status = HTTP_STATUS.INTERNAL_ERROR;
+  return { message: error instanceof Error ? error.message : fallback };
+};
+
+/** Values never leave the server; responses only carry names and dates. */
+const toSecretResponse = (secret: WorkspaceSecret) => ({
+  name: secret.name,
+  workspaceId: secret.workspaceId,
+  createdAt: secret.createdAt.toISOString(),
+  updatedAt: secret.updatedAt.toISOString(),
+});
+
+/**
+ * Secrets end up in every cell's service environment, so only principals
+ * that are not limited to their own cells may manage them.
+ */
+function assertSecretAccess(request: Request) {
+  if (!hasUnrestrictedCellAccess(getRequestPrincipal(request))) {
+    throw new SecretRouteError(
+      HTTP_STATUS.FORBIDDEN,
+      "Managing secrets requires an admin token"
+    );
+  }
+}
+
+function validateSecretName(name: string): string {
+  if (!SECRET_NAME_PATTERN.test(name)) {
+    throw new SecretRouteError(
+      HTTP_STATUS.BAD_REQUEST,
+      `Invalid secret name "${name}"; use letters, digits and underscores`
+    );
+  }
+  return name;
+}
+
+export function createSecretRoutes({
+  db = DatabaseService.db,
+  resolveWorkspaceContext = defaultResolveWorkspaceContext,
+  store = createWorkspaceSecretStore({ db }),
+}: SecretRouteDependencies = {}) {
+  return new Elysia({ prefix: "/api/secrets" })
+    .get(
+      "/",
+      async ({ query, set, request }) => {
+        try {
+          assertSecretAccess(request);
+          const secrets = await store.list(query.workspaceId);
+          return { secrets: secrets.map(toSecretResponse) };
+        } catch (error) {
+          return handleRouteFailure(set, error, "Failed to list secrets");
+        }
+      },
+      {
+        query: WorkspaceSecretQuerySchema,
+        response: {
+          200: WorkspaceSecretListResponseSchema,
+          403: ErrorSchema,
+          500: ErrorSchema,
+        },
+      }
+    )
+    .put(
+      "/:name",
+      async ({ params, body, set, request }) => {
+        try {
+          assertSecretAccess(request);
+          const name = validateSecretName(params.name);
+          const { workspace } = await resolveWorkspaceContext(
+            body.workspaceId
+          );
+          const secret = await store.set(workspace.id, name, body.value);
+          return toSecretResponse(secret);
+        } catch (error) {
+          return handleRouteFailure(set, error, "Failed to save secret");
+        }
+      },
+      {
+        params: SecretParamsSchema,
+        body: SetWorkspaceSecretBodySchema,
+        response: {
+          200: WorkspaceSecretSchema,
+          400: ErrorSchema,
+          403: ErrorSchema,
+          404: ErrorSchema,
+          500: ErrorSchema,
+        },
+      }
+    )
+    .delete(
+      "/:name",
+      async ({ params, query, set, request }) => {
+        try {
+          assertSecretAccess(request);
+          const removed = await store.remove(query.workspaceId, params.name);
+          if (!removed) {
+            throw new SecretRouteError(
+              HTTP_STATUS.NOT_FOUND,
+              `Secret "${params.name}" not found`
+            );
+          }
+          return { message: "Secret deleted" };
+        } catch (error) {
+          return handleRouteFailure(set, error, "Failed to delete secret");
+        }
+      },
+      {
+        params: SecretParamsSchema,
+        query: WorkspaceSecretQuerySchema,
+        response: {
+          200: t.Object({ message: t.String() }),
+          403: ErrorSchema,
+          404: ErrorSchema,
+          500: ErrorSchema,
+        },
+      }
+    );
+}
+
+export const secretRoutes = createSecretRoutes();
@@ -6,8 +6,13 @@ import {
   type EffectiveOpencodeDefaults,
   loadEffectiveOpencodeDefaults,
 } from "../agents/opencode-config";
+import { findConfigPath } from "../config/files";
 import { loadConfig } from "../config/loader";
 import type { Template } from "../config/schema";
+import {
+  getTemplateFieldOrigins,
+  type TemplateFieldOrigins,
+} from "../config/template-inheritance";
 import {
   TemplateListResponseSchema,
   TemplateResponseSchema,
@@ -40,7 +45,6 @@ const LEADING_GLOB_PREFIX_REGEX = /^\*\*\//;
 const TRAILING_SEPARATOR_REGEX = /\/+$/;
 const WORKSPACE_CONFIG_CACHE_TTL_MS = 60_000;
 const OPENCODE_DEFAULTS_CACHE_TTL_MS = 60_000;
-const HIVE_CONFIG_FILENAME = "hive.config.json";
 
 const workspaceConfigCache = new Map<
   string,
@@ -178,7 +182,8 @@ type TemplatesRouteResponse<T> = {
 function templateToResponse(
   id: string,
   template: Template,
-  includeDirectories?: string[]
+  includeDirectories?: string[],
+  fieldOrigins?: TemplateFieldOrigins
 ): TemplateResponse {
   const response: TemplateResponse = {
     id,
@@ -190,6 +195,9 @@ function templateToResponse(
   if (includeDirectories && includeDirectories.length > 0) {
     response.includeDirectories = includeDirectories;
   }
+  if (fieldOrigins) {
+    response.fieldOrigins = fieldOrigins;
+  }
 
   return response;
 }
@@ -241,7 +249,11 @@ const readWorkspaceConfigMtimeMs = async (
   workspacePath: string
 ): Promise<number | null> => {
   try {
-    const stats = await stat(join(workspacePath, HIVE_CONFIG_FILENAME));
+    const configPath = findConfigPath(workspacePath);
+    if (!configPath) {
+      return null;
+    }
+    const stats = await stat(configPath);
     return stats.mtimeMs;
   } catch {
     return null;
@@ -406,7 +418,12 @@ const loadTemplateDetail = async (
     template
   );
 
-  return templateToResponse(templateId, template, includeDirectories);
+  return templateToResponse(
+    templateId,
+    template,
+    includeDirectories,
+    getTemplateFieldOrigins(templateId, template)
+  );
 };
 
 const matchTemplatesResult = async <T>(
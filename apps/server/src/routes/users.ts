@@ -0,0 +1,92 @@
+import { Elysia, t } from "elysia";
+import {
+  createUser,
+  deleteUser,
+  listUsers,
+  UserAccountError,
+} from "../auth/users";
+import {
+  DatabaseService,
+  type DatabaseService as DatabaseServiceType,
+} from "../db";
+import {
+  CreateUserBodySchema,
+  UserListResponseSchema,
+  UserSchema,
+} from "../schema/api";
+import type { User } from "../schema/users";
+
+const HTTP_STATUS = {
+  CREATED: 201,
+  BAD_REQUEST: 400,
+  NOT_FOUND: 404,
+} as const;
+
+const MessageSchema = t.Object({ message: t.String() });
+
+type DatabaseClient = DatabaseServiceType["db"];
+
+export type UserRouteDependencies = {
+  db?: DatabaseClient;
+};
+
+export const toUserResponse = (user: User) => ({
+  id: user.id,
+  username: user.username,
+  displayName: user.displayName,
+  createdAt: user.createdAt.toISOString(),
+});
+
+export function createUserRoutes({
+  db = DatabaseService.db,
+}: UserRouteDependencies = {}) {
+  return new Elysia({ prefix: "/api/users" })
+    .get(
+      "/",
+      async () => ({ users: (await listUsers(db)).map(toUserResponse) }),
+      { response: { 200: UserListResponseSchema } }
+    )
+    .post(
+      "/",
+      async ({ body, set }) => {
+        try {
+          const user = await createUser(db, body);
+          set.status = HTTP_STATUS.CREATED;
+          return toUserResponse(user);
+        } catch (error) {
+          if (error instanceof UserAccountError) {
+            set.status = HTTP_STATUS.BAD_REQUEST;
+            return { message: error.message };
+          }
+          throw error;
+        }
+      },
+      {
+        body: CreateUserBodySchema,
+        response: {
+          201: UserSchema,
+          400: MessageSchema,
+        },
+      }
+    )
+    .delete(
+      "/:id",
+      async ({ params, set }) => {
+        const deleted = await deleteUser(db, params.id);
+        if (!deleted) {
+          set.status = HTTP_STATUS.NOT_FOUND;
+          return { message: "User not found" };
+        }
+        return toUserResponse(deleted);
+      },
+      {
+        params: t.Object({ id: t.String() }),
+        response: {
+          200: UserSchema,
+          404: MessageSchema,
+        },
+      }
+    );
+}
+
+export const userRoutes = createUserRoutes();
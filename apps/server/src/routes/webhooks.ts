@@ -0,0 +1,337 @@
+import { desc, eq } from "drizzle-orm";
+import { Elysia, t } from "elysia";
+import { hasUnrestrictedCellAccess } from "../auth/access";
+import { getRequestPrincipal } from "../auth/plugin";
+import {
+  DatabaseService,
+  type DatabaseService as DatabaseServiceType,
+} from "../db";
+import {
+  CreateWebhookBodySchema,
+  CreateWebhookResponseSchema,
+  UpdateWebhookBodySchema,
+  WebhookDeliveryListQuerySchema,
+  WebhookDeliveryListResponseSchema,
+  WebhookDeliverySchema,
+  WebhookListQuerySchema,
+  WebhookListResponseSchema,
+  WebhookSchema,
+} from "../schema/api";
+import {
+  WEBHOOK_EVENT_TYPES,
+  type WebhookDelivery,
+  type WorkspaceWebhook,
+  webhookDeliveries,
+  workspaceWebhooks,
+} from "../schema/webhooks";
+import {
+  generateWebhookSecret,
+  type WebhookDispatcher,
+  webhookDispatcher as defaultWebhookDispatcher,
+} from "../services/webhooks";
+import {
+  type ResolveWorkspaceContext,
+  resolveWorkspaceContext as defaultResolveWorkspaceContext,
+  WorkspaceContextError,
+} from "../workspaces/context";
+
+const HTTP_STATUS = {
+  CREATED: 201,
+  BAD_REQUEST: 400,
+  FORBIDDEN: 403,
+  NOT_FOUND: 404,
+  INTERNAL_ERROR: 500,
+} as const;
+
+const DEFAULT_DELIVERY_LIMIT = 20;
+
+const ErrorSchema = t.Object({ message: t.String() });
+const WebhookParamsSchema = t.Object({ id: t.String() });
+
+type DatabaseClient = DatabaseServiceType["db"];
+
+export type WebhookRouteDependencies = {
+  db?: DatabaseClient;
+  resolveWorkspaceContext?: ResolveWorkspaceContext;
+  dispatcher?: Pick<WebhookDispatcher, "ping">;
+};
+
+class WebhookRouteError extends Error {
+  readonly status: number;
+
+  constructor(status: number, message: string) {
+    super(message);
+    this.name = "WebhookRouteError";
+    this.status = status;
+  }
+}
+
+const handleRouteFailure = (
+  set: { status?: number | string },
+  error: unknown,
+  fallback: string
+) => {
+  if (error instanceof WebhookRouteError) {
+    set.status = error.status;
+    return { message: error.message };
+  }
+  if (error instanceof WorkspaceContextError) {
+    set.status = HTTP_STATUS.NOT_FOUND;
+    return { message: error.message };
+  }
+  set.status = HTTP_STATUS.INTERNAL_ERROR;
+  return { message: error instanceof Error ? error.message : fallback };
+};
+
+export const toWebhookResponse = (webhook: WorkspaceWebhook) => ({
+  id: webhook.id,
+  workspaceId: webhook.workspaceId,
+  url: webhook.url,
+  events: webhook.events,
+  enabled: webhook.enabled,
+  createdAt: webhook.createdAt.toISOString(),
+  updatedAt: webhook.updatedAt.toISOString(),
+});
+
+const toDeliveryResponse = (delivery: WebhookDelivery) => ({
+  id: delivery.id,
+  webhookId: delivery.webhookId,
+  event: delivery.event,
+  status: delivery.status,
+  attempts: delivery.attempts,
+  responseStatus: delivery.responseStatus,
+  error: delivery.error,
+  payload: delivery.payload,
+  createdAt: delivery.createdAt.toISOString(),
+  lastAttemptAt: delivery.lastAttemptAt?.toISOString() ?? null,
+  nextAttemptAt: delivery.nextAttemptAt?.toISOString() ?? null,
+});
+
+/**
+ * Webhooks see every cell in a workspace, so only principals that are not
+ * limited to their own cells may manage or inspect them.
+ */
+function assertWebhookAccess(request: Request) {
+  if (!hasUnrestrictedCellAccess(getRequestPrincipal(request))) {
+    throw new WebhookRouteError(
+      HTTP_STATUS.FORBIDDEN,
+      "Managing webhooks requires an admin token"
+    );
+  }
+}
+
+function validateWebhookUrl(url: string): string {
+  let parsed: URL;
+  try {
+    parsed = new URL(url);
+  } catch {
+    throw new WebhookRouteError(
+      HTTP_STATUS.BAD_REQUEST,
+      `Invalid webhook URL: ${url}`
+    );
+  }
+  if (parsed.protocol !== "http:" && parsed.protocol !== "https:") {
+    throw new WebhookRouteError(
+      HTTP_STATUS.BAD_REQUEST,
+      "Webhook URL must use http or https"
+    );
+  }
+  return parsed.toString();
+}
+
+async function loadWebhook(
+  database: DatabaseClient,
+  webhookId: string
+): Promise<WorkspaceWebhook> {
+  const webhook = await database.query.workspaceWebhooks.findFirst({
+    where: eq(workspaceWebhooks.id, webhookId),
+  });
+  if (!webhook) {
+    throw new WebhookRouteError(HTTP_STATUS.NOT_FOUND, "Webhook not found");
+  }
+  return webhook;
+}
+
+export function createWebhookRoutes({
+  db = DatabaseService.db,
+  resolveWorkspaceContext = defaultResolveWorkspaceContext,
+  dispatcher = defaultWebhookDispatcher,
+}: WebhookRouteDependencies = {}) {
+  return new Elysia({ prefix: "/api/webhooks" })
+    .get(
+      "/",
+      async ({ query, set, request }) => {
+        try {
+          assertWebhookAccess(request);
+          const webhooks = await db
+            .select()
+            .from(workspaceWebhooks)
+            .where(eq(workspaceWebhooks.workspaceId, query.workspaceId))
+            .orderBy(workspaceWebhooks.createdAt);
+          return { webhooks: webhooks.map(toWebhookResponse) };
+        } catch (error) {
+          return handleRouteFailure(set, error, "Failed to list webhooks");
+        }
+      },
+      {
+        query: WebhookListQuerySchema,
+        response: {
+          200: WebhookListResponseSchema,
+          403: ErrorSchema,
+          500: ErrorSchema,
+        },
+      }
+    )
+    .post(
+      "/",
+      async ({ body, set, request }) => {
+        try {
+          assertWebhookAccess(request);
+          const { workspace } = await resolveWorkspaceContext(
+            body.workspaceId
+          );
+          const secret = body.secret ?? generateWebhookSecret();
+          const now = new Date();
+          const [webhook] = await db
+            .insert(workspaceWebhooks)
+            .values({
+              id: crypto.randomUUID(),
+              workspaceId: workspace.id,
+              url: validateWebhookUrl(body.url),
+              secret,
+              events: body.events ?? [...WEBHOOK_EVENT_TYPES],
+              enabled: body.enabled ?? true,
+              createdAt: now,
+              updatedAt: now,
+            })
+            .returning();
+          if (!webhook) {
+            throw new Error("Failed to create webhook");
+          }
+
+          set.status = HTTP_STATUS.CREATED;
+          return { secret, webhook: toWebhookResponse(webhook) };
+        } catch (error) {
+          return handleRouteFailure(set, error, "Failed to create webhook");
+        }
+      },
+      {
+        body: CreateWebhookBodySchema,
+        response: {
+          201: CreateWebhookResponseSchema,
+          400: ErrorSchema,
+          403: ErrorSchema,
+          404: ErrorSchema,
+          500: ErrorSchema,
+        },
+      }
+    )
+    .patch(
+      "/:id",
+      async ({ params, body, set, request }) => {
+        try {
+          assertWebhookAccess(request);
+          await loadWebhook(db, params.id);
+          await db
+            .update(workspaceWebhooks)
+            .set({
+              ...(body.url ? { url: validateWebhookUrl(body.url) } : {}),
+              ...(body.secret ? { secret: body.secret } : {}),
+              ...(body.events ? { events: body.events } : {}),
+              ...(body.enabled === undefined ? {} : { enabled: body.enabled }),
+              updatedAt: new Date(),
+            })
+            .where(eq(workspaceWebhooks.id, params.id));
+          return toWebhookResponse(await loadWebhook(db, params.id));
+        } catch (error) {
+          return handleRouteFailure(set, error, "Failed to update webhook");
+        }
+      },
+      {
+        params: WebhookParamsSchema,
+        body: UpdateWebhookBodySchema,
+        response: {
+          200: WebhookSchema,
+          400: ErrorSchema,
+          403: ErrorSchema,
+          404: ErrorSchema,
+          500: ErrorSchema,
+        },
+      }
+    )
+    .delete(
+      "/:id",
+      async ({ params, set, request }) => {
+        try {
+          assertWebhookAccess(request);
+          await loadWebhook(db, params.id);
+          await db
+            .delete(workspaceWebhooks)
+            .where(eq(workspaceWebhooks.id, params.id));
+          return { message: "Webhook deleted" };
+        } catch (error) {
+          return handleRouteFailure(set, error, "Failed to delete webhook");
+        }
+      },
+      {
+        params: WebhookParamsSchema,
+        response: {
+          200: t.Object({ message: t.String() }),
+          403: ErrorSchema,
+          404: ErrorSchema,
+          500: ErrorSchema,
+        },
+      }
+    )
+    .get(
+      "/:id/deliveries",
+      async ({ params, query, set, request }) => {
+        try {
+          assertWebhookAccess(request);
+          await loadWebhook(db, params.id);
+          const deliveries = await db
+            .select()
+            .from(webhookDeliveries)
+            .where(eq(webhookDeliveries.webhookId, params.id))
+            .orderBy(desc(webhookDeliveries.createdAt))
+            .limit(query.limit ?? DEFAULT_DELIVERY_LIMIT);
+          return { deliveries: deliveries.map(toDeliveryResponse) };
+        } catch (error) {
+          return handleRouteFailure(set, error, "Failed to list deliveries");
+        }
+      },
+      {
+        params: WebhookParamsSchema,
+        query: WebhookDeliveryListQuerySchema,
+        response: {
+          200: WebhookDeliveryListResponseSchema,
+          403: ErrorSchema,
+          404: ErrorSchema,
+          500: ErrorSchema,
+        },
+      }
+    )
+    .post(
+      "/:id/ping",
+      async ({ params, set, request }) => {
+        try {
+          assertWebhookAccess(request);
+          const webhook = await loadWebhook(db, params.id);
+          return toDeliveryResponse(await dispatcher.ping(webhook));
+        } catch (error) {
+          return handleRouteFailure(set, error, "Failed to ping webhook");
+        }
+      },
+      {
+        params: WebhookParamsSchema,
+        response: {
+          200: WebhookDeliverySchema,
+          403: ErrorSchema,
+          404: ErrorSchema,
+          500: ErrorSchema,
+        },
+      }
+    );
+}
+
+export const webhookRoutes = createWebhookRoutes();
@@ -0,0 +1,12 @@
+import { selectEngineTable } from "./engine";
+import { apiTokens as pgApiTokens } from "./pg/api-tokens";
+import { apiTokens as sqliteApiTokens } from "./sqlite/api-tokens";
+
+export {
+  API_TOKEN_SCOPES,
+  type ApiToken,
+  type ApiTokenScope,
+  type NewApiToken,
+} from "./sqlite/api-tokens";
+
+export const apiTokens = selectEngineTable(sqliteApiTokens, pgApiTokens);
@@ -16,6 +16,7 @@ export const CellResponseSchema = t.Object({
   lastSetupError: t.Optional(t.String()),
   branchName: t.Optional(t.String()),
   baseCommit: t.Optional(t.String()),
+  ownerId: t.Optional(t.String()),
   setupLog: t.Optional(t.String()),
   setupLogPath: t.Optional(t.String()),
 });
@@ -85,6 +86,36 @@ export const CellServiceListResponseSchema = t.Object({
   services: t.Array(CellServiceSchema),
 });
 
+export const ConfigFieldChangeSchema = t.Object({
+  path: t.String({ description: "Changed field, e.g. run or env.PORT" }),
+  before: t.Optional(t.Unknown()),
+  after: t.Optional(t.Unknown()),
+});
+
+export const ServiceConfigChangeSchema = t.Object({
+  serviceId: t.Union([t.String(), t.Null()]),
+  name: t.String(),
+  change: t.Union([
+    t.Literal("added"),
+    t.Literal("removed"),
+    t.Literal("modified"),
+  ]),
+  fields: t.Array(ConfigFieldChangeSchema),
+});
+
+export const CellConfigImpactSchema = t.Object({
+  cellId: t.String(),
+  cellName: t.String(),
+  workspaceId: t.String(),
+  templateId: t.String(),
+  services: t.Array(ServiceConfigChangeSchema),
+});
+
+export const CellConfigApplyResponseSchema = t.Object({
+  applied: CellConfigImpactSchema,
+  services: t.Array(CellServiceSchema),
+});
+
 export const CellResourceProcessSchema = t.Object({
   kind: t.Union([
     t.Literal("service"),
@@ -221,6 +252,7 @@ export const CellActivityEventSchema = t.Object({
   type: t.String(),
   source: t.Union([t.String(), t.Null()]),
   toolName: t.Union([t.String(), t.Null()]),
+  userId: t.Union([t.String(), t.Null()]),
   metadata: t.Any(),
   createdAt: t.String(),
 });
@@ -321,6 +353,239 @@ export const CellDiffResponseSchema = t.Object({
   details: t.Optional(t.Array(DiffFileDetailSchema)),
 });
 
+export const CellCommitBodySchema = t.Object({
+  message: t.String({ minLength: 1 }),
+  files: t.Optional(t.Array(t.String({ minLength: 1 }))),
+});
+
+export const CellCommitResponseSchema = t.Object({
+  commit: t.String(),
+  branchName: t.Union([t.String(), t.Null()]),
+  files: t.Array(t.String()),
+});
+
+export const CellPushBodySchema = t.Object({
+  remote: t.Optional(t.String({ minLength: 1 })),
+  force: t.Optional(t.Boolean()),
+});
+
+export const CellPushResponseSchema = t.Object({
+  remote: t.String(),
+  branchName: t.String(),
+  commit: t.String(),
+});
+
+export const CellPullRequestBodySchema = t.Object({
+  title: t.String({ minLength: 1 }),
+  body: t.Optional(t.String()),
+  base: t.Optional(t.String({ minLength: 1 })),
+  draft: t.Optional(t.Boolean()),
+});
+
+export const CellPullRequestResponseSchema = t.Object({
+  number: t.Number(),
+  url: t.String(),
+  head: t.String(),
+  base: t.String(),
+  commit: t.String(),
+});
+
+export const CellMergeBodySchema = t.Object({
+  base: t.Optional(t.String({ minLength: 1 })),
+  strategy: t.Optional(t.Union([t.Literal("merge"), t.Literal("rebase")])),
+  dryRun: t.Optional(t.Boolean()),
+  message: t.Optional(t.String({ minLength: 1 })),
+});
+
+export const CellMergeResponseSchema = t.Object({
+  status: t.Union([
+    t.Literal("clean"),
+    t.Literal("conflicted"),
+    t.Literal("up_to_date"),
+  ]),
+  strategy: t.Union([t.Literal("merge"), t.Literal("rebase")]),
+  dryRun: t.Boolean(),
+  applied: t.Boolean(),
+  branchName: t.String(),
+  baseBranch: t.String(),
+  baseCommit: t.String(),
+  headCommit: t.String(),
+  resultCommit: t.Union([t.String(), t.Null()]),
+  conflicts: t.Array(DiffFileSummarySchema),
+});
+
+export const CellSyncBodySchema = t.Object({
+  strategy: t.Optional(t.Union([t.Literal("merge"), t.Literal("rebase")])),
+});
+
+export const CellSyncResponseSchema = t.Object({
+  status: t.Union([
+    t.Literal("synced"),
+    t.Literal("conflicted"),
+    t.Literal("up_to_date"),
+  ]),
+  strategy: t.Union([t.Literal("merge"), t.Literal("rebase")]),
+  branchName: t.String(),
+  upstream: t.String(),
+  baseCommit: t.String(),
+  headCommit: t.String(),
+  conflicts: t.Array(DiffFileSummarySchema),
+  message: t.Union([t.String(), t.Null()]),
+});
+
+export const CellSnapshotSchema = t.Object({
+  id: t.String(),
+  cellId: t.String(),
+  name: t.String(),
+  gitRef: t.String(),
+  snapshotCommit: t.String(),
+  headCommit: t.String(),
+  hasArchive: t.Boolean(),
+  agentMessageId: t.Union([t.String(), t.Null()]),
+  createdAt: t.String(),
+});
+
+export const CellSnapshotListQuerySchema = t.Object({
+  agentMessageId: t.Optional(t.String({ minLength: 1 })),
+});
+
+export const CellSnapshotListResponseSchema = t.Object({
+  snapshots: t.Array(CellSnapshotSchema),
+});
+
+export const CreateCellSnapshotBodySchema = t.Object({
+  name: t.Optional(t.String({ minLength: 1, maxLength: 255 })),
+  agentMessageId: t.Optional(t.String({ minLength: 1 })),
+});
+
+export const RestoreCellSnapshotBodySchema = t.Object({
+  backup: t.Optional(t.Boolean()),
+});
+
+export const RestoreCellSnapshotResponseSchema = t.Object({
+  restored: CellSnapshotSchema,
+  backup: t.Union([CellSnapshotSchema, t.Null()]),
+});
+
+export const ImportCellQuerySchema = t.Object({
+  workspaceId: t.Optional(t.String({ minLength: 1 })),
+  name: t.Optional(t.String({ minLength: 1, maxLength: 255 })),
+});
+
+export const ImportCellResponseSchema = t.Object({
+  id: t.String(),
+  name: t.String(),
+  workspaceId: t.String(),
+  workspacePath: t.String(),
+  branchName: t.Union([t.String(), t.Null()]),
+  sourceCellId: t.String(),
+  services: t.Number(),
+  activityEvents: t.Number(),
+  timingEvents: t.Number(),
+  transcriptMessages: t.Number(),
+  transcriptRestored: t.Boolean(),
+});
+
+const ApiTokenScopeSchema = t.Union([
+  t.Literal("read"),
+  t.Literal("operate"),
+  t.Literal("admin"),
+]);
+
+export const ApiTokenSchema = t.Object({
+  id: t.String(),
+  name: t.String(),
+  tokenPrefix: t.String(),
+  scope: ApiTokenScopeSchema,
+  userId: t.Union([t.String(), t.Null()]),
+  createdAt: t.String(),
+  lastUsedAt: t.Union([t.String(), t.Null()]),
+  revokedAt: t.Union([t.String(), t.Null()]),
+});
+
+export const ApiTokenListResponseSchema = t.Object({
+  tokens: t.Array(ApiTokenSchema),
+});
+
+export const CreateApiTokenBodySchema = t.Object({
+  name: t.String({ minLength: 1, maxLength: 255 }),
+  scope: ApiTokenScopeSchema,
+  user: t.Optional(
+    t.String({ description: "Id or username the token acts as" })
+  ),
+});
+
+export const CreateApiTokenResponseSchema = t.Object({
+  token: t.String(),
+  record: ApiTokenSchema,
+});
+
+export const AuthSessionResponseSchema = t.Object({
+  mode: t.Union([t.Literal("required"), t.Literal("off")]),
+  principal: t.Union([
+    t.Object({
+      kind: t.Union([
+        t.Literal("token"),
+        t.Literal("internal"),
+        t.Literal("local"),
+      ]),
+      scope: ApiTokenScopeSchema,
+      tokenId: t.Union([t.String(), t.Null()]),
+      name: t.String(),
+      userId: t.Union([t.String(), t.Null()]),
+      cellId: t.Union([t.String(), t.Null()]),
+    }),
+    t.Null(),
+  ]),
+});
+
+export const UserSchema = t.Object({
+  id: t.String(),
+  username: t.String(),
+  displayName: t.Union([t.String(), t.Null()]),
+  createdAt: t.String(),
+});
+
+export const UserListResponseSchema = t.Object({
+  users: t.Array(UserSchema),
+});
+
+export const CreateUserBodySchema = t.Object({
+  username: t.String({ minLength: 1, maxLength: 64 }),
+  displayName: t.Optional(t.String({ maxLength: 255 })),
+});
+
+export const CellShareSchema = t.Object({
+  cellId: t.String(),
+  userId: t.String(),
+  username: t.String(),
+  createdAt: t.String(),
+});
+
+export const CellShareListResponseSchema = t.Object({
+  shares: t.Array(CellShareSchema),
+});
+
+export const CreateCellShareBodySchema = t.Object({
+  user: t.String({ minLength: 1, description: "Id or username to share with" }),
+});
+
+export const AuthLoginQuerySchema = t.Object({
+  token: t.String({ minLength: 1 }),
+  redirect: t.Optional(t.String()),
+});
+
+export const CellPromptAttachmentSchema = t.Object({
+  name: t.String({
+    minLength: 1,
+    maxLength: 255,
+  }),
+  content: t.String({
+    description: "Base64-encoded file contents",
+  }),
+  mimeType: t.Optional(t.String()),
+});
+
 export const CreateCellSchema = t.Object({
   name: t.String({
     minLength: 1,
@@ -347,16 +612,85 @@ export const CreateCellSchema = t.Object({
   ),
   startMode: t.Optional(t.Union([t.Literal("plan"), t.Literal("build")])),
   spawnFromMode: t.Optional(
-    t.Union([t.Literal("head"), t.Literal("branch"), t.Literal("pr")])
+    t.Union([
+      t.Literal("head"),
+      t.Literal("branch"),
+      t.Literal("pr"),
+      t.Literal("cell"),
+    ])
   ),
   spawnFromValue: t.Optional(
     t.String({
       minLength: 1,
     })
   ),
+  forkSession: t.Optional(t.Boolean()),
+  initialPrompt: t.Optional(
+    t.String({
+      minLength: 1,
+      description:
+        "First message for the agent, sent once its session is up. Falls back to the name and description when omitted.",
+    })
+  ),
+  attachments: t.Optional(
+    t.Array(CellPromptAttachmentSchema, {
+      maxItems: 10,
+    })
+  ),
+  parameters: t.Optional(
+    t.Record(t.String(), t.Union([t.String(), t.Number(), t.Boolean()]), {
+      description:
+        "Values for the template's parameters. Omitted parameters use their defaults.",
+    })
+  ),
+  workspaceId: t.String({
+    minLength: 1,
+  }),
+});
+
+export const CreateCellBatchSchema = t.Object({
   workspaceId: t.String({
     minLength: 1,
   }),
+  manifest: t.Unknown({
+    description:
+      "YAML or JSON text, a list of cells, or an object with cells, concurrency and a default templateId",
+  }),
+  concurrency: t.Optional(
+    t.Integer({
+      minimum: 1,
+      maximum: 20,
+    })
+  ),
+});
+
+const CellBatchItemStatusSchema = t.Union([
+  t.Literal("queued"),
+  t.Literal("creating"),
+  t.Literal("provisioning"),
+  t.Literal("ready"),
+  t.Literal("failed"),
+]);
+
+export const CellBatchItemSchema = t.Object({
+  index: t.Number(),
+  name: t.String(),
+  status: CellBatchItemStatusSchema,
+  cellId: t.Union([t.String(), t.Null()]),
+  promptSent: t.Boolean(),
+  error: t.Union([t.String(), t.Null()]),
+});
+
+export const CellBatchResponseSchema = t.Object({
+  id: t.String(),
+  workspaceId: t.String(),
+  status: t.Union([t.Literal("running"), t.Literal("completed")]),
+  concurrency: t.Number(),
+  createdAt: t.String(),
+  finishedAt: t.Union([t.String(), t.Null()]),
+  total: t.Number(),
+  counts: t.Record(t.String(), t.Number()),
+  items: t.Array(CellBatchItemSchema),
 });
 
 export const DeleteCellsSchema = t.Object({
@@ -377,6 +711,7 @@ export const TemplateResponseSchema = t.Object({
   type: t.String(),
   configJson: t.Any(),
   includeDirectories: t.Optional(t.Array(t.String())),
+  fieldOrigins: t.Optional(t.Record(t.String(), t.String())),
 });
 
 export const DefaultsResponseSchema = t.Object({
@@ -443,6 +778,24 @@ export const AgentSessionByCellResponseSchema = t.Object({
   session: t.Union([AgentSessionSchema, t.Null()]),
 });
 
+export const SendAgentMessageBodySchema = t.Object({
+  content: t.String({ minLength: 1 }),
+});
+
+export const SendAgentMessageResponseSchema = t.Object({
+  sessionId: t.String(),
+  status: t.Literal("accepted"),
+});
+
+export const AgentEventsQuerySchema = t.Object({
+  includeParts: t.Optional(
+    t.Boolean({
+      description:
+        "Also stream message and part events (assistant text and tool calls)",
+    })
+  ),
+});
+
 export const SendAgentMessageSchema = t.Object({
   content: t.String({ minLength: 1 }),
 });
@@ -542,3 +895,113 @@ export const LinearIssueListResponseSchema = t.Object({
   nextCursor: t.Union([t.String(), t.Null()]),
   hasNextPage: t.Boolean(),
 });
+
+const WebhookEventTypeSchema = t.Union([
+  t.Literal("cell.status"),
+  t.Literal("cell.setup_failed"),
+  t.Literal("service.crashed"),
+  t.Literal("agent.status"),
+]);
+
+export const WebhookSchema = t.Object({
+  id: t.String(),
+  workspaceId: t.String(),
+  url: t.String(),
+  events: t.Array(WebhookEventTypeSchema),
+  enabled: t.Boolean(),
+  createdAt: t.String(),
+  updatedAt: t.String(),
+});
+
+export const WebhookListQuerySchema = t.Object({
+  workspaceId: t.String({ minLength: 1 }),
+});
+
+export const WebhookListResponseSchema = t.Object({
+  webhooks: t.Array(WebhookSchema),
+});
+
+export const CreateWebhookBodySchema = t.Object({
+  workspaceId: t.String({ minLength: 1 }),
+  url: t.String({ minLength: 1 }),
+  secret: t.Optional(
+    t.String({
+      minLength: 16,
+      description: "HMAC signing key; one is generated when omitted",
+    })
+  ),
+  events: t.Optional(
+    t.Array(WebhookEventTypeSchema, {
+      minItems: 1,
+      description: "Events to deliver; defaults to all of them",
+    })
+  ),
+  enabled: t.Optional(t.Boolean()),
+});
+
+export const CreateWebhookResponseSchema = t.Object({
+  secret: t.String(),
+  webhook: WebhookSchema,
+});
+
+export const UpdateWebhookBodySchema = t.Object({
+  url: t.Optional(t.String({ minLength: 1 })),
+  secret: t.Optional(t.String({ minLength: 16 })),
+  events: t.Optional(t.Array(WebhookEventTypeSchema, { minItems: 1 })),
+  enabled: t.Optional(t.Boolean()),
+});
+
+export const WebhookDeliverySchema = t.Object({
+  id: t.String(),
+  webhookId: t.String(),
+  event: t.String(),
+  status: t.Union([
+    t.Literal("pending"),
+    t.Literal("succeeded"),
+    t.Literal("failed"),
+  ]),
+  attempts: t.Number(),
+  responseStatus: t.Union([t.Number(), t.Null()]),
+  error: t.Union([t.String(), t.Null()]),
+  payload: t.Record(t.String(), t.Unknown()),
+  createdAt: t.String(),
+  lastAttemptAt: t.Union([t.String(), t.Null()]),
+  nextAttemptAt: t.Union([t.String(), t.Null()]),
+});
+
+export const WebhookDeliveryListQuerySchema = t.Object({
+  limit: t.Optional(
+    t.Number({
+      minimum: 1,
+      maximum: 100,
+      default: 20,
+      description: "Max deliveries to return, newest first (1-100)",
+    })
+  ),
+});
+
+export const WebhookDeliveryListResponseSchema = t.Object({
+  deliveries: t.Array(WebhookDeliverySchema),
+});
+
+export const WorkspaceSecretSchema = t.Object({
+  name: t.String(),
+  workspaceId: t.String(),
+  createdAt: t.String(),
+  updatedAt: t.String(),
+});
+
+export const WorkspaceSecretQuerySchema = t.Object({
+  workspaceId: t.String({ minLength: 1 }),
+});
+
+export const WorkspaceSecretListResponseSchema = t.Object({
+  secrets: t.Array(WorkspaceSecretSchema),
+});
+
+export const SetWorkspaceSecretBodySchema = t.Object({
+  workspaceId: t.String({ minLength: 1 }),
+  value: t.String({
+    description: "Stored encrypted; never returned by the API",
+  }),
+});
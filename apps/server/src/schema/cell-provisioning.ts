@@ -1,20 +1,18 @@
-import { integer, sqliteTable, text } from "drizzle-orm/sqlite-core";
+import { selectEngineTable } from "./engine";
+import {
+  cellProvisioningStates as pgCellProvisioningStates,
+} from "./pg/cell-provisioning";
+import {
+  cellProvisioningStates as sqliteCellProvisioningStates,
+} from "./sqlite/cell-provisioning";
 
-import { cells } from "./cells";
+export {
+  type CellPromptAttachment,
+  type CellProvisioningState,
+  type NewCellProvisioningState,
+} from "./sqlite/cell-provisioning";
 
-export const cellProvisioningStates = sqliteTable("cell_provisioning_state", {
-  cellId: text("cell_id")
-    .primaryKey()
-    .references(() => cells.id, { onDelete: "cascade" }),
-  modelIdOverride: text("model_id_override"),
-  providerIdOverride: text("provider_id_override"),
-  variantOverride: text("variant_override"),
-  startMode: text("start_mode"),
-  startedAt: integer("started_at", { mode: "timestamp" }),
-  finishedAt: integer("finished_at", { mode: "timestamp" }),
-  attemptCount: integer("attempt_count").notNull().default(0),
-});
-
-export type CellProvisioningState = typeof cellProvisioningStates.$inferSelect;
-export type NewCellProvisioningState =
-  typeof cellProvisioningStates.$inferInsert;
+export const cellProvisioningStates = selectEngineTable(
+  sqliteCellProvisioningStates,
+  pgCellProvisioningStates
+);
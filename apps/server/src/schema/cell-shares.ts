@@ -0,0 +1,7 @@
+import { selectEngineTable } from "./engine";
+import { cellShares as pgCellShares } from "./pg/cell-shares";
+import { cellShares as sqliteCellShares } from "./sqlite/cell-shares";
+
+export { type CellShare, type NewCellShare } from "./sqlite/cell-shares";
+
+export const cellShares = selectEngineTable(sqliteCellShares, pgCellShares);
@@ -0,0 +1,13 @@
+import { selectEngineTable } from "./engine";
+import { cellSnapshots as pgCellSnapshots } from "./pg/cell-snapshots";
+import { cellSnapshots as sqliteCellSnapshots } from "./sqlite/cell-snapshots";
+
+export {
+  type CellSnapshot,
+  type NewCellSnapshot,
+} from "./sqlite/cell-snapshots";
+
+export const cellSnapshots = selectEngineTable(
+  sqliteCellSnapshots,
+  pgCellSnapshots
+);
@@ -0,0 +1,11 @@
+import { databaseEngine } from "../config/database";
+
+/**
+ * Queries across the server are typed against the SQLite tables. The tables in
+ * ./pg mirror them column for column, so with a postgres:// DATABASE_URL the
+ * same export resolves to the Postgres table instead.
+ */
+export const selectEngineTable = <TTable>(
+  sqliteTable: TTable,
+  pgTable: unknown
+): TTable => (databaseEngine === "postgres" ? pgTable : sqliteTable) as TTable;
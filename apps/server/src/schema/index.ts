@@ -1,10 +1,16 @@
 import { cellActivityEvents } from "./activity-events";
+import { apiTokens } from "./api-tokens";
 import { cellProvisioningStates } from "./cell-provisioning";
+import { cellShares } from "./cell-shares";
+import { cellSnapshots } from "./cell-snapshots";
 import { cells } from "./cells";
 import { linearIntegrations } from "./linear-integrations";
 import { cellResourceHistory, cellResourceRollups } from "./resource-history";
 import { cellServices } from "./services";
 import { cellTimingEvents } from "./timing-events";
+import { users } from "./users";
+import { webhookDeliveries, workspaceWebhooks } from "./webhooks";
+import { workspaceSecrets } from "./workspace-secrets";
 
 export const schema = {
   cells,
@@ -14,5 +20,12 @@ export const schema = {
   cellProvisioningStates,
   cellActivityEvents,
   cellTimingEvents,
+  cellSnapshots,
   linearIntegrations,
+  apiTokens,
+  users,
+  cellShares,
+  workspaceWebhooks,
+  webhookDeliveries,
+  workspaceSecrets,
 };
@@ -0,0 +1,23 @@
+import { jsonb, pgTable, text, timestamp } from "drizzle-orm/pg-core";
+import type { ActivityEventType } from "../sqlite/activity-events";
+import { cells } from "./cells";
+import { cellServices } from "./services";
+import { users } from "./users";
+
+export const cellActivityEvents = pgTable("cell_activity_events", {
+  id: text("id").primaryKey(),
+  cellId: text("cell_id")
+    .notNull()
+    .references(() => cells.id, { onDelete: "cascade" }),
+  serviceId: text("service_id").references(() => cellServices.id, {
+    onDelete: "cascade",
+  }),
+  type: text("type").$type<ActivityEventType>().notNull(),
+  source: text("source"),
+  toolName: text("tool_name"),
+  userId: text("user_id").references(() => users.id, {
+    onDelete: "set null",
+  }),
+  metadata: jsonb("metadata").$type<Record<string, unknown>>().notNull(),
+  createdAt: timestamp("created_at", { withTimezone: true }).notNull(),
+});
@@ -0,0 +1,33 @@
+import {
+  integer,
+  jsonb,
+  pgTable,
+  text,
+  timestamp,
+} from "drizzle-orm/pg-core";
+
+import type { TemplateParameterValues } from "../../config/template-parameters";
+import type { CellPromptAttachment } from "../sqlite/cell-provisioning";
+import { cells } from "./cells";
+
+export const cellProvisioningStates = pgTable("cell_provisioning_state", {
+  cellId: text("cell_id")
+    .primaryKey()
+    .references(() => cells.id, { onDelete: "cascade" }),
+  modelIdOverride: text("model_id_override"),
+  providerIdOverride: text("provider_id_override"),
+  variantOverride: text("variant_override"),
+  startMode: text("start_mode"),
+  startedAt: timestamp("started_at", { withTimezone: true }),
+  finishedAt: timestamp("finished_at", { withTimezone: true }),
+  attemptCount: integer("attempt_count").notNull().default(0),
+  initialPrompt: text("initial_prompt"),
+  initialPromptAttachments: jsonb("initial_prompt_attachments").$type<
+    CellPromptAttachment[]
+  >(),
+  initialPromptSentAt: timestamp("initial_prompt_sent_at", {
+    withTimezone: true,
+  }),
+  templateParameters:
+    jsonb("template_parameters").$type<TemplateParameterValues>(),
+});
@@ -0,0 +1,23 @@
+import { pgTable, text, timestamp, uniqueIndex } from "drizzle-orm/pg-core";
+import { cells } from "./cells";
+import { users } from "./users";
+
+export const cellShares = pgTable(
+  "cell_shares",
+  {
+    id: text("id").primaryKey(),
+    cellId: text("cell_id")
+      .notNull()
+      .references(() => cells.id, { onDelete: "cascade" }),
+    userId: text("user_id")
+      .notNull()
+      .references(() => users.id, { onDelete: "cascade" }),
+    createdAt: timestamp("created_at", { withTimezone: true }).notNull(),
+  },
+  (table) => ({
+    cellUserIdx: uniqueIndex("cell_shares_cell_user_idx").on(
+      table.cellId,
+      table.userId
+    ),
+  })
+);
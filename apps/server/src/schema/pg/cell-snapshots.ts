@@ -0,0 +1,22 @@
+import { index, pgTable, text, timestamp } from "drizzle-orm/pg-core";
+import { cells } from "./cells";
+
+export const cellSnapshots = pgTable(
+  "cell_snapshots",
+  {
+    id: text("id").primaryKey(),
+    cellId: text("cell_id")
+      .notNull()
+      .references(() => cells.id, { onDelete: "cascade" }),
+    name: text("name").notNull(),
+    gitRef: text("git_ref").notNull(),
+    snapshotCommit: text("snapshot_commit").notNull(),
+    headCommit: text("head_commit").notNull(),
+    archivePath: text("archive_path"),
+    agentMessageId: text("agent_message_id"),
+    createdAt: timestamp("created_at", { withTimezone: true }).notNull(),
+  },
+  (table) => ({
+    cellIdIdx: index("cell_snapshots_cell_id_idx").on(table.cellId),
+  })
+);
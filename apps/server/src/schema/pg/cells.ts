@@ -0,0 +1,26 @@
+import { boolean, pgTable, text, timestamp } from "drizzle-orm/pg-core";
+import { users } from "./users";
+
+export const cells = pgTable("cells", {
+  id: text("id").primaryKey(),
+  name: text("name").notNull(),
+  description: text("description"),
+  templateId: text("template_id").notNull(),
+  workspacePath: text("workspace_path").notNull(),
+  workspaceId: text("workspace_id").notNull(),
+  workspaceRootPath: text("workspace_root_path").notNull().default(""),
+  opencodeSessionId: text("opencode_session_id"),
+  resumeAgentSessionOnStartup: boolean("resume_agent_session_on_startup")
+    .notNull()
+    .default(false),
+  createdAt: timestamp("created_at", { withTimezone: true }).notNull(),
+  status: text("status").notNull().default("ready"),
+  lastSetupError: text("last_setup_error"),
+  branchName: text("branch_name"),
+  baseCommit: text("base_commit"),
+  startPointMode: text("start_point_mode"),
+  startPointRef: text("start_point_ref"),
+  ownerId: text("owner_id").references(() => users.id, {
+    onDelete: "set null",
+  }),
+});
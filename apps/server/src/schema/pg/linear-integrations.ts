@@ -0,0 +1,22 @@
+import { pgTable, text, timestamp } from "drizzle-orm/pg-core";
+
+export const linearIntegrations = pgTable("workspace_linear_integrations", {
+  workspaceId: text("workspace_id").primaryKey(),
+  accessToken: text("access_token").notNull(),
+  refreshToken: text("refresh_token"),
+  accessTokenExpiresAt: timestamp("access_token_expires_at", {
+    withTimezone: true,
+  }),
+  tokenType: text("token_type"),
+  scope: text("scope"),
+  linearUserId: text("linear_user_id").notNull(),
+  linearUserName: text("linear_user_name"),
+  linearUserEmail: text("linear_user_email"),
+  linearOrganizationId: text("linear_organization_id"),
+  linearOrganizationName: text("linear_organization_name"),
+  teamId: text("team_id"),
+  teamKey: text("team_key"),
+  teamName: text("team_name"),
+  createdAt: timestamp("created_at", { withTimezone: true }).notNull(),
+  updatedAt: timestamp("updated_at", { withTimezone: true }).notNull(),
+});
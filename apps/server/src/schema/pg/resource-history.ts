@@ -0,0 +1,67 @@
+import {
+  bigint,
+  doublePrecision,
+  index,
+  integer,
+  jsonb,
+  pgTable,
+  text,
+  timestamp,
+} from "drizzle-orm/pg-core";
+import type { StoredResourceProcess } from "../sqlite/resource-history";
+import { cells } from "./cells";
+
+export const cellResourceHistory = pgTable(
+  "cell_resource_history",
+  {
+    id: text("id").primaryKey(),
+    cellId: text("cell_id")
+      .notNull()
+      .references(() => cells.id, { onDelete: "cascade" }),
+    sampledAt: timestamp("sampled_at", { withTimezone: true }).notNull(),
+    processCount: integer("process_count").notNull(),
+    activeProcessCount: integer("active_process_count").notNull(),
+    totalCpuPercent: doublePrecision("total_cpu_percent").notNull(),
+    totalRssBytes: bigint("total_rss_bytes", { mode: "number" }).notNull(),
+    activeCpuPercent: doublePrecision("active_cpu_percent").notNull(),
+    activeRssBytes: bigint("active_rss_bytes", { mode: "number" }).notNull(),
+    processes: jsonb("processes").$type<StoredResourceProcess[]>().notNull(),
+    createdAt: timestamp("created_at", { withTimezone: true }).notNull(),
+  },
+  (table) => ({
+    cellSampledIdx: index("cell_resource_history_cell_sampled_idx").on(
+      table.cellId,
+      table.sampledAt
+    ),
+  })
+);
+
+export const cellResourceRollups = pgTable(
+  "cell_resource_rollups",
+  {
+    id: text("id").primaryKey(),
+    cellId: text("cell_id")
+      .notNull()
+      .references(() => cells.id, { onDelete: "cascade" }),
+    bucketStartAt: timestamp("bucket_start_at", {
+      withTimezone: true,
+    }).notNull(),
+    sampleCount: integer("sample_count").notNull(),
+    sumActiveCpuPercent: doublePrecision("sum_active_cpu_percent").notNull(),
+    sumActiveRssBytes: bigint("sum_active_rss_bytes", {
+      mode: "number",
+    }).notNull(),
+    peakActiveCpuPercent: doublePrecision("peak_active_cpu_percent").notNull(),
+    peakActiveRssBytes: bigint("peak_active_rss_bytes", {
+      mode: "number",
+    }).notNull(),
+    createdAt: timestamp("created_at", { withTimezone: true }).notNull(),
+    updatedAt: timestamp("updated_at", { withTimezone: true }).notNull(),
+  },
+  (table) => ({
+    cellBucketIdx: index("cell_resource_rollups_cell_bucket_idx").on(
+      table.cellId,
+      table.bucketStartAt
+    ),
+  })
+);
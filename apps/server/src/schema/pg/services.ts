@@ -0,0 +1,24 @@
+import { integer, jsonb, pgTable, text, timestamp } from "drizzle-orm/pg-core";
+import type { Service } from "../../config/schema";
+import type { ServiceStatus } from "../sqlite/services";
+import { cells } from "./cells";
+
+export const cellServices = pgTable("cell_services", {
+  id: text("id").primaryKey(),
+  cellId: text("cell_id")
+    .notNull()
+    .references(() => cells.id, { onDelete: "cascade" }),
+  name: text("name").notNull(),
+  type: text("type").notNull(),
+  command: text("command").notNull(),
+  cwd: text("cwd").notNull(),
+  env: jsonb("env").$type<Record<string, string>>().notNull(),
+  status: text("status").$type<ServiceStatus>().notNull().default("pending"),
+  port: integer("port"),
+  pid: integer("pid"),
+  readyTimeoutMs: integer("ready_timeout_ms"),
+  definition: jsonb("definition").$type<Service>().notNull(),
+  lastKnownError: text("last_known_error"),
+  createdAt: timestamp("created_at", { withTimezone: true }).notNull(),
+  updatedAt: timestamp("updated_at", { withTimezone: true }).notNull(),
+});
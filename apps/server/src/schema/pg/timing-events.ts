@@ -0,0 +1,43 @@
+import {
+  index,
+  integer,
+  jsonb,
+  pgTable,
+  text,
+  timestamp,
+} from "drizzle-orm/pg-core";
+import type {
+  CellTimingStatus,
+  CellTimingWorkflow,
+} from "../sqlite/timing-events";
+
+export const cellTimingEvents = pgTable(
+  "cell_timing_events",
+  {
+    id: text("id").primaryKey(),
+    cellId: text("cell_id").notNull(),
+    cellName: text("cell_name"),
+    workspaceId: text("workspace_id"),
+    templateId: text("template_id"),
+    workflow: text("workflow").$type<CellTimingWorkflow>().notNull(),
+    runId: text("run_id").notNull(),
+    step: text("step").notNull(),
+    status: text("status").$type<CellTimingStatus>().notNull(),
+    durationMs: integer("duration_ms").notNull(),
+    attempt: integer("attempt"),
+    error: text("error"),
+    metadata: jsonb("metadata").$type<Record<string, unknown>>().notNull(),
+    createdAt: timestamp("created_at", { withTimezone: true }).notNull(),
+  },
+  (table) => ({
+    cellCreatedIdx: index("cell_timing_events_cell_created_idx").on(
+      table.cellId,
+      table.createdAt
+    ),
+    runCreatedIdx: index("cell_timing_events_run_created_idx").on(
+      table.runId,
+      table.createdAt
+    ),
+    createdIdx: index("cell_timing_events_created_idx").on(table.createdAt),
+  })
+);
@@ -0,0 +1,19 @@
+import { pgTable, text, timestamp, uniqueIndex } from "drizzle-orm/pg-core";
+
+export const workspaceSecrets = pgTable(
+  "workspace_secrets",
+  {
+    id: text("id").primaryKey(),
+    workspaceId: text("workspace_id").notNull(),
+    name: text("name").notNull(),
+    encryptedValue: text("encrypted_value").notNull(),
+    createdAt: timestamp("created_at", { withTimezone: true }).notNull(),
+    updatedAt: timestamp("updated_at", { withTimezone: true }).notNull(),
+  },
+  (table) => ({
+    workspaceNameIdx: uniqueIndex("workspace_secrets_workspace_name_idx").on(
+      table.workspaceId,
+      table.name
+    ),
+  })
+);
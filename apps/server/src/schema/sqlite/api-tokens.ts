@@ -0,0 +1,41 @@
+import {
+  integer,
+  sqliteTable,
+  text,
+  uniqueIndex,
+} from "drizzle-orm/sqlite-core";
+import { cells } from "./cells";
+import { users } from "./users";
+
+/** Ordered from least to most privileged; each scope implies the previous. */
+export const API_TOKEN_SCOPES = ["read", "operate", "admin"] as const;
+
+export type ApiTokenScope = (typeof API_TOKEN_SCOPES)[number];
+
+export const apiTokens = sqliteTable(
+  "api_tokens",
+  {
+    id: text("id").primaryKey(),
+    name: text("name").notNull(),
+    tokenHash: text("token_hash").notNull(),
+    tokenPrefix: text("token_prefix").notNull(),
+    scope: text("scope").$type<ApiTokenScope>().notNull(),
+    /** Tokens without a user act as shared service accounts. */
+    userId: text("user_id").references(() => users.id, {
+      onDelete: "cascade",
+    }),
+    /** Agent tokens are bound to one cell and minted by Hive itself. */
+    cellId: text("cell_id").references(() => cells.id, {
+      onDelete: "cascade",
+    }),
+    createdAt: integer("created_at", { mode: "timestamp" }).notNull(),
+    lastUsedAt: integer("last_used_at", { mode: "timestamp" }),
+    revokedAt: integer("revoked_at", { mode: "timestamp" }),
+  },
+  (table) => ({
+    tokenHashIdx: uniqueIndex("api_tokens_token_hash_idx").on(table.tokenHash),
+  })
+);
+
+export type ApiToken = typeof apiTokens.$inferSelect;
+export type NewApiToken = typeof apiTokens.$inferInsert;
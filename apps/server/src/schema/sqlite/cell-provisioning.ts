@@ -0,0 +1,38 @@
+import { integer, sqliteTable, text } from "drizzle-orm/sqlite-core";
+
+import type { TemplateParameterValues } from "../../config/template-parameters";
+import { cells } from "./cells";
+
+/** A file handed to the agent with its first prompt, base64-encoded. */
+export type CellPromptAttachment = {
+  name: string;
+  content: string;
+  mimeType?: string;
+};
+
+export const cellProvisioningStates = sqliteTable("cell_provisioning_state", {
+  cellId: text("cell_id")
+    .primaryKey()
+    .references(() => cells.id, { onDelete: "cascade" }),
+  modelIdOverride: text("model_id_override"),
+  providerIdOverride: text("provider_id_override"),
+  variantOverride: text("variant_override"),
+  startMode: text("start_mode"),
+  startedAt: integer("started_at", { mode: "timestamp" }),
+  finishedAt: integer("finished_at", { mode: "timestamp" }),
+  attemptCount: integer("attempt_count").notNull().default(0),
+  initialPrompt: text("initial_prompt"),
+  initialPromptAttachments: text("initial_prompt_attachments", {
+    mode: "json",
+  }).$type<CellPromptAttachment[]>(),
+  initialPromptSentAt: integer("initial_prompt_sent_at", {
+    mode: "timestamp",
+  }),
+  templateParameters: text("template_parameters", {
+    mode: "json",
+  }).$type<TemplateParameterValues>(),
+});
+
+export type CellProvisioningState = typeof cellProvisioningStates.$inferSelect;
+export type NewCellProvisioningState =
+  typeof cellProvisioningStates.$inferInsert;
@@ -0,0 +1,32 @@
+import {
+  integer,
+  sqliteTable,
+  text,
+  uniqueIndex,
+} from "drizzle-orm/sqlite-core";
+import { cells } from "./cells";
+import { users } from "./users";
+
+/** Read-only access to a cell for someone other than its owner. */
+export const cellShares = sqliteTable(
+  "cell_shares",
+  {
+    id: text("id").primaryKey(),
+    cellId: text("cell_id")
+      .notNull()
+      .references(() => cells.id, { onDelete: "cascade" }),
+    userId: text("user_id")
+      .notNull()
+      .references(() => users.id, { onDelete: "cascade" }),
+    createdAt: integer("created_at", { mode: "timestamp" }).notNull(),
+  },
+  (table) => ({
+    cellUserIdx: uniqueIndex("cell_shares_cell_user_idx").on(
+      table.cellId,
+      table.userId
+    ),
+  })
+);
+
+export type CellShare = typeof cellShares.$inferSelect;
+export type NewCellShare = typeof cellShares.$inferInsert;
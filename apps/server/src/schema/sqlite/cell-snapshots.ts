@@ -0,0 +1,19 @@
+import { integer, sqliteTable, text } from "drizzle-orm/sqlite-core";
+import { cells } from "./cells";
+
+export const cellSnapshots = sqliteTable("cell_snapshots", {
+  id: text("id").primaryKey(),
+  cellId: text("cell_id")
+    .notNull()
+    .references(() => cells.id, { onDelete: "cascade" }),
+  name: text("name").notNull(),
+  gitRef: text("git_ref").notNull(),
+  snapshotCommit: text("snapshot_commit").notNull(),
+  headCommit: text("head_commit").notNull(),
+  archivePath: text("archive_path"),
+  agentMessageId: text("agent_message_id"),
+  createdAt: integer("created_at", { mode: "timestamp" }).notNull(),
+});
+
+export type CellSnapshot = typeof cellSnapshots.$inferSelect;
+export type NewCellSnapshot = typeof cellSnapshots.$inferInsert;
@@ -0,0 +1,41 @@
+import { integer, sqliteTable, text } from "drizzle-orm/sqlite-core";
+import { users } from "./users";
+
+export const cellStatusValues = [
+  "spawning",
+  "pending",
+  "ready",
+  "error",
+  "deleting",
+  "suspended",
+] as const;
+export type CellStatus = (typeof cellStatusValues)[number];
+
+export const cells = sqliteTable("cells", {
+  id: text("id").primaryKey(),
+  name: text("name").notNull(),
+  description: text("description"),
+  templateId: text("template_id").notNull(),
+  workspacePath: text("workspace_path").notNull(),
+  workspaceId: text("workspace_id").notNull(),
+  workspaceRootPath: text("workspace_root_path").notNull().default(""),
+  opencodeSessionId: text("opencode_session_id"),
+  resumeAgentSessionOnStartup: integer("resume_agent_session_on_startup", {
+    mode: "boolean",
+  })
+    .notNull()
+    .default(false),
+  createdAt: integer("created_at", { mode: "timestamp" }).notNull(),
+  status: text("status").notNull().default("ready"),
+  lastSetupError: text("last_setup_error"),
+  branchName: text("branch_name"),
+  baseCommit: text("base_commit"),
+  startPointMode: text("start_point_mode"),
+  startPointRef: text("start_point_ref"),
+  ownerId: text("owner_id").references(() => users.id, {
+    onDelete: "set null",
+  }),
+});
+
+export type Cell = typeof cells.$inferSelect;
+export type NewCell = typeof cells.$inferInsert;
@@ -0,0 +1,72 @@
+import { index, integer, sqliteTable, text } from "drizzle-orm/sqlite-core";
+
+export const WEBHOOK_EVENT_TYPES = [
+  "cell.status",
+  "cell.setup_failed",
+  "service.crashed",
+  "agent.status",
+] as const;
+
+export type WebhookEventType = (typeof WEBHOOK_EVENT_TYPES)[number];
+
+export const WEBHOOK_DELIVERY_STATUSES = [
+  "pending",
+  "succeeded",
+  "failed",
+] as const;
+
+export type WebhookDeliveryStatus = (typeof WEBHOOK_DELIVERY_STATUSES)[number];
+
+export const workspaceWebhooks = sqliteTable(
+  "workspace_webhooks",
+  {
+    id: text("id").primaryKey(),
+    workspaceId: text("workspace_id").notNull(),
+    url: text("url").notNull(),
+    /** HMAC key for the `X-Hive-Signature` header; never sent back out. */
+    secret: text("secret").notNull(),
+    events: text("events", { mode: "json" })
+      .$type<WebhookEventType[]>()
+      .notNull(),
+    enabled: integer("enabled", { mode: "boolean" }).notNull().default(true),
+    createdAt: integer("created_at", { mode: "timestamp" }).notNull(),
+    updatedAt: integer("updated_at", { mode: "timestamp" }).notNull(),
+  },
+  (table) => ({
+    workspaceIdx: index("workspace_webhooks_workspace_id_idx").on(
+      table.workspaceId
+    ),
+  })
+);
+
+export const webhookDeliveries = sqliteTable(
+  "webhook_deliveries",
+  {
+    id: text("id").primaryKey(),
+    webhookId: text("webhook_id")
+      .notNull()
+      .references(() => workspaceWebhooks.id, { onDelete: "cascade" }),
+    event: text("event").notNull(),
+    payload: text("payload", { mode: "json" })
+      .$type<Record<string, unknown>>()
+      .notNull(),
+    status: text("status").$type<WebhookDeliveryStatus>().notNull(),
+    attempts: integer("attempts").notNull().default(0),
+    responseStatus: integer("response_status"),
+    error: text("error"),
+    createdAt: integer("created_at", { mode: "timestamp" }).notNull(),
+    lastAttemptAt: integer("last_attempt_at", { mode: "timestamp" }),
+    nextAttemptAt: integer("next_attempt_at", { mode: "timestamp" }),
+  },
+  (table) => ({
+    webhookCreatedIdx: index("webhook_deliveries_webhook_created_idx").on(
+      table.webhookId,
+      table.createdAt
+    ),
+  })
+);
+
+export type WorkspaceWebhook = typeof workspaceWebhooks.$inferSelect;
+export type NewWorkspaceWebhook = typeof workspaceWebhooks.$inferInsert;
+export type WebhookDelivery = typeof webhookDeliveries.$inferSelect;
+export type NewWebhookDelivery = typeof webhookDeliveries.$inferInsert;
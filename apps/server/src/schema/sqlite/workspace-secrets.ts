@@ -0,0 +1,32 @@
+import {
+  integer,
+  sqliteTable,
+  text,
+  uniqueIndex,
+} from "drizzle-orm/sqlite-core";
+
+/**
+ * Values templates reference as `${secret:NAME}`. Only the AES-GCM ciphertext
+ * is stored; the plaintext never leaves the server except in a spawned
+ * process's environment.
+ */
+export const workspaceSecrets = sqliteTable(
+  "workspace_secrets",
+  {
+    id: text("id").primaryKey(),
+    workspaceId: text("workspace_id").notNull(),
+    name: text("name").notNull(),
+    encryptedValue: text("encrypted_value").notNull(),
+    createdAt: integer("created_at", { mode: "timestamp" }).notNull(),
+    updatedAt: integer("updated_at", { mode: "timestamp" }).notNull(),
+  },
+  (table) => ({
+    workspaceNameIdx: uniqueIndex("workspace_secrets_workspace_name_idx").on(
+      table.workspaceId,
+      table.name
+    ),
+  })
+);
+
+export type WorkspaceSecret = typeof workspaceSecrets.$inferSelect;
+export type NewWorkspaceSecret = typeof workspaceSecrets.$inferInsert;
@@ -0,0 +1,7 @@
+import { selectEngineTable } from "./engine";
+import { users as pgUsers } from "./pg/users";
+import { users as sqliteUsers } from "./sqlite/users";
+
+export { type NewUser, type User } from "./sqlite/users";
+
+export const users = selectEngineTable(sqliteUsers, pgUsers);
@@ -0,0 +1,29 @@
+import { selectEngineTable } from "./engine";
+import {
+  webhookDeliveries as pgWebhookDeliveries,
+  workspaceWebhooks as pgWorkspaceWebhooks,
+} from "./pg/webhooks";
+import {
+  webhookDeliveries as sqliteWebhookDeliveries,
+  workspaceWebhooks as sqliteWorkspaceWebhooks,
+} from "./sqlite/webhooks";
+
+export {
+  type NewWebhookDelivery,
+  type NewWorkspaceWebhook,
+  WEBHOOK_DELIVERY_STATUSES,
+  WEBHOOK_EVENT_TYPES,
+  type WebhookDelivery,
+  type WebhookDeliveryStatus,
+  type WebhookEventType,
+  type WorkspaceWebhook,
+} from "./sqlite/webhooks";
+
+export const workspaceWebhooks = selectEngineTable(
+  sqliteWorkspaceWebhooks,
+  pgWorkspaceWebhooks
+);
+export const webhookDeliveries = selectEngineTable(
+  sqliteWebhookDeliveries,
+  pgWebhookDeliveries
+);
@@ -0,0 +1,15 @@
+import { selectEngineTable } from "./engine";
+import { workspaceSecrets as pgWorkspaceSecrets } from "./pg/workspace-secrets";
+import {
+  workspaceSecrets as sqliteWorkspaceSecrets,
+} from "./sqlite/workspace-secrets";
+
+export {
+  type NewWorkspaceSecret,
+  type WorkspaceSecret,
+} from "./sqlite/workspace-secrets";
+
+export const workspaceSecrets = selectEngineTable(
+  sqliteWorkspaceSecrets,
+  pgWorkspaceSecrets
+);
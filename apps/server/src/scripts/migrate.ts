@@ -1,21 +1,24 @@
-import { Database } from "bun:sqlite";
 import { dirname, resolve } from "node:path";
 import { fileURLToPath } from "node:url";
-import { drizzle } from "drizzle-orm/bun-sqlite";
-import { migrate } from "drizzle-orm/bun-sqlite/migrator";
-import { sqliteDatabasePath } from "../config/database";
-import { schema } from "../schema";
+import { databaseEngine } from "../config/database";
+import {
+  closeDatabase,
+  engineDatabase,
+  migrateDatabase,
+  resolveMigrationsDirectoryName,
+} from "../db";
 
 async function runMigrations() {
-  const sqlite = new Database(sqliteDatabasePath);
-  const db = drizzle(sqlite, { schema });
-
   const scriptsDir = dirname(fileURLToPath(import.meta.url));
-  const migrationsFolder = resolve(scriptsDir, "../migrations");
+  const migrationsFolder = resolve(
+    scriptsDir,
+    "..",
+    resolveMigrationsDirectoryName(databaseEngine)
+  );
 
-  await migrate(db, { migrationsFolder });
-  console.log("✅ Database migrations complete");
-  sqlite.close();
+  await migrateDatabase(engineDatabase, migrationsFolder);
+  console.log(`✅ Database migrations complete (${databaseEngine})`);
+  await closeDatabase(engineDatabase);
 }
 
 runMigrations().catch((error) => {
@@ -6,7 +6,6 @@ import { fileURLToPath } from "node:url";
 import { logger } from "@bogeychan/elysia-logger";
 
 import { cors } from "@elysiajs/cors";
-import { migrate } from "drizzle-orm/bun-sqlite/migrator";
 import { Elysia } from "elysia";
 import { loadOpencodeConfig } from "./agents/opencode-config";
 import {
@@ -18,26 +17,64 @@ import {
   markAgentSessionsForResume,
   resumeAgentSessionsOnStartup,
 } from "./agents/service";
+import {
+  createApiAuthPlugin,
+  redactRequestUrl,
+  resolveApiAuthMode,
+} from "./auth/plugin";
+import { listApiTokens } from "./auth/tokens";
 import { resolveWorkspaceRoot } from "./config/context";
-import { DatabaseService } from "./db";
+import { databaseEngine } from "./config/database";
+import {
+  DatabaseService,
+  engineDatabase,
+  migrateDatabase,
+  resolveMigrationsDirectoryName,
+} from "./db";
 import { agentsRoutes } from "./routes/agents";
+import { createAuthRoutes } from "./routes/auth";
+import { cellGitRoutes } from "./routes/cell-git";
+import { cellShareRoutes } from "./routes/cell-shares";
+import { cellSnapshotRoutes } from "./routes/cell-snapshots";
+import { cellTransferRoutes } from "./routes/cell-transfer";
 import { cellsRoutes, resumeSpawningCells } from "./routes/cells";
 import { linearRoutes } from "./routes/linear";
+import { secretRoutes } from "./routes/secrets";
 import { templatesRoutes } from "./routes/templates";
+import { userRoutes } from "./routes/users";
+import { webhookRoutes } from "./routes/webhooks";
 import { workspacesRoutes } from "./routes/workspaces";
 import { cells } from "./schema/cells";
+import { cellIdleMonitor, createCellIdlePlugin } from "./services/cell-idle";
 import { chatTerminalService } from "./services/chat-terminal";
+import { configWatcher } from "./services/config-watcher";
 import { ServiceSupervisorService } from "./services/supervisor";
 import { cellTerminalService } from "./services/terminal";
+import { webhookDispatcher } from "./services/webhooks";
 import {
   ensureWorkspaceRegistered,
   resolveHiveHome,
 } from "./workspaces/registry";
 
+export {
+  createApiToken,
+  listApiTokens,
+  revokeApiToken,
+} from "./auth/tokens";
+export {
+  createUser,
+  deleteUser,
+  findUser,
+  listUsers,
+} from "./auth/users";
+export { DatabaseService } from "./db";
+export { API_TOKEN_SCOPES, type ApiTokenScope } from "./schema/api-tokens";
+
 const DEFAULT_SERVER_PORT = 3000;
 const DEFAULT_HOSTNAME = "localhost";
 const PORT = Number(process.env.PORT ?? DEFAULT_SERVER_PORT);
 const HOSTNAME = process.env.HOST ?? process.env.HOSTNAME ?? DEFAULT_HOSTNAME;
+const API_AUTH_MODE = resolveApiAuthMode(HOSTNAME);
 
 function formatHostForLocalUrl(hostname: string) {
   if (hostname === "0.0.0.0") {
@@ -92,6 +129,10 @@ function shouldIgnoreAutoRequestLog(ctx: {
   }
 
   const pathname = readPathname(ctx.request.url);
+  // Login links carry the token in the query string.
+  if (pathname === "/api/auth/login") {
+    return true;
+  }
   if (
     SILENCE_TERMINAL_TRAFFIC_LOGS &&
     TERMINAL_TRAFFIC_PATH_PATTERNS.some((pattern) => pattern.test(pathname))
@@ -117,7 +158,10 @@ export const DEFAULT_API_URL = `http://${formatHostForLocalUrl(HOSTNAME)}:${DEFA
 
 const moduleDir = dirname(fileURLToPath(import.meta.url));
 export const binaryDirectory = dirname(process.execPath);
-const forcedMigrationsDirectory = process.env.HIVE_MIGRATIONS_DIR;
+const forcedMigrationsDirectory =
+  databaseEngine === "postgres"
+    ? process.env.HIVE_PG_MIGRATIONS_DIR
+    : process.env.HIVE_MIGRATIONS_DIR;
 const hiveHome = resolveHiveHome();
 export const pidFilePath =
   process.env.HIVE_PID_FILE ?? join(hiveHome, "hive.pid");
@@ -170,10 +214,11 @@ const resolveCorsOrigin = (request: Request) => {
 };
 
 const resolveMigrationsDirectory = () => {
+  const directoryName = resolveMigrationsDirectoryName(databaseEngine);
   const candidates = [
     forcedMigrationsDirectory,
-    join(binaryDirectory, "migrations"),
-    join(moduleDir, "migrations"),
+    join(binaryDirectory, directoryName),
+    join(moduleDir, directoryName),
   ].filter((dir): dir is string => Boolean(dir));
 
   for (const directory of candidates) {
@@ -187,10 +232,10 @@ const resolveMigrationsDirectory = () => {
   );
 };
 
-const runMigrations = async (): Promise<void> => {
+export const runMigrations = async (): Promise<void> => {
   try {
     const migrationsFolder = resolveMigrationsDirectory();
-    await migrate(DatabaseService.db, { migrationsFolder });
+    await migrateDatabase(engineDatabase, migrationsFolder);
     process.stderr.write("Database migrations applied.\n");
   } catch (error) {
     process.stderr.write(
@@ -215,6 +260,11 @@ export const cleanupPidFile = () => {
   }
 };
 
+// Agent tools authenticate with per-cell tokens written into the worktree.
+// Older servers exported a shared token instead; drop any inherited copy so
+// services, terminals and hooks never see it.
+delete process.env.HIVE_INTERNAL_API_TOKEN;
+
 const createApp = () =>
   new Elysia()
     .use(
@@ -223,6 +273,13 @@ const createApp = () =>
         autoLogging: {
           ignore: shouldIgnoreAutoRequestLog,
         },
+        serializers: {
+          request: (request: Request) => ({
+            method: request.method,
+            url: redactRequestUrl(request.url),
+            referrer: request.headers.get("referer"),
+          }),
+        },
       })
     )
 
@@ -233,15 +290,25 @@ const createApp = () =>
         credentials: true,
       })
     )
+    .use(createApiAuthPlugin({ mode: API_AUTH_MODE }))
+    .use(createCellIdlePlugin())
     .get("/health", () => ({ service: "hive", status: "ok" }))
     .get("/api/example", () => ({
       message: "Hello from Elysia!",
       timestamp: Date.now(),
     }))
+    .use(createAuthRoutes({ mode: API_AUTH_MODE }))
     .use(linearRoutes)
     .use(templatesRoutes)
     .use(workspacesRoutes)
+    .use(userRoutes)
     .use(cellsRoutes)
+    .use(cellGitRoutes)
+    .use(cellSnapshotRoutes)
+    .use(cellTransferRoutes)
+    .use(cellShareRoutes)
+    .use(webhookRoutes)
+    .use(secretRoutes)
     .use(agentsRoutes);
 
 export type App = ReturnType<typeof createApp>;
@@ -250,6 +317,9 @@ let shuttingDown = false;
 let signalsRegistered = false;
 
 const shutdown = async (): Promise<void> => {
+  webhookDispatcher.stop();
+  cellIdleMonitor.stop();
+  configWatcher.stop();
   await ServiceSupervisorService.stopAll();
   chatTerminalService.stopAll();
   cellTerminalService.stopAll();
@@ -336,6 +406,42 @@ const bootstrapSupervisor = async (): Promise<void> => {
   }
 };
 
+const startWebhookDispatcher = async (): Promise<void> => {
+  try {
+    await webhookDispatcher.start();
+  } catch (failure) {
+    process.stderr.write(
+      `Failed to start webhook delivery: ${
+        failure instanceof Error ? failure.message : String(failure)
+      }\n`
+    );
+  }
+};
+
+const startIdleMonitor = async (): Promise<void> => {
+  try {
+    await cellIdleMonitor.start();
+  } catch (failure) {
+    process.stderr.write(
+      `Failed to start idle cell monitor: ${
+        failure instanceof Error ? failure.message : String(failure)
+      }\n`
+    );
+  }
+};
+
+const startConfigWatcher = async (): Promise<void> => {
+  try {
+    await configWatcher.start();
+  } catch (failure) {
+    process.stderr.write(
+      `Failed to watch workspace configs: ${
+        failure instanceof Error ? failure.message : String(failure)
+      }\n`
+    );
+  }
+};
+
 const resumeProvisioning = async (): Promise<void> => {
   await resumeSpawningCells();
 };
@@ -347,6 +453,9 @@ const startAllServices = async (): Promise<void> => {
   }
 
   for (const cell of allCells) {
+    if (cell.status === "suspended") {
+      continue;
+    }
     try {
       await ServiceSupervisorService.startCellServices(cell.id);
     } catch (failure) {
@@ -382,11 +491,31 @@ const runStartupRecoveryTask = async (
   }
 };
 
+const reportApiAuthMode = async (): Promise<void> => {
+  if (API_AUTH_MODE === "off") {
+    return;
+  }
+
+  const activeTokens = (await listApiTokens(DatabaseService.db)).filter(
+    (token) => !token.revokedAt
+  );
+  process.stderr.write("API token authentication is required.\n");
+  if (activeTokens.length === 0) {
+    process.stderr.write(
+      "No API tokens exist yet. Create one with `hive tokens create <name> --scope admin`.\n"
+    );
+  }
+};
+
 const bootstrapServerCore = async (workspaceRoot: string): Promise<void> => {
   await runMigrations();
+  await reportApiAuthMode();
   await registerWorkspace(workspaceRoot);
   await startOpencodeServer(workspaceRoot);
+  await startWebhookDispatcher();
   await bootstrapSupervisor();
+  await startIdleMonitor();
+  await startConfigWatcher();
 };
 
 const runStartupRecoveryTasks = async (): Promise<void> => {
@@ -25,6 +25,7 @@ export type CellActivityPage = {
     type: ActivityEventType;
     source: string | null;
     toolName: string | null;
+    userId: string | null;
     metadata: Record<string, unknown>;
     createdAt: string;
   }>;
@@ -80,10 +81,12 @@ export async function fetchCellActivityPage(args: {
   limit: number;
   types: ActivityEventType[] | null;
   cursor: ActivityCursor | null;
+  userId?: string | null;
 }): Promise<CellActivityPage> {
   const whereClause = and(
     eq(cellActivityEvents.cellId, args.cellId),
     args.types ? inArray(cellActivityEvents.type, args.types) : undefined,
+    args.userId ? eq(cellActivityEvents.userId, args.userId) : undefined,
     args.cursor
       ? or(
           lt(cellActivityEvents.createdAt, args.cursor.createdAt),
@@ -122,6 +125,7 @@ export async function fetchCellActivityPage(args: {
       type: event.type,
       source: event.source,
       toolName: event.toolName,
+      userId: event.userId,
       metadata: event.metadata,
       createdAt: event.createdAt.toISOString(),
     })),
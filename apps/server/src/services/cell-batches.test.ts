@@ -0,0 +1,218 @@
+import { describe, expect, test, vi } from "vitest";
+import {
+  type CellBatchDependencies,
+  type CellBatchEvent,
+  CellBatchManifestError,
+  createCellBatchService,
+  parseCellBatchManifest,
+  resolveManifestSpawnFrom,
+} from "./cell-batches";
+
+const flush = () => new Promise((resolve) => setTimeout(resolve, 0));
+
+const deferred = () => {
+  let resolve: () => void = () => 0;
+  const promise = new Promise<void>((res) => {
+    resolve = res;
+  });
+  return { promise, resolve };
+};
+
+describe("parseCellBatchManifest", () => {
+  test("reads a YAML manifest with a default template", () => {
+    const manifest = parseCellBatchManifest(`
+concurrency: 2
+templateId: basic
+cells:
+  - name: refactor-api
+    spawnFrom: branch:main
+    initialPrompt: Move the API client to fetch
+  - name: refactor-web
+    templateId: web
+    startMode: build
+`);
+
+    expect(manifest).toEqual({
+      concurrency: 2,
+      cells: [
+        {
+          name: "refactor-api",
+          templateId: "basic",
+          spawnFrom: "branch:main",
+          initialPrompt: "Move the API client to fetch",
+        },
+        { name: "refactor-web", templateId: "web", startMode: "build" },
+      ],
+    });
+  });
+
+  test("accepts a JSON list of cells", () => {
+    const manifest = parseCellBatchManifest(
+      JSON.stringify([{ name: "one", templateId: "basic" }])
+    );
+    expect(manifest.cells).toEqual([{ name: "one", templateId: "basic" }]);
+  });
+
+  test("points at the entry that is invalid", () => {
+    expect(() =>
+      parseCellBatchManifest([
+        { name: "one", templateId: "basic" },
+        { name: "two", templateId: "basic", spawnFrom: "tag:v1" },
+      ])
+    ).toThrow(/cells\[1\]\.spawnFrom/);
+    expect(() => parseCellBatchManifest([{ name: "one" }])).toThrow(
+      CellBatchManifestError
+    );
+    expect(() => parseCellBatchManifest("cells: [")).toThrow(
+      /not valid YAML or JSON/
+    );
+  });
+});
+
+describe("resolveManifestSpawnFrom", () => {
+  test("maps manifest values to create-cell fields", () => {
+    expect(resolveManifestSpawnFrom()).toEqual({});
+    expect(resolveManifestSpawnFrom("head")).toEqual({ spawnFromMode: "head" });
+    expect(resolveManifestSpawnFrom("pr:42")).toEqual({
+      spawnFromMode: "pr",
+      spawnFromValue: "42",
+    });
+  });
+});
+
+describe("createCellBatchService", () => {
+  const createDeps = (
+    overrides: Partial<CellBatchDependencies> = {}
+  ): CellBatchDependencies => ({
+    createCell: vi.fn(({ entry }) =>
+      Promise.resolve({ id: `${entry.name}-id` })
+    ),
+    waitForCellReady: vi.fn(() => Promise.resolve()),
+    readInitialPromptSentAt: vi.fn(() => Promise.resolve(new Date())),
+    ...overrides,
+  });
+
+  test("never runs more cells than the concurrency limit", async () => {
+    const pending = new Map<string, ReturnType<typeof deferred>>();
+    let active = 0;
+    let peak = 0;
+    const service = createCellBatchService(
+      createDeps({
+        waitForCellReady: (cellId) => {
+          active += 1;
+          peak = Math.max(peak, active);
+          const wait = deferred();
+          pending.set(cellId, wait);
+          return wait.promise.finally(() => {
+            active -= 1;
+          });
+        },
+      })
+    );
+
+    const batch = service.start({
+      workspaceId: "ws",
+      concurrency: 2,
+      manifest: {
+        cells: ["a", "b", "c", "d"].map((name) => ({
+          name,
+          templateId: "basic",
+        })),
+      },
+    });
+    expect(batch.counts.queued).toBe(4);
+
+    while (service.get(batch.id)?.status === "running") {
+      await flush();
+      for (const [cellId, wait] of pending) {
+        pending.delete(cellId);
+        wait.resolve();
+      }
+    }
+
+    expect(peak).toBe(2);
+    expect(service.get(batch.id)?.counts.ready).toBe(4);
+  });
+
+  test("forwards initial prompts and reports failures per cell", async () => {
+    const deps = createDeps({
+      waitForCellReady: (cellId) =>
+        cellId === "broken-id"
+          ? Promise.reject(new Error("bun install exited with 1"))
+          : Promise.resolve(),
+    });
+    const service = createCellBatchService(deps);
+    const events: CellBatchEvent[] = [];
+
+    const batch = service.start({
+      workspaceId: "ws",
+      manifest: {
+        cells: [
+          { name: "fix", templateId: "basic", initialPrompt: "Fix the bug" },
+          { name: "broken", templateId: "basic", initialPrompt: "Never sent" },
+        ],
+      },
+    });
+    const unsubscribe = service.subscribe(batch.id, (event) => {
+      events.push(event);
+    });
+    while (service.get(batch.id)?.status === "running") {
+      await flush();
+    }
+    unsubscribe();
+
+    expect(deps.createCell).toHaveBeenCalledWith(
+      expect.objectContaining({
+        entry: expect.objectContaining({ initialPrompt: "Fix the bug" }),
+      })
+    );
+
+    const finished = service.get(batch.id);
+    expect(finished?.items).toEqual([
+      {
+        index: 0,
+        name: "fix",
+        status: "ready",
+        cellId: "fix-id",
+        promptSent: true,
+        error: null,
+      },
+      {
+        index: 1,
+        name: "broken",
+        status: "failed",
+        cellId: "broken-id",
+        promptSent: false,
+        error: "bun install exited with 1",
+      },
+    ]);
+    expect(events.at(-1)).toMatchObject({
+      type: "completed",
+      batch: { counts: { ready: 1, failed: 1 } },
+    });
+  });
+
+  test("reports prompts as sent only once delivered", async () => {
+    const service = createCellBatchService(
+      createDeps({
+        readInitialPromptSentAt: vi.fn(() => Promise.resolve(null)),
+      }),
+      { promptDeliveryTimeoutMs: 0 }
+    );
+
+    const batch = service.start({
+      workspaceId: "ws",
+      manifest: {
+        cells: [{ name: "slow", templateId: "basic", initialPrompt: "Wait" }],
+      },
+    });
+    while (service.get(batch.id)?.status === "running") {
+      await flush();
+    }
+
+    expect(service.get(batch.id)?.items[0]).toMatchObject({
+      status: "ready",
+      promptSent: false,
+    });
+  });
+});
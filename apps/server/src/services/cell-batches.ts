@@ -0,0 +1,405 @@
+import { randomUUID } from "node:crypto";
+import { EventEmitter } from "node:events";
+import { parse as parseYaml } from "yaml";
+import { z } from "zod";
+import type { ApiPrincipal } from "../auth/plugin";
+
+export const DEFAULT_CELL_BATCH_CONCURRENCY = 3;
+export const MAX_CELL_BATCH_CONCURRENCY = 20;
+export const MAX_CELL_BATCH_SIZE = 100;
+const RETAINED_FINISHED_BATCHES = 20;
+const PROMPT_DELIVERY_TIMEOUT_MS = 30_000;
+const PROMPT_DELIVERY_POLL_MS = 500;
+
+const SPAWN_FROM_PATTERN = /^(branch|pr|cell):(.+)$/;
+
+const manifestEntrySchema = z
+  .object({
+    name: z.string().trim().min(1).max(255),
+    description: z.string().optional(),
+    templateId: z.string().trim().min(1).optional(),
+    startMode: z.enum(["plan", "build"]).optional(),
+    spawnFrom: z
+      .string()
+      .trim()
+      .refine((value) => value === "head" || SPAWN_FROM_PATTERN.test(value), {
+        message: "Use head, branch:<name>, pr:<number> or cell:<id>",
+      })
+      .optional(),
+    initialPrompt: z.string().trim().min(1).optional(),
+  })
+  .strict();
+
+const manifestSchema = z
+  .object({
+    concurrency: z
+      .number()
+      .int()
+      .min(1)
+      .max(MAX_CELL_BATCH_CONCURRENCY)
+      .optional(),
+    templateId: z.string().trim().min(1).optional(),
+    cells: z.array(manifestEntrySchema).min(1).max(MAX_CELL_BATCH_SIZE),
+  })
+  .strict();
+
+export type CellBatchManifestEntry = z.infer<typeof manifestEntrySchema>;
+
+export type CellBatchManifest = {
+  concurrency?: number;
+  cells: Array<CellBatchManifestEntry & { templateId: string }>;
+};
+
+export class CellBatchManifestError extends Error {
+  constructor(message: string) {
+    super(message);
+    this.name = "CellBatchManifestError";
+  }
+}
+
+const formatIssuePath = (path: PropertyKey[]) =>
+  path.reduce<string>((formatted, segment) => {
+    if (typeof segment === "number") {
+      return `${formatted}[${segment}]`;
+    }
+    return formatted ? `${formatted}.${String(segment)}` : String(segment);
+  }, "");
+
+/**
+ * Reads a batch manifest. The manifest is either a list of cells or an object
+ * with `cells`, an optional `concurrency` and a `templateId` that entries
+ * without their own fall back to. Strings are parsed as YAML, which also
+ * covers JSON.
+ */
+export function parseCellBatchManifest(input: unknown): CellBatchManifest {
+  let raw = input;
+  if (typeof input === "string") {
+    try {
+      raw = parseYaml(input);
+    } catch (error) {
+      throw new CellBatchManifestError(
+        `Manifest is not valid YAML or JSON: ${
+          error instanceof Error ? error.message : String(error)
+        }`
+      );
+    }
+  }
+
+  const parsed = manifestSchema.safeParse(
+    Array.isArray(raw) ? { cells: raw } : raw
+  );
+  if (!parsed.success) {
+    const [issue] = parsed.error.issues;
+    const location = issue ? formatIssuePath(issue.path) : "";
+    throw new CellBatchManifestError(
+      `Invalid manifest${location ? ` at ${location}` : ""}: ${
+        issue?.message ?? "unknown error"
+      }`
+    );
+  }
+
+  const { concurrency, templateId: defaultTemplateId } = parsed.data;
+  const entries = parsed.data.cells.map((entry, index) => {
+    const templateId = entry.templateId ?? defaultTemplateId;
+    if (!templateId) {
+      throw new CellBatchManifestError(
+        `Invalid manifest at cells[${index}].templateId: Required`
+      );
+    }
+    return { ...entry, templateId };
+  });
+
+  return {
+    ...(concurrency ? { concurrency } : {}),
+    cells: entries,
+  };
+}
+
+/** Splits a manifest `spawnFrom` value into the create-cell fields. */
+export function resolveManifestSpawnFrom(spawnFrom?: string): {
+  spawnFromMode?: "head" | "branch" | "pr" | "cell";
+  spawnFromValue?: string;
+} {
+  if (!spawnFrom || spawnFrom === "head") {
+    return spawnFrom ? { spawnFromMode: "head" } : {};
+  }
+  const match = spawnFrom.match(SPAWN_FROM_PATTERN);
+  if (!(match?.[1] && match[2])) {
+    return {};
+  }
+  return {
+    spawnFromMode: match[1] as "branch" | "pr" | "cell",
+    spawnFromValue: match[2],
+  };
+}
+
+export type CellBatchItemStatus =
+  | "queued"
+  | "creating"
+  | "provisioning"
+  | "ready"
+  | "failed";
+
+export type CellBatchItem = {
+  index: number;
+  name: string;
+  status: CellBatchItemStatus;
+  cellId: string | null;
+  promptSent: boolean;
+  error: string | null;
+};
+
+export type CellBatchSnapshot = {
+  id: string;
+  workspaceId: string;
+  status: "running" | "completed";
+  concurrency: number;
+  createdAt: string;
+  finishedAt: string | null;
+  total: number;
+  counts: Record<CellBatchItemStatus, number>;
+  items: CellBatchItem[];
+};
+
+export type CellBatchEvent =
+  | { type: "item"; batchId: string; item: CellBatchItem }
+  | { type: "completed"; batch: CellBatchSnapshot };
+
+export type CellBatchDependencies = {
+  /**
+   * Creates the cell, initial prompt included, and returns once its record
+   * exists.
+   */
+  createCell: (args: {
+    workspaceId: string;
+    entry: CellBatchManifest["cells"][number];
+    principal: ApiPrincipal | null;
+  }) => Promise<{ id: string }>;
+  /** Resolves once provisioning finished; rejects when setup failed. */
+  waitForCellReady: (cellId: string, workspaceId: string) => Promise<void>;
+  /** When the cell's agent received its initial prompt, or null if not yet. */
+  readInitialPromptSentAt: (cellId: string) => Promise<Date | null>;
+};
+
+export type CellBatchOptions = {
+  /** How long a ready cell may take to deliver its initial prompt. */
+  promptDeliveryTimeoutMs?: number;
+};
+
+type CellBatchRecord = {
+  snapshot: CellBatchSnapshot;
+  principal: ApiPrincipal | null;
+};
+
+const countItems = (items: CellBatchItem[]) => {
+  const counts: Record<CellBatchItemStatus, number> = {
+    queued: 0,
+    creating: 0,
+    provisioning: 0,
+    ready: 0,
+    failed: 0,
+  };
+  for (const item of items) {
+    counts[item.status] += 1;
+  }
+  return counts;
+};
+
+const errorMessage = (error: unknown) =>
+  error instanceof Error ? error.message : String(error);
+
+/**
+ * Runs manifest batches: creates cells a few at a time, waits for each to
+ * finish provisioning and publishes progress.
+ * Batches live in memory; the cells they create are ordinary cells and
+ * survive a restart, the progress record does not.
+ */
+export function createCellBatchService(
+  deps: CellBatchDependencies,
+  options: CellBatchOptions = {}
+) {
+  const promptDeliveryTimeoutMs =
+    options.promptDeliveryTimeoutMs ?? PROMPT_DELIVERY_TIMEOUT_MS;
+  const batches = new Map<string, CellBatchRecord>();
+  const emitter = new EventEmitter();
+  emitter.setMaxListeners(0);
+
+  const snapshotOf = (record: CellBatchRecord): CellBatchSnapshot => ({
+    ...record.snapshot,
+    counts: countItems(record.snapshot.items),
+    items: record.snapshot.items.map((item) => ({ ...item })),
+  });
+
+  const updateItem = (
+    record: CellBatchRecord,
+    index: number,
+    changes: Partial<CellBatchItem>
+  ) => {
+    const current = record.snapshot.items[index];
+    if (!current) {
+      return;
+    }
+    const item = { ...current, ...changes };
+    record.snapshot.items[index] = item;
+    emitter.emit(record.snapshot.id, {
+      type: "item",
+      batchId: record.snapshot.id,
+      item: { ...item },
+    } satisfies CellBatchEvent);
+  };
+
+  const pruneFinishedBatches = () => {
+    const finished = [...batches.values()].filter(
+      (record) => record.snapshot.status === "completed"
+    );
+    for (const record of finished.slice(
+      0,
+      Math.max(0, finished.length - RETAINED_FINISHED_BATCHES)
+    )) {
+      batches.delete(record.snapshot.id);
+    }
+  };
+
+  const waitForPromptDelivery = async (cellId: string) => {
+    const deadline = Date.now() + promptDeliveryTimeoutMs;
+    for (;;) {
+      if (await deps.readInitialPromptSentAt(cellId)) {
+        return true;
+      }
+      if (Date.now() >= deadline) {
+        return false;
+      }
+      await new Promise((resolve) =>
+        setTimeout(resolve, PROMPT_DELIVERY_POLL_MS)
+      );
+    }
+  };
+
+  const runItem = async (
+    record: CellBatchRecord,
+    entry: CellBatchManifest["cells"][number],
+    index: number
+  ) => {
+    updateItem(record, index, { status: "creating" });
+    let cellId: string | null = null;
+    try {
+      const cell = await deps.createCell({
+        workspaceId: record.snapshot.workspaceId,
+        entry,
+        principal: record.principal,
+      });
+      cellId = cell.id;
+      updateItem(record, index, { status: "provisioning", cellId });
+
+      await deps.waitForCellReady(cellId, record.snapshot.workspaceId);
+      // The prompt is dispatched in the background once the agent session
+      // is up, so only report it as sent once its delivery was recorded.
+      const promptSent = entry.initialPrompt
+        ? await waitForPromptDelivery(cellId)
+        : false;
+      updateItem(record, index, { status: "ready", promptSent });
+    } catch (error) {
+      updateItem(record, index, {
+        status: "failed",
+        cellId,
+        error: errorMessage(error),
+      });
+    }
+  };
+
+  const run = async (
+    record: CellBatchRecord,
+    entries: CellBatchManifest["cells"]
+  ) => {
+    let next = 0;
+    const worker = async () => {
+      while (next < entries.length) {
+        const index = next;
+        next += 1;
+        const entry = entries[index];
+        if (entry) {
+          await runItem(record, entry, index);
+        }
+      }
+    };
+
+    const workerCount = Math.min(record.snapshot.concurrency, entries.length);
+    await Promise.all(Array.from({ length: workerCount }, () => worker()));
+
+    record.snapshot.status = "completed";
+    record.snapshot.finishedAt = new Date().toISOString();
+    emitter.emit(record.snapshot.id, {
+      type: "completed",
+      batch: snapshotOf(record),
+    } satisfies CellBatchEvent);
+    pruneFinishedBatches();
+  };
+
+  return {
+    start(args: {
+      workspaceId: string;
+      manifest: CellBatchManifest;
+      concurrency?: number;
+      principal?: ApiPrincipal | null;
+    }): CellBatchSnapshot {
+      const { cells } = args.manifest;
+      const concurrency = Math.min(
+        args.concurrency ??
+          args.manifest.concurrency ??
+          DEFAULT_CELL_BATCH_CONCURRENCY,
+        MAX_CELL_BATCH_CONCURRENCY
+      );
+      const items = cells.map<CellBatchItem>((entry, index) => ({
+        index,
+        name: entry.name,
+        status: "queued",
+        cellId: null,
+        promptSent: false,
+        error: null,
+      }));
+      const record: CellBatchRecord = {
+        principal: args.principal ?? null,
+        snapshot: {
+          id: randomUUID(),
+          workspaceId: args.workspaceId,
+          status: "running",
+          concurrency,
+          createdAt: new Date().toISOString(),
+          finishedAt: null,
+          total: items.length,
+          counts: countItems(items),
+          items,
+        },
+      };
+      batches.set(record.snapshot.id, record);
+
+      const snapshot = snapshotOf(record);
+      run(record, cells).catch(() => {
+        // runItem records every failure on its item, so nothing escapes here.
+      });
+      return snapshot;
+    },
+
+    get(batchId: string): CellBatchSnapshot | null {
+      const record = batches.get(batchId);
+      return record ? snapshotOf(record) : null;
+    },
+
+    /** The user who started the batch, or null when no user was involved. */
+    ownerOf(batchId: string): string | null {
+      return batches.get(batchId)?.principal?.userId ?? null;
+    },
+
+    subscribe(
+      batchId: string,
+      listener: (event: CellBatchEvent) => void
+    ): () => void {
+      emitter.on(batchId, listener);
+      return () => {
+        emitter.off(batchId, listener);
+      };
+    },
+  };
+}
+
+export type CellBatchService = ReturnType<typeof createCellBatchService>;
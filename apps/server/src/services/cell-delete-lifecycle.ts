@@ -14,6 +14,7 @@ import {
   describeWorktreeError,
   type WorktreeManagerError,
 } from "../worktree/manager";
+import { removeCellSnapshots } from "./cell-snapshots";
 import { emitCellStatusUpdate } from "./events";
 
 type DatabaseClient = DatabaseServiceType["db"];
@@ -62,6 +63,7 @@ type DeleteLifecycleArgs = {
       releasePorts: boolean;
     }
   ) => Promise<unknown>;
+  teardownCellServices?: (cellId: string) => Promise<unknown>;
   getWorktreeService: (workspaceId: string) => Promise<AsyncWorktreeManager>;
   log: DeleteLifecycleLogger;
   recordTimingEvent: (args: DeleteTimingEventArgs) => Promise<void>;
@@ -70,6 +72,8 @@ type DeleteLifecycleArgs = {
 const DELETE_CLOSE_AGENT_SESSION_TIMEOUT_MS = 15_000;
 const DELETE_CLOSE_TERMINALS_TIMEOUT_MS = 5000;
 const DELETE_STOP_SERVICES_TIMEOUT_MS = 30_000;
+const DELETE_TEARDOWN_SERVICES_TIMEOUT_MS = 60_000;
+const DELETE_REMOVE_SNAPSHOTS_TIMEOUT_MS = 30_000;
 const DELETE_REMOVE_WORKSPACE_TIMEOUT_MS = 120_000;
 const DELETE_REMOVE_RECORD_TIMEOUT_MS = 10_000;
 
@@ -290,6 +294,32 @@ async function deleteCellWithTiming(args: DeleteLifecycleArgs) {
       warnMessage: "Failed to stop services before cell removal",
     });
 
+    const teardownCellServices = args.teardownCellServices;
+    if (teardownCellServices) {
+      await runStep({
+        step: "teardown_services",
+        action: () => teardownCellServices(args.cell.id),
+        timeoutMs: DELETE_TEARDOWN_SERVICES_TIMEOUT_MS,
+        continueOnError: true,
+        warnMessage: "Failed to tear down service containers before removal",
+      });
+    }
+
+    // Snapshot refs live in the shared repository, so drop them while the
+    // worktree still exists to run git from.
+    await runStep({
+      step: "remove_snapshots",
+      action: () =>
+        removeCellSnapshots({
+          database: args.database,
+          cellId: args.cell.id,
+          workspacePath: args.cell.workspacePath,
+        }),
+      timeoutMs: DELETE_REMOVE_SNAPSHOTS_TIMEOUT_MS,
+      continueOnError: true,
+      warnMessage: "Failed to remove cell snapshots during deletion",
+    });
+
     await runStep({
       step: "remove_workspace",
       action: async () => {
@@ -0,0 +1,146 @@
+import { GitCommandError, runGit } from "./git";
+
+export const DEFAULT_GIT_REMOTE = "origin";
+const FALLBACK_BASE_BRANCH = "main";
+
+export type CellGitErrorKind =
+  | "nothing_to_commit"
+  | "missing_branch"
+  | "missing_remote"
+  | "uncommitted_changes"
+  | "rejected";
+
+export class CellGitError extends Error {
+  readonly kind: CellGitErrorKind;
+
+  constructor(kind: CellGitErrorKind, message: string) {
+    super(message);
+    this.name = "CellGitError";
+    this.kind = kind;
+  }
+}
+
+export type CellCommitResult = {
+  commit: string;
+  files: string[];
+};
+
+export type CellPushResult = {
+  remote: string;
+  branchName: string;
+  commit: string;
+};
+
+/**
+ * Stages the given paths (or every change, including untracked files) and
+ * commits them on the cell's current branch. With `files`, only those paths
+ * are committed; anything else already staged stays staged.
+ */
+export async function commitCellChanges(args: {
+  workspacePath: string;
+  message: string;
+  files?: string[];
+}): Promise<CellCommitResult> {
+  const { workspacePath, message } = args;
+  const files = args.files?.filter(Boolean) ?? [];
+  const pathspec = files.length ? ["--", ...files] : [];
+
+  await runGit(["add", "--all", ...pathspec], workspacePath);
+
+  const staged = await runGit(
+    ["diff", "--cached", "--name-only", ...pathspec],
+    workspacePath
+  );
+  const stagedFiles = staged.stdout.split("\n").filter(Boolean);
+  if (stagedFiles.length === 0) {
+    throw new CellGitError("nothing_to_commit", "No changes to commit");
+  }
+
+  await runGit(["commit", "--message", message, ...pathspec], workspacePath);
+  const { stdout: commit } = await runGit(["rev-parse", "HEAD"], workspacePath);
+
+  return { commit, files: stagedFiles };
+}
+
+export async function pushCellBranch(args: {
+  workspacePath: string;
+  branchName: string | null;
+  remote?: string;
+  force?: boolean;
+}): Promise<CellPushResult> {
+  const { workspacePath, branchName } = args;
+  if (!branchName) {
+    throw new CellGitError("missing_branch", "Cell has no branch to push");
+  }
+
+  const remote = args.remote ?? DEFAULT_GIT_REMOTE;
+  await resolveRemoteUrl(workspacePath, remote);
+
+  const pushArgs = ["push", "--set-upstream"];
+  if (args.force) {
+    pushArgs.push("--force-with-lease");
+  }
+  pushArgs.push(remote, `${branchName}:refs/heads/${branchName}`);
+
+  try {
+    await runGit(pushArgs, workspacePath);
+  } catch (error) {
+    if (error instanceof GitCommandError) {
+      throw new CellGitError(
+        "rejected",
+        `Push to ${remote} was rejected: ${error.stderr.trim() || error.message}`
+      );
+    }
+    throw error;
+  }
+
+  const { stdout: commit } = await runGit(
+    ["rev-parse", branchName],
+    workspacePath
+  );
+  return { remote, branchName, commit };
+}
+
+export async function resolveRemoteUrl(
+  workspacePath: string,
+  remote: string
+): Promise<string> {
+  try {
+    const { stdout } = await runGit(
+      ["remote", "get-url", remote],
+      workspacePath
+    );
+    return stdout.trim();
+  } catch {
+    throw new CellGitError(
+      "missing_remote",
+      `Git remote "${remote}" is not configured`
+    );
+  }
+}
+
+/**
+ * Uses the configured base branch, then the remote's default branch, and
+ * finally falls back to `main`.
+ */
+export async function resolveBaseBranch(args: {
+  workspacePath: string;
+  remote: string;
+  configured?: string;
+}): Promise<string> {
+  if (args.configured) {
+    return args.configured;
+  }
+
+  try {
+    const { stdout } = await runGit(
+      ["symbolic-ref", "--short", `refs/remotes/${args.remote}/HEAD`],
+      args.workspacePath
+    );
+    const prefix = `${args.remote}/`;
+    const branch = stdout.trim();
+    return branch.startsWith(prefix) ? branch.slice(prefix.length) : branch;
+  } catch {
+    return FALLBACK_BASE_BRANCH;
+  }
+}
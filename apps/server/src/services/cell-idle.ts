@@ -0,0 +1,367 @@
+import { randomUUID } from "node:crypto";
+import { and, eq, isNotNull } from "drizzle-orm";
+import { Elysia } from "elysia";
+import {
+  isAgentSessionWorking,
+  resumeAgentSessionForCell,
+  suspendAgentSessionForCell,
+} from "../agents/service";
+import { resolveRequestCell } from "../auth/access";
+import { hiveConfigService } from "../config/context";
+import type { Template } from "../config/schema";
+import {
+  DatabaseService,
+  type DatabaseService as DatabaseServiceType,
+} from "../db";
+import { cellActivityEvents } from "../schema/activity-events";
+import { type Cell, cells } from "../schema/cells";
+import { cellServices } from "../schema/services";
+import { emitCellStatusUpdate, subscribeToLifecycleEvents } from "./events";
+import { ServiceSupervisorService } from "./supervisor";
+
+type DatabaseClient = DatabaseServiceType["db"];
+
+export const DEFAULT_IDLE_SWEEP_INTERVAL_MS = 60_000;
+const MS_PER_MINUTE = 60_000;
+const ACTIVITY_SOURCE = "idle-monitor";
+
+export type CellIdleMonitorDependencies = {
+  db?: DatabaseClient;
+  now?: () => number;
+  loadTemplate?: (cell: Cell) => Promise<Template | undefined>;
+  /** Established connections on a port, as stable endpoint strings. */
+  probeConnections?: (port: number) => Promise<string[]>;
+  isAgentWorking?: (cellId: string) => boolean;
+  suspendAgent?: (cellId: string) => Promise<void>;
+  resumeAgent?: (cellId: string) => Promise<void>;
+  stopServices?: (
+    cellId: string,
+    options: { suspend: boolean }
+  ) => Promise<void>;
+  resumeServices?: (cellId: string) => Promise<void>;
+};
+
+const errorMessage = (error: unknown) =>
+  error instanceof Error ? error.message : String(error);
+
+const loadTemplateForCell = async (
+  cell: Cell
+): Promise<Template | undefined> => {
+  const config = await hiveConfigService.load(
+    cell.workspaceRootPath || cell.workspacePath
+  );
+  return config.templates[cell.templateId];
+};
+
+/**
+ * Lists established TCP connections on a port via `lsof`. Any connection
+ * that was not there on the previous sweep counts as service traffic.
+ */
+export async function probeEstablishedConnections(
+  port: number
+): Promise<string[]> {
+  if (process.platform === "win32") {
+    return [];
+  }
+
+  const child = Bun.spawn({
+    cmd: ["lsof", "-nP", `-iTCP:${port}`, "-sTCP:ESTABLISHED", "-Fn"],
+    stdout: "pipe",
+    stderr: "pipe",
+  });
+  const output = await new Response(child.stdout).text();
+  await child.exited;
+  return output
+    .split("\n")
+    .filter((line) => line.startsWith("n"))
+    .map((line) => line.slice(1));
+}
+
+/**
+ * Suspends cells whose template sets an `idle` policy once nothing has
+ * happened in them for `suspendAfterMinutes`: no agent activity, no
+ * terminal input and no new connections to their service ports. Suspending
+ * stops the services (leaving them `needs_resume`) and the agent runtime;
+ * the next request that targets the cell resumes both.
+ */
+export function createCellIdleMonitor({
+  db = DatabaseService.db,
+  now = Date.now,
+  loadTemplate = loadTemplateForCell,
+  probeConnections = probeEstablishedConnections,
+  isAgentWorking = isAgentSessionWorking,
+  suspendAgent = suspendAgentSessionForCell,
+  resumeAgent = resumeAgentSessionForCell,
+  stopServices = ServiceSupervisorService.stopCellServices,
+  resumeServices = ServiceSupervisorService.resumeCellServices,
+}: CellIdleMonitorDependencies = {}) {
+  const lastActivity = new Map<string, number>();
+  const knownConnections = new Map<string, Set<string>>();
+  const suspendedCells = new Set<string>();
+  const pendingResumes = new Map<string, Promise<void>>();
+  let sweepTimer: ReturnType<typeof setInterval> | null = null;
+  let unsubscribe: (() => void) | null = null;
+  let sweeping = false;
+
+  const recordActivity = (cellId: string) => {
+    lastActivity.set(cellId, now());
+  };
+
+  const recordEvent = async (
+    cellId: string,
+    type: "cell.suspend" | "cell.resume",
+    metadata: Record<string, unknown>
+  ) => {
+    await db.insert(cellActivityEvents).values({
+      id: randomUUID(),
+      cellId,
+      type,
+      source: ACTIVITY_SOURCE,
+      metadata,
+      createdAt: new Date(),
+    });
+  };
+
+  /** True when a service port saw a connection since the last sweep. */
+  const detectServiceTraffic = async (cellId: string) => {
+    const services = await db
+      .select({ id: cellServices.id, port: cellServices.port })
+      .from(cellServices)
+      .where(
+        and(
+          eq(cellServices.cellId, cellId),
+          eq(cellServices.status, "running"),
+          isNotNull(cellServices.port)
+        )
+      );
+
+    let active = false;
+    for (const service of services) {
+      if (typeof service.port !== "number") {
+        continue;
+      }
+      const current = new Set(
+        await probeConnections(service.port).catch(() => [])
+      );
+      const previous = knownConnections.get(service.id);
+      if (previous && [...current].some((entry) => !previous.has(entry))) {
+        active = true;
+      }
+      knownConnections.set(service.id, current);
+    }
+    return active;
+  };
+
+  async function suspend(cell: Cell, idleMinutes: number): Promise<boolean> {
+    const [updated] = await db
+      .update(cells)
+      .set({ status: "suspended" })
+      .where(and(eq(cells.id, cell.id), eq(cells.status, "ready")))
+      .returning();
+    if (!updated) {
+      return false;
+    }
+
+    suspendedCells.add(cell.id);
+    lastActivity.delete(cell.id);
+    emitCellStatusUpdate({
+      workspaceId: cell.workspaceId,
+      cellId: cell.id,
+      status: "suspended",
+    });
+
+    try {
+      await suspendAgent(cell.id);
+    } catch (error) {
+      process.stderr.write(
+        `[idle] Failed to stop agent for ${cell.id}: ${errorMessage(error)}\n`
+      );
+    }
+    await stopServices(cell.id, { suspend: true });
+    await recordEvent(cell.id, "cell.suspend", { idleMinutes });
+    return true;
+  }
+
+  const restartCell = async (cell: Cell, trigger: string) => {
+    try {
+      await resumeServices(cell.id);
+      if (cell.resumeAgentSessionOnStartup) {
+        await resumeAgent(cell.id);
+      }
+      await recordEvent(cell.id, "cell.resume", { trigger });
+    } catch (error) {
+      process.stderr.write(
+        `[idle] Failed to resume ${cell.id}: ${errorMessage(error)}\n`
+      );
+    }
+  };
+
+  async function resumeCell(cellId: string, trigger: string): Promise<void> {
+    const [cell] = await db
+      .update(cells)
+      .set({ status: "ready" })
+      .where(and(eq(cells.id, cellId), eq(cells.status, "suspended")))
+      .returning();
+    suspendedCells.delete(cellId);
+    if (!cell) {
+      return;
+    }
+
+    recordActivity(cellId);
+    emitCellStatusUpdate({
+      workspaceId: cell.workspaceId,
+      cellId,
+      status: "ready",
+    });
+    // Services come back in the background so the request that woke the
+    // cell does not wait for their readiness probes.
+    restartCell(cell, trigger).catch(() => {
+      // restartCell reports its own failures.
+    });
+  }
+
+  /**
+   * Marks a suspended cell ready again and restarts its services and agent
+   * in the background. Concurrent callers share one resume.
+   */
+  const resume = (cellId: string, trigger = "request"): Promise<void> => {
+    const pending = pendingResumes.get(cellId);
+    if (pending) {
+      return pending;
+    }
+    const next = resumeCell(cellId, trigger).finally(() => {
+      pendingResumes.delete(cellId);
+    });
+    pendingResumes.set(cellId, next);
+    return next;
+  };
+
+  /**
+   * Checks every ready cell against its template's idle policy. A cell seen
+   * for the first time starts its idle clock now, so restarting the server
+   * never suspends cells straight away.
+   */
+  async function sweep(): Promise<string[]> {
+    if (sweeping) {
+      return [];
+    }
+    sweeping = true;
+    const suspended: string[] = [];
+    try {
+      const readyCells = await db
+        .select()
+        .from(cells)
+        .where(eq(cells.status, "ready"));
+      for (const cell of readyCells) {
+        try {
+          const policy = (await loadTemplate(cell))?.idle;
+          if (!policy) {
+            continue;
+          }
+          const busy =
+            isAgentWorking(cell.id) || (await detectServiceTraffic(cell.id));
+          if (busy) {
+            recordActivity(cell.id);
+          }
+          const last = lastActivity.get(cell.id);
+          if (last === undefined) {
+            recordActivity(cell.id);
+            continue;
+          }
+          if (now() - last < policy.suspendAfterMinutes * MS_PER_MINUTE) {
+            continue;
+          }
+          if (await suspend(cell, policy.suspendAfterMinutes)) {
+            suspended.push(cell.id);
+          }
+        } catch (error) {
+          process.stderr.write(
+            `[idle] Failed to check ${cell.id}: ${errorMessage(error)}\n`
+          );
+        }
+      }
+    } finally {
+      sweeping = false;
+    }
+    return suspended;
+  }
+
+  return {
+    recordActivity,
+    sweep,
+    resume,
+    isSuspended: (cellId: string) => suspendedCells.has(cellId),
+    hasSuspendedCells: () => suspendedCells.size > 0,
+
+    async start(
+      intervalMs: number = DEFAULT_IDLE_SWEEP_INTERVAL_MS
+    ): Promise<void> {
+      if (unsubscribe) {
+        return;
+      }
+      unsubscribe = subscribeToLifecycleEvents((lifecycle) => {
+        if (
+          lifecycle.type === "agent.status" ||
+          (lifecycle.type === "cell.status" &&
+            lifecycle.event.status === "ready")
+        ) {
+          recordActivity(lifecycle.event.cellId);
+        }
+      });
+
+      const suspended = await db
+        .select({ id: cells.id })
+        .from(cells)
+        .where(eq(cells.status, "suspended"));
+      for (const { id } of suspended) {
+        suspendedCells.add(id);
+      }
+
+      sweepTimer = setInterval(() => {
+        sweep().catch((error) => {
+          process.stderr.write(
+            `[idle] Idle sweep failed: ${errorMessage(error)}\n`
+          );
+        });
+      }, intervalMs);
+    },
+
+    stop(): void {
+      unsubscribe?.();
+      unsubscribe = null;
+      if (sweepTimer) {
+        clearInterval(sweepTimer);
+        sweepTimer = null;
+      }
+    },
+  };
+}
+
+export type CellIdleMonitor = ReturnType<typeof createCellIdleMonitor>;
+
+export const cellIdleMonitor = createCellIdleMonitor();
+
+/** Resets the idle clock of a cell, e.g. on terminal input. */
+export const recordCellActivity = (cellId: string) => {
+  cellIdleMonitor.recordActivity(cellId);
+};
+
+/**
+ * Opening a suspended cell in the UI or the CLI, or sending its agent a
+ * message, resumes it. The request proceeds once the cell is ready again;
+ * its services keep starting in the background.
+ */
+export function createCellIdlePlugin({
+  db = DatabaseService.db,
+  monitor = cellIdleMonitor,
+}: { db?: DatabaseClient; monitor?: CellIdleMonitor } = {}) {
+  return new Elysia({ name: "cell-idle" }).onRequest(async ({ request }) => {
+    if (!monitor.hasSuspendedCells()) {
+      return;
+    }
+    const cell = await resolveRequestCell(db, new URL(request.url).pathname);
+    if (cell && monitor.isSuspended(cell.id)) {
+      await monitor.resume(cell.id);
+    }
+  });
+}
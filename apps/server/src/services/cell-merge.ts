@@ -0,0 +1,373 @@
+import { mkdtemp, rm } from "node:fs/promises";
+import { tmpdir } from "node:os";
+import { join } from "node:path";
+import { CellGitError } from "./cell-git";
+import { type DiffFileSummary, summarizeCommitRange } from "./diff-service";
+import {
+  GitCommandError,
+  isAncestorCommit,
+  listConflictedPaths,
+  runGit,
+} from "./git";
+
+export type CellMergeStrategy = "merge" | "rebase";
+export type CellMergeStatus = "clean" | "conflicted" | "up_to_date";
+
+export type CellMergeResult = {
+  status: CellMergeStatus;
+  strategy: CellMergeStrategy;
+  dryRun: boolean;
+  applied: boolean;
+  branchName: string;
+  baseBranch: string;
+  baseCommit: string;
+  headCommit: string;
+  resultCommit: string | null;
+  conflicts: DiffFileSummary[];
+};
+
+type BaseTip = {
+  commit: string;
+  /** False when only `<remote>/<base>` exists; applying creates the branch. */
+  local: boolean;
+};
+
+type ScratchOutcome =
+  | { clean: true; resultCommit: string }
+  | { clean: false; conflicts: string[] };
+
+/**
+ * Merges (or rebases) the cell branch onto its base branch inside a scratch
+ * worktree so neither the cell workspace nor the base checkout is touched
+ * until the result is known to be clean. Rebase conflicts are reported for
+ * the first commit that fails to apply. Only committed work is merged, so a
+ * cell worktree with uncommitted changes is refused.
+ */
+export async function mergeCellIntoBase(args: {
+  workspacePath: string;
+  branchName: string | null;
+  baseBranch: string;
+  remote: string;
+  strategy?: CellMergeStrategy;
+  dryRun?: boolean;
+  message?: string;
+}): Promise<CellMergeResult> {
+  const { workspacePath, branchName, baseBranch } = args;
+  if (!branchName) {
+    throw new CellGitError("missing_branch", "Cell has no branch to merge");
+  }
+
+  const { stdout: status } = await runGit(
+    ["status", "--porcelain", "--untracked-files=no"],
+    workspacePath
+  );
+  if (status.trim()) {
+    throw new CellGitError(
+      "uncommitted_changes",
+      "Commit or stash the changes in the cell worktree before merging"
+    );
+  }
+
+  const strategy = args.strategy ?? "merge";
+  const dryRun = args.dryRun ?? false;
+  const base = await resolveBaseTip(workspacePath, args.remote, baseBranch);
+  const baseCommit = base.commit;
+  const headCommit = await resolveLocalBranch(workspacePath, branchName);
+  const result: CellMergeResult = {
+    status: "clean",
+    strategy,
+    dryRun,
+    applied: false,
+    branchName,
+    baseBranch,
+    baseCommit,
+    headCommit,
+    resultCommit: null,
+    conflicts: [],
+  };
+
+  if (await isAncestorCommit(workspacePath, headCommit, baseCommit)) {
+    return { ...result, status: "up_to_date" };
+  }
+
+  const outcome = await withScratchWorktree(
+    workspacePath,
+    baseCommit,
+    (scratchPath) =>
+      strategy === "rebase"
+        ? rebaseInScratch(scratchPath, { baseCommit, headCommit })
+        : mergeInScratch(scratchPath, {
+            headCommit,
+            message:
+              args.message ?? `Merge branch '${branchName}' into ${baseBranch}`,
+          })
+  );
+
+  if (!outcome.clean) {
+    const { stdout: mergeBase } = await runGit(
+      ["merge-base", baseCommit, headCommit],
+      workspacePath
+    );
+    const conflicts = await summarizeCommitRange({
+      cwd: workspacePath,
+      from: mergeBase,
+      to: headCommit,
+      paths: outcome.conflicts,
+    });
+    return { ...result, status: "conflicted", conflicts };
+  }
+
+  if (dryRun) {
+    return result;
+  }
+
+  if (base.local) {
+    await advanceBranch({
+      workspacePath,
+      branch: baseBranch,
+      expectedCommit: baseCommit,
+      targetCommit: outcome.resultCommit,
+    });
+  } else {
+    await createTrackingBranch({
+      workspacePath,
+      remote: args.remote,
+      branch: baseBranch,
+      targetCommit: outcome.resultCommit,
+    });
+  }
+
+  return { ...result, applied: true, resultCommit: outcome.resultCommit };
+}
+
+/**
+ * The base branch tip. A base that only exists on the remote (say `main` in
+ * a clone that never checked it out) falls back to `<remote>/<base>`,
+ * fetching it once when the remote-tracking ref is missing too.
+ */
+async function resolveBaseTip(
+  workspacePath: string,
+  remote: string,
+  branch: string
+): Promise<BaseTip> {
+  const local = await resolveCommit(workspacePath, `refs/heads/${branch}`);
+  if (local) {
+    return { commit: local, local: true };
+  }
+
+  const remoteRef = `refs/remotes/${remote}/${branch}`;
+  let tracked = await resolveCommit(workspacePath, remoteRef);
+  if (!tracked) {
+    await runGit(
+      ["fetch", "--quiet", remote, `+refs/heads/${branch}:${remoteRef}`],
+      workspacePath
+    ).catch(() => undefined);
+    tracked = await resolveCommit(workspacePath, remoteRef);
+  }
+  if (!tracked) {
+    throw new CellGitError(
+      "missing_branch",
+      `Branch "${branch}" does not exist locally or on ${remote}`
+    );
+  }
+  return { commit: tracked, local: false };
+}
+
+async function resolveCommit(
+  workspacePath: string,
+  ref: string
+): Promise<string | null> {
+  try {
+    const { stdout } = await runGit(
+      ["rev-parse", "--verify", "--quiet", `${ref}^{commit}`],
+      workspacePath
+    );
+    return stdout.trim() || null;
+  } catch {
+    return null;
+  }
+}
+
+async function resolveLocalBranch(
+  workspacePath: string,
+  branch: string
+): Promise<string> {
+  try {
+    const { stdout } = await runGit(
+      ["rev-parse", "--verify", `refs/heads/${branch}^{commit}`],
+      workspacePath
+    );
+    return stdout.trim();
+  } catch {
+    throw new CellGitError(
+      "missing_branch",
+      `Branch "${branch}" does not exist locally`
+    );
+  }
+}
+
+async function withScratchWorktree<T>(
+  workspacePath: string,
+  commit: string,
+  run: (scratchPath: string) => Promise<T>
+): Promise<T> {
+  const scratchPath = await mkdtemp(join(tmpdir(), "hive-merge-"));
+  try {
+    await runGit(
+      ["worktree", "add", "--detach", scratchPath, commit],
+      workspacePath
+    );
+    return await run(scratchPath);
+  } finally {
+    await runGit(
+      ["worktree", "remove", "--force", scratchPath],
+      workspacePath
+    ).catch(() => undefined);
+    await rm(scratchPath, { recursive: true, force: true });
+  }
+}
+
+async function mergeInScratch(
+  scratchPath: string,
+  args: { headCommit: string; message: string }
+): Promise<ScratchOutcome> {
+  try {
+    await runGit(
+      ["merge", "--no-ff", "--no-commit", args.headCommit],
+      scratchPath
+    );
+  } catch (error) {
+    const conflicts = await listConflictedPaths(scratchPath);
+    await runGit(["merge", "--abort"], scratchPath).catch(() => undefined);
+    if (conflicts.length === 0) {
+      throw error;
+    }
+    return { clean: false, conflicts };
+  }
+
+  await runGit(
+    ["commit", "--no-verify", "--message", args.message],
+    scratchPath
+  );
+  const { stdout } = await runGit(["rev-parse", "HEAD"], scratchPath);
+  return { clean: true, resultCommit: stdout.trim() };
+}
+
+async function rebaseInScratch(
+  scratchPath: string,
+  args: { baseCommit: string; headCommit: string }
+): Promise<ScratchOutcome> {
+  await runGit(["checkout", "--detach", args.headCommit], scratchPath);
+  try {
+    await runGit(["rebase", args.baseCommit], scratchPath);
+  } catch (error) {
+    const conflicts = await listConflictedPaths(scratchPath);
+    await runGit(["rebase", "--abort"], scratchPath).catch(() => undefined);
+    if (conflicts.length === 0) {
+      throw error;
+    }
+    return { clean: false, conflicts };
+  }
+
+  const { stdout } = await runGit(["rev-parse", "HEAD"], scratchPath);
+  return { clean: true, resultCommit: stdout.trim() };
+}
+
+/**
+ * Moves `branch` to `targetCommit`. When the branch is checked out somewhere
+ * (usually the workspace root) the checkout is fast-forwarded so its working
+ * tree follows; otherwise only the ref is updated.
+ */
+async function advanceBranch(args: {
+  workspacePath: string;
+  branch: string;
+  expectedCommit: string;
+  targetCommit: string;
+}) {
+  const checkoutPath = await findBranchCheckout(
+    args.workspacePath,
+    args.branch
+  );
+
+  if (!checkoutPath) {
+    await runGit(
+      [
+        "update-ref",
+        `refs/heads/${args.branch}`,
+        args.targetCommit,
+        args.expectedCommit,
+      ],
+      args.workspacePath
+    );
+    return;
+  }
+
+  const { stdout: status } = await runGit(
+    ["status", "--porcelain", "--untracked-files=no"],
+    checkoutPath
+  );
+  if (status.trim()) {
+    throw new CellGitError(
+      "rejected",
+      `Branch "${args.branch}" is checked out at ${checkoutPath} with uncommitted changes`
+    );
+  }
+
+  try {
+    await runGit(["merge", "--ff-only", args.targetCommit], checkoutPath);
+  } catch (error) {
+    if (error instanceof GitCommandError) {
+      throw new CellGitError(
+        "rejected",
+        `Could not fast-forward "${args.branch}": ${error.stderr.trim() || error.message}`
+      );
+    }
+    throw error;
+  }
+}
+
+/** Creates the local base branch at the merge result, tracking the remote. */
+async function createTrackingBranch(args: {
+  workspacePath: string;
+  remote: string;
+  branch: string;
+  targetCommit: string;
+}) {
+  try {
+    await runGit(
+      ["branch", "--no-track", args.branch, args.targetCommit],
+      args.workspacePath
+    );
+  } catch (error) {
+    if (error instanceof GitCommandError) {
+      throw new CellGitError(
+        "rejected",
+        `Could not create branch "${args.branch}": ${error.stderr.trim() || error.message}`
+      );
+    }
+    throw error;
+  }
+  await runGit(
+    ["branch", `--set-upstream-to=${args.remote}/${args.branch}`, args.branch],
+    args.workspacePath
+  ).catch(() => undefined);
+}
+
+async function findBranchCheckout(
+  workspacePath: string,
+  branch: string
+): Promise<string | null> {
+  const { stdout } = await runGit(
+    ["worktree", "list", "--porcelain"],
+    workspacePath
+  );
+  let currentPath: string | null = null;
+  for (const line of stdout.split("\n")) {
+    if (line.startsWith("worktree ")) {
+      currentPath = line.slice("worktree ".length);
+    } else if (line === `branch refs/heads/${branch}`) {
+      return currentPath;
+    }
+  }
+  return null;
+}
@@ -0,0 +1,65 @@
+import { mkdtemp, readFile, rm } from "node:fs/promises";
+import { tmpdir } from "node:os";
+import { join } from "node:path";
+import { afterEach, describe, expect, test } from "vitest";
+import {
+  appendAttachmentList,
+  validatePromptAttachments,
+  writePromptAttachments,
+} from "./cell-prompt-attachments";
+
+const encode = (text: string) => Buffer.from(text).toString("base64");
+
+describe("validatePromptAttachments", () => {
+  test("accepts plain file names with base64 content", () => {
+    expect(
+      validatePromptAttachments([
+        { name: "spec.md", content: encode("# Spec") },
+        { name: "screenshot.png", content: "", mimeType: "image/png" },
+      ])
+    ).toBeNull();
+  });
+
+  test("rejects paths, duplicates and content that is not base64", () => {
+    expect(
+      validatePromptAttachments([{ name: "docs/spec.md", content: "" }])
+    ).toContain("plain file name");
+    expect(
+      validatePromptAttachments([
+        { name: "a.txt", content: "" },
+        { name: "a.txt", content: "" },
+      ])
+    ).toContain("more than once");
+    expect(
+      validatePromptAttachments([{ name: "a.txt", content: "not base64!" }])
+    ).toContain("not base64-encoded");
+  });
+});
+
+describe("writePromptAttachments", () => {
+  let worktree: string | null = null;
+
+  afterEach(async () => {
+    if (worktree) {
+      await rm(worktree, { recursive: true, force: true });
+      worktree = null;
+    }
+  });
+
+  test("writes files under .hive/attachments and lists them", async () => {
+    worktree = await mkdtemp(join(tmpdir(), "hive-attachments-"));
+
+    const paths = await writePromptAttachments(worktree, [
+      { name: "notes.txt", content: encode("remember the cache") },
+    ]);
+
+    expect(paths).toEqual([".hive/attachments/notes.txt"]);
+    expect(
+      await readFile(join(worktree, ".hive/attachments/notes.txt"), "utf8")
+    ).toBe("remember the cache");
+    expect(appendAttachmentList("Do the thing", paths)).toBe(
+      "Do the thing\n\nAttached files (in this worktree):\n- .hive/attachments/notes.txt"
+    );
+    expect(appendAttachmentList("Do the thing", [])).toBe("Do the thing");
+  });
+});
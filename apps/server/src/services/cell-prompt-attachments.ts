@@ -0,0 +1,78 @@
+import { mkdir, writeFile } from "node:fs/promises";
+import { join } from "node:path";
+import type { CellPromptAttachment } from "../schema/cell-provisioning";
+
+export const PROMPT_ATTACHMENTS_DIR = ".hive/attachments";
+export const MAX_PROMPT_ATTACHMENT_BYTES = 10 * 1024 * 1024;
+
+const BASE64_PATTERN = /^[A-Za-z0-9+/]*={0,2}$/;
+const UNSAFE_NAME_PATTERN = /[/\\\0]/;
+
+const decodedSize = (content: string) => Buffer.byteLength(content, "base64");
+
+/**
+ * Checks attachments before a cell is created. Names must be plain file names
+ * because they become paths inside the worktree. Returns an error message, or
+ * null when the attachments are usable.
+ */
+export function validatePromptAttachments(
+  attachments: CellPromptAttachment[]
+): string | null {
+  const seen = new Set<string>();
+  let totalBytes = 0;
+  for (const attachment of attachments) {
+    const { name } = attachment;
+    if (name === "." || name === ".." || UNSAFE_NAME_PATTERN.test(name)) {
+      return `Attachment name "${name}" must be a plain file name`;
+    }
+    if (seen.has(name)) {
+      return `Attachment "${name}" is listed more than once`;
+    }
+    seen.add(name);
+    if (!BASE64_PATTERN.test(attachment.content)) {
+      return `Attachment "${name}" is not base64-encoded`;
+    }
+    totalBytes += decodedSize(attachment.content);
+  }
+  if (totalBytes > MAX_PROMPT_ATTACHMENT_BYTES) {
+    return `Attachments exceed ${MAX_PROMPT_ATTACHMENT_BYTES / 1024 / 1024} MB`;
+  }
+  return null;
+}
+
+/**
+ * Writes attachments under `.hive/attachments` in the worktree and returns
+ * their worktree-relative paths. Rewriting on a retried attempt is harmless.
+ */
+export async function writePromptAttachments(
+  workspacePath: string,
+  attachments: CellPromptAttachment[]
+): Promise<string[]> {
+  if (attachments.length === 0) {
+    return [];
+  }
+  const directory = join(workspacePath, PROMPT_ATTACHMENTS_DIR);
+  await mkdir(directory, { recursive: true });
+  const paths: string[] = [];
+  for (const attachment of attachments) {
+    await writeFile(
+      join(directory, attachment.name),
+      Buffer.from(attachment.content, "base64")
+    );
+    paths.push(`${PROMPT_ATTACHMENTS_DIR}/${attachment.name}`);
+  }
+  return paths;
+}
+
+/** Points the agent at the attached files below the prompt text. */
+export function appendAttachmentList(prompt: string, paths: string[]): string {
+  if (paths.length === 0) {
+    return prompt;
+  }
+  return [
+    prompt,
+    "",
+    "Attached files (in this worktree):",
+    ...paths.map((path) => `- ${path}`),
+  ].join("\n");
+}
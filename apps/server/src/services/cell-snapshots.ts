@@ -0,0 +1,375 @@
+import { randomUUID } from "node:crypto";
+import { mkdir, mkdtemp, rm, writeFile } from "node:fs/promises";
+import { tmpdir } from "node:os";
+import { join } from "node:path";
+import { and, desc, eq, like } from "drizzle-orm";
+import { glob } from "tinyglobby";
+import type { HiveConfig } from "../config/schema";
+import type { DatabaseService as DatabaseServiceType } from "../db";
+import { type CellSnapshot, cellSnapshots } from "../schema/cell-snapshots";
+import type { Cell } from "../schema/cells";
+import { resolveHiveHome } from "../workspaces/registry";
+import { runGit } from "./git";
+
+type DatabaseClient = DatabaseServiceType["db"];
+
+const SNAPSHOT_REF_PREFIX = "refs/hive/snapshots";
+const AGENT_TURN_SNAPSHOT_PREFIX = "Before agent turn";
+/** Agent-turn restore points kept per cell; manual snapshots never expire. */
+const RETAINED_AGENT_TURN_SNAPSHOTS = 50;
+const ALWAYS_IGNORED_ARCHIVE_PATTERNS = ["**/.git", "**/.git/**"];
+const SNAPSHOT_GIT_IDENTITY = {
+  GIT_AUTHOR_NAME: "Hive",
+  GIT_AUTHOR_EMAIL: "hive@localhost",
+  GIT_COMMITTER_NAME: "Hive",
+  GIT_COMMITTER_EMAIL: "hive@localhost",
+};
+
+export type SnapshotPatterns = {
+  includePatterns: string[];
+  ignorePatterns: string[];
+};
+
+export class CellSnapshotError extends Error {
+  constructor(message: string) {
+    super(message);
+    this.name = "CellSnapshotError";
+  }
+}
+
+export function resolveSnapshotArchiveRoot(cellId: string): string {
+  return join(resolveHiveHome(), "snapshots", cellId);
+}
+
+export function resolveSnapshotPatterns(
+  config: Pick<HiveConfig, "templates">,
+  templateId: string
+): SnapshotPatterns {
+  const template = config.templates[templateId];
+  return {
+    includePatterns: template?.includePatterns ?? [],
+    ignorePatterns: template?.ignorePatterns ?? [],
+  };
+}
+
+/**
+ * Records the worktree (tracked changes and untracked files) as a commit under
+ * a hidden ref, and archives the files matched by the template's
+ * includePatterns since those are usually git-ignored.
+ */
+export async function createCellSnapshot(args: {
+  database: DatabaseClient;
+  cell: Cell;
+  patterns: SnapshotPatterns;
+  name?: string;
+  agentMessageId?: string | null;
+  archiveRoot?: string;
+}): Promise<CellSnapshot> {
+  const { database, cell } = args;
+  const id = randomUUID();
+  const createdAt = new Date();
+  const name = args.name?.trim() || `Snapshot ${createdAt.toISOString()}`;
+  const { headCommit, snapshotCommit } = await commitWorktreeState(
+    cell.workspacePath,
+    `Hive snapshot: ${name}`
+  );
+  const gitRef = `${SNAPSHOT_REF_PREFIX}/${cell.id}/${id}`;
+  await runGit(["update-ref", gitRef, snapshotCommit], cell.workspacePath);
+
+  let archivePath: string | null = null;
+  try {
+    archivePath = await archiveIncludedFiles({
+      workspacePath: cell.workspacePath,
+      patterns: args.patterns,
+      archivePath: join(
+        args.archiveRoot ?? resolveSnapshotArchiveRoot(cell.id),
+        `${id}.tar.gz`
+      ),
+    });
+
+    const [snapshot] = await database
+      .insert(cellSnapshots)
+      .values({
+        id,
+        cellId: cell.id,
+        name,
+        gitRef,
+        snapshotCommit,
+        headCommit,
+        archivePath,
+        agentMessageId: args.agentMessageId ?? null,
+        createdAt,
+      })
+      .returning();
+    if (!snapshot) {
+      throw new CellSnapshotError("Failed to record snapshot");
+    }
+    return snapshot;
+  } catch (error) {
+    await discardSnapshotArtifacts(cell.workspacePath, { gitRef, archivePath });
+    throw error;
+  }
+}
+
+/**
+ * Rolls the worktree back to a snapshot: the branch returns to the commit it
+ * pointed at, files created since are removed (git-ignored files are kept),
+ * and the snapshot's working tree and archived files are written back.
+ */
+export async function restoreCellSnapshot(args: {
+  workspacePath: string;
+  snapshot: CellSnapshot;
+}): Promise<void> {
+  const { workspacePath, snapshot } = args;
+  await runGit(
+    ["reset", "--hard", "--quiet", snapshot.headCommit],
+    workspacePath
+  );
+  await runGit(["clean", "-fd", "--quiet"], workspacePath);
+  await applyCellSnapshot({ workspacePath, snapshot });
+}
+
+/**
+ * Writes a snapshot's uncommitted changes and archived files into a worktree
+ * whose HEAD is already at the snapshot's head commit. Used on its own when a
+ * new cell is forked from another cell's snapshot.
+ */
+export async function applyCellSnapshot(args: {
+  workspacePath: string;
+  snapshot: Pick<CellSnapshot, "snapshotCommit" | "headCommit" | "archivePath">;
+}): Promise<void> {
+  const { workspacePath, snapshot } = args;
+  await runGit(
+    ["read-tree", "-u", "--reset", snapshot.snapshotCommit],
+    workspacePath
+  );
+  // Leave the snapshot's changes unstaged, as they were when captured.
+  await runGit(["reset", "--quiet", snapshot.headCommit], workspacePath);
+
+  const { archivePath } = snapshot;
+  if (archivePath && (await Bun.file(archivePath).exists())) {
+    await runTar(["-xzf", archivePath, "-C", workspacePath]);
+  }
+}
+
+export async function listCellSnapshots(
+  database: DatabaseClient,
+  cellId: string,
+  filter: { agentMessageId?: string } = {}
+): Promise<CellSnapshot[]> {
+  return await database
+    .select()
+    .from(cellSnapshots)
+    .where(
+      filter.agentMessageId
+        ? and(
+            eq(cellSnapshots.cellId, cellId),
+            eq(cellSnapshots.agentMessageId, filter.agentMessageId)
+          )
+        : eq(cellSnapshots.cellId, cellId)
+    )
+    .orderBy(desc(cellSnapshots.createdAt));
+}
+
+export async function deleteCellSnapshot(args: {
+  database: DatabaseClient;
+  workspacePath: string;
+  snapshot: CellSnapshot;
+}): Promise<void> {
+  await discardSnapshotArtifacts(args.workspacePath, args.snapshot);
+  await args.database
+    .delete(cellSnapshots)
+    .where(eq(cellSnapshots.id, args.snapshot.id));
+}
+
+/** Drops every snapshot ref and archive of a cell that is being deleted. */
+export async function removeCellSnapshots(args: {
+  database: DatabaseClient;
+  cellId: string;
+  workspacePath: string;
+}): Promise<void> {
+  const snapshots = await listCellSnapshots(args.database, args.cellId);
+  for (const snapshot of snapshots) {
+    await discardSnapshotArtifacts(args.workspacePath, snapshot);
+  }
+  await args.database
+    .delete(cellSnapshots)
+    .where(eq(cellSnapshots.cellId, args.cellId));
+}
+
+/**
+ * Takes the restore point for an agent turn. Prompts sent through Hive are
+ * snapshotted before the user message exists and linked to it afterwards;
+ * messages typed elsewhere pass their id, and repeated events for the same
+ * message are ignored so each turn gets exactly one snapshot. Only the
+ * newest `RETAINED_AGENT_TURN_SNAPSHOTS` of them are kept.
+ */
+export async function captureAgentTurnSnapshot(args: {
+  database: DatabaseClient;
+  loadHiveConfig: (workspaceRoot?: string) => Promise<HiveConfig>;
+  cell: Cell;
+  agentMessageId?: string;
+  retain?: number;
+}): Promise<CellSnapshot | null> {
+  if (args.agentMessageId) {
+    const existing = await listCellSnapshots(args.database, args.cell.id, {
+      agentMessageId: args.agentMessageId,
+    });
+    if (existing.length > 0) {
+      return null;
+    }
+  }
+
+  const config = await args.loadHiveConfig(args.cell.workspaceRootPath);
+  const snapshot = await createCellSnapshot({
+    database: args.database,
+    cell: args.cell,
+    patterns: resolveSnapshotPatterns(config, args.cell.templateId),
+    name: `${AGENT_TURN_SNAPSHOT_PREFIX} ${args.agentMessageId ?? new Date().toISOString()}`,
+    agentMessageId: args.agentMessageId ?? null,
+  });
+  await pruneAgentTurnSnapshots(
+    args.database,
+    args.cell,
+    args.retain ?? RETAINED_AGENT_TURN_SNAPSHOTS
+  ).catch(() => undefined);
+  return snapshot;
+}
+
+async function pruneAgentTurnSnapshots(
+  database: DatabaseClient,
+  cell: Cell,
+  retain: number
+) {
+  const snapshots = await database
+    .select()
+    .from(cellSnapshots)
+    .where(
+      and(
+        eq(cellSnapshots.cellId, cell.id),
+        like(cellSnapshots.name, `${AGENT_TURN_SNAPSHOT_PREFIX} %`)
+      )
+    )
+    .orderBy(desc(cellSnapshots.createdAt));
+  for (const snapshot of snapshots.slice(retain)) {
+    await deleteCellSnapshot({
+      database,
+      workspacePath: cell.workspacePath,
+      snapshot,
+    });
+  }
+}
+
+export async function linkAgentTurnSnapshot(args: {
+  database: DatabaseClient;
+  snapshotId: string;
+  agentMessageId: string;
+}): Promise<void> {
+  await args.database
+    .update(cellSnapshots)
+    .set({ agentMessageId: args.agentMessageId })
+    .where(eq(cellSnapshots.id, args.snapshotId));
+}
+
+export async function commitWorktreeState(
+  workspacePath: string,
+  message: string
+): Promise<{ headCommit: string; snapshotCommit: string }> {
+  const { stdout: headCommit } = await runGit(
+    ["rev-parse", "HEAD"],
+    workspacePath
+  );
+  // A throwaway index keeps the cell's staging area untouched.
+  const scratchDir = await mkdtemp(join(tmpdir(), "hive-snapshot-"));
+  const env = {
+    ...SNAPSHOT_GIT_IDENTITY,
+    GIT_INDEX_FILE: join(scratchDir, "index"),
+  };
+
+  try {
+    await runGit(["read-tree", headCommit], workspacePath, { env });
+    await runGit(["add", "--all"], workspacePath, { env });
+    const { stdout: tree } = await runGit(["write-tree"], workspacePath, {
+      env,
+    });
+    const { stdout: snapshotCommit } = await runGit(
+      ["commit-tree", tree, "-p", headCommit, "-m", message],
+      workspacePath,
+      { env }
+    );
+    return { headCommit, snapshotCommit };
+  } finally {
+    await rm(scratchDir, { recursive: true, force: true });
+  }
+}
+
+export async function archiveIncludedFiles(args: {
+  workspacePath: string;
+  patterns: SnapshotPatterns;
+  archivePath: string;
+}): Promise<string | null> {
+  const { includePatterns, ignorePatterns } = args.patterns;
+  if (includePatterns.length === 0) {
+    return null;
+  }
+
+  // Bare names match at any depth, mirroring how worktrees copy them.
+  const expandedPatterns = includePatterns.flatMap((pattern) =>
+    pattern.includes("/") ? [pattern] : [pattern, `**/${pattern}`]
+  );
+  const files = await glob(expandedPatterns, {
+    cwd: args.workspacePath,
+    ignore: [...ignorePatterns, ...ALWAYS_IGNORED_ARCHIVE_PATTERNS],
+    dot: true,
+    onlyFiles: true,
+  });
+  if (files.length === 0) {
+    return null;
+  }
+
+  const archiveDir = join(args.archivePath, "..");
+  await mkdir(archiveDir, { recursive: true });
+  const listPath = `${args.archivePath}.files`;
+  await writeFile(listPath, `${files.join("\n")}\n`, "utf8");
+  try {
+    await runTar([
+      "-czf",
+      args.archivePath,
+      "-C",
+      args.workspacePath,
+      "-T",
+      listPath,
+    ]);
+  } finally {
+    await rm(listPath, { force: true });
+  }
+  return args.archivePath;
+}
+
+async function discardSnapshotArtifacts(
+  workspacePath: string,
+  snapshot: Pick<CellSnapshot, "gitRef" | "archivePath">
+) {
+  await runGit(["update-ref", "-d", snapshot.gitRef], workspacePath).catch(
+    () => undefined
+  );
+  if (snapshot.archivePath) {
+    await rm(snapshot.archivePath, { force: true });
+  }
+}
+
+export async function runTar(args: string[]): Promise<void> {
+  const child = Bun.spawn({
+    cmd: ["tar", ...args],
+    stdout: "ignore",
+    stderr: "pipe",
+  });
+  const stderrPromise = new Response(child.stderr).text();
+  const exitCode = await child.exited;
+  if (exitCode !== 0) {
+    const stderr = (await stderrPromise).trim();
+    throw new CellSnapshotError(
+      `tar ${args[0]} failed with code ${exitCode}${stderr ? `: ${stderr}` : ""}`
+    );
+  }
+}
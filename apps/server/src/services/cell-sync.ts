@@ -0,0 +1,228 @@
+import type { WorktreeStartPointRef } from "../worktree/manager";
+import { CellGitError } from "./cell-git";
+import type { CellMergeStrategy } from "./cell-merge";
+import { type DiffFileSummary, summarizeCommitRange } from "./diff-service";
+import { isAncestorCommit, listConflictedPaths, runGit } from "./git";
+
+export type CellSyncStatus = "synced" | "conflicted" | "up_to_date";
+
+export type CellSyncResult = {
+  status: CellSyncStatus;
+  strategy: CellMergeStrategy;
+  branchName: string;
+  upstream: string;
+  baseCommit: string;
+  headCommit: string;
+  conflicts: DiffFileSummary[];
+  message: string | null;
+};
+
+type UpstreamTip = {
+  upstream: string;
+  commit: string;
+};
+
+/**
+ * Rebases (or merges) the cell branch onto the current tip of its start point
+ * directly in the cell worktree. Conflicts are left in place for the agent to
+ * resolve; running the sync again afterwards records the new base commit.
+ */
+export async function syncCellWithUpstream(args: {
+  workspacePath: string;
+  branchName: string | null;
+  startPoint: WorktreeStartPointRef;
+  remote: string;
+  strategy?: CellMergeStrategy;
+}): Promise<CellSyncResult> {
+  const { workspacePath, branchName } = args;
+  if (!branchName) {
+    throw new CellGitError("missing_branch", "Cell has no branch to sync");
+  }
+
+  const { stdout: status } = await runGit(
+    ["status", "--porcelain", "--untracked-files=no"],
+    workspacePath
+  );
+  if (status.trim()) {
+    throw new CellGitError(
+      "uncommitted_changes",
+      "Commit or stash the changes in the cell worktree before syncing"
+    );
+  }
+
+  const strategy = args.strategy ?? "rebase";
+  const tip = await resolveUpstreamTip({
+    workspacePath,
+    remote: args.remote,
+    startPoint: args.startPoint,
+  });
+  const headBefore = await readHead(workspacePath);
+  const result: CellSyncResult = {
+    status: "up_to_date",
+    strategy,
+    branchName,
+    upstream: tip.upstream,
+    baseCommit: tip.commit,
+    headCommit: headBefore,
+    conflicts: [],
+    message: null,
+  };
+
+  if (await isAncestorCommit(workspacePath, tip.commit, headBefore)) {
+    return result;
+  }
+
+  try {
+    if (strategy === "rebase") {
+      await runGit(["rebase", tip.commit], workspacePath);
+    } else {
+      await runGit(["merge", "--no-edit", tip.commit], workspacePath);
+    }
+  } catch (error) {
+    const conflictedPaths = await listConflictedPaths(workspacePath);
+    if (conflictedPaths.length === 0) {
+      await runGit([strategy, "--abort"], workspacePath).catch(
+        () => undefined
+      );
+      throw error;
+    }
+
+    const { stdout: mergeBase } = await runGit(
+      ["merge-base", tip.commit, headBefore],
+      workspacePath
+    );
+    const conflicts = await summarizeCommitRange({
+      cwd: workspacePath,
+      from: mergeBase,
+      to: headBefore,
+      paths: conflictedPaths,
+    });
+    return {
+      ...result,
+      status: "conflicted",
+      conflicts,
+      message: describeConflicts({
+        strategy,
+        branchName,
+        upstream: tip.upstream,
+        paths: conflictedPaths,
+      }),
+    };
+  }
+
+  return {
+    ...result,
+    status: "synced",
+    headCommit: await readHead(workspacePath),
+  };
+}
+
+/**
+ * Fetches the start point and returns its newest tip. Branch start points
+ * prefer the remote-tracking ref when it is ahead of the local branch.
+ */
+async function resolveUpstreamTip(args: {
+  workspacePath: string;
+  remote: string;
+  startPoint: WorktreeStartPointRef;
+}): Promise<UpstreamTip> {
+  const { workspacePath, remote, startPoint } = args;
+
+  if (startPoint.mode === "pr") {
+    const localPullRef = `refs/remotes/${remote}/pr/${startPoint.ref}`;
+    const fetched = await fetchRef(
+      workspacePath,
+      remote,
+      `+refs/pull/${startPoint.ref}/head:${localPullRef}`
+    );
+    const commit = fetched
+      ? await resolveCommit(workspacePath, localPullRef)
+      : null;
+    if (!commit) {
+      throw new CellGitError(
+        "missing_branch",
+        `Unable to fetch PR #${startPoint.ref} from ${remote}`
+      );
+    }
+    return { upstream: `${remote}/pr/${startPoint.ref}`, commit };
+  }
+
+  const remoteRef = `refs/remotes/${remote}/${startPoint.ref}`;
+  await fetchRef(
+    workspacePath,
+    remote,
+    `+refs/heads/${startPoint.ref}:${remoteRef}`
+  );
+  const [localCommit, remoteCommit] = await Promise.all([
+    resolveCommit(workspacePath, `refs/heads/${startPoint.ref}`),
+    resolveCommit(workspacePath, remoteRef),
+  ]);
+
+  if (
+    remoteCommit &&
+    (!localCommit ||
+      (await isAncestorCommit(workspacePath, localCommit, remoteCommit)))
+  ) {
+    return { upstream: `${remote}/${startPoint.ref}`, commit: remoteCommit };
+  }
+  if (localCommit) {
+    return { upstream: startPoint.ref, commit: localCommit };
+  }
+
+  throw new CellGitError(
+    "missing_branch",
+    `Branch "${startPoint.ref}" not found locally or on ${remote}`
+  );
+}
+
+async function fetchRef(
+  workspacePath: string,
+  remote: string,
+  refspec: string
+): Promise<boolean> {
+  try {
+    await runGit(["fetch", "--quiet", remote, refspec], workspacePath);
+    return true;
+  } catch {
+    return false;
+  }
+}
+
+async function resolveCommit(
+  workspacePath: string,
+  ref: string
+): Promise<string | null> {
+  try {
+    const { stdout } = await runGit(
+      ["rev-parse", "--verify", "--quiet", `${ref}^{commit}`],
+      workspacePath
+    );
+    return stdout.trim() || null;
+  } catch {
+    return null;
+  }
+}
+
+async function readHead(workspacePath: string): Promise<string> {
+  const { stdout } = await runGit(["rev-parse", "HEAD"], workspacePath);
+  return stdout.trim();
+}
+
+function describeConflicts(args: {
+  strategy: CellMergeStrategy;
+  branchName: string;
+  upstream: string;
+  paths: string[];
+}): string {
+  const verb = args.strategy === "rebase" ? "Rebasing" : "Merging";
+  const finish =
+    args.strategy === "rebase"
+      ? "`git add` them and run `git rebase --continue` (or `git rebase --abort` to give up)"
+      : "`git add` them and run `git commit` (or `git merge --abort` to give up)";
+  return [
+    `${verb} ${args.branchName} onto ${args.upstream} stopped with conflicts in ${args.paths.length} file(s):`,
+    ...args.paths.map((path) => `- ${path}`),
+    `Resolve the conflict markers in the worktree, ${finish}.`,
+    "Sync again afterwards to record the new base commit.",
+  ].join("\n");
+}
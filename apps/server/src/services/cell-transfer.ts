@@ -0,0 +1,619 @@
+import { randomUUID } from "node:crypto";
+import { mkdtemp, readFile, rm, writeFile } from "node:fs/promises";
+import { tmpdir } from "node:os";
+import { join } from "node:path";
+import { asc, eq } from "drizzle-orm";
+import { z } from "zod";
+import type { AgentMessageRecord } from "../agents/types";
+import { serviceSchema } from "../config/schema";
+import type { DatabaseService as DatabaseServiceType } from "../db";
+import {
+  ACTIVITY_EVENT_TYPES,
+  type CellActivityEvent,
+  cellActivityEvents,
+} from "../schema/activity-events";
+import {
+  type CellProvisioningState,
+  cellProvisioningStates,
+} from "../schema/cell-provisioning";
+import { type Cell, cells } from "../schema/cells";
+import {
+  type CellService,
+  cellServices,
+  SERVICE_STATUSES,
+} from "../schema/services";
+import {
+  CELL_TIMING_STATUSES,
+  CELL_TIMING_WORKFLOWS,
+  type CellTimingEvent,
+  cellTimingEvents,
+} from "../schema/timing-events";
+import type { AsyncWorktreeManager } from "../worktree/manager";
+import {
+  applyCellSnapshot,
+  archiveIncludedFiles,
+  commitWorktreeState,
+  runTar,
+  type SnapshotPatterns,
+} from "./cell-snapshots";
+import { runGit } from "./git";
+
+type DatabaseClient = DatabaseServiceType["db"];
+
+export const CELL_EXPORT_FORMAT_VERSION = 1;
+
+const EXPORT_REF_PREFIX = "refs/hive/exports";
+const IMPORT_REF_PREFIX = "refs/hive/imports";
+const MANIFEST_FILE = "manifest.json";
+const TRANSCRIPT_FILE = "transcript.json";
+const BUNDLE_FILE = "repository.bundle";
+const INCLUDED_FILES_ARCHIVE = "included-files.tar.gz";
+const ISO_DATE_PATTERN = /^\d{4}-\d{2}-\d{2}T\d{2}:\d{2}:\d{2}(\.\d+)?Z$/;
+const DATE_COLUMN_PATTERN = /At$/;
+const COMMIT_PATTERN = /^[0-9a-f]{40}([0-9a-f]{24})?$/;
+
+/** JSON form of a row: dates travel as ISO strings. */
+type Serialized<T> = {
+  [K in keyof T]: T[K] extends Date
+    ? string
+    : T[K] extends Date | null
+      ? string | null
+      : T[K];
+};
+
+/**
+ * Describes everything in a cell export archive except the git bundle, the
+ * included-files archive and the agent transcript, which sit next to it.
+ */
+export type CellExportManifest = {
+  version: number;
+  exportedAt: string;
+  cell: Serialized<Cell>;
+  git: { ref: string; headCommit: string; snapshotCommit: string };
+  hasIncludedFiles: boolean;
+  services: Serialized<CellService>[];
+  provisioning: Serialized<CellProvisioningState> | null;
+  activity: Serialized<CellActivityEvent>[];
+  timings: Serialized<CellTimingEvent>[];
+};
+
+const timestampSchema = z
+  .string()
+  .regex(ISO_DATE_PATTERN, { message: "Expected an ISO timestamp" });
+const commitSchema = z
+  .string()
+  .regex(COMMIT_PATTERN, { message: "Expected a commit hash" });
+const metadataSchema = z.record(z.string(), z.unknown());
+
+// Archives can be edited by hand, so rows are checked against the columns
+// they are inserted into before anything touches git or the database.
+const manifestSchema: z.ZodType<CellExportManifest> = z.object({
+  version: z.literal(CELL_EXPORT_FORMAT_VERSION),
+  exportedAt: timestampSchema,
+  cell: z.object({
+    id: z.string().min(1),
+    name: z.string().min(1),
+    description: z.string().nullable(),
+    templateId: z.string().min(1),
+    workspacePath: z.string(),
+    workspaceId: z.string(),
+    workspaceRootPath: z.string(),
+    opencodeSessionId: z.string().nullable(),
+    resumeAgentSessionOnStartup: z.boolean(),
+    createdAt: timestampSchema,
+    status: z.string(),
+    lastSetupError: z.string().nullable(),
+    branchName: z.string().nullable(),
+    baseCommit: z.string().nullable(),
+    startPointMode: z.string().nullable(),
+    startPointRef: z.string().nullable(),
+    ownerId: z.string().nullable(),
+  }),
+  git: z.object({
+    ref: z.string().startsWith(`${EXPORT_REF_PREFIX}/`, {
+      message: `Expected a ref under ${EXPORT_REF_PREFIX}/`,
+    }),
+    headCommit: commitSchema,
+    snapshotCommit: commitSchema,
+  }),
+  hasIncludedFiles: z.boolean(),
+  services: z.array(
+    z.object({
+      id: z.string().min(1),
+      cellId: z.string(),
+      name: z.string().min(1),
+      type: z.string(),
+      command: z.string(),
+      cwd: z.string(),
+      env: z.record(z.string(), z.string()),
+      status: z.enum(SERVICE_STATUSES),
+      port: z.number().int().nullable(),
+      pid: z.number().int().nullable(),
+      readyTimeoutMs: z.number().int().nullable(),
+      definition: serviceSchema,
+      lastKnownError: z.string().nullable(),
+      createdAt: timestampSchema,
+      updatedAt: timestampSchema,
+    })
+  ),
+  provisioning: z
+    .object({
+      cellId: z.string(),
+      modelIdOverride: z.string().nullable(),
+      providerIdOverride: z.string().nullable(),
+      variantOverride: z.string().nullable(),
+      startMode: z.string().nullable(),
+      startedAt: timestampSchema.nullable(),
+      finishedAt: timestampSchema.nullable(),
+      attemptCount: z.number().int(),
+      initialPrompt: z.string().nullable(),
+      initialPromptAttachments: z
+        .array(
+          z.object({
+            name: z.string(),
+            content: z.string(),
+            mimeType: z.string().optional(),
+          })
+        )
+        .nullable(),
+      initialPromptSentAt: timestampSchema.nullable(),
+      templateParameters: z
+        .record(z.string(), z.union([z.string(), z.number(), z.boolean()]))
+        .nullable(),
+    })
+    .nullable(),
+  activity: z.array(
+    z.object({
+      id: z.string(),
+      cellId: z.string(),
+      serviceId: z.string().nullable(),
+      type: z.enum(ACTIVITY_EVENT_TYPES),
+      source: z.string().nullable(),
+      toolName: z.string().nullable(),
+      userId: z.string().nullable(),
+      metadata: metadataSchema,
+      createdAt: timestampSchema,
+    })
+  ),
+  timings: z.array(
+    z.object({
+      id: z.string(),
+      cellId: z.string(),
+      cellName: z.string().nullable(),
+      workspaceId: z.string().nullable(),
+      templateId: z.string().nullable(),
+      workflow: z.enum(CELL_TIMING_WORKFLOWS),
+      runId: z.string(),
+      step: z.string(),
+      status: z.enum(CELL_TIMING_STATUSES),
+      durationMs: z.number(),
+      attempt: z.number().int().nullable(),
+      error: z.string().nullable(),
+      metadata: metadataSchema,
+      createdAt: timestampSchema,
+    })
+  ),
+});
+
+export type CellImportResult = {
+  cell: Cell;
+  sourceCellId: string;
+  serviceCount: number;
+  activityCount: number;
+  timingCount: number;
+  transcript: AgentMessageRecord[];
+};
+
+export class CellTransferError extends Error {
+  constructor(message: string) {
+    super(message);
+    this.name = "CellTransferError";
+  }
+}
+
+/**
+ * Packs a cell into a tar archive: its branch plus uncommitted work as a git
+ * bundle, the template's included files, its database rows and the agent
+ * transcript. The cell itself is left untouched.
+ */
+export async function exportCell(args: {
+  database: DatabaseClient;
+  cell: Cell;
+  patterns: SnapshotPatterns;
+  transcript: AgentMessageRecord[];
+}): Promise<Uint8Array> {
+  const { database, cell } = args;
+  const scratchDir = await mkdtemp(join(tmpdir(), "hive-export-"));
+  const archivePath = `${scratchDir}.tar`;
+
+  try {
+    const { headCommit, snapshotCommit } = await commitWorktreeState(
+      cell.workspacePath,
+      `Hive export: ${cell.name}`
+    );
+    const exportRef = `${EXPORT_REF_PREFIX}/${cell.id}`;
+    await runGit(["update-ref", exportRef, snapshotCommit], cell.workspacePath);
+    try {
+      await runGit(
+        ["bundle", "create", join(scratchDir, BUNDLE_FILE), exportRef],
+        cell.workspacePath
+      );
+    } finally {
+      await runGit(["update-ref", "-d", exportRef], cell.workspacePath).catch(
+        () => undefined
+      );
+    }
+
+    const includedFiles = await archiveIncludedFiles({
+      workspacePath: cell.workspacePath,
+      patterns: args.patterns,
+      archivePath: join(scratchDir, INCLUDED_FILES_ARCHIVE),
+    });
+
+    const manifest: CellExportManifest = {
+      version: CELL_EXPORT_FORMAT_VERSION,
+      exportedAt: new Date().toISOString(),
+      cell: serializeRow(cell),
+      git: { ref: exportRef, headCommit, snapshotCommit },
+      hasIncludedFiles: includedFiles !== null,
+      ...(await loadCellRows(database, cell.id)),
+    };
+    await writeFile(
+      join(scratchDir, MANIFEST_FILE),
+      JSON.stringify(manifest, null, 2),
+      "utf8"
+    );
+    await writeFile(
+      join(scratchDir, TRANSCRIPT_FILE),
+      JSON.stringify(args.transcript),
+      "utf8"
+    );
+
+    await runTar([
+      "-cf",
+      archivePath,
+      "-C",
+      scratchDir,
+      MANIFEST_FILE,
+      TRANSCRIPT_FILE,
+      BUNDLE_FILE,
+      ...(includedFiles ? [INCLUDED_FILES_ARCHIVE] : []),
+    ]);
+    return new Uint8Array(await Bun.file(archivePath).arrayBuffer());
+  } finally {
+    await rm(scratchDir, { recursive: true, force: true });
+    await rm(archivePath, { force: true });
+  }
+}
+
+/**
+ * Recreates an exported cell in the target workspace under a new id. The
+ * worktree path and workspace root are rewritten to the target, as are
+ * service working directories and env values that pointed into the old
+ * worktree. Services come back stopped with fresh ports.
+ */
+export async function importCell(args: {
+  database: DatabaseClient;
+  archive: Uint8Array;
+  workspace: { id: string; path: string };
+  worktreeManager: AsyncWorktreeManager;
+  templateIds: string[];
+  ownerId: string | null;
+  name?: string;
+}): Promise<CellImportResult> {
+  const { database, workspace } = args;
+  const scratchDir = await mkdtemp(join(tmpdir(), "hive-import-"));
+
+  try {
+    const archivePath = join(scratchDir, "cell.tar");
+    await writeFile(archivePath, args.archive);
+    await runTar(["-xf", archivePath, "-C", scratchDir]);
+
+    const manifest = await readManifest(scratchDir);
+    const source = manifest.cell;
+    if (!args.templateIds.includes(source.templateId)) {
+      throw new CellTransferError(
+        `Template "${source.templateId}" is not defined in the target workspace`
+      );
+    }
+
+    const cellId = randomUUID();
+    const importRef = `${IMPORT_REF_PREFIX}/${cellId}`;
+    await runGit(
+      [
+        "fetch",
+        "--quiet",
+        join(scratchDir, BUNDLE_FILE),
+        `${manifest.git.ref}:${importRef}`,
+      ],
+      workspace.path
+    );
+
+    let worktreeCreated = false;
+    let insertStarted = false;
+    try {
+      const location = await args.worktreeManager.createWorktree(cellId, {
+        templateId: source.templateId,
+        startPoint: {
+          mode: "cell",
+          value: source.id,
+          commit: manifest.git.headCommit,
+        },
+      });
+      worktreeCreated = true;
+
+      await applyCellSnapshot({
+        workspacePath: location.path,
+        snapshot: {
+          headCommit: manifest.git.headCommit,
+          snapshotCommit: manifest.git.snapshotCommit,
+          archivePath: manifest.hasIncludedFiles
+            ? join(scratchDir, INCLUDED_FILES_ARCHIVE)
+            : null,
+        },
+      });
+
+      const rewritePath = createPathRewriter([
+        [source.workspacePath, location.path],
+        [source.workspaceRootPath, workspace.path],
+      ]);
+      insertStarted = true;
+      const cell = await insertImportedRows({
+        database,
+        manifest,
+        cell: {
+          ...deserializeRow(source),
+          id: cellId,
+          name: args.name?.trim() || source.name,
+          workspaceId: workspace.id,
+          workspacePath: location.path,
+          workspaceRootPath: workspace.path,
+          opencodeSessionId: null,
+          resumeAgentSessionOnStartup: false,
+          status: "ready",
+          lastSetupError: null,
+          branchName: location.branch,
+          baseCommit: source.baseCommit ?? location.baseCommit,
+          ownerId: args.ownerId,
+        },
+        rewritePath,
+      });
+
+      return {
+        cell,
+        sourceCellId: source.id,
+        serviceCount: manifest.services.length,
+        activityCount: manifest.activity.length,
+        timingCount: manifest.timings.length,
+        transcript: await readTranscript(scratchDir),
+      };
+    } catch (error) {
+      if (insertStarted) {
+        await removeImportedRows(database, cellId).catch(() => undefined);
+      }
+      if (worktreeCreated) {
+        await args.worktreeManager
+          .removeWorktree(cellId)
+          .catch(() => undefined);
+      }
+      throw error;
+    } finally {
+      await runGit(["update-ref", "-d", importRef], workspace.path).catch(
+        () => undefined
+      );
+    }
+  } finally {
+    await rm(scratchDir, { recursive: true, force: true });
+  }
+}
+
+/** Renders an imported conversation as context for the cell's new session. */
+export function formatImportedTranscript(
+  cellName: string,
+  messages: AgentMessageRecord[]
+): string | null {
+  const turns = messages
+    .filter((message) => message.content?.trim())
+    .map((message) => `### ${message.role}\n\n${message.content?.trim()}`);
+  if (turns.length === 0) {
+    return null;
+  }
+  return [
+    `This cell was imported from another Hive installation. The conversation from "${cellName}" so far:`,
+    ...turns,
+  ].join("\n\n");
+}
+
+async function loadCellRows(database: DatabaseClient, cellId: string) {
+  const services = await database
+    .select()
+    .from(cellServices)
+    .where(eq(cellServices.cellId, cellId))
+    .orderBy(asc(cellServices.createdAt));
+  const provisioning = await database.query.cellProvisioningStates.findFirst({
+    where: eq(cellProvisioningStates.cellId, cellId),
+  });
+  const activity = await database
+    .select()
+    .from(cellActivityEvents)
+    .where(eq(cellActivityEvents.cellId, cellId))
+    .orderBy(asc(cellActivityEvents.createdAt));
+  const timings = await database
+    .select()
+    .from(cellTimingEvents)
+    .where(eq(cellTimingEvents.cellId, cellId))
+    .orderBy(asc(cellTimingEvents.createdAt));
+
+  return {
+    services: services.map(serializeRow),
+    provisioning: provisioning ? serializeRow(provisioning) : null,
+    activity: activity.map(serializeRow),
+    timings: timings.map(serializeRow),
+  };
+}
+
+async function insertImportedRows(args: {
+  database: DatabaseClient;
+  manifest: CellExportManifest;
+  cell: Cell;
+  rewritePath: (value: string) => string;
+}): Promise<Cell> {
+  const { database, manifest, cell, rewritePath } = args;
+  const [inserted] = await database.insert(cells).values(cell).returning();
+  if (!inserted) {
+    throw new CellTransferError("Failed to record imported cell");
+  }
+
+  const serviceIds = new Map<string, string>();
+  const now = new Date();
+  for (const service of manifest.services) {
+    const id = randomUUID();
+    serviceIds.set(service.id, id);
+    await database.insert(cellServices).values({
+      ...deserializeRow(service),
+      id,
+      cellId: cell.id,
+      cwd: rewritePath(service.cwd),
+      env: Object.fromEntries(
+        Object.entries(service.env).map(([key, value]) => [
+          key,
+          rewritePath(value),
+        ])
+      ),
+      status: "stopped",
+      port: null,
+      pid: null,
+      lastKnownError: null,
+      updatedAt: now,
+    });
+  }
+
+  if (manifest.provisioning) {
+    await database.insert(cellProvisioningStates).values({
+      ...deserializeRow(manifest.provisioning),
+      cellId: cell.id,
+    });
+  }
+
+  for (const event of manifest.activity) {
+    await database.insert(cellActivityEvents).values({
+      ...deserializeRow(event),
+      id: randomUUID(),
+      cellId: cell.id,
+      serviceId: event.serviceId
+        ? (serviceIds.get(event.serviceId) ?? null)
+        : null,
+      // Users are local to each installation.
+      userId: null,
+    });
+  }
+
+  for (const timing of manifest.timings) {
+    await database.insert(cellTimingEvents).values({
+      ...deserializeRow(timing),
+      id: randomUUID(),
+      cellId: cell.id,
+      cellName: cell.name,
+      workspaceId: cell.workspaceId,
+    });
+  }
+
+  return inserted;
+}
+
+/**
+ * Undoes a partial `insertImportedRows`. Timing events are the only rows
+ * that do not cascade from the cell.
+ */
+async function removeImportedRows(database: DatabaseClient, cellId: string) {
+  await database
+    .delete(cellTimingEvents)
+    .where(eq(cellTimingEvents.cellId, cellId));
+  await database.delete(cells).where(eq(cells.id, cellId));
+}
+
+async function readManifest(directory: string): Promise<CellExportManifest> {
+  let raw: unknown;
+  try {
+    raw = JSON.parse(await readFile(join(directory, MANIFEST_FILE), "utf8"));
+  } catch {
+    throw new CellTransferError("Archive is not a Hive cell export");
+  }
+  if (typeof raw !== "object" || raw === null || !("version" in raw)) {
+    throw new CellTransferError("Archive is not a Hive cell export");
+  }
+  if (raw.version !== CELL_EXPORT_FORMAT_VERSION) {
+    throw new CellTransferError(
+      `Unsupported cell export version ${String(raw.version)}`
+    );
+  }
+
+  const parsed = manifestSchema.safeParse(raw);
+  if (!parsed.success) {
+    const [issue] = parsed.error.issues;
+    const path = issue?.path.map(String).join(".") || "manifest";
+    throw new CellTransferError(
+      `Invalid cell export manifest: ${path}: ${issue?.message ?? "invalid"}`
+    );
+  }
+
+  const manifest = parsed.data;
+  // The ref ends up in a fetch refspec.
+  try {
+    await runGit(["check-ref-format", manifest.git.ref], directory);
+  } catch {
+    throw new CellTransferError(
+      `Invalid cell export manifest: git.ref: ${manifest.git.ref} is not a valid ref`
+    );
+  }
+  return manifest;
+}
+
+async function readTranscript(
+  directory: string
+): Promise<AgentMessageRecord[]> {
+  try {
+    return JSON.parse(
+      await readFile(join(directory, TRANSCRIPT_FILE), "utf8")
+    ) as AgentMessageRecord[];
+  } catch {
+    return [];
+  }
+}
+
+function createPathRewriter(replacements: Array<[string, string]>) {
+  const active = replacements.filter(([from]) => from.trim().length > 0);
+  return (value: string) => {
+    for (const [from, to] of active) {
+      if (value.includes(from)) {
+        return value.split(from).join(to);
+      }
+    }
+    return value;
+  };
+}
+
+function serializeRow<T extends Record<string, unknown>>(
+  row: T
+): Serialized<T> {
+  return Object.fromEntries(
+    Object.entries(row).map(([key, value]) => [
+      key,
+      value instanceof Date ? value.toISOString() : value,
+    ])
+  ) as Serialized<T>;
+}
+
+/** Inverse of serializeRow; timestamp columns all end in `At`. */
+function deserializeRow<T>(row: Serialized<T>): T {
+  return Object.fromEntries(
+    Object.entries(row as Record<string, unknown>).map(([key, value]) => [
+      key,
+      DATE_COLUMN_PATTERN.test(key) &&
+      typeof value === "string" &&
+      ISO_DATE_PATTERN.test(value)
+        ? new Date(value)
+        : value,
+    ])
+  ) as T;
+}
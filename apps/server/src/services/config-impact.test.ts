@@ -0,0 +1,42 @@
+import { describe, expect, it } from "vitest";
+
+import { diffConfigFields } from "./config-impact";
+
+describe("diffConfigFields", () => {
+  it("reports changed, added and removed leaf fields by path", () => {
+    expect(
+      diffConfigFields(
+        {
+          type: "process",
+          run: "bun run dev",
+          env: { PORT: "3000", DEBUG: "1" },
+        },
+        {
+          type: "process",
+          run: "bun run dev --watch",
+          env: { PORT: "3000", LOG_LEVEL: "info" },
+        }
+      )
+    ).toEqual([
+      { path: "env.DEBUG", before: "1" },
+      { path: "env.LOG_LEVEL", after: "info" },
+      { path: "run", before: "bun run dev", after: "bun run dev --watch" },
+    ]);
+  });
+
+  it("compares arrays as a whole and ignores equal values", () => {
+    expect(
+      diffConfigFields(
+        { setup: ["bun install"], ports: [5432] },
+        { setup: ["bun install", "bun run migrate"], ports: [5432] },
+        "services.db"
+      )
+    ).toEqual([
+      {
+        path: "services.db.setup",
+        before: ["bun install"],
+        after: ["bun install", "bun run migrate"],
+      },
+    ]);
+  });
+});
@@ -0,0 +1,61 @@
+import { isDeepStrictEqual } from "node:util";
+
+/** One changed field, e.g. `run` or `env.DATABASE_URL`. */
+export type ConfigFieldChange = {
+  path: string;
+  before?: unknown;
+  after?: unknown;
+};
+
+export type ServiceConfigChangeKind = "added" | "removed" | "modified";
+
+export type ServiceConfigChange = {
+  /** Null for services the new config adds. */
+  serviceId: string | null;
+  name: string;
+  change: ServiceConfigChangeKind;
+  fields: ConfigFieldChange[];
+};
+
+/** How the current config differs from what a cell's services run with. */
+export type CellConfigImpact = {
+  cellId: string;
+  cellName: string;
+  workspaceId: string;
+  templateId: string;
+  services: ServiceConfigChange[];
+};
+
+const isPlainObject = (value: unknown): value is Record<string, unknown> =>
+  typeof value === "object" && value !== null && !Array.isArray(value);
+
+/**
+ * Lists the leaf fields that differ between two values. Objects are walked
+ * key by key; arrays and scalars are compared as a whole.
+ */
+export function diffConfigFields(
+  before: unknown,
+  after: unknown,
+  path = ""
+): ConfigFieldChange[] {
+  if (isPlainObject(before) && isPlainObject(after)) {
+    const keys = [...new Set([...Object.keys(before), ...Object.keys(after)])];
+    return keys
+      .sort()
+      .flatMap((key) =>
+        diffConfigFields(before[key], after[key], path ? `${path}.${key}` : key)
+      );
+  }
+
+  if (isDeepStrictEqual(before, after)) {
+    return [];
+  }
+
+  return [
+    {
+      path,
+      ...(before === undefined ? {} : { before }),
+      ...(after === undefined ? {} : { after }),
+    },
+  ];
+}
@@ -0,0 +1,307 @@
+import { watch } from "node:fs";
+import { basename, dirname } from "node:path";
+import { and, eq, ne } from "drizzle-orm";
+import { hiveConfigService } from "../config/context";
+import { CONFIG_FILENAMES } from "../config/files";
+import { listConfigImports } from "../config/loader";
+import {
+  DatabaseService,
+  type DatabaseService as DatabaseServiceType,
+} from "../db";
+import { cells } from "../schema/cells";
+import {
+  listWorkspaces as listRegisteredWorkspaces,
+  type WorkspaceRecord,
+} from "../workspaces/registry";
+import type { CellConfigImpact } from "./config-impact";
+import { type ConfigReloadEvent, emitConfigReload } from "./events";
+import { ServiceSupervisorService } from "./supervisor";
+
+type DatabaseClient = DatabaseServiceType["db"];
+type WatchedWorkspace = Pick<WorkspaceRecord, "id" | "path">;
+
+// Editors often write a file in several steps; reload once they settle.
+export const CONFIG_RELOAD_DEBOUNCE_MS = 250;
+export const DEFAULT_WORKSPACE_SYNC_INTERVAL_MS = 30_000;
+
+const CONFIG_FILES: ReadonlySet<string> = new Set(CONFIG_FILENAMES);
+
+export type ConfigWatcherDependencies = {
+  db?: DatabaseClient;
+  listWorkspaces?: () => Promise<WatchedWorkspace[]>;
+  /** Reports changed file names in `directory`; returns an unsubscribe. */
+  watchDirectory?: (
+    directory: string,
+    onChange: (filename: string | null) => void
+  ) => () => void;
+  loadConfig?: (workspaceRoot: string) => Promise<unknown>;
+  /** Local files a code config imports; edits to them reload it too. */
+  listImports?: (workspaceRoot: string) => string[];
+  clearConfig?: (workspaceRoot: string) => void;
+  reloadTemplates?: () => void;
+  previewCellConfig?: (cellId: string) => Promise<CellConfigImpact | null>;
+  publish?: (event: ConfigReloadEvent) => void;
+  debounceMs?: number;
+  now?: () => Date;
+};
+
+const errorMessage = (error: unknown): string => {
+  if (
+    error &&
+    typeof error === "object" &&
+    (error as { _tag?: string })._tag === "HiveConfigError"
+  ) {
+    return errorMessage((error as { cause: unknown }).cause);
+  }
+  return error instanceof Error ? error.message : String(error);
+};
+
+const watchConfigDirectory = (
+  directory: string,
+  onChange: (filename: string | null) => void
+) => {
+  // Watch the directory rather than the file: editors that save by renaming
+  // would otherwise leave us watching a deleted inode.
+  const watcher = watch(directory, (_event, filename) => {
+    onChange(filename ? filename.toString() : null);
+  });
+  return () => watcher.close();
+};
+
+/**
+ * Watches each registered workspace's config file and the local files a
+ * code config imports. When one changes, the config is revalidated and a `ConfigReloadEvent` lists the cells whose
+ * services no longer match it, field by field. An invalid config is
+ * only reported: running services keep the definitions they started with,
+ * but starting services or creating cells fails until the file is fixed.
+ */
+export function createConfigWatcher({
+  db = DatabaseService.db,
+  listWorkspaces = listRegisteredWorkspaces,
+  watchDirectory = watchConfigDirectory,
+  loadConfig = hiveConfigService.load,
+  listImports = listConfigImports,
+  clearConfig = hiveConfigService.clear,
+  reloadTemplates = () => ServiceSupervisorService.reloadTemplates(),
+  previewCellConfig = ServiceSupervisorService.previewCellConfig,
+  publish = emitConfigReload,
+  debounceMs = CONFIG_RELOAD_DEBOUNCE_MS,
+  now = () => new Date(),
+}: ConfigWatcherDependencies = {}) {
+  const watchers = new Map<
+    string,
+    { path: string; imports: string; close: () => void }
+  >();
+  const pendingReloads = new Map<string, ReturnType<typeof setTimeout>>();
+  let syncTimer: ReturnType<typeof setInterval> | null = null;
+
+  async function collectImpacts(
+    workspaceId: string
+  ): Promise<CellConfigImpact[]> {
+    const workspaceCells = await db
+      .select({ id: cells.id })
+      .from(cells)
+      .where(
+        and(eq(cells.workspaceId, workspaceId), ne(cells.status, "deleting"))
+      );
+
+    const impacts: CellConfigImpact[] = [];
+    for (const { id } of workspaceCells) {
+      try {
+        const impact = await previewCellConfig(id);
+        if (impact && impact.services.length > 0) {
+          impacts.push(impact);
+        }
+      } catch (error) {
+        process.stderr.write(
+          `[config] Failed to compare ${id} with the config: ${errorMessage(error)}\n`
+        );
+      }
+    }
+    return impacts;
+  }
+
+  /**
+   * Validates a workspace's config and lists the cells it affects. Only a
+   * file change (`refresh`) drops the cached config and templates; a new
+   * subscriber is served from the caches.
+   */
+  async function describeWorkspace(
+    workspace: WatchedWorkspace,
+    { refresh }: { refresh: boolean }
+  ): Promise<ConfigReloadEvent> {
+    if (refresh) {
+      clearConfig(workspace.path);
+    }
+    try {
+      await loadConfig(workspace.path);
+    } catch (error) {
+      return {
+        workspaceId: workspace.id,
+        status: "invalid",
+        error: errorMessage(error),
+        cells: [],
+        timestamp: now().toISOString(),
+      };
+    }
+
+    if (refresh) {
+      reloadTemplates();
+    }
+    return {
+      workspaceId: workspace.id,
+      status: "valid",
+      cells: await collectImpacts(workspace.id),
+      timestamp: now().toISOString(),
+    };
+  }
+
+  async function reload(workspace: WatchedWorkspace): Promise<void> {
+    try {
+      const event = await describeWorkspace(workspace, { refresh: true });
+      const watched = watchers.get(workspace.id);
+      if (watched && watched.imports !== listImports(workspace.path).join()) {
+        watched.close();
+        watchers.delete(workspace.id);
+        watchWorkspace(workspace);
+      }
+      publish(event);
+    } catch (error) {
+      process.stderr.write(
+        `[config] Failed to reload config for ${workspace.id}: ${errorMessage(error)}\n`
+      );
+    }
+  }
+
+  function scheduleReload(workspace: WatchedWorkspace): void {
+    const pending = pendingReloads.get(workspace.id);
+    if (pending) {
+      clearTimeout(pending);
+    }
+    pendingReloads.set(
+      workspace.id,
+      setTimeout(() => {
+        pendingReloads.delete(workspace.id);
+        reload(workspace).catch(() => {
+          // reload reports its own failures.
+        });
+      }, debounceMs)
+    );
+  }
+
+  function unwatch(workspaceId: string): void {
+    watchers.get(workspaceId)?.close();
+    watchers.delete(workspaceId);
+    const pending = pendingReloads.get(workspaceId);
+    if (pending) {
+      clearTimeout(pending);
+      pendingReloads.delete(workspaceId);
+    }
+  }
+
+  /**
+   * Watches the workspace root for config files and the directories of
+   * the local files the config imports for those files.
+   */
+  function watchWorkspace(workspace: WatchedWorkspace): void {
+    const imports = listImports(workspace.path);
+    const watchedFiles = new Map<string, Set<string>>([
+      [workspace.path, new Set(CONFIG_FILES)],
+    ]);
+    for (const file of imports) {
+      const names = watchedFiles.get(dirname(file)) ?? new Set<string>();
+      names.add(basename(file));
+      watchedFiles.set(dirname(file), names);
+    }
+
+    const closers: (() => void)[] = [];
+    for (const [directory, names] of watchedFiles) {
+      try {
+        closers.push(
+          watchDirectory(directory, (filename) => {
+            if (filename === null || names.has(filename)) {
+              scheduleReload(workspace);
+            }
+          })
+        );
+      } catch (error) {
+        process.stderr.write(
+          `[config] Cannot watch ${directory}: ${errorMessage(error)}\n`
+        );
+      }
+    }
+    if (closers.length > 0) {
+      watchers.set(workspace.id, {
+        path: workspace.path,
+        imports: imports.join(),
+        close: () => {
+          for (const close of closers) {
+            close();
+          }
+        },
+      });
+    }
+  }
+
+  /** Starts watching newly registered workspaces and drops removed ones. */
+  async function sync(): Promise<void> {
+    const workspaces = await listWorkspaces();
+    const current = new Map(workspaces.map((entry) => [entry.id, entry]));
+
+    for (const [id, watched] of watchers) {
+      if (current.get(id)?.path !== watched.path) {
+        unwatch(id);
+      }
+    }
+
+    for (const workspace of workspaces) {
+      if (!watchers.has(workspace.id)) {
+        watchWorkspace(workspace);
+      }
+    }
+  }
+
+  return {
+    sync,
+
+    /** The current config state of a workspace, e.g. for a new subscriber. */
+    async describe(workspaceId: string): Promise<ConfigReloadEvent | null> {
+      const workspace = (await listWorkspaces()).find(
+        (entry) => entry.id === workspaceId
+      );
+      return workspace
+        ? await describeWorkspace(workspace, { refresh: false })
+        : null;
+    },
+
+    async start(
+      syncIntervalMs: number = DEFAULT_WORKSPACE_SYNC_INTERVAL_MS
+    ): Promise<void> {
+      if (syncTimer) {
+        return;
+      }
+      await sync();
+      syncTimer = setInterval(() => {
+        sync().catch((error) => {
+          process.stderr.write(
+            `[config] Workspace sync failed: ${errorMessage(error)}\n`
+          );
+        });
+      }, syncIntervalMs);
+    },
+
+    stop(): void {
+      if (syncTimer) {
+        clearInterval(syncTimer);
+        syncTimer = null;
+      }
+      for (const id of [...watchers.keys()]) {
+        unwatch(id);
+      }
+    },
+  };
+}
+
+export type ConfigWatcher = ReturnType<typeof createConfigWatcher>;
+
+export const configWatcher = createConfigWatcher();
@@ -17,6 +17,8 @@ const baseCell: Cell = {
   lastSetupError: null,
   branchName: "cell-1",
   baseCommit: "abc123",
+  startPointMode: "head",
+  startPointRef: "main",
 };
 
 describe("parseDiffRequest", () => {
@@ -1,5 +1,7 @@
 import { join } from "node:path";
 
+import { runGit } from "./git";
+
 export type DiffMode = "workspace" | "branch";
 export type DiffStatus = "modified" | "added" | "deleted";
 
@@ -24,35 +26,6 @@ const PATCH_CONTEXT_ARG = "--unified=200";
 type StatsMap = Map<string, { additions: number; deletions: number }>;
 type StatusMap = Map<string, DiffStatus>;
 
-async function runGit(
-  args: string[],
-  cwd: string
-): Promise<{ stdout: string; stderr: string }> {
-  const child = Bun.spawn({
-    cmd: ["git", ...args],
-    cwd,
-    stdout: "pipe",
-    stderr: "pipe",
-  });
-
-  const stdoutPromise = new Response(child.stdout).text();
-  const stderrPromise = new Response(child.stderr).text();
-  const exitCode = await child.exited;
-  const stdout = await stdoutPromise;
-  const stderr = await stderrPromise;
-
-  if (exitCode !== 0) {
-    throw new Error(
-      `git ${args.join(" ")} failed with code ${exitCode}${stderr ? `: ${stderr.trim()}` : ""}`
-    );
-  }
-
-  return {
-    stdout: stdout.trimEnd(),
-    stderr: stderr.trimEnd(),
-  };
-}
-
 function normalizeNumstatPath(rawPath: string): string {
   if (!rawPath.includes(" => ")) {
     return rawPath;
@@ -257,6 +230,34 @@ export async function getCellDiffSummary(args: {
   };
 }
 
+/**
+ * Summarizes the changes between two commits, optionally limited to `paths`.
+ * Used to describe merge conflicts with the same shape as cell diffs.
+ */
+export async function summarizeCommitRange(args: {
+  cwd: string;
+  from: string;
+  to: string;
+  paths?: string[];
+}): Promise<DiffFileSummary[]> {
+  const { cwd, from, to } = args;
+  const pathArgs = ["--", ...(args.paths ?? [])];
+  const [numstatOutput, statusOutput] = await Promise.all([
+    runGit(["diff", "--numstat", from, to, ...pathArgs], cwd),
+    runGit(["diff", "--name-status", from, to, ...pathArgs], cwd),
+  ]);
+
+  const statsMap = parseNumstatOutput(numstatOutput.stdout);
+  const statusMap = parseStatusOutput(statusOutput.stdout);
+  for (const path of args.paths ?? []) {
+    if (!(statsMap.has(path) || statusMap.has(path))) {
+      statusMap.set(path, "modified");
+    }
+  }
+
+  return buildFileSummaries(statsMap, statusMap);
+}
+
 export async function getCellDiffDetails(args: {
   workspacePath: string;
   mode: DiffMode;
@@ -0,0 +1,223 @@
+import { isAbsolute, resolve as resolvePath } from "node:path";
+
+import {
+  assignedPortTarget,
+  type ComposeService,
+  type DockerService,
+} from "../config/schema";
+
+const CONTAINER_NAME_INVALID_CHARS = /[^a-zA-Z0-9_.-]/g;
+const COMPOSE_PROJECT_INVALID_CHARS = /[^a-z0-9_-]/g;
+const SAFE_SHELL_ARG = /^[A-Za-z0-9_./:=@%+,-]+$/;
+
+export const DOCKER_STOP_TIMEOUT_SECONDS = 5;
+
+export function quoteShellArg(value: string): string {
+  if (value.length > 0 && SAFE_SHELL_ARG.test(value)) {
+    return value;
+  }
+  return `'${value.replace(/'/g, "'\\''")}'`;
+}
+
+export function buildDockerContainerName(
+  cellId: string,
+  serviceName: string
+): string {
+  return `hive-${cellId}-${serviceName}`
+    .replace(CONTAINER_NAME_INVALID_CHARS, "-")
+    .toLowerCase();
+}
+
+/**
+ * Short, stable description of a docker service stored on the service row.
+ * The full `docker run` invocation depends on assigned ports and is built at
+ * start time.
+ */
+export function describeDockerCommand(definition: DockerService): string {
+  return definition.command
+    ? `docker run ${definition.image} ${definition.command}`
+    : `docker run ${definition.image}`;
+}
+
+/**
+ * Port entries may be written as `5432` or `$PORT:5432` (published on the
+ * service's assigned port) or as a full docker mapping, whose tokens are
+ * interpolated. `$PORT:5432` only means "assigned port -> 5432" here;
+ * elsewhere `$PORT:<name>` is another service's port.
+ */
+export function normalizeDockerPortMapping(
+  mapping: string,
+  assignedPort: number,
+  interpolate: (value: string) => string = (value) => value
+): string {
+  const target = assignedPortTarget(mapping);
+  if (target !== null) {
+    return `${assignedPort}:${target}`;
+  }
+  return interpolate(mapping.trim());
+}
+
+export function resolveDockerVolume(
+  volume: string,
+  workspacePath: string
+): string {
+  const [source, ...rest] = volume.split(":");
+  if (!(source && rest.length)) {
+    return volume;
+  }
+
+  // Named volumes and absolute host paths are passed through untouched.
+  const isRelativeHostPath = source.startsWith(".") || source.startsWith("~");
+  if (!isRelativeHostPath || isAbsolute(source)) {
+    return volume;
+  }
+
+  const home = process.env.HOME ?? "";
+  const hostPath = source.startsWith("~")
+    ? resolvePath(home, source.slice(1).replace(/^\/+/, ""))
+    : resolvePath(workspacePath, source);
+
+  return [hostPath, ...rest].join(":");
+}
+
+export function buildDockerRunCommand({
+  containerName,
+  definition,
+  port,
+  workspacePath,
+  envKeys,
+  interpolate,
+}: {
+  containerName: string;
+  definition: DockerService;
+  port: number;
+  workspacePath: string;
+  envKeys: string[];
+  interpolate: (value: string) => string;
+}): string {
+  const args = ["docker", "run", "--rm", "--init", "--name", containerName];
+
+  for (const mapping of definition.ports ?? []) {
+    args.push("-p", normalizeDockerPortMapping(mapping, port, interpolate));
+  }
+
+  // Values are read from the docker client's environment so they never have
+  // to be quoted onto the command line.
+  for (const key of envKeys) {
+    args.push("-e", key);
+  }
+
+  for (const volume of definition.volumes ?? []) {
+    args.push("-v", resolveDockerVolume(interpolate(volume), workspacePath));
+  }
+
+  args.push(definition.image);
+
+  const quoted = args.map(quoteShellArg).join(" ");
+  if (!definition.command) {
+    return quoted;
+  }
+
+  return `${quoted} ${interpolate(definition.command)}`;
+}
+
+export function buildDockerStopCommand(containerName: string): string {
+  return `docker stop --time ${DOCKER_STOP_TIMEOUT_SECONDS} ${quoteShellArg(containerName)}`;
+}
+
+export function buildDockerRemoveCommand(containerName: string): string {
+  return `docker rm --force ${quoteShellArg(containerName)}`;
+}
+
+/**
+ * Every cell gets its own compose project so container names, networks and
+ * named volumes never collide between cells running the same stack.
+ */
+export function buildComposeProjectName(cellId: string): string {
+  return `hive-${cellId}`
+    .toLowerCase()
+    .replace(COMPOSE_PROJECT_INVALID_CHARS, "-");
+}
+
+export function describeComposeCommand(definition: ComposeService): string {
+  const services = definition.services ?? [];
+  return services.length
+    ? `docker compose -f ${definition.file} up ${services.join(" ")}`
+    : `docker compose -f ${definition.file} up`;
+}
+
+function buildComposeCommand(args: {
+  projectName?: string;
+  file: string;
+  subcommand: string[];
+}): string {
+  const parts = ["docker", "compose"];
+  if (args.projectName) {
+    parts.push("--project-name", args.projectName);
+  }
+  parts.push("--file", args.file, ...args.subcommand);
+  return parts.map(quoteShellArg).join(" ");
+}
+
+export function buildComposeListServicesCommand(file: string): string {
+  return buildComposeCommand({ file, subcommand: ["config", "--services"] });
+}
+
+export function buildComposeUpCommand(
+  projectName: string,
+  file: string,
+  service: string
+): string {
+  return buildComposeCommand({
+    projectName,
+    file,
+    subcommand: ["up", "--detach", service],
+  });
+}
+
+export function buildComposeLogsCommand(
+  projectName: string,
+  file: string,
+  service: string
+): string {
+  return buildComposeCommand({
+    projectName,
+    file,
+    subcommand: ["logs", "--follow", "--no-log-prefix", service],
+  });
+}
+
+export function buildComposeStopCommand(
+  projectName: string,
+  file: string,
+  service: string
+): string {
+  return buildComposeCommand({
+    projectName,
+    file,
+    subcommand: [
+      "stop",
+      "--timeout",
+      String(DOCKER_STOP_TIMEOUT_SECONDS),
+      service,
+    ],
+  });
+}
+
+export function buildComposeDownCommand(
+  projectName: string,
+  file: string
+): string {
+  return buildComposeCommand({
+    projectName,
+    file,
+    subcommand: ["down", "--volumes", "--remove-orphans"],
+  });
+}
+
+export function parseComposeServiceList(output: string): string[] {
+  return output
+    .split(/\r?\n/)
+    .map((line) => line.trim())
+    .filter((line) => line.length > 0);
+}
@@ -2,12 +2,15 @@ import { afterEach, describe, expect, test, vi } from "vitest";
 import {
   type CellStatusEvent,
   type CellTimingEvent,
+  emitAgentStatusUpdate,
   emitCellStatusUpdate,
   emitCellTimingUpdate,
+  emitServiceCrash,
   emitServiceUpdate,
   type ServiceUpdateEvent,
   subscribeToCellStatusEvents,
   subscribeToCellTimingEvents,
+  subscribeToLifecycleEvents,
   subscribeToServiceEvents,
 } from "./events";
 
@@ -205,3 +208,48 @@ describe("cell timing events", () => {
     expect(handler).not.toHaveBeenCalled();
   });
 });
+
+describe("lifecycle events", () => {
+  test("carries cell, service and agent events from every workspace", () => {
+    const handler = vi.fn();
+    const unsubscribe = subscribeToLifecycleEvents(handler);
+
+    const cellEvent: CellStatusEvent = {
+      workspaceId: "workspace-1",
+      cellId: "cell-1",
+      status: "error",
+      lastSetupError: "bun install failed",
+    };
+    emitCellStatusUpdate(cellEvent);
+    emitServiceCrash({
+      workspaceId: "workspace-2",
+      cellId: "cell-2",
+      serviceId: "service-1",
+      serviceName: "web",
+      exitCode: 1,
+      willRestart: true,
+    });
+    emitAgentStatusUpdate({
+      workspaceId: "workspace-2",
+      cellId: "cell-2",
+      sessionId: "session-1",
+      status: "awaiting_input",
+      previousStatus: "working",
+    });
+
+    expect(handler.mock.calls.map(([event]) => event.type)).toEqual([
+      "cell.status",
+      "service.crashed",
+      "agent.status",
+    ]);
+    expect(handler.mock.calls[0]?.[0]).toEqual({
+      type: "cell.status",
+      event: cellEvent,
+    });
+
+    unsubscribe();
+    emitServiceUpdate({ cellId: "cell-1", serviceId: "service-1" });
+    emitCellStatusUpdate(cellEvent);
+    expect(handler).toHaveBeenCalledTimes(3);
+  });
+});
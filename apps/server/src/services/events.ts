@@ -1,9 +1,11 @@
 import { EventEmitter } from "node:events";
+import type { AgentSessionStatus } from "../agents/types";
 import type { CellStatus } from "../schema/cells";
 import type {
   CellTimingStatus,
   CellTimingWorkflow,
 } from "../schema/timing-events";
+import type { CellConfigImpact } from "./config-impact";
 
 export type ServiceUpdateEvent = {
   cellId: string;
@@ -26,6 +28,47 @@ export type CellTimingEvent = {
   createdAt: string;
 };
 
+export type ServiceCrashEvent = {
+  workspaceId: string;
+  cellId: string;
+  serviceId: string;
+  serviceName: string;
+  exitCode: number;
+  /** False once the restart policy gave up or does not restart. */
+  willRestart: boolean;
+};
+
+export type AgentStatusEvent = {
+  workspaceId: string;
+  cellId: string;
+  sessionId: string;
+  status: AgentSessionStatus;
+  previousStatus: AgentSessionStatus;
+  error?: string;
+};
+
+/** Published when a workspace's config file changes and is revalidated. */
+export type ConfigReloadEvent = {
+  workspaceId: string;
+  status: "valid" | "invalid";
+  /** Why the config was rejected; running services keep their definitions. */
+  error?: string;
+  /** Cells whose services no longer match the config. */
+  cells: CellConfigImpact[];
+  timestamp: string;
+};
+
+/**
+ * Everything that happens to cells across all workspaces, for consumers such
+ * as webhooks that are not tied to one workspace or cell.
+ */
+export type LifecycleEvent =
+  | { type: "cell.status"; event: CellStatusEvent }
+  | { type: "service.crashed"; event: ServiceCrashEvent }
+  | { type: "agent.status"; event: AgentStatusEvent };
+
+const LIFECYCLE_CHANNEL = "lifecycle";
+
 const emitter = new EventEmitter();
 emitter.setMaxListeners(0);
 
@@ -45,6 +88,10 @@ export function subscribeToServiceEvents(
 
 export function emitCellStatusUpdate(event: CellStatusEvent): void {
   emitter.emit(`workspace:${event.workspaceId}`, event);
+  emitter.emit(LIFECYCLE_CHANNEL, {
+    type: "cell.status",
+    event,
+  } satisfies LifecycleEvent);
 }
 
 export function subscribeToCellStatusEvents(
@@ -72,3 +119,41 @@ export function subscribeToCellTimingEvents(
     emitter.off(channel, listener);
   };
 }
+
+export function emitServiceCrash(event: ServiceCrashEvent): void {
+  emitter.emit(LIFECYCLE_CHANNEL, {
+    type: "service.crashed",
+    event,
+  } satisfies LifecycleEvent);
+}
+
+export function emitAgentStatusUpdate(event: AgentStatusEvent): void {
+  emitter.emit(LIFECYCLE_CHANNEL, {
+    type: "agent.status",
+    event,
+  } satisfies LifecycleEvent);
+}
+
+export function subscribeToLifecycleEvents(
+  listener: (event: LifecycleEvent) => void
+): () => void {
+  emitter.on(LIFECYCLE_CHANNEL, listener);
+  return () => {
+    emitter.off(LIFECYCLE_CHANNEL, listener);
+  };
+}
+
+export function emitConfigReload(event: ConfigReloadEvent): void {
+  emitter.emit(`config:${event.workspaceId}`, event);
+}
+
+export function subscribeToConfigReloadEvents(
+  workspaceId: string,
+  listener: (event: ConfigReloadEvent) => void
+): () => void {
+  const channel = `config:${workspaceId}`;
+  emitter.on(channel, listener);
+  return () => {
+    emitter.off(channel, listener);
+  };
+}
@@ -0,0 +1,75 @@
+export class GitCommandError extends Error {
+  readonly args: string[];
+  readonly exitCode: number;
+  readonly stdout: string;
+  readonly stderr: string;
+
+  constructor(params: {
+    args: string[];
+    exitCode: number;
+    stdout: string;
+    stderr: string;
+  }) {
+    super(
+      `git ${params.args.join(" ")} failed with code ${params.exitCode}${
+        params.stderr ? `: ${params.stderr.trim()}` : ""
+      }`
+    );
+    this.name = "GitCommandError";
+    this.args = params.args;
+    this.exitCode = params.exitCode;
+    this.stdout = params.stdout;
+    this.stderr = params.stderr;
+  }
+}
+
+export async function runGit(
+  args: string[],
+  cwd: string,
+  options: { env?: Record<string, string> } = {}
+): Promise<{ stdout: string; stderr: string }> {
+  const child = Bun.spawn({
+    cmd: ["git", ...args],
+    cwd,
+    stdout: "pipe",
+    stderr: "pipe",
+    env: options.env ? { ...process.env, ...options.env } : undefined,
+  });
+
+  const stdoutPromise = new Response(child.stdout).text();
+  const stderrPromise = new Response(child.stderr).text();
+  const exitCode = await child.exited;
+  const stdout = await stdoutPromise;
+  const stderr = await stderrPromise;
+
+  if (exitCode !== 0) {
+    throw new GitCommandError({ args, exitCode, stdout, stderr });
+  }
+
+  return {
+    stdout: stdout.trimEnd(),
+    stderr: stderr.trimEnd(),
+  };
+}
+
+export async function isAncestorCommit(
+  cwd: string,
+  ancestor: string,
+  descendant: string
+): Promise<boolean> {
+  try {
+    await runGit(["merge-base", "--is-ancestor", ancestor, descendant], cwd);
+    return true;
+  } catch {
+    return false;
+  }
+}
+
+/** Paths left unmerged by a stopped merge, rebase or cherry-pick. */
+export async function listConflictedPaths(cwd: string): Promise<string[]> {
+  const { stdout } = await runGit(
+    ["diff", "--name-only", "--diff-filter=U"],
+    cwd
+  );
+  return stdout.split("\n").filter(Boolean);
+}
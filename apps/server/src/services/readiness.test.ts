@@ -0,0 +1,116 @@
+import { createServer as createHttpServer, type Server } from "node:http";
+import { createServer as createNetServer } from "node:net";
+import { afterEach, describe, expect, test } from "vitest";
+import { ReadinessError, waitForReadiness } from "./readiness";
+
+const pendingExit = () => new Promise<number>(() => undefined);
+
+const baseContext = {
+  readOutput: () => "",
+  runCommand: () => Promise.resolve(),
+};
+
+function listen(server: Server | ReturnType<typeof createNetServer>) {
+  return new Promise<number>((resolvePort, rejectPort) => {
+    server.once("error", rejectPort);
+    server.listen(0, "127.0.0.1", () => {
+      const address = server.address();
+      resolvePort(address && typeof address === "object" ? address.port : 0);
+    });
+  });
+}
+
+describe("readiness probes", () => {
+  const servers: Array<{ close: (callback: () => void) => void }> = [];
+
+  afterEach(async () => {
+    await Promise.all(
+      servers.map(
+        (server) => new Promise<void>((resolve) => server.close(resolve))
+      )
+    );
+    servers.length = 0;
+  });
+
+  test("http probe waits for the expected status", async () => {
+    let requests = 0;
+    const server = createHttpServer((request, response) => {
+      requests += 1;
+      const healthy = request.url === "/health" && requests > 1;
+      response.statusCode = healthy ? 204 : 503;
+      response.end();
+    });
+    servers.push(server);
+    const port = await listen(server);
+
+    await waitForReadiness(
+      { type: "http", path: "/health", expectedStatus: 204, intervalMs: 10 },
+      { ...baseContext, port, exited: pendingExit() },
+      1000
+    );
+
+    expect(requests).toBeGreaterThan(1);
+  });
+
+  test("tcp probe connects to the assigned port", async () => {
+    const server = createNetServer((socket) => socket.end());
+    servers.push(server);
+    const port = await listen(server);
+
+    await expect(
+      waitForReadiness(
+        { type: "tcp", intervalMs: 10 },
+        { ...baseContext, port, exited: pendingExit() },
+        500
+      )
+    ).resolves.toBeUndefined();
+  });
+
+  test("log probe matches output without ANSI escapes", async () => {
+    let output = "";
+    setTimeout(() => {
+      output = "\u001b[32mready\u001b[0m on port 3000\n";
+    }, 20);
+
+    await waitForReadiness(
+      { type: "log", pattern: "^ready on port \\d+", intervalMs: 10 },
+      {
+        ...baseContext,
+        readOutput: () => output,
+        port: null,
+        exited: pendingExit(),
+      },
+      1000
+    );
+  });
+
+  test("command probe reports the last failure on timeout", async () => {
+    const error = await waitForReadiness(
+      { type: "command", run: "pg_isready", intervalMs: 10 },
+      {
+        ...baseContext,
+        port: null,
+        exited: pendingExit(),
+        runCommand: () => Promise.reject(new Error("no response")),
+      },
+      50
+    ).catch((cause: unknown) => cause);
+
+    expect(error).toBeInstanceOf(ReadinessError);
+    expect((error as ReadinessError).message).toContain(
+      'Readiness check (command "pg_isready") timed out after 50ms'
+    );
+    expect((error as ReadinessError).message).toContain("no response");
+  });
+
+  test("fails fast when the service exits before becoming ready", async () => {
+    const error = await waitForReadiness(
+      { type: "tcp", port: 1, intervalMs: 10 },
+      { ...baseContext, port: null, exited: Promise.resolve(3) },
+      5000
+    ).catch((cause: unknown) => cause);
+
+    expect(error).toBeInstanceOf(ReadinessError);
+    expect((error as ReadinessError).exitCode).toBe(3);
+  });
+});
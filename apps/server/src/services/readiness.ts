@@ -0,0 +1,255 @@
+import { connect } from "node:net";
+import { setTimeout as delay } from "node:timers/promises";
+
+import type { Readiness } from "../config/schema";
+
+export const DEFAULT_READINESS_TIMEOUT_MS = 60_000;
+const DEFAULT_READINESS_INTERVAL_MS = 500;
+const PROBE_ATTEMPT_TIMEOUT_MS = 2000;
+const DEFAULT_PROBE_HOST = "127.0.0.1";
+const DEFAULT_HTTP_PATH = "/";
+const DEFAULT_HTTP_STATUS = 200;
+
+export type ReadinessProbeContext = {
+  port: number | null;
+  readOutput: () => string;
+  runCommand: (command: string) => Promise<void>;
+  exited: Promise<number>;
+  fetch?: typeof fetch;
+};
+
+type ProbeAttempt = {
+  ready: boolean;
+  detail?: string;
+};
+
+export class ReadinessError extends Error {
+  readonly probe: Readiness["type"];
+  readonly exitCode?: number;
+
+  constructor(params: {
+    probe: Readiness["type"];
+    message: string;
+    exitCode?: number;
+  }) {
+    super(params.message);
+    this.name = "ReadinessError";
+    this.probe = params.probe;
+    if (typeof params.exitCode === "number") {
+      this.exitCode = params.exitCode;
+    }
+  }
+}
+
+export function resolveReadinessTimeoutMs(
+  probe: Readiness,
+  readyTimeoutMs?: number | null
+): number {
+  return probe.timeoutMs ?? readyTimeoutMs ?? DEFAULT_READINESS_TIMEOUT_MS;
+}
+
+export function describeReadinessProbe(probe: Readiness): string {
+  switch (probe.type) {
+    case "http":
+      return `HTTP GET ${probe.path ?? DEFAULT_HTTP_PATH} expecting ${
+        probe.expectedStatus ?? DEFAULT_HTTP_STATUS
+      }`;
+    case "tcp":
+      return "TCP connect";
+    case "log":
+      return `output matching /${probe.pattern}/`;
+    case "command":
+      return `command "${probe.run}"`;
+    default:
+      return "readiness probe";
+  }
+}
+
+/**
+ * Polls the probe until it passes, the service exits, or the timeout
+ * elapses. Rejects with a ReadinessError describing the last failed attempt.
+ */
+export async function waitForReadiness(
+  probe: Readiness,
+  context: ReadinessProbeContext,
+  timeoutMs: number
+): Promise<void> {
+  const intervalMs = probe.intervalMs ?? DEFAULT_READINESS_INTERVAL_MS;
+  const deadline = Date.now() + timeoutMs;
+  let exitCode: number | null = null;
+  context.exited.then(
+    (code) => {
+      exitCode = code;
+    },
+    () => {
+      exitCode = -1;
+    }
+  );
+
+  let lastDetail: string | undefined;
+  while (true) {
+    if (exitCode !== null) {
+      throw new ReadinessError({
+        probe: probe.type,
+        message: `Service exited with code ${exitCode} before becoming ready`,
+        exitCode,
+      });
+    }
+
+    const attempt = await runProbeAttempt(probe, context);
+    if (attempt.ready) {
+      return;
+    }
+    lastDetail = attempt.detail ?? lastDetail;
+
+    if (Date.now() + intervalMs > deadline) {
+      break;
+    }
+    await delay(intervalMs);
+  }
+
+  throw new ReadinessError({
+    probe: probe.type,
+    message: `Readiness check (${describeReadinessProbe(probe)}) timed out after ${timeoutMs}ms${
+      lastDetail ? `: ${lastDetail}` : ""
+    }`,
+  });
+}
+
+async function runProbeAttempt(
+  probe: Readiness,
+  context: ReadinessProbeContext
+): Promise<ProbeAttempt> {
+  switch (probe.type) {
+    case "http":
+      return await probeHttp(probe, context);
+    case "tcp":
+      return await probeTcp(probe, context);
+    case "log":
+      return probeLog(probe, context);
+    case "command":
+      return await probeCommand(probe, context);
+    default:
+      return { ready: false, detail: "Unsupported readiness probe" };
+  }
+}
+
+function resolveProbePort(
+  probe: { port?: number },
+  context: ReadinessProbeContext
+): number | null {
+  return probe.port ?? context.port;
+}
+
+async function probeHttp(
+  probe: Extract<Readiness, { type: "http" }>,
+  context: ReadinessProbeContext
+): Promise<ProbeAttempt> {
+  const port = resolveProbePort(probe, context);
+  if (port === null) {
+    return { ready: false, detail: "No port available to probe" };
+  }
+
+  const rawPath = probe.path ?? DEFAULT_HTTP_PATH;
+  const path = rawPath.startsWith("/") ? rawPath : `/${rawPath}`;
+  const expectedStatus = probe.expectedStatus ?? DEFAULT_HTTP_STATUS;
+  const url = `http://${probe.host ?? DEFAULT_PROBE_HOST}:${port}${path}`;
+  const fetcher = context.fetch ?? fetch;
+  try {
+    const response = await fetcher(url, {
+      method: "GET",
+      redirect: "manual",
+      signal: AbortSignal.timeout(PROBE_ATTEMPT_TIMEOUT_MS),
+    });
+    await response.body?.cancel().catch(() => {
+      /* ignore body disposal errors */
+    });
+    if (response.status === expectedStatus) {
+      return { ready: true };
+    }
+    return {
+      ready: false,
+      detail: `GET ${url} returned ${response.status}`,
+    };
+  } catch (error) {
+    return {
+      ready: false,
+      detail: `GET ${url} failed: ${
+        error instanceof Error ? error.message : String(error)
+      }`,
+    };
+  }
+}
+
+function probeTcp(
+  probe: Extract<Readiness, { type: "tcp" }>,
+  context: ReadinessProbeContext
+): Promise<ProbeAttempt> {
+  const port = resolveProbePort(probe, context);
+  if (port === null) {
+    return Promise.resolve({
+      ready: false,
+      detail: "No port available to probe",
+    });
+  }
+
+  const host = probe.host ?? DEFAULT_PROBE_HOST;
+  return new Promise((resolveAttempt) => {
+    const socket = connect({ host, port });
+    const finish = (attempt: ProbeAttempt) => {
+      socket.removeAllListeners();
+      socket.destroy();
+      resolveAttempt(attempt);
+    };
+
+    socket.setTimeout(PROBE_ATTEMPT_TIMEOUT_MS);
+    socket.once("connect", () => finish({ ready: true }));
+    socket.once("timeout", () =>
+      finish({
+        ready: false,
+        detail: `Connecting to ${host}:${port} timed out`,
+      })
+    );
+    socket.once("error", (error) =>
+      finish({
+        ready: false,
+        detail: `Connecting to ${host}:${port} failed: ${error.message}`,
+      })
+    );
+  });
+}
+
+function probeLog(
+  probe: Extract<Readiness, { type: "log" }>,
+  context: ReadinessProbeContext
+): ProbeAttempt {
+  const pattern = new RegExp(probe.pattern, "m");
+  if (pattern.test(stripAnsi(context.readOutput()))) {
+    return { ready: true };
+  }
+  return { ready: false, detail: `No output matched /${probe.pattern}/` };
+}
+
+async function probeCommand(
+  probe: Extract<Readiness, { type: "command" }>,
+  context: ReadinessProbeContext
+): Promise<ProbeAttempt> {
+  try {
+    await context.runCommand(probe.run);
+    return { ready: true };
+  } catch (error) {
+    return {
+      ready: false,
+      detail: error instanceof Error ? error.message : String(error),
+    };
+  }
+}
+
+const ANSI_ESCAPE_PATTERN = new RegExp(
+  `${String.fromCharCode(27)}\\[[0-9;?]*[ -/]*[@-~]`,
+  "g"
+);
+
+function stripAnsi(value: string): string {
+  return value.replace(ANSI_ESCAPE_PATTERN, "");
+}
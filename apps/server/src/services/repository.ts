@@ -1,5 +1,11 @@
 import { and, eq } from "drizzle-orm";
+import type { TemplateParameterValues } from "../config/template-parameters";
 import { db } from "../db";
+import {
+  type ActivityEventType,
+  cellActivityEvents,
+} from "../schema/activity-events";
+import { cellProvisioningStates } from "../schema/cell-provisioning";
 import type { Cell } from "../schema/cells";
 import { cells } from "../schema/cells";
 import type { CellService } from "../schema/services";
@@ -12,6 +18,14 @@ type ServiceRow = {
   cell: Cell;
 };
 
+type ServiceActivityEvent = {
+  cellId: string;
+  serviceId: string;
+  type: ActivityEventType;
+  source?: string;
+  metadata?: Record<string, unknown>;
+};
+
 export function createServiceRepository(database: DbClient, now: () => Date) {
   async function findByCellAndName(
     cellId: string,
@@ -96,6 +110,20 @@ export function createServiceRepository(database: DbClient, now: () => Date) {
     return rows.map(mapRow);
   }
 
+  async function deleteService(serviceId: string): Promise<void> {
+    await database.delete(cellServices).where(eq(cellServices.id, serviceId));
+  }
+
+  async function fetchCell(cellId: string): Promise<Cell | undefined> {
+    const [record] = await database
+      .select()
+      .from(cells)
+      .where(eq(cells.id, cellId))
+      .limit(1);
+
+    return record;
+  }
+
   async function fetchAllServices(): Promise<ServiceRow[]> {
     const rows = await database
       .select()
@@ -105,6 +133,31 @@ export function createServiceRepository(database: DbClient, now: () => Date) {
     return rows.map(mapRow);
   }
 
+  async function fetchTemplateParameters(
+    cellId: string
+  ): Promise<TemplateParameterValues | null> {
+    const [record] = await database
+      .select({ templateParameters: cellProvisioningStates.templateParameters })
+      .from(cellProvisioningStates)
+      .where(eq(cellProvisioningStates.cellId, cellId))
+      .limit(1);
+
+    return record?.templateParameters ?? null;
+  }
+
+  async function insertActivityEvent(event: ServiceActivityEvent) {
+    await database.insert(cellActivityEvents).values({
+      id: crypto.randomUUID(),
+      cellId: event.cellId,
+      serviceId: event.serviceId,
+      type: event.type,
+      source: event.source ?? null,
+      toolName: null,
+      metadata: event.metadata ?? {},
+      createdAt: now(),
+    });
+  }
+
   return {
     findByCellAndName,
     insertService,
@@ -112,7 +165,11 @@ export function createServiceRepository(database: DbClient, now: () => Date) {
     markError,
     fetchServiceRowById,
     fetchServicesForCell,
+    deleteService,
+    fetchCell,
     fetchAllServices,
+    fetchTemplateParameters,
+    insertActivityEvent,
   };
 }
 
@@ -143,7 +200,13 @@ export type ServiceRepositoryService = {
     serviceId: string
   ) => Promise<ServiceRow | undefined>;
   readonly fetchServicesForCell: (cellId: string) => Promise<ServiceRow[]>;
+  readonly deleteService: (serviceId: string) => Promise<void>;
+  readonly fetchCell: (cellId: string) => Promise<Cell | undefined>;
   readonly fetchAllServices: () => Promise<ServiceRow[]>;
+  readonly fetchTemplateParameters: (
+    cellId: string
+  ) => Promise<TemplateParameterValues | null>;
+  readonly insertActivityEvent: (event: ServiceActivityEvent) => Promise<void>;
 };
 
 export const serviceRepository: ServiceRepositoryService =
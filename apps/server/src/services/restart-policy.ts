@@ -0,0 +1,49 @@
+import type { RestartPolicy } from "../config/schema";
+
+export const DEFAULT_RESTART_MAX_RETRIES = 5;
+export const DEFAULT_RESTART_BACKOFF_MS = 1000;
+const MAX_RESTART_BACKOFF_MS = 60_000;
+/** A restarted service that stays up this long starts over with its retries. */
+export const RESTART_STABLE_WINDOW_MS = 5 * 60_000;
+
+export type ResolvedRestartPolicy = {
+  policy: NonNullable<RestartPolicy["policy"]>;
+  maxRetries: number;
+  backoffMs: number;
+};
+
+export function resolveRestartPolicy(
+  restart: RestartPolicy | undefined
+): ResolvedRestartPolicy {
+  return {
+    policy: restart?.policy ?? "never",
+    maxRetries: restart?.maxRetries ?? DEFAULT_RESTART_MAX_RETRIES,
+    backoffMs: restart?.backoffMs ?? DEFAULT_RESTART_BACKOFF_MS,
+  };
+}
+
+export function shouldRestartAfterExit(
+  policy: ResolvedRestartPolicy,
+  exitCode: number
+): boolean {
+  switch (policy.policy) {
+    case "always":
+      return true;
+    case "on-failure":
+      return exitCode !== 0;
+    default:
+      return false;
+  }
+}
+
+/**
+ * Exponential backoff: the first restart waits `backoffMs`, each further
+ * attempt doubles the delay up to a one minute ceiling.
+ */
+export function computeRestartDelayMs(
+  policy: ResolvedRestartPolicy,
+  attempt: number
+): number {
+  const exponent = Math.max(attempt - 1, 0);
+  return Math.min(policy.backoffMs * 2 ** exponent, MAX_RESTART_BACKOFF_MS);
+}
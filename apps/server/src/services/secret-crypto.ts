@@ -0,0 +1,57 @@
+import {
+  createCipheriv,
+  createDecipheriv,
+  createHash,
+  randomBytes,
+} from "node:crypto";
+
+const ALGORITHM = "aes-256-gcm";
+const IV_LENGTH_BYTES = 12;
+const SECRET_PART_COUNT = 3;
+
+/**
+ * Derives a 256-bit key from a passphrase. The purpose prefix keeps keys for
+ * different stores distinct even when they share a passphrase.
+ */
+export const deriveSecretKey = (purpose: string, secret: string) =>
+  createHash("sha256").update(`${purpose}:${secret}`).digest();
+
+const encode = (value: Buffer) => value.toString("base64url");
+const decode = (value: string) => Buffer.from(value, "base64url");
+
+/** Encrypts to `iv.authTag.ciphertext`, each part base64url encoded. */
+export const encryptSecretValue = (value: string, key: Buffer) => {
+  const iv = randomBytes(IV_LENGTH_BYTES);
+  const cipher = createCipheriv(ALGORITHM, key, iv);
+  const encrypted = Buffer.concat([
+    cipher.update(value, "utf8"),
+    cipher.final(),
+  ]);
+  const authTag = cipher.getAuthTag();
+
+  return [iv, authTag, encrypted].map(encode).join(".");
+};
+
+export const decryptSecretValue = (
+  value: string,
+  key: Buffer,
+  malformedMessage = "Stored secret is malformed"
+) => {
+  const parts = value.split(".");
+  if (parts.length !== SECRET_PART_COUNT) {
+    throw new Error(malformedMessage);
+  }
+
+  const [ivPart, authTagPart, encryptedPart] = parts as [
+    string,
+    string,
+    string,
+  ];
+  const decipher = createDecipheriv(ALGORITHM, key, decode(ivPart));
+  decipher.setAuthTag(decode(authTagPart));
+
+  return Buffer.concat([
+    decipher.update(decode(encryptedPart)),
+    decipher.final(),
+  ]).toString("utf8");
+};
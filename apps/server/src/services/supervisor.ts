@@ -2,20 +2,69 @@ import { randomUUID } from "node:crypto";
 import { existsSync, mkdirSync } from "node:fs";
 import { createServer } from "node:net";
 import { constants as osConstants } from "node:os";
-import { resolve as resolvePath } from "node:path";
+import { dirname, resolve as resolvePath } from "node:path";
 import { setTimeout as delay } from "node:timers/promises";
 
 import { type IExitEvent, spawn as spawnPty } from "bun-pty";
 
 import { resolveWorkspaceRoot } from "../config/context";
 import { loadConfig } from "../config/loader";
-import type { HiveConfig, ProcessService, Template } from "../config/schema";
+import type {
+  ComposeService,
+  DockerService,
+  HiveConfig,
+  ProcessService,
+  Service,
+  Template,
+} from "../config/schema";
+import {
+  collectServiceDependencies,
+  resolveServiceStartOrder,
+} from "../config/service-dependencies";
+import {
+  applyTemplateParameters,
+  resolveTemplateParameterValues,
+  TemplateParameterError,
+  type TemplateParameterValues,
+} from "../config/template-parameters";
 import { db as defaultDb } from "../db";
 import type { Cell } from "../schema/cells";
 import type { CellService, ServiceStatus } from "../schema/services";
-import { emitServiceUpdate } from "./events";
+import {
+  type CellConfigImpact,
+  type ConfigFieldChange,
+  diffConfigFields,
+  type ServiceConfigChange,
+} from "./config-impact";
+import {
+  buildComposeDownCommand,
+  buildComposeListServicesCommand,
+  buildComposeLogsCommand,
+  buildComposeProjectName,
+  buildComposeStopCommand,
+  buildComposeUpCommand,
+  buildDockerContainerName,
+  buildDockerRemoveCommand,
+  buildDockerRunCommand,
+  buildDockerStopCommand,
+  describeComposeCommand,
+  describeDockerCommand,
+  parseComposeServiceList,
+} from "./docker";
+import { emitServiceCrash, emitServiceUpdate } from "./events";
 import { createPortManager } from "./port-manager";
+import {
+  describeReadinessProbe,
+  resolveReadinessTimeoutMs,
+  waitForReadiness,
+} from "./readiness";
 import { createServiceRepository } from "./repository";
+import {
+  computeRestartDelayMs,
+  RESTART_STABLE_WINDOW_MS,
+  resolveRestartPolicy,
+  shouldRestartAfterExit,
+} from "./restart-policy";
 import {
   createServiceTerminalRuntime,
   type ServiceTerminalEvent,
@@ -23,6 +72,11 @@ import {
   type ServiceTerminalSession,
   serviceTerminalRuntime,
 } from "./service-terminal";
+import {
+  createWorkspaceSecretStore,
+  type ResolvedSecretEnv,
+  type ResolveSecretEnv,
+} from "./workspace-secrets";
 
 const AUTO_RESTART_STATUSES: ReadonlySet<ServiceStatus> = new Set([
   "pending",
@@ -31,6 +85,12 @@ const AUTO_RESTART_STATUSES: ReadonlySet<ServiceStatus> = new Set([
   "needs_resume",
 ]);
 
+// Applying a config change restarts only services that are up right now.
+const RESTART_ON_APPLY_STATUSES: ReadonlySet<ServiceStatus> = new Set([
+  "running",
+  "starting",
+]);
+
 const cellServiceLocks = new Map<string, Promise<void>>();
 const serviceStartLocks = new Map<string, Promise<void>>();
 
@@ -42,6 +102,7 @@ const TERMINAL_NAME = "xterm-256color";
 const DEFAULT_TERMINAL_COLS = 120;
 const DEFAULT_TERMINAL_ROWS = 36;
 const SIGNAL_CODES = osConstants?.signals ?? {};
+const READINESS_OUTPUT_LIMIT = 64 * 1024;
 
 function resolveTemplateSetupCommandTimeoutMs(): number {
   const raw = process.env.HIVE_TEMPLATE_SETUP_COMMAND_TIMEOUT_MS;
@@ -245,6 +306,15 @@ export type EnsureCellServicesTimingEvent = {
   metadata?: Record<string, unknown>;
 };
 
+export type StopCellServicesOptions = {
+  releasePorts?: boolean;
+  /**
+   * Leave services that were running as `needs_resume` so a later
+   * `resumeCellServices` brings them back, like a server shutdown does.
+   */
+  suspend?: boolean;
+};
+
 export type ServiceSupervisor = {
   bootstrap(): Promise<void>;
   ensureCellServices(args: {
@@ -260,9 +330,15 @@ export type ServiceSupervisor = {
   ): Promise<void>;
   stopCellServices(
     cellId: string,
-    options?: { releasePorts?: boolean }
+    options?: StopCellServicesOptions
   ): Promise<void>;
+  resumeCellServices(cellId: string): Promise<void>;
+  teardownCellServices(cellId: string): Promise<void>;
   stopAll(): Promise<void>;
+  /** Drops cached templates so later starts read the current config. */
+  reloadTemplates(workspaceRootPath?: string): void;
+  previewCellConfig(cellId: string): Promise<CellConfigImpact | null>;
+  applyCellConfig(cellId: string): Promise<CellConfigImpact | null>;
 };
 
 export type SupervisorDependencies = {
@@ -273,6 +349,7 @@ export type SupervisorDependencies = {
   logger: ServiceLogger;
   loadHiveConfig: (workspaceRoot?: string) => Promise<HiveConfig>;
   terminalRuntime: ServiceTerminalRuntime;
+  resolveSecretEnv: ResolveSecretEnv;
 };
 
 type ServiceLogger = {
@@ -286,16 +363,25 @@ type ServiceRow = {
   cell: Cell;
 };
 
+type SupervisedService = ProcessService | DockerService | ComposeService;
+
+type PreparedService = {
+  row: ServiceRow;
+  definition: SupervisedService;
+};
+
 type ActiveServiceHandle = {
   handle: ProcessHandle;
 };
 
 type ServiceProcessOptions = {
   row: ServiceRow;
-  definition: ProcessService;
+  definition: SupervisedService;
   env: Record<string, string>;
   cwd: string;
   command: string;
+  createOutputRedactor: ResolvedSecretEnv["createOutputRedactor"];
+  onTimingEvent?: (event: EnsureCellServicesTimingEvent) => void;
 };
 
 function createDefaultLogger(): ServiceLogger {
@@ -440,41 +526,87 @@ export function createServiceSupervisor(
       loadConfig(workspaceRoot ?? resolveWorkspaceRoot()));
   const terminalRuntime =
     overrides.terminalRuntime ?? createServiceTerminalRuntime();
+  const resolveSecretEnv =
+    overrides.resolveSecretEnv ?? createWorkspaceSecretStore({ db }).resolveEnv;
 
   const activeServices = new Map<string, ActiveServiceHandle>();
   const repository = createServiceRepository(db, now);
   const portManager = createPortManager({ db, now });
   const templateCache = new Map<string, Map<string, Template | undefined>>();
+  const restartAttempts = new Map<string, number>();
+  const serviceReadyAt = new Map<string, number>();
+  const pendingRestarts = new Map<string, ReturnType<typeof setTimeout>>();
 
   async function bootstrap(): Promise<void> {
     const grouped = groupServicesByCell(await repository.fetchAllServices());
 
     for (const { cell, rows: cellRows } of grouped.values()) {
-      const template = await loadTemplateCached(
-        cell.templateId,
-        cell.workspaceRootPath ?? cell.workspacePath
+      // Suspended cells keep their services down until they are opened.
+      if (cell.status === "suspended") {
+        continue;
+      }
+      await resumeServiceRows(cell, cellRows);
+    }
+  }
+
+  async function resumeServiceRows(
+    cell: Cell,
+    cellRows: ServiceRow[]
+  ): Promise<void> {
+    let template: Template | undefined;
+    try {
+      template = await loadTemplateForStart(
+        cell,
+        cellRows.filter((row) => AUTO_RESTART_STATUSES.has(row.service.status))
       );
-      const templateEnv = template?.env ?? {};
-      const portMap = await buildPortMap(cellRows);
+    } catch (error) {
+      // The rows are already marked errored; keep resuming other cells.
+      if (error instanceof TemplateParameterError) {
+        return;
+      }
+      throw error;
+    }
+    const templateEnv = template?.env ?? {};
+    const portMap = await buildPortMap(cellRows);
 
-      await restartServicesForCell({
-        rows: cellRows,
-        portMap,
-        templateEnv,
-      });
+    await restartServicesForCell({
+      rows: orderRowsForStart(cellRows, template),
+      template,
+      portMap,
+      templateEnv,
+    });
+  }
+
+  /** Restarts the services a suspended cell left as `needs_resume`. */
+  async function resumeCellServices(cellId: string): Promise<void> {
+    const rows = await repository.fetchServicesForCell(cellId);
+    const cell = rows[0]?.cell;
+    if (!cell) {
+      return;
     }
+    await resumeServiceRows(cell, rows);
   }
 
-  async function shouldSkipRestart(row: ServiceRow): Promise<boolean> {
+  /**
+   * Why a service is left alone on restart: `running` when its process is
+   * still alive, `unavailable` when it was stopped or something else holds
+   * its port. Null when it should be restarted.
+   */
+  async function restartSkipReason(
+    row: ServiceRow
+  ): Promise<"running" | "unavailable" | null> {
     if (!AUTO_RESTART_STATUSES.has(row.service.status)) {
-      return true;
+      return "unavailable";
     }
 
     if (row.service.pid && isProcessAlive(row.service.pid)) {
-      return true;
+      return "running";
     }
 
-    if (!row.service.pid && typeof row.service.port === "number") {
+    if (
+      !(row.service.pid || isDetachedService(row)) &&
+      typeof row.service.port === "number"
+    ) {
       const portFree = await isPortFree(row.service.port);
       if (!portFree) {
         logger.warn("Skipping service restart because port is already in use", {
@@ -482,11 +614,11 @@ export function createServiceSupervisor(
           cellId: row.cell.id,
           port: row.service.port,
         });
-        return true;
+        return "unavailable";
       }
     }
 
-    return false;
+    return null;
   }
 
   async function normalizeServiceForRestart(row: ServiceRow): Promise<void> {
@@ -504,27 +636,58 @@ export function createServiceSupervisor(
 
   async function restartServicesForCell(args: {
     rows: ServiceRow[];
+    template: Template | undefined;
     portMap: Map<string, number>;
     templateEnv: Record<string, string>;
   }) {
-    const { rows, portMap, templateEnv } = args;
+    const { rows, template, portMap, templateEnv } = args;
+    const services = template?.services;
+    // Template keys of services that are not coming back, with the reason;
+    // anything depending on them is left in error instead of starting
+    // against a dead dependency.
+    const failed = new Map<string, string>();
 
     for (const row of rows) {
-      if (await shouldSkipRestart(row)) {
+      await removeStaleDockerContainer(row);
+
+      const key = services ? resolveTemplateServiceKey(services, row) : null;
+      const skipReason = await restartSkipReason(row);
+      if (skipReason) {
+        if (skipReason === "unavailable" && key) {
+          failed.set(key, "is not running");
+        }
         continue;
       }
 
       await normalizeServiceForRestart(row);
 
-      await startService(row, undefined, templateEnv, portMap).catch(
-        (error) => {
-          logger.error("Failed to restart service", {
-            serviceId: row.service.id,
-            cellId: row.cell.id,
-            error: error instanceof Error ? error.message : String(error),
-          });
-        }
+      const failedDependency = [...collectRowDependencies(row, template)].find(
+        (dependency) => failed.has(dependency)
       );
+      if (failedDependency) {
+        if (key) {
+          failed.set(key, "failed to start");
+        }
+        await markServiceError(
+          row.service.id,
+          row.cell.id,
+          `Dependency ${failedDependency} ${failed.get(failedDependency)}`
+        );
+        continue;
+      }
+
+      try {
+        await startService(row, undefined, templateEnv, portMap);
+      } catch (error) {
+        if (key) {
+          failed.set(key, "failed to start");
+        }
+        logger.error("Failed to restart service", {
+          serviceId: row.service.id,
+          cellId: row.cell.id,
+          error: error instanceof Error ? error.message : String(error),
+        });
+      }
     }
   }
 
@@ -538,12 +701,7 @@ export function createServiceSupervisor(
     onTimingEvent?: (event: EnsureCellServicesTimingEvent) => void;
   }): Promise<void> {
     return runWithCellLock(cell.id, async () => {
-      const resolvedTemplate =
-        template ??
-        (await loadTemplateCached(
-          cell.templateId,
-          cell.workspaceRootPath ?? cell.workspacePath
-        ));
+      const resolvedTemplate = template ?? (await loadCellTemplate(cell));
 
       if (!resolvedTemplate) {
         return;
@@ -561,7 +719,7 @@ export function createServiceSupervisor(
         return;
       }
 
-      const prepared = await prepareProcessServices(cell, resolvedTemplate);
+      const prepared = await prepareSupervisedServices(cell, resolvedTemplate);
       if (!prepared.length) {
         return;
       }
@@ -580,14 +738,21 @@ export function createServiceSupervisor(
     });
   }
 
-  async function prepareProcessServices(
+  async function prepareSupervisedServices(
     cell: Cell,
     template: Template
-  ): Promise<Array<{ row: ServiceRow; definition: ProcessService }>> {
-    const prepared: Array<{ row: ServiceRow; definition: ProcessService }> = [];
-
-    for (const [name, definition] of Object.entries(template.services ?? {})) {
-      if (definition.type !== "process") {
+  ): Promise<PreparedService[]> {
+    const prepared: PreparedService[] = [];
+    const services = template.services ?? {};
+    const serviceNames = new Set(Object.keys(services));
+    const startOrder = resolveServiceStartOrder(services, template.id);
+
+    for (const name of startOrder) {
+      const definition = services[name];
+      if (!definition) {
+        continue;
+      }
+      if (!isSupervisedService(definition)) {
         logger.warn("Unsupported service type. Skipping.", {
           cellId: cell.id,
           service: name,
@@ -596,16 +761,65 @@ export function createServiceSupervisor(
         continue;
       }
 
-      const row = await ensureService(cell, name, definition);
-      prepared.push({ row, definition });
+      if (definition.type !== "compose") {
+        const row = await ensureService(cell, name, definition);
+        prepared.push({ row, definition });
+        continue;
+      }
+
+      // Each compose service is tracked as its own row so status, ports and
+      // logs are visible per container.
+      const composeNames = await resolveComposeServiceNames(cell, definition);
+      for (const composeName of composeNames) {
+        if (serviceNames.has(composeName) && composeName !== name) {
+          logger.warn("Compose service name collides with template service", {
+            cellId: cell.id,
+            service: composeName,
+            file: definition.file,
+          });
+          continue;
+        }
+        serviceNames.add(composeName);
+
+        const composeDefinition: ComposeService = {
+          ...definition,
+          services: [composeName],
+        };
+        const row = await ensureService(cell, composeName, composeDefinition);
+        prepared.push({ row, definition: composeDefinition });
+      }
     }
 
     return prepared;
   }
 
+  async function resolveComposeServiceNames(
+    cell: Cell,
+    definition: ComposeService
+  ): Promise<string[]> {
+    if (definition.services?.length) {
+      return definition.services;
+    }
+
+    const file = resolveComposeFile(cell.workspacePath, definition);
+    let output = "";
+    await runCommand(buildComposeListServicesCommand(file), {
+      cwd: dirname(file),
+      env: {
+        ...buildBaseEnv({ serviceName: "compose", cell }),
+        ...(definition.env ?? {}),
+      },
+      onData: (chunk) => {
+        output += chunk;
+      },
+    });
+
+    return parseComposeServiceList(output);
+  }
+
   async function startOrFail(args: {
     row: ServiceRow;
-    definition: ProcessService;
+    definition: SupervisedService;
     templateEnv: Record<string, string>;
     portMap: Map<string, number>;
     onTimingEvent?: (event: EnsureCellServicesTimingEvent) => void;
@@ -613,7 +827,7 @@ export function createServiceSupervisor(
     const { row, definition, templateEnv, portMap, onTimingEvent } = args;
     const startedAt = Date.now();
     try {
-      await startService(row, definition, templateEnv, portMap);
+      await startService(row, definition, templateEnv, portMap, onTimingEvent);
       const durationMs = Date.now() - startedAt;
       logger.info("Service startup completed", {
         serviceId: row.service.id,
@@ -666,13 +880,16 @@ export function createServiceSupervisor(
       throw new Error("Cell workspace path missing");
     }
 
-    const env = {
-      ...buildBaseEnv({ serviceName: template.id, cell }),
-      ...templateEnv,
-      HIVE_WORKTREE_SETUP: "true",
-      HIVE_MAIN_REPO: cell.workspaceRootPath ?? cell.workspacePath,
-      FORCE_COLOR: "1",
-    };
+    const { env, createOutputRedactor } = await resolveSecretEnv(
+      cell.workspaceId,
+      {
+        ...buildBaseEnv({ serviceName: template.id, cell }),
+        ...templateEnv,
+        HIVE_WORKTREE_SETUP: "true",
+        HIVE_MAIN_REPO: cell.workspaceRootPath ?? cell.workspacePath,
+        FORCE_COLOR: "1",
+      }
+    );
 
     terminalRuntime.startSetupSession({
       cellId: cell.id,
@@ -694,11 +911,14 @@ export function createServiceSupervisor(
       for (const command of template.setup) {
         const commandStartedAt = Date.now();
         terminalRuntime.appendSetupLine(cell.id, `[setup] Running: ${command}`);
+        const output = createOutputRedactor((text) =>
+          terminalRuntime.appendSetupOutput(cell.id, text)
+        );
         const proc = spawnProcess({
           command,
           cwd: cell.workspacePath,
           env,
-          onData: (chunk) => terminalRuntime.appendSetupOutput(cell.id, chunk),
+          onData: output.write,
         });
 
         terminalRuntime.attachSetupProcess({
@@ -729,6 +949,7 @@ export function createServiceSupervisor(
         if (timeoutHandle) {
           clearTimeout(timeoutHandle);
         }
+        output.end();
 
         if (exitResult.type === "timeout") {
           const durationMs = Date.now() - commandStartedAt;
@@ -888,10 +1109,11 @@ export function createServiceSupervisor(
   async function ensureService(
     cell: Cell,
     name: string,
-    definition: ProcessService
+    definition: SupervisedService
   ): Promise<ServiceRow> {
     let record = await repository.findByCellAndName(cell.id, name);
-    const resolvedCwd = resolveServiceCwd(cell.workspacePath, definition.cwd);
+    const resolvedCwd = resolveDefinitionCwd(cell.workspacePath, definition);
+    const command = describeServiceCommand(definition);
 
     if (record) {
       const shouldUpdate = needsDefinitionUpdate(
@@ -902,7 +1124,7 @@ export function createServiceSupervisor(
       if (shouldUpdate) {
         record =
           (await repository.updateService(record.id, {
-            command: definition.run,
+            command,
             cwd: resolvedCwd,
             readyTimeoutMs: definition.readyTimeoutMs ?? null,
             definition,
@@ -913,7 +1135,7 @@ export function createServiceSupervisor(
         id: randomUUID(),
         name,
         type: definition.type,
-        command: definition.run,
+        command,
         cwd: resolvedCwd,
         env: buildBaseEnv({ serviceName: name, cell }),
         port: null,
@@ -943,36 +1165,47 @@ export function createServiceSupervisor(
     if (!cell) {
       return;
     }
-    const template = await loadTemplateCached(
-      cell.templateId,
-      cell.workspaceRootPath ?? cell.workspacePath
-    );
+    const template = await loadTemplateForStart(cell, rows);
     const templateEnv = template?.env ?? {};
     const portMap = await buildPortMap(rows);
 
-    for (const row of rows) {
+    // Dependencies come first and startService waits for readiness, so each
+    // dependent only starts once everything it depends on is ready.
+    for (const row of orderRowsForStart(rows, template)) {
       await startService(row, undefined, templateEnv, portMap);
     }
   }
 
   async function stopCellServices(
     cellId: string,
-    options?: { releasePorts?: boolean }
+    options?: StopCellServicesOptions
   ): Promise<void> {
     const rows = await repository.fetchServicesForCell(cellId);
+    const cell = rows[0]?.cell;
+    if (!cell) {
+      return;
+    }
 
-    for (const row of rows) {
-      await stopService(row, options?.releasePorts ?? false);
+    const template = await loadTemplateForOrdering(cell);
+    for (const row of orderRowsForStop(rows, template)) {
+      const statusAfterStop =
+        options?.suspend && AUTO_RESTART_STATUSES.has(row.service.status)
+          ? "needs_resume"
+          : "stopped";
+      await stopService(row, options?.releasePorts ?? false, statusAfterStop);
     }
   }
 
   async function stopAll(): Promise<void> {
-    const rows = await repository.fetchAllServices();
+    const grouped = groupServicesByCell(await repository.fetchAllServices());
 
-    for (const row of rows) {
-      const statusAfterStop =
-        row.service.status === "stopped" ? "stopped" : "needs_resume";
-      await stopService(row, true, statusAfterStop);
+    for (const { cell, rows } of grouped.values()) {
+      const template = await loadTemplateForOrdering(cell);
+      for (const row of orderRowsForStop(rows, template)) {
+        const statusAfterStop =
+          row.service.status === "stopped" ? "stopped" : "needs_resume";
+        await stopService(row, true, statusAfterStop);
+      }
     }
 
     terminalRuntime.stopAll();
@@ -983,7 +1216,7 @@ export function createServiceSupervisor(
       return true;
     }
 
-    if (typeof row.service.port === "number") {
+    if (typeof row.service.port === "number" && !isDetachedService(row)) {
       const portFree = await isPortFree(row.service.port);
       if (!portFree) {
         const status = row.service.status;
@@ -1006,19 +1239,18 @@ export function createServiceSupervisor(
 
   async function startService(
     row: ServiceRow,
-    definitionOverride?: ProcessService,
+    definitionOverride?: SupervisedService,
     templateEnv: Record<string, string> = {},
-    portLookup?: Map<string, number>
+    portLookup?: Map<string, number>,
+    onTimingEvent?: (event: EnsureCellServicesTimingEvent) => void
   ): Promise<void> {
     await runWithServiceLock(row.service.id, async () => {
       const latestRow = await repository.fetchServiceRowById(row.service.id);
       const serviceRow = latestRow ?? row;
-      const definition =
-        definitionOverride ??
-        (serviceRow.service.definition as ProcessService | null);
+      const definition = definitionOverride ?? serviceRow.service.definition;
 
-      if (!definition || definition.type !== "process") {
-        logger.warn("Cannot start non-process service", {
+      if (!isSupervisedService(definition)) {
+        logger.warn("Cannot start unsupported service type", {
           serviceId: serviceRow.service.id,
           cellId: serviceRow.cell.id,
         });
@@ -1030,9 +1262,9 @@ export function createServiceSupervisor(
       }
 
       const port = await prepareServicePort(serviceRow, portLookup);
-      const cwd = resolveServiceCwd(
+      const cwd = resolveDefinitionCwd(
         serviceRow.cell.workspacePath,
-        definition.cwd
+        definition
       );
 
       if (!(await ensureServiceDirectory(serviceRow, cwd))) {
@@ -1047,7 +1279,10 @@ export function createServiceSupervisor(
         cell: serviceRow.cell,
         portMap: portLookup,
       });
+      const resolved = await resolveServiceSecrets(serviceRow, env);
 
+      // The stored env keeps `${secret:NAME}` references; only the spawned
+      // process sees the values.
       await repository.updateService(serviceRow.service.id, {
         status: "starting",
         env,
@@ -1058,16 +1293,41 @@ export function createServiceSupervisor(
 
       notifyServiceUpdate(serviceRow);
 
+      const command = buildServiceCommand({
+        row: serviceRow,
+        definition,
+        port,
+        portLookup,
+      });
+
       await runServiceProcess({
         row: serviceRow,
         definition,
-        env,
+        env: resolved.env,
         cwd,
-        command: definition.run,
+        command,
+        createOutputRedactor: resolved.createOutputRedactor,
+        onTimingEvent,
       });
     });
   }
 
+  async function resolveServiceSecrets(
+    row: ServiceRow,
+    env: Record<string, string>
+  ): Promise<ResolvedSecretEnv> {
+    try {
+      return await resolveSecretEnv(row.cell.workspaceId, env);
+    } catch (error) {
+      await markServiceError(
+        row.service.id,
+        row.cell.id,
+        error instanceof Error ? error.message : String(error)
+      );
+      throw error;
+    }
+  }
+
   async function prepareServicePort(
     row: ServiceRow,
     portLookup?: Map<string, number>
@@ -1104,7 +1364,10 @@ export function createServiceSupervisor(
     env,
     cwd,
     command,
+    createOutputRedactor,
+    onTimingEvent,
   }: ServiceProcessOptions) {
+    let recentOutput = "";
     try {
       terminalRuntime.startServiceSession({
         serviceId: row.service.id,
@@ -1118,15 +1381,19 @@ export function createServiceSupervisor(
         `[service:${row.service.name}] Starting ${command}\n`
       );
 
-      await runServiceSetup(row, definition, cwd, env);
+      await runServiceSetup(row, definition, cwd, env, createOutputRedactor);
 
+      const output = createOutputRedactor((visible) => {
+        terminalRuntime.appendServiceOutput(row.service.id, visible);
+        recentOutput = (recentOutput + visible).slice(-READINESS_OUTPUT_LIMIT);
+      });
       const handle = spawnProcess({
         command,
         cwd,
         env,
-        onData: (chunk) =>
-          terminalRuntime.appendServiceOutput(row.service.id, chunk),
+        onData: output.write,
         onExit: ({ exitCode, signal }) => {
+          output.end();
           terminalRuntime.markServiceExit({
             serviceId: row.service.id,
             exitCode,
@@ -1149,12 +1416,29 @@ export function createServiceSupervisor(
       activeServices.set(row.service.id, { handle });
 
       await repository.updateService(row.service.id, {
-        status: "running",
+        status: definition.readiness ? "starting" : "running",
         pid: handle.pid,
       });
 
       notifyServiceUpdate(row);
 
+      if (definition.readiness) {
+        await awaitServiceReadiness({
+          row,
+          definition,
+          handle,
+          cwd,
+          env,
+          readOutput: () => recentOutput,
+          onTimingEvent,
+        });
+
+        await repository.updateService(row.service.id, { status: "running" });
+        notifyServiceUpdate(row);
+      }
+
+      serviceReadyAt.set(row.service.id, now().getTime());
+
       handle.exited
         .then(async (code) => {
           const active = activeServices.get(row.service.id);
@@ -1163,14 +1447,7 @@ export function createServiceSupervisor(
           }
 
           activeServices.delete(row.service.id);
-          await repository.updateService(row.service.id, {
-            status: code === 0 ? "stopped" : "error",
-            pid: null,
-            lastKnownError:
-              code === 0 ? null : `Exited with code ${code ?? -1}`,
-          });
-
-          notifyServiceUpdate(row);
+          await handleServiceExit(row, definition, code ?? -1);
         })
         .catch((error) => {
           const active = activeServices.get(row.service.id);
@@ -1200,12 +1477,131 @@ export function createServiceSupervisor(
     }
   }
 
-  async function runServiceSetup(
+  async function awaitServiceReadiness({
+    row,
+    definition,
+    handle,
+    cwd,
+    env,
+    readOutput,
+    onTimingEvent,
+  }: {
+    row: ServiceRow;
+    definition: SupervisedService;
+    handle: ProcessHandle;
+    cwd: string;
+    env: Record<string, string>;
+    readOutput: () => string;
+    onTimingEvent?: (event: EnsureCellServicesTimingEvent) => void;
+  }): Promise<void> {
+    const probe = definition.readiness;
+    if (!probe) {
+      return;
+    }
+
+    const timeoutMs = resolveReadinessTimeoutMs(
+      probe,
+      definition.readyTimeoutMs
+    );
+    const metadata = {
+      serviceId: row.service.id,
+      serviceName: row.service.name,
+      probe: probe.type,
+      timeoutMs,
+    };
+    const startedAt = Date.now();
+    terminalRuntime.appendServiceOutput(
+      row.service.id,
+      `[service:${row.service.name}] Waiting for ${describeReadinessProbe(probe)}\n`
+    );
+
+    try {
+      await waitForReadiness(
+        probe,
+        {
+          port: row.service.port ?? null,
+          readOutput,
+          runCommand: (command) => runCommand(command, { cwd, env }),
+          exited: handle.exited,
+        },
+        timeoutMs
+      );
+    } catch (error) {
+      const message = error instanceof Error ? error.message : String(error);
+      terminalRuntime.appendServiceOutput(
+        row.service.id,
+        `[service:${row.service.name}] ${message}\n`
+      );
+      onTimingEvent?.({
+        step: `service_ready:${row.service.name}`,
+        status: "error",
+        durationMs: Date.now() - startedAt,
+        error: message,
+        metadata,
+      });
+      await abortServiceStart(row, definition, handle, cwd, env);
+      throw error;
+    }
+
+    const durationMs = Date.now() - startedAt;
+    logger.info("Service readiness check passed", {
+      ...metadata,
+      cellId: row.cell.id,
+      durationMs,
+    });
+    onTimingEvent?.({
+      step: `service_ready:${row.service.name}`,
+      status: "ok",
+      durationMs,
+      metadata,
+    });
+  }
+
+  async function abortServiceStart(
     row: ServiceRow,
-    definition: ProcessService,
+    definition: SupervisedService,
+    handle: ProcessHandle,
     cwd: string,
     env: Record<string, string>
+  ): Promise<void> {
+    activeServices.delete(row.service.id);
+
+    const stopCommand = resolveStopCommand(row, definition);
+    if (stopCommand) {
+      await runCommand(stopCommand, { cwd, env }).catch(() => {
+        /* best-effort cleanup after a failed start */
+      });
+    }
+
+    await terminateHandle(handle);
+  }
+
+  async function runServiceSetup(
+    row: ServiceRow,
+    definition: SupervisedService,
+    cwd: string,
+    env: Record<string, string>,
+    createOutputRedactor: ResolvedSecretEnv["createOutputRedactor"]
   ) {
+    if (definition.type === "docker") {
+      await removeDockerContainer(row, cwd, env);
+      return;
+    }
+
+    if (definition.type === "compose") {
+      const upCommand = buildComposeUpCommand(
+        buildComposeProjectName(row.cell.id),
+        resolveComposeFile(row.cell.workspacePath, definition),
+        resolveComposeServiceName(row, definition)
+      );
+      terminalRuntime.appendServiceOutput(
+        row.service.id,
+        `[service:${row.service.name}] ${upCommand}\n`
+      );
+      await runRedactedCommand(row, upCommand, cwd, env, createOutputRedactor);
+      return;
+    }
+
     if (!definition.setup?.length) {
       return;
     }
@@ -1216,12 +1612,13 @@ export function createServiceSupervisor(
         row.service.id,
         `[service:${row.service.name}] setup: ${setupCommand}\n`
       );
-      await runCommand(setupCommand, {
+      await runRedactedCommand(
+        row,
+        setupCommand,
         cwd,
         env,
-        onData: (chunk) =>
-          terminalRuntime.appendServiceOutput(row.service.id, chunk),
-      });
+        createOutputRedactor
+      );
       logger.info("Service setup command completed", {
         serviceId: row.service.id,
         serviceName: row.service.name,
@@ -1232,18 +1629,44 @@ export function createServiceSupervisor(
     }
   }
 
+  async function runRedactedCommand(
+    row: ServiceRow,
+    command: string,
+    cwd: string,
+    env: Record<string, string>,
+    createOutputRedactor: ResolvedSecretEnv["createOutputRedactor"]
+  ) {
+    const output = createOutputRedactor((text) =>
+      terminalRuntime.appendServiceOutput(row.service.id, text)
+    );
+    try {
+      await runCommand(command, { cwd, env, onData: output.write });
+    } finally {
+      output.end();
+    }
+  }
+
   async function stopService(
     row: ServiceRow,
     releasePort: boolean,
     statusAfterStop: ServiceStatus = "stopped"
   ): Promise<void> {
-    const definition = row.service.definition as ProcessService | null;
+    const definition = isSupervisedService(row.service.definition)
+      ? row.service.definition
+      : null;
     const env = row.service.env;
-    const cwd = resolveServiceCwd(row.cell.workspacePath, definition?.cwd);
+    const cwd = definition
+      ? resolveDefinitionCwd(row.cell.workspacePath, definition)
+      : row.cell.workspacePath;
     const active = activeServices.get(row.service.id);
-
-    if (definition?.type === "process" && definition.stop) {
-      await runCommand(definition.stop, { cwd, env }).catch((error) => {
+    // Forget the handle before running stop commands so the exit watcher
+    // doesn't record the intentional shutdown as a crash.
+    activeServices.delete(row.service.id);
+    cancelPendingRestart(row.service.id);
+
+    const stopCommand = resolveStopCommand(row, definition);
+    if (stopCommand) {
+      await runCommand(stopCommand, { cwd, env }).catch((error) => {
         logger.warn("Service stop command failed", {
           serviceId: row.service.id,
           error: error instanceof Error ? error.message : String(error),
@@ -1253,7 +1676,6 @@ export function createServiceSupervisor(
 
     if (active) {
       await terminateHandle(active.handle);
-      activeServices.delete(row.service.id);
     } else if (row.service.pid) {
       await terminatePid(row.service.pid);
     }
@@ -1277,6 +1699,74 @@ export function createServiceSupervisor(
     }
   }
 
+  function teardownCellServices(cellId: string): Promise<void> {
+    return runWithCellLock(cellId, async () => {
+      const rows = await repository.fetchServicesForCell(cellId);
+      const composeProjects = new Map<string, ServiceRow>();
+      const cell = rows[0]?.cell;
+      const template = cell ? await loadTemplateForOrdering(cell) : undefined;
+
+      for (const row of orderRowsForStop(rows, template)) {
+        await stopService(row, true);
+
+        const definition = row.service.definition;
+        if (definition.type === "compose") {
+          composeProjects.set(
+            resolveComposeFile(row.cell.workspacePath, definition),
+            row
+          );
+        }
+      }
+
+      for (const [file, row] of composeProjects) {
+        const downCommand = buildComposeDownCommand(
+          buildComposeProjectName(cellId),
+          file
+        );
+        await runCommand(downCommand, {
+          cwd: dirname(file),
+          env: row.service.env,
+        }).catch((error) => {
+          logger.warn("Failed to tear down compose project", {
+            cellId,
+            file,
+            error: error instanceof Error ? error.message : String(error),
+          });
+        });
+      }
+    });
+  }
+
+  async function removeDockerContainer(
+    row: ServiceRow,
+    cwd: string,
+    env: Record<string, string>
+  ): Promise<void> {
+    const containerName = buildDockerContainerName(
+      row.cell.id,
+      row.service.name
+    );
+    // A container left behind by a previous run would hold the name and port.
+    await runCommand(buildDockerRemoveCommand(containerName), {
+      cwd,
+      env,
+    }).catch(() => {
+      /* no stale container to remove */
+    });
+  }
+
+  async function removeStaleDockerContainer(row: ServiceRow): Promise<void> {
+    if (row.service.definition?.type !== "docker") {
+      return;
+    }
+
+    if (row.service.pid && isProcessAlive(row.service.pid)) {
+      return;
+    }
+
+    await removeDockerContainer(row, row.cell.workspacePath, row.service.env);
+  }
+
   async function buildPortMap(
     rows: ServiceRow[]
   ): Promise<Map<string, number>> {
@@ -1308,6 +1798,21 @@ export function createServiceSupervisor(
     });
   }
 
+  function notifyServiceCrash(
+    row: ServiceRow,
+    exitCode: number,
+    willRestart: boolean
+  ): void {
+    emitServiceCrash({
+      workspaceId: row.cell.workspaceId,
+      cellId: row.cell.id,
+      serviceId: row.service.id,
+      serviceName: row.service.name,
+      exitCode,
+      willRestart,
+    });
+  }
+
   async function loadTemplateCached(
     templateId: string,
     workspaceRootPath?: string
@@ -1325,24 +1830,342 @@ export function createServiceSupervisor(
     return workspaceTemplates.get(templateId);
   }
 
-  async function startCellServiceById(serviceId: string): Promise<void> {
-    const row = await repository.fetchServiceRowById(serviceId);
-    if (!row) {
-      throw new Error(`Service ${serviceId} not found`);
-    }
-
+  /** The cell's template with the parameter values it was created with. */
+  async function loadCellTemplate(cell: Cell): Promise<Template | undefined> {
     const template = await loadTemplateCached(
-      row.cell.templateId,
-      row.cell.workspaceRootPath ?? row.cell.workspacePath
+      cell.templateId,
+      cell.workspaceRootPath ?? cell.workspacePath
     );
+    if (!template?.parameters?.length) {
+      return template;
+    }
+    const stored = await repository.fetchTemplateParameters(cell.id);
+    return applyTemplateParameters(
+      template,
+      resolveCellParameterValues(cell, template, stored)
+    );
+  }
+
+  /**
+   * The cell's stored values, with defaults for parameters it has no value
+   * for (e.g. the template gained them after the cell was created). Falls
+   * back to defaults alone when the stored values no longer fit, and throws
+   * a `TemplateParameterError` when even those do not.
+   */
+  function resolveCellParameterValues(
+    cell: Cell,
+    template: Template,
+    stored: TemplateParameterValues | null
+  ): TemplateParameterValues {
+    if (stored) {
+      const known = new Set(
+        (template.parameters ?? []).map((parameter) => parameter.name)
+      );
+      try {
+        return resolveTemplateParameterValues(
+          template,
+          Object.fromEntries(
+            Object.entries(stored).filter(([name]) => known.has(name))
+          )
+        );
+      } catch (error) {
+        logger.warn("Stored template parameters no longer apply", {
+          cellId: cell.id,
+          templateId: cell.templateId,
+          error: error instanceof Error ? error.message : String(error),
+        });
+      }
+    }
+    return resolveTemplateParameterValues(template, {});
+  }
+
+  /**
+   * Loads the template services are about to start from. When its
+   * parameters cannot be filled in, `rows` are marked errored instead of
+   * being started with a literal `${params.<name>}`.
+   */
+  async function loadTemplateForStart(
+    cell: Cell,
+    rows: ServiceRow[]
+  ): Promise<Template | undefined> {
+    try {
+      return await loadCellTemplate(cell);
+    } catch (error) {
+      if (error instanceof TemplateParameterError) {
+        for (const row of rows) {
+          await markServiceError(row.service.id, cell.id, error.message);
+        }
+      }
+      throw error;
+    }
+  }
+
+  async function loadTemplateForOrdering(
+    cell: Cell
+  ): Promise<Template | undefined> {
+    try {
+      return await loadTemplateCached(
+        cell.templateId,
+        cell.workspaceRootPath ?? cell.workspacePath
+      );
+    } catch (error) {
+      // Stopping must still work when the config no longer loads.
+      logger.warn("Failed to load template for service ordering", {
+        cellId: cell.id,
+        templateId: cell.templateId,
+        error: error instanceof Error ? error.message : String(error),
+      });
+      return;
+    }
+  }
+
+  function orderRowsForStart(
+    rows: ServiceRow[],
+    template: Template | undefined
+  ): ServiceRow[] {
+    const services = template?.services;
+    if (!services) {
+      return rows;
+    }
+
+    let startOrder: string[];
+    try {
+      startOrder = resolveServiceStartOrder(services, template.id);
+    } catch (error) {
+      logger.warn("Ignoring invalid service dependencies", {
+        templateId: template.id,
+        error: error instanceof Error ? error.message : String(error),
+      });
+      return rows;
+    }
+
+    const rank = new Map(startOrder.map((name, index) => [name, index]));
+    return rows
+      .map((row, index) => ({
+        row,
+        index,
+        rank:
+          rank.get(resolveTemplateServiceKey(services, row)) ??
+          Number.MAX_SAFE_INTEGER,
+      }))
+      .sort((left, right) => left.rank - right.rank || left.index - right.index)
+      .map((entry) => entry.row);
+  }
+
+  function orderRowsForStop(
+    rows: ServiceRow[],
+    template: Template | undefined
+  ): ServiceRow[] {
+    return orderRowsForStart(rows, template).reverse();
+  }
+
+  /**
+   * Applies the service's restart policy after an exit the supervisor did not
+   * initiate. Restarts are delayed with exponential backoff and recorded as
+   * `service.restart` activity; once retries run out the service is left in
+   * `error`. A restart that fails to spawn or become ready counts as another
+   * failed exit, described by `failure`.
+   */
+  async function handleServiceExit(
+    row: ServiceRow,
+    definition: SupervisedService,
+    exitCode: number,
+    failure?: string
+  ): Promise<void> {
+    const serviceId = row.service.id;
+    const exitError =
+      failure ?? (exitCode === 0 ? null : `Exited with code ${exitCode}`);
+    const policy = resolveRestartPolicy(definition.restart);
+
+    const readyAt = serviceReadyAt.get(serviceId);
+    serviceReadyAt.delete(serviceId);
+    if (
+      readyAt !== undefined &&
+      now().getTime() - readyAt >= RESTART_STABLE_WINDOW_MS
+    ) {
+      restartAttempts.delete(serviceId);
+    }
+
+    if (!shouldRestartAfterExit(policy, exitCode)) {
+      restartAttempts.delete(serviceId);
+      await repository.updateService(serviceId, {
+        status: exitCode === 0 ? "stopped" : "error",
+        pid: null,
+        lastKnownError: exitError,
+      });
+      notifyServiceUpdate(row);
+      if (exitCode !== 0) {
+        notifyServiceCrash(row, exitCode, false);
+      }
+      return;
+    }
+
+    const attempt = (restartAttempts.get(serviceId) ?? 0) + 1;
+    if (attempt > policy.maxRetries) {
+      restartAttempts.delete(serviceId);
+      logger.error("Service restart retries exhausted", {
+        serviceId,
+        cellId: row.cell.id,
+        exitCode,
+        maxRetries: policy.maxRetries,
+      });
+      await repository.updateService(serviceId, {
+        status: "error",
+        pid: null,
+        lastKnownError: `${exitError ?? `Exited with code ${exitCode}`}; gave up after ${policy.maxRetries} restart attempts`,
+      });
+      notifyServiceUpdate(row);
+      notifyServiceCrash(row, exitCode, false);
+      return;
+    }
+
+    restartAttempts.set(serviceId, attempt);
+    const delayMs = computeRestartDelayMs(policy, attempt);
+
+    await repository.updateService(serviceId, {
+      status: "starting",
+      pid: null,
+      lastKnownError: exitError,
+    });
+    notifyServiceUpdate(row);
+    if (exitCode !== 0) {
+      notifyServiceCrash(row, exitCode, true);
+    }
+
+    await repository.insertActivityEvent({
+      cellId: row.cell.id,
+      serviceId,
+      type: "service.restart",
+      source: "supervisor",
+      metadata: {
+        serviceName: row.service.name,
+        exitCode,
+        attempt,
+        maxRetries: policy.maxRetries,
+        policy: policy.policy,
+        delayMs,
+      },
+    });
+
+    logger.warn("Restarting exited service", {
+      serviceId,
+      cellId: row.cell.id,
+      exitCode,
+      attempt,
+      delayMs,
+    });
+
+    const timer = setTimeout(() => {
+      pendingRestarts.delete(serviceId);
+      startServiceById(serviceId).catch(async (error) => {
+        const message = error instanceof Error ? error.message : String(error);
+        logger.error("Failed to restart service", {
+          serviceId,
+          cellId: row.cell.id,
+          error: message,
+        });
+        // A stop issued while the restart was starting up wins.
+        if (!restartAttempts.has(serviceId)) {
+          return;
+        }
+        await handleServiceExit(row, definition, 1, message).catch(
+          (retryError) => {
+            logger.error("Failed to schedule service restart", {
+              serviceId,
+              cellId: row.cell.id,
+              error:
+                retryError instanceof Error
+                  ? retryError.message
+                  : String(retryError),
+            });
+          }
+        );
+      });
+    }, delayMs);
+    pendingRestarts.set(serviceId, timer);
+  }
+
+  function cancelPendingRestart(serviceId: string) {
+    const timer = pendingRestarts.get(serviceId);
+    if (timer) {
+      clearTimeout(timer);
+      pendingRestarts.delete(serviceId);
+    }
+    restartAttempts.delete(serviceId);
+    serviceReadyAt.delete(serviceId);
+  }
+
+  function startCellServiceById(serviceId: string): Promise<void> {
+    // A manual start gets a fresh set of restart attempts.
+    cancelPendingRestart(serviceId);
+    return startServiceById(serviceId);
+  }
+
+  async function startServiceById(serviceId: string): Promise<void> {
+    const row = await repository.fetchServiceRowById(serviceId);
+    if (!row) {
+      throw new Error(`Service ${serviceId} not found`);
+    }
+
+    const template = await loadTemplateForStart(row.cell, [row]);
     const templateEnv = template?.env ?? {};
 
     const siblings = await repository.fetchServicesForCell(row.cell.id);
     const portMap = await buildPortMap(siblings);
 
+    for (const dependency of resolveDependencyRows(row, siblings, template)) {
+      await startService(dependency, undefined, templateEnv, portMap);
+    }
     await startService(row, undefined, templateEnv, portMap);
   }
 
+  /** Template keys of every service `row` transitively depends on. */
+  function collectRowDependencies(
+    row: ServiceRow,
+    template: Template | undefined
+  ): Set<string> {
+    const services = template?.services;
+    if (!services) {
+      return new Set();
+    }
+
+    try {
+      return new Set(
+        collectServiceDependencies(
+          services,
+          resolveTemplateServiceKey(services, row)
+        )
+      );
+    } catch (error) {
+      logger.warn("Ignoring invalid service dependencies", {
+        templateId: template.id,
+        error: error instanceof Error ? error.message : String(error),
+      });
+      return new Set();
+    }
+  }
+
+  function resolveDependencyRows(
+    row: ServiceRow,
+    siblings: ServiceRow[],
+    template: Template | undefined
+  ): ServiceRow[] {
+    const services = template?.services;
+    const required = collectRowDependencies(row, template);
+    if (!(services && required.size)) {
+      return [];
+    }
+
+    return orderRowsForStart(
+      siblings.filter(
+        (sibling) =>
+          sibling.service.id !== row.service.id &&
+          required.has(resolveTemplateServiceKey(services, sibling))
+      ),
+      template
+    );
+  }
+
   async function stopCellServiceById(
     serviceId: string,
     options?: { releasePorts?: boolean }
@@ -1355,6 +2178,179 @@ export function createServiceSupervisor(
     await stopService(row, options?.releasePorts ?? false);
   }
 
+  function reloadTemplates(workspaceRootPath?: string): void {
+    if (workspaceRootPath) {
+      templateCache.delete(workspaceRootPath);
+      return;
+    }
+    templateCache.clear();
+  }
+
+  /**
+   * Compares each service row, which records the definition and env it was
+   * last started with, against the cell's template in the current config.
+   */
+  function describeCellConfigImpact(
+    cell: Cell,
+    rows: ServiceRow[],
+    template: Template | undefined
+  ): CellConfigImpact {
+    const impact: CellConfigImpact = {
+      cellId: cell.id,
+      cellName: cell.name,
+      workspaceId: cell.workspaceId,
+      templateId: cell.templateId,
+      services: [],
+    };
+    // A template missing from the config says nothing about its services.
+    if (!template) {
+      return impact;
+    }
+
+    const services = template.services ?? {};
+    const portMap = new Map<string, number>();
+    for (const row of rows) {
+      if (typeof row.service.port === "number") {
+        portMap.set(row.service.name, row.service.port);
+      }
+    }
+
+    const matchedKeys = new Set<string>();
+    for (const row of rows) {
+      const key = resolveTemplateServiceKey(services, row);
+      const definition = services[key];
+      if (!isSupervisedService(definition)) {
+        impact.services.push({
+          serviceId: row.service.id,
+          name: row.service.name,
+          change: "removed",
+          fields: [],
+        });
+        continue;
+      }
+
+      matchedKeys.add(key);
+      const fields = diffServiceConfig({
+        row,
+        definition:
+          definition.type === "compose"
+            ? { ...definition, services: [row.service.name] }
+            : definition,
+        templateEnv: template.env ?? {},
+        portMap,
+      });
+      if (fields.length > 0) {
+        impact.services.push({
+          serviceId: row.service.id,
+          name: row.service.name,
+          change: "modified",
+          fields,
+        });
+      }
+    }
+
+    for (const [name, definition] of Object.entries(services)) {
+      if (!matchedKeys.has(name) && isSupervisedService(definition)) {
+        impact.services.push({
+          serviceId: null,
+          name,
+          change: "added",
+          fields: [],
+        });
+      }
+    }
+
+    return impact;
+  }
+
+  async function previewCellConfig(
+    cellId: string
+  ): Promise<CellConfigImpact | null> {
+    const cell = await repository.fetchCell(cellId);
+    if (!cell) {
+      return null;
+    }
+    const rows = await repository.fetchServicesForCell(cellId);
+    return describeCellConfigImpact(cell, rows, await loadCellTemplate(cell));
+  }
+
+  /**
+   * Brings a cell's services in line with the current config. Changed
+   * services that are up are restarted, removed ones are stopped and
+   * dropped, and added ones start if the cell is ready; services the change
+   * does not touch keep running.
+   */
+  async function applyCellConfigChanges(
+    cellId: string
+  ): Promise<CellConfigImpact | null> {
+    const cell = await repository.fetchCell(cellId);
+    if (!cell) {
+      return null;
+    }
+    const rows = await repository.fetchServicesForCell(cellId);
+    const template = await loadCellTemplate(cell);
+    const impact = describeCellConfigImpact(cell, rows, template);
+    if (!template || impact.services.length === 0) {
+      return impact;
+    }
+
+    const changes = new Map<string, ServiceConfigChange>(
+      impact.services.map((change) => [change.name, change])
+    );
+    const restarting = new Set<string>();
+    for (const row of orderRowsForStop(rows, template)) {
+      const change = changes.get(row.service.name);
+      if (!change) {
+        continue;
+      }
+      if (change.change === "removed") {
+        await stopService(row, true);
+        await repository.deleteService(row.service.id);
+        notifyServiceUpdate(row);
+        continue;
+      }
+      if (RESTART_ON_APPLY_STATUSES.has(row.service.status)) {
+        await stopService(row, false);
+        restarting.add(row.service.name);
+      }
+    }
+
+    // Stored definitions are updated for every service, including stopped
+    // ones, so their next start uses the new config.
+    const prepared = await prepareSupervisedServices(cell, template);
+    const portMap = await buildPortMap(prepared.map((entry) => entry.row));
+    const services = template.services ?? {};
+    for (const { row, definition } of prepared) {
+      const added =
+        changes.get(resolveTemplateServiceKey(services, row))?.change ===
+          "added" && cell.status === "ready";
+      if (!(added || restarting.has(row.service.name))) {
+        continue;
+      }
+      await startService(row, definition, template.env ?? {}, portMap).catch(
+        (error) => {
+          logger.error("Failed to start service after config change", {
+            serviceId: row.service.id,
+            cellId,
+            error: error instanceof Error ? error.message : String(error),
+          });
+        }
+      );
+    }
+
+    return impact;
+  }
+
+  async function applyCellConfig(
+    cellId: string
+  ): Promise<CellConfigImpact | null> {
+    let impact: CellConfigImpact | null = null;
+    await runWithCellLock(cellId, async () => {
+      impact = await applyCellConfigChanges(cellId);
+    });
+    return impact;
+  }
+
   return {
     bootstrap,
     ensureCellServices,
@@ -1362,7 +2358,12 @@ export function createServiceSupervisor(
     startCellServices,
     stopCellService: stopCellServiceById,
     stopCellServices,
+    resumeCellServices,
+    teardownCellServices,
     stopAll,
+    reloadTemplates,
+    previewCellConfig,
+    applyCellConfig,
   };
 
   async function markServiceError(
@@ -1440,13 +2441,159 @@ function groupServicesByCell(
   return grouped;
 }
 
+function isSupervisedService(
+  definition: Service | null | undefined
+): definition is SupervisedService {
+  return (
+    definition?.type === "process" ||
+    definition?.type === "docker" ||
+    definition?.type === "compose"
+  );
+}
+
+function describeServiceCommand(definition: SupervisedService): string {
+  switch (definition.type) {
+    case "docker":
+      return describeDockerCommand(definition);
+    case "compose":
+      return describeComposeCommand(definition);
+    default:
+      return definition.run;
+  }
+}
+
+function buildServiceCommand({
+  row,
+  definition,
+  port,
+  portLookup,
+}: {
+  row: ServiceRow;
+  definition: SupervisedService;
+  port: number;
+  portLookup?: Map<string, number>;
+}): string {
+  const serviceName = row.service.name;
+
+  switch (definition.type) {
+    case "docker":
+      return buildDockerRunCommand({
+        containerName: buildDockerContainerName(row.cell.id, serviceName),
+        definition,
+        port,
+        workspacePath: row.cell.workspacePath,
+        envKeys: Object.keys(definition.env ?? {}),
+        interpolate: (value) =>
+          interpolatePortTokens(
+            value,
+            buildPortLookup(serviceName, port, portLookup),
+            serviceName
+          ),
+      });
+    case "compose":
+      // Containers run detached; the service process follows their logs.
+      return buildComposeLogsCommand(
+        buildComposeProjectName(row.cell.id),
+        resolveComposeFile(row.cell.workspacePath, definition),
+        resolveComposeServiceName(row, definition)
+      );
+    default:
+      return definition.run;
+  }
+}
+
+/**
+ * Compose containers run detached and keep their published ports after the
+ * log follower exits, so an occupied port does not mean the service is live.
+ */
+function isDetachedService(row: ServiceRow): boolean {
+  return row.service.definition?.type === "compose";
+}
+
+function resolveComposeFile(
+  workspacePath: string,
+  definition: ComposeService
+): string {
+  return resolveServiceCwd(workspacePath, definition.file);
+}
+
+function resolveComposeServiceName(
+  row: ServiceRow,
+  definition: ComposeService
+): string {
+  return definition.services?.[0] ?? row.service.name;
+}
+
+/**
+ * Maps a service row back to the template entry it came from. Compose stacks
+ * expand into one row per compose service, so those rows are matched by file.
+ */
+function resolveTemplateServiceKey(
+  services: Record<string, Service>,
+  row: ServiceRow
+): string {
+  const name = row.service.name;
+  const definition = row.service.definition;
+  if (definition?.type !== "compose" || services[name]?.type === "compose") {
+    return name;
+  }
+
+  for (const [key, candidate] of Object.entries(services)) {
+    if (
+      candidate.type === "compose" &&
+      candidate.file === definition.file &&
+      (!candidate.services || candidate.services.includes(name))
+    ) {
+      return key;
+    }
+  }
+
+  return name;
+}
+
+function resolveDefinitionCwd(
+  workspacePath: string,
+  definition: SupervisedService
+): string {
+  switch (definition.type) {
+    case "process":
+      return resolveServiceCwd(workspacePath, definition.cwd);
+    case "compose":
+      return dirname(resolveComposeFile(workspacePath, definition));
+    default:
+      return workspacePath;
+  }
+}
+
+function resolveStopCommand(
+  row: ServiceRow,
+  definition: SupervisedService | null
+): string | null {
+  switch (definition?.type) {
+    case "docker":
+      return buildDockerStopCommand(
+        buildDockerContainerName(row.cell.id, row.service.name)
+      );
+    case "compose":
+      return buildComposeStopCommand(
+        buildComposeProjectName(row.cell.id),
+        resolveComposeFile(row.cell.workspacePath, definition),
+        resolveComposeServiceName(row, definition)
+      );
+    case "process":
+      return definition.stop ?? null;
+    default:
+      return null;
+  }
+}
+
 function needsDefinitionUpdate(
   record: CellService,
-  definition: ProcessService,
+  definition: SupervisedService,
   cwd: string
 ): boolean {
   if (
-    record.command !== definition.run ||
+    record.command !== describeServiceCommand(definition) ||
     record.cwd !== cwd ||
     (record.readyTimeoutMs ?? null) !== (definition.readyTimeoutMs ?? null)
   ) {
@@ -1458,6 +2605,63 @@ function needsDefinitionUpdate(
   return existingDefinition !== nextDefinition;
 }
 
+/**
+ * Field-level differences between a service row and its new definition.
+ * Once a service has run, its stored env is compared with the env it would
+ * start with now, which also picks up template-level env changes. Other
+ * services' `<NAME>_PORT` variables are left out: they follow port
+ * assignment, not the config.
+ */
+function diffServiceConfig({
+  row,
+  definition,
+  templateEnv,
+  portMap,
+}: {
+  row: ServiceRow;
+  definition: SupervisedService;
+  templateEnv: Record<string, string>;
+  portMap: Map<string, number>;
+}): ConfigFieldChange[] {
+  const { env: previousEnv, ...previous } = row.service.definition;
+  const { env: nextEnv, ...next } = definition;
+  const fields = diffConfigFields(previous, next);
+
+  const { port, status } = row.service;
+  if (status === "pending" || typeof port !== "number") {
+    return [
+      ...fields,
+      ...diffConfigFields(previousEnv ?? {}, nextEnv ?? {}, "env"),
+    ];
+  }
+
+  const env = buildServiceEnv({
+    serviceName: row.service.name,
+    port,
+    templateEnv,
+    serviceEnv: nextEnv ?? {},
+    cell: row.cell,
+    portMap,
+  });
+  const siblingPorts = new Set(
+    [...portMap.keys()]
+      .filter((name) => name !== row.service.name)
+      .map((name) => `${sanitizeServiceName(name)}_PORT`)
+  );
+  const withoutSiblingPorts = (values: Record<string, string>) =>
+    Object.fromEntries(
+      Object.entries(values).filter(([key]) => !siblingPorts.has(key))
+    );
+  return [
+    ...fields,
+    ...diffConfigFields(
+      withoutSiblingPorts(row.service.env),
+      withoutSiblingPorts(env),
+      "env"
+    ),
+  ];
+}
+
 function resolveServiceCwd(workspacePath: string, cwd?: string): string {
   if (!cwd) {
     return workspacePath;
@@ -1516,8 +2720,7 @@ function buildServiceEnv({
   const upper = sanitizeServiceName(serviceName);
   const portString = String(port);
 
-  const portLookup = new Map(portMap ?? new Map());
-  portLookup.set(serviceName, port);
+  const portLookup = buildPortLookup(serviceName, port, portMap);
 
   const sharedPorts: Record<string, string> = {};
   if (portLookup.size > 0) {
@@ -1542,25 +2745,42 @@ function buildServiceEnv({
   return interpolatedEnv;
 }
 
+function buildPortLookup(
+  serviceName: string,
+  port: number,
+  portMap?: Map<string, number>
+): Map<string, number> {
+  const portLookup = new Map(portMap ?? new Map());
+  portLookup.set(serviceName, port);
+  return portLookup;
+}
+
+function interpolatePortTokens(
+  value: string,
+  portLookup: Map<string, number>,
+  serviceName: string
+): string {
+  const tokenRegex = /\$(?:\{?PORT(?::([A-Za-z0-9_-]+))?\}?)/g;
+
+  return value.replace(tokenRegex, (_match, target?: string) => {
+    const targetName = target ?? serviceName;
+    const portValue = portLookup.get(targetName) ?? null;
+    return portValue != null ? String(portValue) : _match;
+  });
+}
+
 function interpolatePorts(
   env: Record<string, string>,
   portLookup: Map<string, number>,
   serviceName: string
 ): Record<string, string> {
-  const tokenRegex = /\$(?:\{?PORT(?::([A-Za-z0-9_-]+))?\}?)/g;
-
   const result: Record<string, string> = {};
   for (const [key, value] of Object.entries(env)) {
     if (typeof value !== "string") {
       result[key] = value;
       continue;
     }
-    const replaced = value.replace(tokenRegex, (_match, target?: string) => {
-      const targetName = target ?? serviceName;
-      const portValue = portLookup.get(targetName) ?? null;
-      return portValue != null ? String(portValue) : _match;
-    });
-    result[key] = replaced;
+    result[key] = interpolatePortTokens(value, portLookup, serviceName);
   }
 
   return result;
@@ -1579,10 +2799,12 @@ const makeServiceSupervisorError = (
 });
 
 const wrapSupervisorPromise =
-  <Args extends unknown[]>(fn: (...args: Args) => Promise<void>) =>
-  async (...args: Args): Promise<void> => {
+  <Args extends unknown[], Result = void>(
+    fn: (...args: Args) => Promise<Result>
+  ) =>
+  async (...args: Args): Promise<Result> => {
     try {
-      await fn(...args);
+      return await fn(...args);
     } catch (cause) {
       throw makeServiceSupervisorError(cause);
     }
@@ -1603,9 +2825,16 @@ export type ServiceSupervisorService = {
   ) => Promise<void>;
   readonly stopCellServices: (
     cellId: string,
-    options?: { releasePorts?: boolean }
+    options?: StopCellServicesOptions
   ) => Promise<void>;
+  readonly resumeCellServices: (cellId: string) => Promise<void>;
+  readonly teardownCellServices: (cellId: string) => Promise<void>;
   readonly stopAll: () => Promise<void>;
+  readonly reloadTemplates: (workspaceRootPath?: string) => void;
+  readonly previewCellConfig: (
+    cellId: string
+  ) => Promise<CellConfigImpact | null>;
+  readonly applyCellConfig: (cellId: string) => Promise<CellConfigImpact | null>;
   readonly getServiceTerminalSession: (
     serviceId: string
   ) => ServiceTerminalSession | null;
@@ -1653,7 +2882,16 @@ const makeServiceSupervisorService = (
     wrapSupervisorPromise(supervisor.stopCellService)(serviceId, options),
   stopCellServices: (cellId, options) =>
     wrapSupervisorPromise(supervisor.stopCellServices)(cellId, options),
+  resumeCellServices: (cellId) =>
+    wrapSupervisorPromise(supervisor.resumeCellServices)(cellId),
+  teardownCellServices: (cellId) =>
+    wrapSupervisorPromise(supervisor.teardownCellServices)(cellId),
   stopAll: wrapSupervisorPromise(supervisor.stopAll),
+  reloadTemplates: supervisor.reloadTemplates,
+  previewCellConfig: (cellId) =>
+    wrapSupervisorPromise(supervisor.previewCellConfig)(cellId),
+  applyCellConfig: (cellId) =>
+    wrapSupervisorPromise(supervisor.applyCellConfig)(cellId),
   getServiceTerminalSession: terminalRuntime.getServiceSession,
   readServiceTerminalOutput: terminalRuntime.readServiceOutput,
   subscribeToServiceTerminal: terminalRuntime.subscribeToService,
@@ -0,0 +1,387 @@
+import { createHmac, randomBytes, randomUUID } from "node:crypto";
+import { and, desc, eq, inArray } from "drizzle-orm";
+import type { AgentSessionStatus } from "../agents/types";
+import {
+  DatabaseService,
+  type DatabaseService as DatabaseServiceType,
+} from "../db";
+import { cells } from "../schema/cells";
+import {
+  type WebhookDelivery,
+  type WebhookEventType,
+  type WorkspaceWebhook,
+  webhookDeliveries,
+  workspaceWebhooks,
+} from "../schema/webhooks";
+import { type LifecycleEvent, subscribeToLifecycleEvents } from "./events";
+
+type DatabaseClient = DatabaseServiceType["db"];
+
+/** Delays before the second, third, ... attempt of a failed delivery. */
+export const DEFAULT_WEBHOOK_RETRY_DELAYS_MS = [
+  10_000, 60_000, 300_000, 1_800_000,
+];
+export const WEBHOOK_TIMEOUT_MS = 10_000;
+/** Deliveries kept per webhook; older entries are pruned as new ones land. */
+export const WEBHOOK_DELIVERY_LOG_LIMIT = 100;
+export const WEBHOOK_PING_EVENT = "ping";
+
+const NOTIFIED_AGENT_STATUSES = new Set<AgentSessionStatus>([
+  "awaiting_input",
+  "completed",
+  "error",
+]);
+const RESPONSE_SNIPPET_LIMIT = 500;
+
+export const generateWebhookSecret = () =>
+  `whsec_${randomBytes(24).toString("base64url")}`;
+
+/**
+ * Signs `<timestamp>.<body>` so receivers can reject replayed or tampered
+ * payloads. Sent as `X-Hive-Signature: sha256=<hex>`.
+ */
+export function signWebhookPayload(
+  secret: string,
+  timestamp: string,
+  body: string
+): string {
+  const digest = createHmac("sha256", secret)
+    .update(`${timestamp}.${body}`)
+    .digest("hex");
+  return `sha256=${digest}`;
+}
+
+export type WebhookNotification = {
+  type: WebhookEventType;
+  workspaceId: string;
+  cellId: string;
+  data: Record<string, unknown>;
+};
+
+/**
+ * Maps an in-process lifecycle event to the webhook events it triggers. A
+ * failed setup also counts as a cell status change, so it yields both.
+ */
+export function toWebhookNotifications(
+  lifecycle: LifecycleEvent
+): WebhookNotification[] {
+  switch (lifecycle.type) {
+    case "cell.status": {
+      const { workspaceId, cellId, status, lastSetupError } = lifecycle.event;
+      const notifications: WebhookNotification[] = [
+        {
+          type: "cell.status",
+          workspaceId,
+          cellId,
+          data: { status, lastSetupError: lastSetupError ?? null },
+        },
+      ];
+      if (status === "error" && lastSetupError) {
+        notifications.push({
+          type: "cell.setup_failed",
+          workspaceId,
+          cellId,
+          data: { error: lastSetupError },
+        });
+      }
+      return notifications;
+    }
+    case "service.crashed": {
+      const { workspaceId, cellId, ...data } = lifecycle.event;
+      return [{ type: "service.crashed", workspaceId, cellId, data }];
+    }
+    case "agent.status": {
+      const { workspaceId, cellId, ...data } = lifecycle.event;
+      if (!NOTIFIED_AGENT_STATUSES.has(data.status)) {
+        return [];
+      }
+      return [{ type: "agent.status", workspaceId, cellId, data }];
+    }
+    default:
+      return [];
+  }
+}
+
+export type WebhookDispatcherDependencies = {
+  db?: DatabaseClient;
+  fetch?: typeof fetch;
+  retryDelaysMs?: number[];
+  timeoutMs?: number;
+};
+
+const errorMessage = (error: unknown) =>
+  error instanceof Error ? error.message : String(error);
+
+/**
+ * Delivers lifecycle events to the webhooks configured for their workspace.
+ * Every delivery is logged in `webhook_deliveries`; failed attempts are
+ * retried with the configured delays and pending deliveries are picked up
+ * again when the server restarts.
+ */
+export function createWebhookDispatcher({
+  db = DatabaseService.db,
+  fetch: fetchImpl = fetch,
+  retryDelaysMs = DEFAULT_WEBHOOK_RETRY_DELAYS_MS,
+  timeoutMs = WEBHOOK_TIMEOUT_MS,
+}: WebhookDispatcherDependencies = {}) {
+  const retryTimers = new Map<string, ReturnType<typeof setTimeout>>();
+  const maxAttempts = retryDelaysMs.length + 1;
+  let unsubscribe: (() => void) | null = null;
+
+  const pruneDeliveryLog = async (webhookId: string) => {
+    const rows = await db
+      .select({ id: webhookDeliveries.id })
+      .from(webhookDeliveries)
+      .where(eq(webhookDeliveries.webhookId, webhookId))
+      .orderBy(desc(webhookDeliveries.createdAt));
+    const stale = rows.slice(WEBHOOK_DELIVERY_LOG_LIMIT);
+    if (stale.length > 0) {
+      await db
+        .delete(webhookDeliveries)
+        .where(inArray(webhookDeliveries.id, stale.map((row) => row.id)));
+    }
+  };
+
+  const scheduleRetry = (deliveryId: string, delayMs: number) => {
+    const timer = setTimeout(() => {
+      retryTimers.delete(deliveryId);
+      attemptDelivery(deliveryId).catch(() => {
+        // attemptDelivery records its own failures.
+      });
+    }, delayMs);
+    retryTimers.set(deliveryId, timer);
+  };
+
+  const post = async (webhook: WorkspaceWebhook, delivery: WebhookDelivery) => {
+    const body = JSON.stringify(delivery.payload);
+    const timestamp = String(Math.floor(Date.now() / 1000));
+    const response = await fetchImpl(webhook.url, {
+      method: "POST",
+      headers: {
+        "content-type": "application/json",
+        "user-agent": "Hive-Webhooks",
+        "x-hive-event": delivery.event,
+        "x-hive-delivery": delivery.id,
+        "x-hive-timestamp": timestamp,
+        "x-hive-signature": signWebhookPayload(webhook.secret, timestamp, body),
+      },
+      body,
+      signal: AbortSignal.timeout(timeoutMs),
+    });
+    if (response.ok) {
+      return { responseStatus: response.status, error: null };
+    }
+    const text = await response.text().catch(() => "");
+    return {
+      responseStatus: response.status,
+      error: `HTTP ${response.status}${
+        text ? `: ${text.slice(0, RESPONSE_SNIPPET_LIMIT)}` : ""
+      }`,
+    };
+  };
+
+  /**
+   * Makes one attempt. The webhook is re-read each time, so deliveries to a
+   * webhook that was disabled since are dropped instead of retried; only an
+   * explicit ping goes out regardless.
+   */
+  async function attemptDelivery(
+    deliveryId: string,
+    { ignoreDisabled = false }: { ignoreDisabled?: boolean } = {}
+  ): Promise<void> {
+    const delivery = await db.query.webhookDeliveries.findFirst({
+      where: eq(webhookDeliveries.id, deliveryId),
+    });
+    if (!delivery || delivery.status !== "pending") {
+      return;
+    }
+    const webhook = await db.query.workspaceWebhooks.findFirst({
+      where: eq(workspaceWebhooks.id, delivery.webhookId),
+    });
+    if (!webhook) {
+      return;
+    }
+    if (!(webhook.enabled || ignoreDisabled)) {
+      await db
+        .update(webhookDeliveries)
+        .set({
+          status: "failed",
+          error: "Webhook is disabled",
+          nextAttemptAt: null,
+        })
+        .where(eq(webhookDeliveries.id, deliveryId));
+      return;
+    }
+
+    let result: { responseStatus: number | null; error: string | null };
+    try {
+      result = await post(webhook, delivery);
+    } catch (error) {
+      result = { responseStatus: null, error: errorMessage(error) };
+    }
+
+    const attempts = delivery.attempts + 1;
+    const now = new Date();
+    const retryDelay =
+      result.error && attempts < maxAttempts
+        ? retryDelaysMs[attempts - 1]
+        : undefined;
+    let status: WebhookDelivery["status"] = "succeeded";
+    if (result.error) {
+      status = retryDelay === undefined ? "failed" : "pending";
+    }
+
+    await db
+      .update(webhookDeliveries)
+      .set({
+        status,
+        attempts,
+        responseStatus: result.responseStatus,
+        error: result.error,
+        lastAttemptAt: now,
+        nextAttemptAt:
+          retryDelay === undefined
+            ? null
+            : new Date(now.getTime() + retryDelay),
+      })
+      .where(eq(webhookDeliveries.id, deliveryId));
+
+    if (retryDelay !== undefined) {
+      scheduleRetry(deliveryId, retryDelay);
+    } else if (status === "failed") {
+      process.stderr.write(
+        `[webhooks] Delivery ${deliveryId} to ${webhook.url} failed after ${attempts} attempts: ${result.error}\n`
+      );
+    }
+  }
+
+  const enqueue = async (
+    webhook: WorkspaceWebhook,
+    event: string,
+    payload: Record<string, unknown>
+  ): Promise<WebhookDelivery> => {
+    const id = randomUUID();
+    const createdAt = new Date();
+    const [delivery] = await db
+      .insert(webhookDeliveries)
+      .values({
+        id,
+        webhookId: webhook.id,
+        event,
+        payload: { id, event, createdAt: createdAt.toISOString(), ...payload },
+        status: "pending",
+        attempts: 0,
+        createdAt,
+      })
+      .returning();
+    await pruneDeliveryLog(webhook.id);
+    if (!delivery) {
+      throw new Error("Failed to record webhook delivery");
+    }
+    return delivery;
+  };
+
+  const describeCell = async (cellId: string) => {
+    const cell = await db.query.cells.findFirst({
+      where: eq(cells.id, cellId),
+    });
+    return cell
+      ? { id: cell.id, name: cell.name, templateId: cell.templateId }
+      : { id: cellId };
+  };
+
+  const publish = async (notification: WebhookNotification) => {
+    const hooks = await db
+      .select()
+      .from(workspaceWebhooks)
+      .where(
+        and(
+          eq(workspaceWebhooks.workspaceId, notification.workspaceId),
+          eq(workspaceWebhooks.enabled, true)
+        )
+      );
+    const subscribed = hooks.filter((hook) =>
+      hook.events.includes(notification.type)
+    );
+    if (subscribed.length === 0) {
+      return;
+    }
+
+    // One slow receiver must not hold up the others.
+    const cell = await describeCell(notification.cellId);
+    await Promise.all(
+      subscribed.map(async (hook) => {
+        const delivery = await enqueue(hook, notification.type, {
+          workspaceId: notification.workspaceId,
+          cell,
+          data: notification.data,
+        });
+        await attemptDelivery(delivery.id);
+      })
+    );
+  };
+
+  return {
+    /**
+     * Starts listening for lifecycle events and resumes deliveries that were
+     * still pending when the server stopped.
+     */
+    async start(): Promise<void> {
+      if (unsubscribe) {
+        return;
+      }
+      unsubscribe = subscribeToLifecycleEvents((lifecycle) => {
+        for (const notification of toWebhookNotifications(lifecycle)) {
+          publish(notification).catch((error) => {
+            process.stderr.write(
+              `[webhooks] Failed to publish ${notification.type}: ${errorMessage(error)}\n`
+            );
+          });
+        }
+      });
+
+      const pending = await db
+        .select()
+        .from(webhookDeliveries)
+        .where(eq(webhookDeliveries.status, "pending"));
+      const now = Date.now();
+      for (const delivery of pending) {
+        scheduleRetry(
+          delivery.id,
+          Math.max(0, (delivery.nextAttemptAt?.getTime() ?? now) - now)
+        );
+      }
+    },
+
+    stop(): void {
+      unsubscribe?.();
+      unsubscribe = null;
+      for (const timer of retryTimers.values()) {
+        clearTimeout(timer);
+      }
+      retryTimers.clear();
+    },
+
+    publish,
+
+    /**
+     * Sends a `ping` delivery right away, ignoring the event filter and
+     * whether the webhook is enabled.
+     */
+    async ping(webhook: WorkspaceWebhook): Promise<WebhookDelivery> {
+      const delivery = await enqueue(webhook, WEBHOOK_PING_EVENT, {
+        workspaceId: webhook.workspaceId,
+        data: { webhookId: webhook.id },
+      });
+      await attemptDelivery(delivery.id, { ignoreDisabled: true });
+      const latest = await db.query.webhookDeliveries.findFirst({
+        where: eq(webhookDeliveries.id, delivery.id),
+      });
+      return latest ?? delivery;
+    },
+  };
+}
+
+export type WebhookDispatcher = ReturnType<typeof createWebhookDispatcher>;
+
+export const webhookDispatcher = createWebhookDispatcher();
@@ -0,0 +1,314 @@
+import { randomBytes, randomUUID } from "node:crypto";
+import { mkdir, readFile, writeFile } from "node:fs/promises";
+import { join } from "node:path";
+import { and, eq, inArray } from "drizzle-orm";
+
+import { db as defaultDb } from "../db";
+import {
+  type WorkspaceSecret,
+  workspaceSecrets,
+} from "../schema/workspace-secrets";
+import { resolveHiveHome } from "../workspaces/registry";
+import {
+  decryptSecretValue,
+  deriveSecretKey,
+  encryptSecretValue,
+} from "./secret-crypto";
+
+export const SECRETS_KEY_ENV = "HIVE_SECRETS_KEY";
+export const SECRET_NAME_PATTERN = /^[A-Za-z_][A-Za-z0-9_]*$/;
+
+const SECRET_REFERENCE = /\$\{secret:([A-Za-z_][A-Za-z0-9_]*)\}/g;
+const KEY_FILE_NAME = "secrets.key";
+const KEY_PURPOSE = "workspace-secret";
+const KEY_FILE_MODE = 0o600;
+const REDACTED = "[redacted]";
+// Shorter values would turn ordinary output into a wall of [redacted].
+const MIN_REDACTED_LENGTH = 4;
+
+type DatabaseClient = typeof defaultDb;
+
+export class MissingSecretError extends Error {
+  readonly names: string[];
+
+  constructor(names: string[]) {
+    super(
+      `Missing workspace secret${names.length === 1 ? "" : "s"}: ${names.join(", ")}`
+    );
+    this.name = "MissingSecretError";
+    this.names = names;
+  }
+}
+
+/**
+ * Masks secrets in a process's output before it reaches `sink`. Text that
+ * could be the start of a secret is held back until the next chunk shows
+ * whether it is, so a secret split across chunks is still masked.
+ */
+export type SecretOutputRedactor = {
+  write: (chunk: string) => void;
+  /** Releases the held-back text once the output has ended. */
+  end: () => void;
+};
+
+/** Environment with secrets filled in, plus a redactor for their values. */
+export type ResolvedSecretEnv = {
+  env: Record<string, string>;
+  createOutputRedactor: (sink: (text: string) => void) => SecretOutputRedactor;
+};
+
+export type ResolveSecretEnv = (
+  workspaceId: string,
+  env: Record<string, string>
+) => Promise<ResolvedSecretEnv>;
+
+export const collectSecretReferences = (
+  env: Record<string, string>
+): string[] => {
+  const names = new Set<string>();
+  for (const value of Object.values(env)) {
+    for (const match of value.matchAll(SECRET_REFERENCE)) {
+      names.add(match[1] as string);
+    }
+  }
+  return [...names];
+};
+
+/** Replaces `${secret:NAME}` in env values; throws when any name is unset. */
+export const resolveSecretReferences = (
+  env: Record<string, string>,
+  values: Record<string, string>
+): Record<string, string> => {
+  const missing = collectSecretReferences(env).filter(
+    (name) => values[name] === undefined
+  );
+  if (missing.length > 0) {
+    throw new MissingSecretError(missing);
+  }
+  return Object.fromEntries(
+    Object.entries(env).map(([key, value]) => [
+      key,
+      value.replace(SECRET_REFERENCE, (_match, name: string) =>
+        String(values[name])
+      ),
+    ])
+  );
+};
+
+const listRedactable = (values: string[]) =>
+  [...new Set(values)]
+    .filter((value) => value.length >= MIN_REDACTED_LENGTH)
+    .sort((left, right) => right.length - left.length);
+
+export const createSecretRedactor = (values: string[]) => {
+  const redactable = listRedactable(values);
+  if (redactable.length === 0) {
+    return (text: string) => text;
+  }
+  return (text: string) =>
+    redactable.reduce(
+      (current, value) => current.split(value).join(REDACTED),
+      text
+    );
+};
+
+/** Length of the longest end of `text` that some secret starts with. */
+const measureSecretPrefix = (text: string, secrets: string[]): number => {
+  const longest = Math.min(text.length, (secrets[0]?.length ?? 1) - 1);
+  for (let length = longest; length > 0; length--) {
+    const tail = text.slice(-length);
+    if (secrets.some((secret) => secret.startsWith(tail))) {
+      return length;
+    }
+  }
+  return 0;
+};
+
+export const createSecretOutputRedactor = (
+  values: string[],
+  sink: (text: string) => void
+): SecretOutputRedactor => {
+  const redactable = listRedactable(values);
+  const redact = createSecretRedactor(values);
+  let held = "";
+  const emit = (text: string) => {
+    if (text) {
+      sink(text);
+    }
+  };
+
+  return {
+    write(chunk) {
+      const text = redact(held + chunk);
+      const heldLength = measureSecretPrefix(text, redactable);
+      held = text.slice(text.length - heldLength);
+      emit(text.slice(0, text.length - heldLength));
+    },
+    end() {
+      emit(held);
+      held = "";
+    },
+  };
+};
+
+let cachedKey: Promise<Buffer> | null = null;
+
+async function readOrCreateKeyFile(): Promise<string> {
+  const hiveHome = resolveHiveHome();
+  const keyPath = join(hiveHome, KEY_FILE_NAME);
+  try {
+    return (await readFile(keyPath, "utf8")).trim();
+  } catch (error) {
+    if ((error as NodeJS.ErrnoException).code !== "ENOENT") {
+      throw error;
+    }
+  }
+
+  await mkdir(hiveHome, { recursive: true });
+  const generated = randomBytes(32).toString("base64url");
+  try {
+    await writeFile(keyPath, `${generated}\n`, {
+      mode: KEY_FILE_MODE,
+      flag: "wx",
+    });
+    return generated;
+  } catch (error) {
+    // Another process created the key first; use theirs.
+    if ((error as NodeJS.ErrnoException).code === "EEXIST") {
+      return (await readFile(keyPath, "utf8")).trim();
+    }
+    throw error;
+  }
+}
+
+/**
+ * Uses `HIVE_SECRETS_KEY` when set, otherwise a key generated on first use
+ * and kept in `$HIVE_HOME/secrets.key`.
+ */
+export function loadSecretsKey(): Promise<Buffer> {
+  const fromEnv = process.env[SECRETS_KEY_ENV]?.trim();
+  if (fromEnv) {
+    return Promise.resolve(deriveSecretKey(KEY_PURPOSE, fromEnv));
+  }
+  cachedKey ??= readOrCreateKeyFile()
+    .then((secret) => deriveSecretKey(KEY_PURPOSE, secret))
+    .catch((error) => {
+      cachedKey = null;
+      throw error;
+    });
+  return cachedKey;
+}
+
+export type WorkspaceSecretStore = {
+  list(workspaceId: string): Promise<WorkspaceSecret[]>;
+  set(
+    workspaceId: string,
+    name: string,
+    value: string
+  ): Promise<WorkspaceSecret>;
+  remove(workspaceId: string, name: string): Promise<boolean>;
+  resolveEnv: ResolveSecretEnv;
+};
+
+export function createWorkspaceSecretStore({
+  db = defaultDb,
+  loadKey = loadSecretsKey,
+  now = () => new Date(),
+}: {
+  db?: DatabaseClient;
+  loadKey?: () => Promise<Buffer>;
+  now?: () => Date;
+} = {}): WorkspaceSecretStore {
+  const findSecret = (workspaceId: string, name: string) =>
+    db.query.workspaceSecrets.findFirst({
+      where: and(
+        eq(workspaceSecrets.workspaceId, workspaceId),
+        eq(workspaceSecrets.name, name)
+      ),
+    });
+
+  return {
+    list(workspaceId) {
+      return db
+        .select()
+        .from(workspaceSecrets)
+        .where(eq(workspaceSecrets.workspaceId, workspaceId))
+        .orderBy(workspaceSecrets.name);
+    },
+
+    async set(workspaceId, name, value) {
+      if (!SECRET_NAME_PATTERN.test(name)) {
+        throw new Error(
+          `Invalid secret name "${name}"; use letters, digits and underscores`
+        );
+      }
+      const encryptedValue = encryptSecretValue(value, await loadKey());
+      const timestamp = now();
+      const existing = await findSecret(workspaceId, name);
+      if (existing) {
+        await db
+          .update(workspaceSecrets)
+          .set({ encryptedValue, updatedAt: timestamp })
+          .where(eq(workspaceSecrets.id, existing.id));
+      } else {
+        await db.insert(workspaceSecrets).values({
+          id: randomUUID(),
+          workspaceId,
+          name,
+          encryptedValue,
+          createdAt: timestamp,
+          updatedAt: timestamp,
+        });
+      }
+      const saved = await findSecret(workspaceId, name);
+      if (!saved) {
+        throw new Error(`Failed to save secret "${name}"`);
+      }
+      return saved;
+    },
+
+    async remove(workspaceId, name) {
+      const existing = await findSecret(workspaceId, name);
+      if (!existing) {
+        return false;
+      }
+      await db
+        .delete(workspaceSecrets)
+        .where(eq(workspaceSecrets.id, existing.id));
+      return true;
+    },
+
+    async resolveEnv(workspaceId, env) {
+      const names = collectSecretReferences(env);
+      if (names.length === 0) {
+        return {
+          env,
+          createOutputRedactor: (sink) => createSecretOutputRedactor([], sink),
+        };
+      }
+
+      const rows = await db
+        .select()
+        .from(workspaceSecrets)
+        .where(
+          and(
+            eq(workspaceSecrets.workspaceId, workspaceId),
+            inArray(workspaceSecrets.name, names)
+          )
+        );
+      const key = await loadKey();
+      const values = Object.fromEntries(
+        rows.map((row) => [
+          row.name,
+          decryptSecretValue(row.encryptedValue, key),
+        ])
+      );
+
+      return {
+        env: resolveSecretReferences(env, values),
+        createOutputRedactor: (sink) =>
+          createSecretOutputRedactor(Object.values(values), sink),
+      };
+    },
+  };
+}
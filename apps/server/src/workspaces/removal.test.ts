@@ -242,7 +242,12 @@ const createTestSupervisor = (
   startCellServices: () => Promise.resolve(),
   stopCellService: () => Promise.resolve(),
   stopCellServices,
+  resumeCellServices: () => Promise.resolve(),
+  teardownCellServices: () => Promise.resolve(),
   stopAll: () => Promise.resolve(),
+  reloadTemplates: () => 0,
+  previewCellConfig: () => Promise.resolve(null),
+  applyCellConfig: () => Promise.resolve(null),
   getServiceTerminalSession: () => null,
   readServiceTerminalOutput: () => "",
   subscribeToServiceTerminal: () => () => 0,
@@ -270,6 +275,7 @@ const createTestAgentRuntime = (
     fetchCompactionStats: unsupported,
     updateAgentSessionModel: unsupported,
     sendAgentMessage: unsupported,
+    appendAgentContext: unsupported,
     interruptAgentSession: unsupported,
     stopAgentSession: unsupported,
     closeAgentSession,
@@ -94,6 +94,15 @@ export async function removeWorkspaceCascade(
         })
       );
 
+    await deps.supervisor
+      .teardownCellServices(cell.id)
+      .catch((cause: unknown) =>
+        logWarning(deps.logger, "Failed to tear down cell services", {
+          cellId: cell.id,
+          error: formatError(cause),
+        })
+      );
+
     await cleanupCellWorkspace({
       workspaceRootPath: context.workspace.path,
       cellWorkspacePath: cell.workspacePath,
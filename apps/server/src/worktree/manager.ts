@@ -96,7 +96,24 @@ export type WorktreeCreateOptions = {
 export type WorktreeStartPoint =
   | { mode: "head" }
   | { mode: "branch"; value: string }
-  | { mode: "pr"; value: string };
+  | { mode: "pr"; value: string }
+  | { mode: "cell"; value: string; commit?: string };
+
+/**
+ * Where a cell branch was started from, persisted so the cell can later be
+ * merged back into or synced with that start point. `ref` is the branch name
+ * for `head` and `branch` modes, the pull request number for `pr` mode and
+ * the source cell id for `cell` mode.
+ */
+export type WorktreeStartPointRef = {
+  mode: WorktreeStartPoint["mode"];
+  ref: string;
+};
+
+type ResolvedStartPoint = WorktreeStartPointRef & {
+  commitish: string;
+  resolvedFrom: string;
+};
 
 export type WorktreeCreateTimingEvent = {
   step: string;
@@ -108,6 +125,7 @@ export type WorktreeLocation = {
   path: string;
   branch: string;
   baseCommit: string;
+  startPoint?: WorktreeStartPointRef;
 };
 
 export type WorktreeErrorKind =
@@ -1072,24 +1090,17 @@ export function createWorktreeManager(
     }
   }
 
-  function resolveHeadStartPoint(): {
-    mode: WorktreeStartPoint["mode"];
-    commitish: string;
-    resolvedFrom: string;
-  } {
+  function resolveHeadStartPoint(): ResolvedStartPoint {
     const branch = getCurrentBranch();
     return {
       mode: "head",
+      ref: branch,
       commitish: branch,
       resolvedFrom: branch,
     };
   }
 
-  function resolveBranchStartPoint(value: string): {
-    mode: WorktreeStartPoint["mode"];
-    commitish: string;
-    resolvedFrom: string;
-  } {
+  function resolveBranchStartPoint(value: string): ResolvedStartPoint {
     const branch = value.trim();
     if (!branch) {
       throw toWorktreeError(
@@ -1106,6 +1117,7 @@ export function createWorktreeManager(
     if (branchExists(localRef)) {
       return {
         mode: "branch",
+        ref: branch,
         commitish: branch,
         resolvedFrom: localRef,
       };
@@ -1140,16 +1152,13 @@ export function createWorktreeManager(
 
     return {
       mode: "branch",
+      ref: branch,
       commitish: `origin/${branch}`,
       resolvedFrom: remoteRef,
     };
   }
 
-  function resolvePrStartPoint(value: string): {
-    mode: WorktreeStartPoint["mode"];
-    commitish: string;
-    resolvedFrom: string;
-  } {
+  function resolvePrStartPoint(value: string): ResolvedStartPoint {
     const prNumber = parsePullRequestNumber(value);
     if (!prNumber) {
       throw toWorktreeError(
@@ -1190,16 +1199,51 @@ export function createWorktreeManager(
 
     return {
       mode: "pr",
+      ref: prNumber,
       commitish: localPullRef,
       resolvedFrom: remotePullRef,
     };
   }
 
-  function resolveStartPoint(startPoint?: WorktreeStartPoint): {
-    mode: WorktreeStartPoint["mode"];
-    commitish: string;
-    resolvedFrom: string;
-  } {
+  function resolveCellStartPoint(
+    sourceCellId: string,
+    commit: string | undefined
+  ): ResolvedStartPoint {
+    if (!commit) {
+      throw toWorktreeError(
+        {
+          message: "Source commit is required when forking a cell",
+          kind: "validation",
+          context: { mode: "cell", sourceCellId },
+        },
+        undefined
+      );
+    }
+
+    try {
+      git("cat-file", "-e", `${commit}^{commit}`);
+    } catch {
+      throw toWorktreeError(
+        {
+          message: `Commit '${commit}' of cell ${sourceCellId} not found`,
+          kind: "validation",
+          context: { sourceCellId, commit },
+        },
+        undefined
+      );
+    }
+
+    return {
+      mode: "cell",
+      ref: sourceCellId,
+      commitish: commit,
+      resolvedFrom: `cell:${sourceCellId}`,
+    };
+  }
+
+  function resolveStartPoint(
+    startPoint?: WorktreeStartPoint
+  ): ResolvedStartPoint {
     if (!startPoint || startPoint.mode === "head") {
       return resolveHeadStartPoint();
     }
@@ -1208,6 +1252,10 @@ export function createWorktreeManager(
       return resolveBranchStartPoint(startPoint.value);
     }
 
+    if (startPoint.mode === "cell") {
+      return resolveCellStartPoint(startPoint.value, startPoint.commit);
+    }
+
     return resolvePrStartPoint(startPoint.value);
   }
 
@@ -1462,6 +1510,7 @@ export function createWorktreeManager(
           path: worktreePath,
           branch,
           baseCommit,
+          startPoint: { mode: startPoint.mode, ref: startPoint.ref },
         } satisfies WorktreeLocation;
       } catch (cause) {
         try {
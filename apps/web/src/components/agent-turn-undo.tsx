@@ -0,0 +1,103 @@
+import { useMutation, useQuery, useQueryClient } from "@tanstack/react-query";
+import { Undo2 } from "lucide-react";
+import { useState } from "react";
+import { toast } from "sonner";
+import {
+  AlertDialog,
+  AlertDialogAction,
+  AlertDialogCancel,
+  AlertDialogContent,
+  AlertDialogDescription,
+  AlertDialogFooter,
+  AlertDialogHeader,
+  AlertDialogTitle,
+} from "@/components/ui/alert-dialog";
+import { Button } from "@/components/ui/button";
+import { cellMutations, cellQueries } from "@/queries/cells";
+
+const SNAPSHOT_POLL_INTERVAL_MS = 10_000;
+
+type AgentTurnUndoProps = {
+  cellId: string;
+};
+
+/**
+ * Rolls the worktree back to the restore point taken before the agent's
+ * latest turn. The restore keeps a backup snapshot, so it can be undone too.
+ */
+export function AgentTurnUndo({ cellId }: AgentTurnUndoProps) {
+  const queryClient = useQueryClient();
+  const [confirming, setConfirming] = useState(false);
+  const snapshotsQuery = useQuery({
+    ...cellQueries.snapshots(cellId),
+    refetchInterval: SNAPSHOT_POLL_INTERVAL_MS,
+  });
+
+  const restoreMutation = useMutation({
+    mutationFn: cellMutations.restoreSnapshot.mutationFn,
+    onSuccess: () => {
+      toast.success("Undid the last agent turn; a backup snapshot was kept");
+      queryClient.invalidateQueries({
+        queryKey: ["cells", cellId, "snapshots"],
+      });
+    },
+    onError: (mutationError) => {
+      const message =
+        mutationError instanceof Error
+          ? mutationError.message
+          : "Failed to undo agent turn";
+      toast.error(message || "Failed to undo agent turn");
+    },
+  });
+
+  // Snapshots are listed newest first.
+  const lastTurn = snapshotsQuery.data?.find(
+    (snapshot) => snapshot.agentMessageId !== null
+  );
+  if (!lastTurn) {
+    return null;
+  }
+
+  return (
+    <>
+      <div className="flex items-center justify-between gap-4 rounded-sm border-2 border-border bg-card px-4 py-2 text-sm">
+        <p className="text-muted-foreground">
+          Last agent turn started{" "}
+          {new Date(lastTurn.createdAt).toLocaleString()}
+        </p>
+        <Button
+          disabled={restoreMutation.isPending}
+          onClick={() => setConfirming(true)}
+          size="sm"
+          type="button"
+          variant="outline"
+        >
+          <Undo2 className="size-4" />
+          {restoreMutation.isPending ? "Undoing…" : "Undo last turn"}
+        </Button>
+      </div>
+      <AlertDialog onOpenChange={setConfirming} open={confirming}>
+        <AlertDialogContent className="max-w-md">
+          <AlertDialogHeader>
+            <AlertDialogTitle>Undo the last agent turn?</AlertDialogTitle>
+            <AlertDialogDescription>
+              The worktree goes back to how it was before the agent's latest
+              turn. The current state is saved as a snapshot first. The chat
+              history is not changed.
+            </AlertDialogDescription>
+          </AlertDialogHeader>
+          <AlertDialogFooter>
+            <AlertDialogCancel>Cancel</AlertDialogCancel>
+            <AlertDialogAction
+              onClick={() =>
+                restoreMutation.mutate({ cellId, snapshotId: lastTurn.id })
+              }
+            >
+              Undo turn
+            </AlertDialogAction>
+          </AlertDialogFooter>
+        </AlertDialogContent>
+      </AlertDialog>
+    </>
+  );
+}
@@ -0,0 +1,104 @@
+import { useMutation, useQuery, useQueryClient } from "@tanstack/react-query";
+import { toast } from "sonner";
+import { Badge } from "@/components/ui/badge";
+import { Button } from "@/components/ui/button";
+import { useConfigReloadStream } from "@/hooks/use-config-reload-stream";
+import { cellMutations, cellQueries } from "@/queries/cells";
+
+type CellConfigChangesProps = {
+  cellId: string;
+  workspaceId: string;
+};
+
+const formatValue = (value: unknown) =>
+  value === undefined ? "(unset)" : JSON.stringify(value);
+
+/** Lists services the current config would change and applies them. */
+export function CellConfigChanges({
+  cellId,
+  workspaceId,
+}: CellConfigChangesProps) {
+  const queryClient = useQueryClient();
+  const reload = useConfigReloadStream(workspaceId);
+  const configQuery = useQuery(cellQueries.config(cellId));
+
+  const applyMutation = useMutation({
+    mutationFn: cellMutations.applyConfig.mutationFn,
+    onSuccess: (data) => {
+      toast.success(
+        data.applied.services.length > 0
+          ? `Applied config to ${data.applied.services.length} service(s)`
+          : "Services already match the config"
+      );
+      queryClient.invalidateQueries({
+        queryKey: ["cells", cellId, "config"],
+      });
+    },
+    onError: (mutationError) => {
+      const message =
+        mutationError instanceof Error
+          ? mutationError.message
+          : "Failed to apply config";
+      toast.error(message || "Failed to apply config");
+    },
+  });
+
+  if (reload?.status === "invalid") {
+    return (
+      <div className="rounded-sm border-2 border-destructive/50 bg-destructive/10 px-4 py-3 text-destructive text-sm">
+        <p className="font-semibold">
+          The workspace config is invalid. Running services are unaffected,
+          but services cannot start until it is fixed.
+        </p>
+        {reload.error ? (
+          <p className="mt-1 whitespace-pre-wrap font-mono text-xs">
+            {reload.error}
+          </p>
+        ) : null}
+      </div>
+    );
+  }
+
+  const changes = configQuery.data?.services ?? [];
+  if (changes.length === 0) {
+    return null;
+  }
+
+  return (
+    <div className="flex flex-col gap-3 rounded-sm border-2 border-border bg-card px-4 py-3 text-sm">
+      <div className="flex items-center justify-between gap-4">
+        <p className="font-semibold">
+          The workspace config changed for {changes.length} service(s).
+        </p>
+        <Button
+          disabled={applyMutation.isPending}
+          onClick={() => applyMutation.mutate({ cellId })}
+          size="sm"
+          type="button"
+        >
+          {applyMutation.isPending ? "Applying…" : "Apply"}
+        </Button>
+      </div>
+      <ul className="flex flex-col gap-2">
+        {changes.map((service) => (
+          <li key={service.serviceId ?? service.name}>
+            <div className="flex items-center gap-2">
+              <span className="font-medium">{service.name}</span>
+              <Badge variant="outline">{service.change}</Badge>
+            </div>
+            {service.fields.length > 0 ? (
+              <ul className="mt-1 flex flex-col gap-0.5 pl-4 font-mono text-muted-foreground text-xs">
+                {service.fields.map((field) => (
+                  <li key={field.path}>
+                    {field.path}: {formatValue(field.before)} →{" "}
+                    {formatValue(field.after)}
+                  </li>
+                ))}
+              </ul>
+            ) : null}
+          </li>
+        ))}
+      </ul>
+    </div>
+  );
+}
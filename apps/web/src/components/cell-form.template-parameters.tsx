@@ -0,0 +1,139 @@
+import { Checkbox } from "@/components/ui/checkbox";
+import { Input } from "@/components/ui/input";
+import { Label } from "@/components/ui/label";
+import {
+  Select,
+  SelectContent,
+  SelectItem,
+  SelectTrigger,
+  SelectValue,
+} from "@/components/ui/select";
+import type { Template, TemplateParameter } from "@/queries/templates";
+
+export type TemplateParameterFormValues = Record<string, string | boolean>;
+
+export function buildDefaultParameterValues(
+  template: Template | undefined
+): TemplateParameterFormValues {
+  const values: TemplateParameterFormValues = {};
+  for (const parameter of template?.configJson.parameters ?? []) {
+    if (parameter.type === "boolean") {
+      values[parameter.name] = parameter.default === true;
+      continue;
+    }
+    values[parameter.name] =
+      parameter.default === undefined ? "" : String(parameter.default);
+  }
+  return values;
+}
+
+/** Drops empty text inputs so the server falls back to the defaults. */
+export function toParameterPayload(
+  values: TemplateParameterFormValues
+): Record<string, string | boolean> | undefined {
+  const entries = Object.entries(values).filter(
+    ([, value]) => typeof value === "boolean" || value.trim().length > 0
+  );
+  return entries.length > 0 ? Object.fromEntries(entries) : undefined;
+}
+
+const parameterInputId = (name: string) => `template-parameter-${name}`;
+
+function TemplateParameterField({
+  parameter,
+  value,
+  disabled,
+  onChange,
+}: {
+  parameter: TemplateParameter;
+  value: string | boolean | undefined;
+  disabled: boolean;
+  onChange: (value: string | boolean) => void;
+}) {
+  const id = parameterInputId(parameter.name);
+
+  if (parameter.type === "boolean") {
+    return (
+      <div className="flex items-center gap-2">
+        <Checkbox
+          checked={value === true}
+          disabled={disabled}
+          id={id}
+          onCheckedChange={(checked) => onChange(checked === true)}
+        />
+        <Label htmlFor={id}>{parameter.name}</Label>
+      </div>
+    );
+  }
+
+  return (
+    <div className="space-y-2">
+      <Label htmlFor={id}>{parameter.name}</Label>
+      {parameter.enum ? (
+        <Select
+          disabled={disabled}
+          onValueChange={onChange}
+          value={typeof value === "string" ? value : ""}
+        >
+          <SelectTrigger id={id}>
+            <SelectValue placeholder={`Select ${parameter.name}`} />
+          </SelectTrigger>
+          <SelectContent>
+            {parameter.enum.map((option) => (
+              <SelectItem key={String(option)} value={String(option)}>
+                {String(option)}
+              </SelectItem>
+            ))}
+          </SelectContent>
+        </Select>
+      ) : (
+        <Input
+          disabled={disabled}
+          id={id}
+          onChange={(event) => onChange(event.target.value)}
+          type={parameter.type === "number" ? "number" : "text"}
+          value={typeof value === "string" ? value : ""}
+        />
+      )}
+    </div>
+  );
+}
+
+export function TemplateParameterFields({
+  template,
+  values,
+  disabled,
+  onChange,
+}: {
+  template: Template | undefined;
+  values: TemplateParameterFormValues;
+  disabled: boolean;
+  onChange: (values: TemplateParameterFormValues) => void;
+}) {
+  const parameters = template?.configJson.parameters ?? [];
+  if (parameters.length === 0) {
+    return null;
+  }
+
+  return (
+    <div className="space-y-4" data-testid="template-parameters">
+      {parameters.map((parameter) => (
+        <div className="space-y-1" key={parameter.name}>
+          <TemplateParameterField
+            disabled={disabled}
+            onChange={(value) =>
+              onChange({ ...values, [parameter.name]: value })
+            }
+            parameter={parameter}
+            value={values[parameter.name]}
+          />
+          {parameter.description ? (
+            <p className="text-muted-foreground text-xs">
+              {parameter.description}
+            </p>
+          ) : null}
+        </div>
+      ))}
+    </div>
+  );
+}
@@ -39,6 +39,14 @@ vi.mock("@/queries/templates", () => ({
                   providerId: "opencode",
                 },
               },
+              parameters: [
+                {
+                  name: "dbSeed",
+                  type: "string",
+                  default: "demo",
+                  description: "Seed data loaded during setup",
+                },
+              ],
             },
           },
         ],
@@ -105,6 +113,21 @@ describe("CellForm", () => {
     );
     expect(screen.getByText("Source: Linear ENG-43")).toBeInTheDocument();
   });
+
+  it("renders inputs for template parameters with their defaults", async () => {
+    render(
+      <TestQueryProvider>
+        <CellForm workspaceId="workspace-1" />
+      </TestQueryProvider>
+    );
+
+    await waitFor(() => {
+      expect(screen.getByLabelText("dbSeed")).toHaveValue("demo");
+    });
+    expect(
+      screen.getByText("Seed data loaded during setup")
+    ).toBeInTheDocument();
+  });
 });
 
 function TestQueryProvider({ children }: { children: ReactNode }) {
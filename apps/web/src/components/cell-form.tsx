@@ -8,6 +8,12 @@ import {
   resolveAutoSelectedModel,
   resolveTemplateModelSelection,
 } from "@/components/cell-form.model-selection";
+import {
+  buildDefaultParameterValues,
+  TemplateParameterFields,
+  type TemplateParameterFormValues,
+  toParameterPayload,
+} from "@/components/cell-form.template-parameters";
 import {
   type ModelSelection,
   type ModelSelectionSource,
@@ -169,6 +175,12 @@ export function CellForm({
     (template) => template.id === activeTemplateId
   );
   const templateAgent = activeTemplate?.configJson.agent;
+  const [parameterValues, setParameterValues] =
+    useState<TemplateParameterFormValues>({});
+
+  useEffect(() => {
+    setParameterValues(buildDefaultParameterValues(activeTemplate));
+  }, [activeTemplate]);
   const providerPreference =
     selectedModel?.providerId ??
     templateAgent?.model?.providerId ??
@@ -236,6 +248,7 @@ export function CellForm({
       form.reset();
       setSelectedModel(undefined);
       setHasExplicitModelSelection(false);
+      setParameterValues(buildDefaultParameterValues(activeTemplate));
       setActiveTemplateId(defaultValues.templateId);
       onSuccess?.();
     },
@@ -260,6 +273,7 @@ export function CellForm({
       const explicitModelSelection = hasExplicitModelSelection
         ? selectedModel
         : undefined;
+      const parameters = toParameterPayload(parameterValues);
       const spawnFromMode = formValues.spawnFromMode ?? "head";
       const spawnFromValue = formValues.spawnFromValue?.trim();
       if (spawnFromMode !== "head" && !spawnFromValue) {
@@ -283,6 +297,7 @@ export function CellForm({
         ...(explicitModelSelection?.variant
           ? { variant: explicitModelSelection.variant }
           : {}),
+        ...(parameters ? { parameters } : {}),
         spawnFromMode,
         ...(spawnFromMode !== "head" && spawnFromValue
           ? { spawnFromValue }
@@ -331,7 +346,8 @@ export function CellForm({
   if (!templates || templates.length === 0) {
     return (
       <div>
-        No templates available. Add templates in hive.config.json to continue.
+        No templates available. Add templates to the workspace hive.config file
+        to continue.
       </div>
     );
   }
@@ -467,6 +483,13 @@ export function CellForm({
             )}
           </form.Field>
 
+          <TemplateParameterFields
+            disabled={mutation.isPending}
+            onChange={setParameterValues}
+            template={activeTemplate}
+            values={parameterValues}
+          />
+
           <div className="space-y-2">
             <Label htmlFor="cell-model-selector">Model</Label>
             <ModelSelector
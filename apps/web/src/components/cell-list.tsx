@@ -792,6 +792,19 @@ function CellStatusNotice({
     );
   }
 
+  if (status === "suspended") {
+    return (
+      <div className="rounded-md border border-muted-foreground/30 bg-muted/20 p-3">
+        <p className="font-semibold text-[11px] text-muted-foreground uppercase tracking-[0.3em]">
+          Suspended
+        </p>
+        <p className="text-[11px] text-muted-foreground uppercase tracking-[0.3em]">
+          Idle cell stopped; opening it resumes services
+        </p>
+      </div>
+    );
+  }
+
   if (status === "deleting") {
     return (
       <div className="flex items-center gap-3 rounded-md border border-destructive/40 bg-destructive/10 p-3">
@@ -0,0 +1,169 @@
+import { useMutation, useQuery, useQueryClient } from "@tanstack/react-query";
+import { KeyRound, Loader2, Trash2 } from "lucide-react";
+import { useState } from "react";
+import { toast } from "sonner";
+import { Button } from "@/components/ui/button";
+import { Input } from "@/components/ui/input";
+import { Label } from "@/components/ui/label";
+import { secretMutations, secretQueries } from "@/queries/secrets";
+
+const SECRET_NAME_PATTERN = /^[A-Za-z_][A-Za-z0-9_]*$/;
+
+/**
+ * Lists a workspace's secret names and lets admins set or delete them.
+ * Values are write-only: the API never returns them.
+ */
+export function WorkspaceSecretsPanel({
+  workspaceId,
+}: {
+  workspaceId: string;
+}) {
+  const [name, setName] = useState("");
+  const [value, setValue] = useState("");
+  const queryClient = useQueryClient();
+  const secretsQuery = useQuery(secretQueries.list(workspaceId));
+  const invalidateSecrets = () =>
+    queryClient.invalidateQueries({
+      queryKey: secretQueries.list(workspaceId).queryKey,
+    });
+
+  const setSecret = useMutation({
+    mutationFn: secretMutations.set.mutationFn,
+    onSuccess: async (secret) => {
+      toast.success(`Saved secret ${secret.name}`);
+      setName("");
+      setValue("");
+      await invalidateSecrets();
+    },
+    onError: (error: Error) => toast.error(error.message),
+  });
+
+  const deleteSecret = useMutation({
+    mutationFn: secretMutations.delete.mutationFn,
+    onSuccess: async () => {
+      toast.success("Secret deleted");
+      await invalidateSecrets();
+    },
+    onError: (error: Error) => toast.error(error.message),
+  });
+
+  const trimmedName = name.trim();
+  const nameError =
+    trimmedName && !SECRET_NAME_PATTERN.test(trimmedName)
+      ? "Use letters, digits and underscores"
+      : undefined;
+
+  const handleSubmit = (event: React.FormEvent<HTMLFormElement>) => {
+    event.preventDefault();
+    if (!trimmedName || nameError) {
+      return;
+    }
+    setSecret.mutate({ workspaceId, name: trimmedName, value });
+  };
+
+  const secrets = secretsQuery.data ?? [];
+
+  return (
+    <div
+      className="flex flex-col gap-3 rounded border border-border bg-card/40 p-4"
+      data-testid="workspace-secrets"
+    >
+      <div>
+        <h3 className="font-semibold text-muted-foreground text-sm uppercase tracking-[0.2em]">
+          Secrets
+        </h3>
+        <p className="text-muted-foreground text-sm">
+          Reference these in template env as{" "}
+          <code className="font-mono">{"${secret:NAME}"}</code>. Values are
+          stored encrypted and only reach service processes.
+        </p>
+      </div>
+
+      {secretsQuery.isPending ? (
+        <div className="flex items-center gap-2 text-muted-foreground text-sm">
+          <Loader2 className="size-4 animate-spin" />
+          <span>Loading secrets…</span>
+        </div>
+      ) : null}
+      {secretsQuery.error ? (
+        <p className="text-destructive text-sm" role="alert">
+          {secretsQuery.error.message}
+        </p>
+      ) : null}
+      {secretsQuery.isSuccess && secrets.length === 0 ? (
+        <p className="text-muted-foreground text-sm">No secrets yet.</p>
+      ) : null}
+      {secrets.length > 0 ? (
+        <ul className="flex flex-col divide-y divide-border/40 rounded border border-border/60">
+          {secrets.map((secret) => (
+            <li
+              className="flex items-center justify-between gap-2 px-3 py-2 text-sm"
+              key={secret.name}
+            >
+              <span className="flex min-w-0 items-center gap-2">
+                <KeyRound className="size-4 shrink-0 text-muted-foreground" />
+                <span className="truncate font-mono">{secret.name}</span>
+              </span>
+              <span className="flex items-center gap-2">
+                <span className="text-muted-foreground text-xs">
+                  Updated {new Date(secret.updatedAt).toLocaleString()}
+                </span>
+                <Button
+                  aria-label={`Delete secret ${secret.name}`}
+                  disabled={deleteSecret.isPending}
+                  onClick={() =>
+                    deleteSecret.mutate({ workspaceId, name: secret.name })
+                  }
+                  size="icon"
+                  type="button"
+                  variant="ghost"
+                >
+                  <Trash2 className="size-4" />
+                </Button>
+              </span>
+            </li>
+          ))}
+        </ul>
+      ) : null}
+
+      <form
+        className="grid gap-3 sm:grid-cols-[1fr_1fr_auto] sm:items-end"
+        onSubmit={handleSubmit}
+      >
+        <div className="space-y-1">
+          <Label htmlFor="workspace-secret-name">Name</Label>
+          <Input
+            autoComplete="off"
+            id="workspace-secret-name"
+            onChange={(event) => setName(event.currentTarget.value)}
+            placeholder="DB_PASSWORD"
+            value={name}
+          />
+        </div>
+        <div className="space-y-1">
+          <Label htmlFor="workspace-secret-value">Value</Label>
+          <Input
+            autoComplete="new-password"
+            id="workspace-secret-value"
+            onChange={(event) => setValue(event.currentTarget.value)}
+            type="password"
+            value={value}
+          />
+        </div>
+        <Button
+          disabled={!trimmedName || Boolean(nameError) || setSecret.isPending}
+          type="submit"
+        >
+          {setSecret.isPending ? (
+            <Loader2 className="size-4 animate-spin" />
+          ) : (
+            "Save secret"
+          )}
+        </Button>
+        {nameError ? (
+          <p className="text-destructive text-xs sm:col-span-3">{nameError}</p>
+        ) : null}
+      </form>
+    </div>
+  );
+}
@@ -34,6 +34,7 @@ import {
   SheetHeader,
   SheetTitle,
 } from "@/components/ui/sheet";
+import { WorkspaceSecretsPanel } from "@/components/workspace-management-sheet.secrets";
 import { cn } from "@/lib/utils";
 import type {
   WorkspaceBrowseEntry,
@@ -478,7 +479,8 @@ function WorkspaceRegisterForm({
           Register Workspace
         </h3>
         <p className="text-muted-foreground text-sm">
-          Provide an absolute path that contains a hive.config.json.
+          Provide an absolute path that contains a hive.config file (.json,
+          .jsonc, .yaml or .ts).
         </p>
       </div>
       <form className="space-y-4" onSubmit={onSubmit}>
@@ -921,6 +923,12 @@ export function WorkspaceManagementSheet({
               </div>
             ) : null}
 
+            {showWorkspaces && management.activeWorkspace ? (
+              <WorkspaceSecretsPanel
+                workspaceId={management.activeWorkspace.id}
+              />
+            ) : null}
+
             {showRegister ? (
               <div className="flex flex-col gap-3 rounded border border-border border-dashed bg-card/60 p-4">
                 {section === "all" ? (
@@ -553,7 +553,8 @@ function WorkspaceRegisterForm({
           Register Workspace
         </h3>
         <p className="text-muted-foreground text-sm">
-          Provide an absolute path that contains a hive.config.json.
+          Provide an absolute path that contains a hive.config file (.json,
+          .jsonc, .yaml or .ts).
         </p>
       </div>
       <form className="space-y-4" onSubmit={onSubmit}>
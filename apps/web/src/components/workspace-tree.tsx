@@ -12,6 +12,7 @@ import {
   ChevronRight,
   CircleX,
   Loader2,
+  Moon,
   Plus,
   Trash2,
   Wrench,
@@ -832,6 +833,10 @@ function getCellStatusIcon({
     );
   }
 
+  if (cellStatus === "suspended") {
+    return <Moon className={cn(iconClass, "text-muted-foreground")} />;
+  }
+
   if (agentStatus === "awaiting_input") {
     return <Check className={cn(iconClass, "text-teal-400")} />;
   }
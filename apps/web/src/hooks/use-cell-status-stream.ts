@@ -24,7 +24,7 @@ export function useCellStatusStream(
     let isActive = true;
 
     const url = `${API_BASE}/api/cells/workspace/${workspaceId}/stream`;
-    const source = new EventSource(url);
+    const source = new EventSource(url, { withCredentials: true });
 
     const cellListener = (event: MessageEvent<string>) => {
       if (!isActive) {
@@ -29,7 +29,8 @@ export function useCellTimingStream(
 
     const query = params.toString();
     const source = new EventSource(
-      `${API_BASE}/api/cells/${cellId}/timings/stream${query ? `?${query}` : ""}`
+      `${API_BASE}/api/cells/${cellId}/timings/stream${query ? `?${query}` : ""}`,
+      { withCredentials: true }
     );
 
     const refreshTimings = () => {
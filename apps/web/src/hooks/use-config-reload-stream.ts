@@ -0,0 +1,82 @@
+import { useQueryClient } from "@tanstack/react-query";
+import { useEffect, useState } from "react";
+import { getApiBase } from "@/lib/api-base";
+import type { CellConfigImpact, ConfigReloadEvent } from "@/queries/cells";
+
+const API_BASE = getApiBase();
+
+type ConfigReloadStreamOptions = {
+  enabled?: boolean;
+};
+
+/**
+ * Follows config reloads for a workspace, keeping each cell's config impact
+ * query current. Returns the latest reload so callers can show an invalid
+ * config.
+ */
+export function useConfigReloadStream(
+  workspaceId: string,
+  options: ConfigReloadStreamOptions = {}
+) {
+  const { enabled = true } = options;
+  const queryClient = useQueryClient();
+  const [latest, setLatest] = useState<ConfigReloadEvent | null>(null);
+
+  useEffect(() => {
+    if (!(enabled && workspaceId) || typeof window === "undefined") {
+      return;
+    }
+
+    let isActive = true;
+
+    const url = `${API_BASE}/api/cells/workspace/${workspaceId}/config/stream`;
+    const source = new EventSource(url, { withCredentials: true });
+
+    const configListener = (event: MessageEvent<string>) => {
+      if (!isActive) {
+        return;
+      }
+      try {
+        const payload = JSON.parse(event.data) as ConfigReloadEvent;
+        setLatest(payload);
+        if (payload.status !== "valid") {
+          return;
+        }
+
+        const affected = new Set<string>();
+        for (const impact of payload.cells) {
+          affected.add(impact.cellId);
+          queryClient.setQueryData<CellConfigImpact>(
+            ["cells", impact.cellId, "config"],
+            impact
+          );
+        }
+        // Cells missing from the event now match the config.
+        queryClient.invalidateQueries({
+          predicate: (query) =>
+            query.queryKey[0] === "cells" &&
+            query.queryKey[2] === "config" &&
+            !affected.has(String(query.queryKey[1])),
+        });
+      } catch {
+        /* ignore malformed events */
+      }
+    };
+
+    const errorListener = () => {
+      // Keep the stream open so EventSource can auto-reconnect.
+    };
+
+    source.addEventListener("config", configListener as EventListener);
+    source.addEventListener("error", errorListener);
+
+    return () => {
+      isActive = false;
+      source.removeEventListener("config", configListener as EventListener);
+      source.removeEventListener("error", errorListener);
+      source.close();
+    };
+  }, [workspaceId, enabled, queryClient]);
+
+  return latest;
+}
@@ -183,7 +183,8 @@ export function useGlobalAgentMonitor() {
         }
 
         const eventSource = new EventSource(
-          `${API_BASE}/api/agents/sessions/${session.id}/events`
+          `${API_BASE}/api/agents/sessions/${session.id}/events`,
+          { withCredentials: true }
         );
         sessionStreams.current.set(cell.id, {
           source: eventSource,
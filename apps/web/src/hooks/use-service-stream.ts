@@ -36,7 +36,8 @@ export function useServiceStream(
     }
     const query = params.toString();
     const source = new EventSource(
-      `${API_BASE}/api/cells/${cellId}/services/stream${query ? `?${query}` : ""}`
+      `${API_BASE}/api/cells/${cellId}/services/stream${query ? `?${query}` : ""}`,
+      { withCredentials: true }
     );
 
     const upsertService = (service: CellServiceSummary) => {
@@ -4,7 +4,10 @@ import { getApiBase } from "@/lib/api-base";
 
 const API_URL = getApiBase();
 
-export const rpc = treaty<App>(API_URL);
+// Send the session cookie so the UI works when the API requires tokens.
+export const rpc = treaty<App>(API_URL, {
+  fetch: { credentials: "include" },
+});
 
 // Helper types for convenience - inferred from Eden Treaty
 export type CreateCellInput = Parameters<typeof rpc.api.cells.post>[0];
@@ -62,6 +62,40 @@ export const cellQueries = {
     },
   }),
 
+  config: (id: string) => ({
+    queryKey: ["cells", id, "config"] as const,
+    queryFn: async () => {
+      const { data, error } = await rpc.api.cells({ id }).config.get();
+      if (error) {
+        throw new Error(formatRpcError(error, "Failed to compare config"));
+      }
+
+      if ("message" in data) {
+        throw new Error(formatRpcResponseError(data, "Cell not found"));
+      }
+
+      return data;
+    },
+  }),
+
+  snapshots: (id: string) => ({
+    queryKey: ["cells", id, "snapshots"] as const,
+    queryFn: async () => {
+      const { data, error } = await rpc.api.cells({ id }).snapshots.get({
+        query: {},
+      });
+      if (error) {
+        throw new Error(formatRpcError(error, "Failed to load snapshots"));
+      }
+
+      if ("message" in data) {
+        throw new Error(formatRpcResponseError(data, "Cell not found"));
+      }
+
+      return data.snapshots;
+    },
+  }),
+
   resources: (
     id: string,
     options: {
@@ -249,6 +283,11 @@ type ServiceBulkActionInput = {
   cellId: string;
 };
 
+type SnapshotActionInput = {
+  cellId: string;
+  snapshotId: string;
+};
+
 export const cellMutations = {
   create: {
     mutationFn: async (input: CreateCellInput) => {
@@ -340,6 +379,43 @@ export const cellMutations = {
     },
   },
 
+  applyConfig: {
+    mutationFn: async ({ cellId }: ServiceBulkActionInput) => {
+      const { data, error } = await rpc.api
+        .cells({ id: cellId })
+        .config.apply.post();
+      if (error) {
+        throw new Error(formatRpcError(error, "Failed to apply config"));
+      }
+
+      if ("message" in data) {
+        throw new Error(formatRpcResponseError(data, "Cell not found"));
+      }
+
+      return data;
+    },
+  },
+
+  restoreSnapshot: {
+    mutationFn: async ({ cellId, snapshotId }: SnapshotActionInput) => {
+      const { data, error } = await rpc.api
+        .cells({ id: cellId })
+        .snapshots({ snapshotId })
+        .restore.post({});
+      if (error) {
+        throw new Error(formatRpcError(error, "Failed to restore snapshot"));
+      }
+
+      if ("message" in data) {
+        throw new Error(
+          formatRpcResponseError(data, "Failed to restore snapshot")
+        );
+      }
+
+      return data;
+    },
+  },
+
   retrySetup: {
     mutationFn: async (cellId: string) => {
       const { data, error } = await rpc.api
@@ -408,7 +484,8 @@ export type CellStatus =
   | "pending"
   | "ready"
   | "error"
-  | "deleting";
+  | "deleting"
+  | "suspended";
 
 export type Cell = Awaited<
   ReturnType<ReturnType<typeof cellQueries.detail>["queryFn"]>
@@ -426,6 +503,23 @@ export type CellServiceSummary = Awaited<
   ReturnType<ReturnType<typeof cellQueries.services>["queryFn"]>
 >[number];
 
+export type CellConfigImpact = Awaited<
+  ReturnType<ReturnType<typeof cellQueries.config>["queryFn"]>
+>;
+
+/** Sent on the workspace config stream whenever the config file changes. */
+export type ConfigReloadEvent = {
+  workspaceId: string;
+  status: "valid" | "invalid";
+  error?: string;
+  cells: CellConfigImpact[];
+  timestamp: string;
+};
+
+export type CellSnapshot = Awaited<
+  ReturnType<ReturnType<typeof cellQueries.snapshots>["queryFn"]>
+>[number];
+
 export type CellResourceSummary = Awaited<
   ReturnType<ReturnType<typeof cellQueries.resources>["queryFn"]>
 >;
@@ -0,0 +1,80 @@
+import { rpc } from "@/lib/rpc";
+import { formatRpcError, formatRpcResponseError } from "@/lib/rpc-error";
+
+export type WorkspaceSecret = {
+  name: string;
+  workspaceId: string;
+  createdAt: string;
+  updatedAt: string;
+};
+
+export type SetWorkspaceSecretInput = {
+  workspaceId: string;
+  name: string;
+  value: string;
+};
+
+export type DeleteWorkspaceSecretInput = {
+  workspaceId: string;
+  name: string;
+};
+
+const ensureSuccessPayload = <T>(data: unknown, fallbackMessage: string): T => {
+  if (data && typeof data === "object" && "message" in data) {
+    throw new Error(formatRpcResponseError(data, fallbackMessage));
+  }
+
+  return data as T;
+};
+
+export const secretQueries = {
+  list: (workspaceId: string) => ({
+    queryKey: ["secrets", workspaceId] as const,
+    queryFn: async (): Promise<WorkspaceSecret[]> => {
+      const { data, error } = await rpc.api.secrets.get({
+        query: { workspaceId },
+      });
+      if (error) {
+        throw new Error(formatRpcError(error, "Failed to load secrets"));
+      }
+      return ensureSuccessPayload<{ secrets: WorkspaceSecret[] }>(
+        data,
+        "Failed to load secrets"
+      ).secrets;
+    },
+  }),
+};
+
+export const secretMutations = {
+  set: {
+    mutationFn: async ({
+      workspaceId,
+      name,
+      value,
+    }: SetWorkspaceSecretInput): Promise<WorkspaceSecret> => {
+      const { data, error } = await rpc.api
+        .secrets({ name })
+        .put({ workspaceId, value });
+      if (error) {
+        throw new Error(formatRpcError(error, "Failed to save secret"));
+      }
+      return ensureSuccessPayload<WorkspaceSecret>(
+        data,
+        "Failed to save secret"
+      );
+    },
+  },
+  delete: {
+    mutationFn: async ({
+      workspaceId,
+      name,
+    }: DeleteWorkspaceSecretInput): Promise<void> => {
+      const { error } = await rpc.api
+        .secrets({ name })
+        .delete(undefined, { query: { workspaceId } });
+      if (error) {
+        throw new Error(formatRpcError(error, "Failed to delete secret"));
+      }
+    },
+  },
+};
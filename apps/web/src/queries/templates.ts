@@ -20,7 +20,16 @@ export type TemplateAgentModel = {
   variant?: string;
 };
 
+export type TemplateParameter = {
+  name: string;
+  type: "string" | "number" | "boolean";
+  default?: string | number | boolean;
+  enum?: Array<string | number>;
+  description?: string;
+};
+
 export type TemplateConfig = {
+  parameters?: TemplateParameter[];
   includePatterns?: string[];
   ignorePatterns?: string[];
   services?: Record<string, TemplateService>;
@@ -42,6 +51,7 @@ export type Template = {
   type: string;
   configJson: TemplateConfig;
   includeDirectories?: string[];
+  fieldOrigins?: Record<string, string>;
 };
 
 export type Defaults = {
@@ -2,6 +2,7 @@ import { useQuery } from "@tanstack/react-query";
 import { createFileRoute, useNavigate } from "@tanstack/react-router";
 import { AlertTriangle, Loader2 } from "lucide-react";
 import { useEffect } from "react";
+import { AgentTurnUndo } from "@/components/agent-turn-undo";
 import { CellTerminal } from "@/components/cell-terminal";
 import { useTheme } from "@/components/theme-provider";
 import { Button } from "@/components/ui/button";
@@ -121,18 +122,21 @@ function CellChat() {
   }
 
   return (
-    <CellTerminal
-      cellId={cellId}
-      connectCommand={cellQuery.data?.opencodeCommand ?? null}
-      endpointBase="chat/terminal"
-      reconnectLabel="Reconnect chat"
-      restartLabel="Restart chat"
-      startupReadiness="terminal-content"
-      startupStatusMessage={startupStatusMessage}
-      startupTextMatch={cellQuery.data?.name ?? null}
-      terminalLineHeight={1}
-      themeMode={themeMode}
-      title="Cell Chat"
-    />
+    <div className="flex h-full min-h-0 flex-1 flex-col gap-3 overflow-hidden">
+      <AgentTurnUndo cellId={cellId} />
+      <CellTerminal
+        cellId={cellId}
+        connectCommand={cellQuery.data?.opencodeCommand ?? null}
+        endpointBase="chat/terminal"
+        reconnectLabel="Reconnect chat"
+        restartLabel="Restart chat"
+        startupReadiness="terminal-content"
+        startupStatusMessage={startupStatusMessage}
+        startupTextMatch={cellQuery.data?.name ?? null}
+        terminalLineHeight={1}
+        themeMode={themeMode}
+        title="Cell Chat"
+      />
+    </div>
   );
 }
@@ -4,6 +4,7 @@ import { Copy } from "lucide-react";
 import type { ReactNode } from "react";
 import { useEffect, useState } from "react";
 import { toast } from "sonner";
+import { CellConfigChanges } from "@/components/cell-config-changes";
 import { PtyStreamTerminal } from "@/components/pty-stream-terminal";
 import { Button } from "@/components/ui/button";
 import {
@@ -145,22 +146,30 @@ function CellServices() {
   };
 
   return (
-    <div className="flex h-full flex-1 overflow-hidden rounded-sm border-2 border-border bg-card">
-      <ServicesPanel
-        cellId={cellId}
-        errorMessage={streamError}
-        isBulkActionPending={isBulkActionPending}
-        isLoading={isLoading}
-        isStartingAll={startAllServicesMutation.isPending}
-        isStoppingAll={stopAllServicesMutation.isPending}
-        onStartAll={handleStartAll}
-        onStartService={handleStart}
-        onStopAll={handleStopAll}
-        onStopService={handleStop}
-        pendingStartId={pendingStartId}
-        pendingStopId={pendingStopId}
-        services={services}
-      />
+    <div className="flex h-full flex-1 flex-col gap-3 overflow-hidden">
+      {cellQuery.data ? (
+        <CellConfigChanges
+          cellId={cellId}
+          workspaceId={cellQuery.data.workspaceId}
+        />
+      ) : null}
+      <div className="flex min-h-0 flex-1 overflow-hidden rounded-sm border-2 border-border bg-card">
+        <ServicesPanel
+          cellId={cellId}
+          errorMessage={streamError}
+          isBulkActionPending={isBulkActionPending}
+          isLoading={isLoading}
+          isStartingAll={startAllServicesMutation.isPending}
+          isStoppingAll={stopAllServicesMutation.isPending}
+          onStartAll={handleStartAll}
+          onStartService={handleStart}
+          onStopAll={handleStopAll}
+          onStopService={handleStop}
+          pendingStartId={pendingStartId}
+          pendingStopId={pendingStopId}
+          services={services}
+        />
+      </div>
     </div>
   );
 }
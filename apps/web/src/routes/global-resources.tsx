@@ -1648,6 +1648,7 @@ function statusClass(status: CellStatus): string {
     spawning: "border-border/60 bg-background/20 text-muted-foreground",
     error: "border-destructive/50 bg-destructive/10 text-destructive",
     deleting: "border-destructive/50 bg-destructive/10 text-destructive",
+    suspended: "border-border/60 bg-background/20 text-muted-foreground",
   };
   return toneMap[status];
 }
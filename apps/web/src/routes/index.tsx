@@ -272,6 +272,7 @@ function StatusBadge({ status }: { status: CellStatus }) {
     spawning: "bg-secondary/20 text-secondary-foreground",
     error: "bg-destructive/10 text-destructive",
     deleting: "bg-destructive/20 text-destructive",
+    suspended: "bg-muted text-muted-foreground",
   };
   return (
     <span
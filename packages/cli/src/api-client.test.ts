@@ -0,0 +1,102 @@
+/// <reference types="vitest" />
+
+import { describe, expect, it, vi } from "vitest";
+
+import {
+  createHiveApiClient,
+  HiveApiError,
+  resolveHiveApiClientConfig,
+} from "./api-client";
+
+const DEFAULT_URL = "http://localhost:3000";
+const HTTP_BAD_REQUEST = 400;
+
+const jsonResponse = (payload: unknown, status = 200) =>
+  new Response(JSON.stringify(payload), {
+    status,
+    headers: { "content-type": "application/json" },
+  });
+
+describe("resolveHiveApiClientConfig", () => {
+  it("falls back to the default URL without a token", () => {
+    expect(resolveHiveApiClientConfig(DEFAULT_URL, {})).toEqual({
+      baseUrl: DEFAULT_URL,
+    });
+  });
+
+  it("reads the server URL and token from the environment", () => {
+    expect(
+      resolveHiveApiClientConfig(DEFAULT_URL, {
+        HIVE_API_URL: "https://hive.example.com",
+        HIVE_TOKEN: " hive_secret ",
+      })
+    ).toEqual({ baseUrl: "https://hive.example.com", token: "hive_secret" });
+  });
+});
+
+describe("createHiveApiClient", () => {
+  it("sends the bearer token and query parameters", async () => {
+    const fetchMock = vi.fn().mockResolvedValue(jsonResponse({ ok: true }));
+    const client = createHiveApiClient({
+      baseUrl: DEFAULT_URL,
+      token: "hive_secret",
+      fetchImpl: fetchMock,
+    });
+
+    await client.json("/api/cells", {
+      query: { workspaceId: "ws-1", name: undefined },
+    });
+
+    const [url, init] = fetchMock.mock.calls[0] as [string, RequestInit];
+    expect(url).toBe(`${DEFAULT_URL}/api/cells?workspaceId=ws-1`);
+    expect(init.method).toBe("GET");
+    expect(init.headers).toMatchObject({ authorization: "Bearer hive_secret" });
+  });
+
+  it("uploads bytes as an octet stream", async () => {
+    const fetchMock = vi.fn().mockResolvedValue(jsonResponse({ id: "cell" }));
+    const client = createHiveApiClient({
+      baseUrl: DEFAULT_URL,
+      fetchImpl: fetchMock,
+    });
+
+    const bytes = new Uint8Array([1, 2, 3]);
+    await client.json("/api/cells/import", { bytes });
+
+    const [, init] = fetchMock.mock.calls[0] as [string, RequestInit];
+    expect(init.method).toBe("POST");
+    expect(init.body).toBe(bytes);
+    expect(init.headers).toEqual({
+      "content-type": "application/octet-stream",
+    });
+  });
+
+  it("surfaces the server's error message", async () => {
+    const client = createHiveApiClient({
+      baseUrl: DEFAULT_URL,
+      fetchImpl: vi
+        .fn()
+        .mockResolvedValue(
+          jsonResponse({ message: "Cell not found" }, HTTP_BAD_REQUEST)
+        ),
+    });
+
+    const failure = await client.json("/api/cells/missing").catch((e) => e);
+    expect(failure).toBeInstanceOf(HiveApiError);
+    expect(failure).toMatchObject({
+      status: HTTP_BAD_REQUEST,
+      message: "Cell not found",
+    });
+  });
+
+  it("explains when the server cannot be reached", async () => {
+    const client = createHiveApiClient({
+      baseUrl: DEFAULT_URL,
+      fetchImpl: vi.fn().mockRejectedValue(new Error("ECONNREFUSED")),
+    });
+
+    await expect(client.bytes("/api/cells/a/export")).rejects.toThrow(
+      `Could not reach Hive at ${DEFAULT_URL}`
+    );
+  });
+});
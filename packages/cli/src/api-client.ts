@@ -0,0 +1,126 @@
+export const API_URL_ENV = "HIVE_API_URL";
+export const API_TOKEN_ENV = "HIVE_TOKEN";
+
+type QueryValue = string | number | boolean | null | undefined;
+
+export type HiveApiClientConfig = {
+  baseUrl: string;
+  token?: string;
+  fetchImpl?: (url: string, init?: RequestInit) => Promise<Response>;
+};
+
+export type HiveApiRequestOptions = {
+  method?: string;
+  query?: Record<string, QueryValue>;
+  json?: unknown;
+  bytes?: Uint8Array;
+  signal?: AbortSignal;
+};
+
+export class HiveApiError extends Error {
+  readonly status: number;
+
+  constructor(status: number, message: string) {
+    super(message);
+    this.name = "HiveApiError";
+    this.status = status;
+  }
+}
+
+/**
+ * Resolves where CLI commands that talk to a running server should connect.
+ * HIVE_API_URL points at a remote server; HIVE_TOKEN is sent as a bearer
+ * token when the server requires one.
+ */
+export const resolveHiveApiClientConfig = (
+  defaultUrl: string,
+  env: NodeJS.ProcessEnv = process.env
+): HiveApiClientConfig => {
+  const token = env[API_TOKEN_ENV]?.trim();
+  return {
+    baseUrl: env[API_URL_ENV]?.trim() || defaultUrl,
+    ...(token ? { token } : {}),
+  };
+};
+
+const buildUrl = (
+  baseUrl: string,
+  path: string,
+  query?: Record<string, QueryValue>
+) => {
+  const url = new URL(path, baseUrl);
+  for (const [key, value] of Object.entries(query ?? {})) {
+    if (value !== undefined && value !== null && value !== "") {
+      url.searchParams.set(key, String(value));
+    }
+  }
+  return url.toString();
+};
+
+const readErrorMessage = async (response: Response) => {
+  const text = await response.text().catch(() => "");
+  try {
+    const payload = JSON.parse(text) as { message?: unknown };
+    if (typeof payload.message === "string" && payload.message) {
+      return payload.message;
+    }
+  } catch {
+    // Not JSON; fall back to the raw body.
+  }
+  return text.trim() || `${response.status} ${response.statusText}`.trim();
+};
+
+export const createHiveApiClient = ({
+  baseUrl,
+  token,
+  fetchImpl = fetch,
+}: HiveApiClientConfig) => {
+  const send = async (path: string, options: HiveApiRequestOptions = {}) => {
+    const headers: Record<string, string> = {};
+    let body: BodyInit | undefined;
+    if (options.bytes) {
+      headers["content-type"] = "application/octet-stream";
+      body = options.bytes;
+    } else if (options.json !== undefined) {
+      headers["content-type"] = "application/json";
+      body = JSON.stringify(options.json);
+    }
+    if (token) {
+      headers.authorization = `Bearer ${token}`;
+    }
+
+    let response: Response;
+    try {
+      response = await fetchImpl(buildUrl(baseUrl, path, options.query), {
+        method: options.method ?? (body ? "POST" : "GET"),
+        headers,
+        ...(body ? { body } : {}),
+        ...(options.signal ? { signal: options.signal } : {}),
+      });
+    } catch (error) {
+      if (options.signal?.aborted) {
+        throw error;
+      }
+      throw new HiveApiError(
+        0,
+        `Could not reach Hive at ${baseUrl}. Is the server running?`
+      );
+    }
+
+    if (!response.ok) {
+      throw new HiveApiError(response.status, await readErrorMessage(response));
+    }
+    return response;
+  };
+
+  return {
+    baseUrl,
+    json: async <T>(path: string, options?: HiveApiRequestOptions) =>
+      (await (await send(path, options)).json()) as T,
+    bytes: async (path: string, options?: HiveApiRequestOptions) =>
+      new Uint8Array(await (await send(path, options)).arrayBuffer()),
+    stream: send,
+  };
+};
+
+export type HiveApiClient = ReturnType<typeof createHiveApiClient>;
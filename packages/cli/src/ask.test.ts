@@ -0,0 +1,201 @@
+/// <reference types="vitest" />
+
+import { describe, expect, it, vi } from "vitest";
+
+import { createHiveApiClient } from "./api-client";
+import {
+  askCommand,
+  createAskRenderer,
+  readServerSentEvents,
+  type ServerSentEvent,
+} from "./ask";
+
+const encoder = new TextEncoder();
+
+const sseChunk = (event: string, data: unknown) =>
+  `event: ${event}\ndata: ${JSON.stringify(data)}\n\n`;
+
+const streamOf = (chunks: string[]) =>
+  new ReadableStream<Uint8Array>({
+    start(controller) {
+      for (const chunk of chunks) {
+        controller.enqueue(encoder.encode(chunk));
+      }
+      controller.close();
+    },
+  });
+
+const collect = async (stream: ReadableStream<Uint8Array>) => {
+  const events: ServerSentEvent[] = [];
+  for await (const event of readServerSentEvents(stream)) {
+    events.push(event);
+  }
+  return events;
+};
+
+const captureOutput = () => {
+  const chunks: string[] = [];
+  return {
+    write: (text: string) => {
+      chunks.push(text);
+    },
+    text: () => chunks.join(""),
+  };
+};
+
+const assistantTurn: ServerSentEvent[] = [
+  { event: "status", data: { status: "working" } },
+  { event: "message", data: { id: "msg-user", role: "user" } },
+  {
+    event: "part",
+    data: { messageId: "msg-user", partId: "p0", type: "text", text: "Hi" },
+  },
+  { event: "message", data: { id: "msg-bot", role: "assistant" } },
+  {
+    event: "part",
+    data: { messageId: "msg-bot", partId: "p1", type: "text", text: "Run" },
+  },
+  {
+    event: "part",
+    data: {
+      messageId: "msg-bot",
+      partId: "p1",
+      type: "text",
+      text: "Running tests",
+    },
+  },
+  {
+    event: "part",
+    data: {
+      messageId: "msg-bot",
+      partId: "p2",
+      type: "tool",
+      tool: "bash",
+      status: "running",
+      title: "bun test",
+    },
+  },
+  {
+    event: "part",
+    data: {
+      messageId: "msg-bot",
+      partId: "p2",
+      type: "tool",
+      tool: "bash",
+      status: "completed",
+      title: "bun test",
+    },
+  },
+  { event: "status", data: { status: "awaiting_input" } },
+];
+
+describe("readServerSentEvents", () => {
+  it("parses events split across chunks", async () => {
+    const payload = `${sseChunk("status", { status: "working" })}${sseChunk(
+      "mode",
+      { currentMode: "build" }
+    )}`;
+    const events = await collect(
+      streamOf([payload.slice(0, 17), payload.slice(17, 40), payload.slice(40)])
+    );
+
+    expect(events).toEqual([
+      { event: "status", data: { status: "working" } },
+      { event: "mode", data: { currentMode: "build" } },
+    ]);
+  });
+});
+
+describe("createAskRenderer", () => {
+  it("prints assistant text and tool progress until the turn ends", () => {
+    const output = captureOutput();
+    const renderer = createAskRenderer(output.write);
+
+    const outcomes = assistantTurn.map((event) => renderer.handle(event));
+
+    expect(outcomes.at(-1)).toBe("completed");
+    expect(outcomes.slice(0, -1).every((outcome) => outcome === null)).toBe(
+      true
+    );
+    expect(output.text()).toBe(
+      "Running tests\n→ bash: bun test\n✓ bash: bun test\n"
+    );
+  });
+
+  it("ignores idle statuses until the prompt reaches the agent", () => {
+    const renderer = createAskRenderer(captureOutput().write);
+    expect(
+      renderer.handle({ event: "status", data: { status: "awaiting_input" } })
+    ).toBeNull();
+  });
+
+  it("stops on errors and input requests", () => {
+    const output = captureOutput();
+    const renderer = createAskRenderer(output.write);
+
+    expect(
+      renderer.handle({
+        event: "status",
+        data: { status: "error", error: "Model overloaded" },
+      })
+    ).toBe("error");
+    expect(
+      renderer.handle({
+        event: "input_required",
+        data: { kind: "permission", title: "plan_exit" },
+      })
+    ).toBe("input_required");
+    expect(output.text()).toContain("Agent error: Model overloaded");
+    expect(output.text()).toContain("waiting for permission: plan_exit");
+  });
+});
+
+describe("askCommand", () => {
+  it("sends the prompt after subscribing and exits with the outcome", async () => {
+    let streamController: ReadableStreamDefaultController<Uint8Array> | null =
+      null;
+    const fetchMock = vi.fn((input: string, init?: RequestInit) => {
+      const { pathname } = new URL(input);
+      if (pathname === "/api/agents/sessions/byCell/cell-1") {
+        return Promise.resolve(
+          Response.json({ session: { id: "s-1", status: "awaiting_input" } })
+        );
+      }
+      if (pathname === "/api/agents/sessions/s-1/events") {
+        const body = new ReadableStream<Uint8Array>({
+          start(controller) {
+            streamController = controller;
+            controller.enqueue(
+              encoder.encode(sseChunk("status", { status: "awaiting_input" }))
+            );
+          },
+        });
+        return Promise.resolve(new Response(body));
+      }
+      if (pathname === "/api/agents/sessions/s-1/messages") {
+        expect(JSON.parse(String(init?.body))).toEqual({ content: "Hi" });
+        for (const event of assistantTurn) {
+          streamController?.enqueue(
+            encoder.encode(sseChunk(event.event, event.data))
+          );
+        }
+        return Promise.resolve(
+          Response.json({ sessionId: "s-1", status: "accepted" })
+        );
+      }
+      return Promise.resolve(new Response("missing", { status: 404 }));
+    });
+    const client = createHiveApiClient({
+      baseUrl: "http://localhost:3000",
+      fetchImpl: fetchMock,
+    });
+
+    const output = captureOutput();
+    const code = await askCommand(client, "cell-1", "Hi", {}, output.write);
+
+    expect(code).toBe(0);
+    expect(output.text()).toContain("Running tests");
+    const eventsUrl = fetchMock.mock.calls[1]?.[0];
+    expect(eventsUrl).toContain("includeParts=true");
+  });
+});
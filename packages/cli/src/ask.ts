@@ -0,0 +1,256 @@
+import type { HiveApiClient } from "./api-client";
+import type { CommandOutput } from "./cell-commands";
+
+export type ServerSentEvent = {
+  event: string;
+  data: unknown;
+};
+
+/** Outcome of an agent turn, mapped to the `hive ask` exit code. */
+export type AskOutcome = "completed" | "error" | "input_required";
+
+export const ASK_EXIT_CODES: Record<AskOutcome, number> = {
+  completed: 0,
+  error: 1,
+  input_required: 2,
+};
+
+type StatusData = { status?: string; error?: string };
+type MessageData = { id?: string; role?: string };
+type PartData = {
+  messageId?: string;
+  partId?: string;
+  type?: string;
+  text?: string;
+  tool?: string;
+  status?: string;
+  title?: string;
+  error?: string;
+};
+type InputRequiredData = { title?: string; kind?: string };
+
+const EVENT_SEPARATOR = /\r?\n\r?\n/;
+const LINE_SEPARATOR = /\r?\n/;
+
+const parseEventBlock = (block: string): ServerSentEvent | null => {
+  let event = "message";
+  const dataLines: string[] = [];
+  for (const line of block.split(LINE_SEPARATOR)) {
+    if (line.startsWith("event:")) {
+      event = line.slice("event:".length).trim();
+    } else if (line.startsWith("data:")) {
+      dataLines.push(line.slice("data:".length).trimStart());
+    }
+  }
+  if (dataLines.length === 0) {
+    return null;
+  }
+  const raw = dataLines.join("\n");
+  try {
+    return { event, data: JSON.parse(raw) };
+  } catch {
+    return { event, data: raw };
+  }
+};
+
+/** Parses a `text/event-stream` body into events as they arrive. */
+export async function* readServerSentEvents(
+  body: ReadableStream<Uint8Array>
+): AsyncGenerator<ServerSentEvent> {
+  const decoder = new TextDecoder();
+  const reader = body.getReader();
+  let buffer = "";
+  try {
+    while (true) {
+      const { done, value } = await reader.read();
+      if (done) {
+        break;
+      }
+      buffer += decoder.decode(value, { stream: true });
+      const blocks = buffer.split(EVENT_SEPARATOR);
+      buffer = blocks.pop() ?? "";
+      for (const block of blocks) {
+        const parsed = parseEventBlock(block);
+        if (parsed) {
+          yield parsed;
+        }
+      }
+    }
+  } finally {
+    reader.releaseLock();
+  }
+}
+
+/**
+ * Turns the agent event stream into terminal output for one prompt. The turn
+ * counts as finished once the prompt shows up as a user message and the
+ * session goes back to waiting for input; errors end it at any point.
+ */
+export const createAskRenderer = (
+  output: CommandOutput,
+  options: { json?: boolean } = {}
+) => {
+  const roles = new Map<string, string>();
+  const printedText = new Map<string, number>();
+  const toolStatuses = new Map<string, string>();
+  let promptSeen = false;
+  let atLineStart = true;
+
+  const write = (text: string) => {
+    if (!text) {
+      return;
+    }
+    output(text);
+    atLineStart = text.endsWith("\n");
+  };
+  const writeLine = (text: string) => {
+    write(`${atLineStart ? "" : "\n"}${text}\n`);
+  };
+
+  const renderPart = (part: PartData) => {
+    if (!(part.messageId && part.partId)) {
+      return;
+    }
+    if (roles.get(part.messageId) !== "assistant") {
+      return;
+    }
+    if (part.type === "text" && typeof part.text === "string") {
+      const printed = printedText.get(part.partId) ?? 0;
+      write(part.text.slice(printed));
+      printedText.set(part.partId, part.text.length);
+      return;
+    }
+    if (part.type === "tool" && part.tool && part.status) {
+      if (toolStatuses.get(part.partId) === part.status) {
+        return;
+      }
+      toolStatuses.set(part.partId, part.status);
+      const label = part.title ? `${part.tool}: ${part.title}` : part.tool;
+      if (part.status === "running") {
+        writeLine(`→ ${label}`);
+      } else if (part.status === "completed") {
+        writeLine(`✓ ${label}`);
+      } else if (part.status === "error") {
+        writeLine(`✗ ${label}${part.error ? ` (${part.error})` : ""}`);
+      }
+    }
+  };
+
+  const handle = (event: ServerSentEvent): AskOutcome | null => {
+    if (options.json) {
+      write(`${JSON.stringify(event)}\n`);
+    }
+
+    if (event.event === "message") {
+      const message = event.data as MessageData;
+      if (message.id && message.role) {
+        roles.set(message.id, message.role);
+        promptSeen ||= message.role === "user";
+      }
+      return null;
+    }
+
+    if (event.event === "part") {
+      if (!options.json) {
+        renderPart(event.data as PartData);
+      }
+      return null;
+    }
+
+    if (event.event === "input_required") {
+      const { title, kind } = event.data as InputRequiredData;
+      if (!options.json) {
+        writeLine(
+          `Agent is waiting for ${kind === "question" ? "an answer" : "permission"}: ${title ?? "input required"}`
+        );
+      }
+      return "input_required";
+    }
+
+    if (event.event === "status") {
+      const { status, error } = event.data as StatusData;
+      if (status === "error") {
+        if (!options.json) {
+          writeLine(`Agent error: ${error ?? "unknown error"}`);
+        }
+        return "error";
+      }
+      const idle = status === "awaiting_input" || status === "completed";
+      if (promptSeen && idle) {
+        if (!(options.json || atLineStart)) {
+          write("\n");
+        }
+        return "completed";
+      }
+    }
+    return null;
+  };
+
+  return { handle };
+};
+
+type AgentSessionPayload = { session: { id: string; status: string } | null };
+
+export const askCommand = async (
+  client: HiveApiClient,
+  cellId: string,
+  prompt: string,
+  options: { json?: boolean; timeoutMs?: number },
+  output: CommandOutput
+): Promise<number> => {
+  const { session } = await client.json<AgentSessionPayload>(
+    `/api/agents/sessions/byCell/${encodeURIComponent(cellId)}`
+  );
+  if (!session) {
+    throw new Error(`Cell ${cellId} has no agent session`);
+  }
+
+  const abortController = new AbortController();
+  const timeout = options.timeoutMs
+    ? setTimeout(() => abortController.abort(), options.timeoutMs)
+    : null;
+  const renderer = createAskRenderer(output, {
+    ...(options.json ? { json: true } : {}),
+  });
+  const sessionPath = `/api/agents/sessions/${encodeURIComponent(session.id)}`;
+
+  try {
+    const response = await client.stream(`${sessionPath}/events`, {
+      query: { includeParts: true },
+      signal: abortController.signal,
+    });
+    if (!response.body) {
+      throw new Error("Agent event stream returned no body");
+    }
+
+    let sent = false;
+    for await (const event of readServerSentEvents(response.body)) {
+      // The first event confirms the subscription, so nothing the prompt
+      // triggers can be missed.
+      if (!sent) {
+        sent = true;
+        await client.json(`${sessionPath}/messages`, {
+          json: { content: prompt },
+        });
+        continue;
+      }
+      const outcome = renderer.handle(event);
+      if (outcome) {
+        return ASK_EXIT_CODES[outcome];
+      }
+    }
+    throw new Error("Agent event stream closed before the reply finished");
+  } catch (error) {
+    if (abortController.signal.aborted) {
+      throw new Error(
+        `Timed out after ${Math.round((options.timeoutMs ?? 0) / 1000)}s waiting for the agent`
+      );
+    }
+    throw error;
+  } finally {
+    if (timeout) {
+      clearTimeout(timeout);
+    }
+    abortController.abort();
+  }
+};
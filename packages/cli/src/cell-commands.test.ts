@@ -0,0 +1,423 @@
+/// <reference types="vitest" />
+
+import { describe, expect, it, vi } from "vitest";
+
+import { createHiveApiClient } from "./api-client";
+import {
+  batchCellsCommand,
+  configCommand,
+  createCellCommand,
+  diffCommand,
+  listCellsCommand,
+  parseSpawnFrom,
+  renderTable,
+  serviceActionCommand,
+  serviceLogsCommand,
+} from "./cell-commands";
+
+const BASE_URL = "http://localhost:3000";
+
+type Route = {
+  method: string;
+  path: string;
+  respond: (url: URL, init?: RequestInit) => unknown;
+};
+
+const createTestClient = (routes: Route[]) => {
+  const fetchMock = vi.fn((input: string, init?: RequestInit) => {
+    const url = new URL(input);
+    const method = init?.method ?? "GET";
+    const route = routes.find(
+      (candidate) =>
+        candidate.method === method && candidate.path === url.pathname
+    );
+    if (!route) {
+      return Promise.resolve(
+        new Response(JSON.stringify({ message: "Not found" }), { status: 404 })
+      );
+    }
+    const result = route.respond(url, init);
+    if (result instanceof Response) {
+      return Promise.resolve(result);
+    }
+    return Promise.resolve(
+      new Response(JSON.stringify(result), { status: 200 })
+    );
+  });
+  return {
+    client: createHiveApiClient({ baseUrl: BASE_URL, fetchImpl: fetchMock }),
+    fetchMock,
+  };
+};
+
+const captureOutput = () => {
+  const chunks: string[] = [];
+  return {
+    write: (text: string) => {
+      chunks.push(text);
+    },
+    text: () => chunks.join(""),
+  };
+};
+
+const buildCell = (overrides: Record<string, unknown> = {}) => ({
+  id: "cell-1",
+  name: "Auth fix",
+  description: null,
+  templateId: "basic",
+  workspaceId: "ws-1",
+  workspacePath: "/tmp/cells/cell-1",
+  status: "ready",
+  createdAt: "2026-01-01T00:00:00.000Z",
+  branchName: "hive/cell-1",
+  ...overrides,
+});
+
+const buildService = (overrides: Record<string, unknown> = {}) => ({
+  id: "svc-1",
+  name: "web",
+  type: "process",
+  status: "running",
+  port: 4100,
+  pid: 42,
+  lastKnownError: null,
+  recentLogs: "listening on 4100\n",
+  ...overrides,
+});
+
+describe("renderTable", () => {
+  it("pads columns to the widest value", () => {
+    const table = renderTable(
+      [
+        { name: "web", status: "running" },
+        { name: "worker-long", status: "stopped" },
+      ],
+      [
+        { header: "NAME", value: (row) => row.name },
+        { header: "STATUS", value: (row) => row.status },
+      ]
+    );
+
+    expect(table.split("\n")).toEqual([
+      "NAME         STATUS",
+      "web          running",
+      "worker-long  stopped",
+    ]);
+  });
+});
+
+describe("parseSpawnFrom", () => {
+  it("maps start points to the create-cell fields", () => {
+    expect(parseSpawnFrom("head")).toEqual({ spawnFromMode: "head" });
+    expect(parseSpawnFrom("branch:feature/login")).toEqual({
+      spawnFromMode: "branch",
+      spawnFromValue: "feature/login",
+    });
+    expect(() => parseSpawnFrom("pr:")).toThrow("Invalid --from");
+  });
+});
+
+describe("cell commands", () => {
+  it("lists cells as a table or JSON", async () => {
+    const { client, fetchMock } = createTestClient([
+      {
+        method: "GET",
+        path: "/api/cells",
+        respond: () => ({ cells: [buildCell()] }),
+      },
+    ]);
+
+    const table = captureOutput();
+    await listCellsCommand(client, { workspace: "ws-1" }, table.write);
+    expect(table.text()).toContain("Auth fix");
+    expect(table.text()).toContain("hive/cell-1");
+    expect(fetchMock.mock.calls[0]?.[0]).toBe(
+      `${BASE_URL}/api/cells?workspaceId=ws-1`
+    );
+
+    const json = captureOutput();
+    await listCellsCommand(client, { json: true }, json.write);
+    expect(JSON.parse(json.text())).toEqual([buildCell()]);
+  });
+
+  it("creates a cell in the active workspace and waits for it", async () => {
+    let polls = 0;
+    let createBody: Record<string, unknown> = {};
+    const { client } = createTestClient([
+      {
+        method: "GET",
+        path: "/api/workspaces",
+        respond: () => ({
+          workspaces: [{ id: "ws-1", label: "One" }],
+          activeWorkspaceId: "ws-1",
+        }),
+      },
+      {
+        method: "POST",
+        path: "/api/cells",
+        respond: (_url, init) => {
+          createBody = JSON.parse(String(init?.body));
+          return buildCell({ status: "spawning" });
+        },
+      },
+      {
+        method: "GET",
+        path: "/api/cells/cell-1",
+        respond: () => {
+          polls += 1;
+          return buildCell({ status: polls > 1 ? "ready" : "spawning" });
+        },
+      },
+    ]);
+
+    const output = captureOutput();
+    const code = await createCellCommand(
+      client,
+      {
+        name: "Auth fix",
+        template: "basic",
+        spawnFrom: "branch:main",
+        wait: true,
+      },
+      output.write
+    );
+
+    expect(code).toBe(0);
+    expect(createBody).toEqual({
+      name: "Auth fix",
+      templateId: "basic",
+      workspaceId: "ws-1",
+      spawnFromMode: "branch",
+      spawnFromValue: "main",
+    });
+    expect(output.text()).toBe("cell-1\tAuth fix\tready\n");
+  }, 10_000);
+});
+
+describe("batchCellsCommand", () => {
+  const batchItem = (name: string, index: number, status: string) => ({
+    index,
+    name,
+    status,
+    cellId: `${name}-id`,
+    promptSent: false,
+    error: status === "failed" ? "Setup failed" : null,
+  });
+  const sseEvent = (event: string, data: unknown) =>
+    `event: ${event}\ndata: ${JSON.stringify(data)}\n\n`;
+
+  it("submits the manifest and follows progress to the end", async () => {
+    let submitted: Record<string, unknown> = {};
+    const running = {
+      id: "batch-1",
+      status: "running",
+      total: 2,
+      counts: { queued: 2, ready: 0, failed: 0 },
+      items: [batchItem("api", 0, "queued"), batchItem("web", 1, "queued")],
+    };
+    const { client } = createTestClient([
+      {
+        method: "POST",
+        path: "/api/cells/batches",
+        respond: (_url, init) => {
+          submitted = JSON.parse(String(init?.body));
+          return running;
+        },
+      },
+      {
+        method: "GET",
+        path: "/api/cells/batches/batch-1/events",
+        respond: () =>
+          new Response(
+            [
+              sseEvent("snapshot", running),
+              sseEvent("item", batchItem("api", 0, "ready")),
+              sseEvent("item", batchItem("web", 1, "failed")),
+              sseEvent("completed", {
+                ...running,
+                status: "completed",
+                counts: { queued: 0, ready: 1, failed: 1 },
+                items: [
+                  batchItem("api", 0, "ready"),
+                  batchItem("web", 1, "failed"),
+                ],
+              }),
+            ].join("")
+          ),
+      },
+    ]);
+
+    const output = captureOutput();
+    const code = await batchCellsCommand(
+      client,
+      { manifest: "- name: api\n", workspace: "ws-1", concurrency: 2 },
+      output.write
+    );
+
+    expect(code).toBe(1);
+    expect(submitted).toEqual({
+      workspaceId: "ws-1",
+      manifest: "- name: api\n",
+      concurrency: 2,
+    });
+    expect(output.text()).toBe(
+      [
+        "Batch batch-1: creating 2 cells",
+        "[1/2] api  ready  api-id",
+        "[2/2] web  failed  Setup failed",
+        "Created 1 of 2 cells; 1 failed",
+        "",
+      ].join("\n")
+    );
+  });
+});
+
+describe("service commands", () => {
+  it("restarts a single service by name", async () => {
+    const { client, fetchMock } = createTestClient([
+      {
+        method: "GET",
+        path: "/api/cells/cell-1/services",
+        respond: () => ({ services: [buildService()] }),
+      },
+      {
+        method: "POST",
+        path: "/api/cells/cell-1/services/svc-1/restart",
+        respond: () => buildService({ pid: 43 }),
+      },
+    ]);
+
+    const output = captureOutput();
+    const code = await serviceActionCommand(
+      client,
+      "restart",
+      "cell-1",
+      { service: "web" },
+      output.write
+    );
+
+    expect(code).toBe(0);
+    expect(output.text()).toContain("43");
+    expect(fetchMock).toHaveBeenCalledTimes(2);
+  });
+
+  it("prints service logs and rejects unknown services", async () => {
+    const { client } = createTestClient([
+      {
+        method: "GET",
+        path: "/api/cells/cell-1/services",
+        respond: () => ({ services: [buildService()] }),
+      },
+    ]);
+
+    const output = captureOutput();
+    await serviceLogsCommand(
+      client,
+      "cell-1",
+      { service: "web", lines: 50 },
+      output.write
+    );
+    expect(output.text()).toBe("listening on 4100\n");
+
+    await expect(
+      serviceLogsCommand(client, "cell-1", { service: "api" }, output.write)
+    ).rejects.toThrow('No service "api" in this cell (services: web)');
+  });
+});
+
+describe("diffCommand", () => {
+  it("summarises changed files and prints patches on request", async () => {
+    const files = [
+      { path: "src/a.ts", status: "modified", additions: 3, deletions: 1 },
+      { path: "src/b.ts", status: "added", additions: 10, deletions: 0 },
+    ];
+    const { client } = createTestClient([
+      {
+        method: "GET",
+        path: "/api/cells/cell-1/diff",
+        respond: (url) =>
+          url.searchParams.get("files")
+            ? {
+                mode: "workspace",
+                files: [],
+                details: files.map((file) => ({
+                  ...file,
+                  patch: `--- ${file.path}\n`,
+                })),
+              }
+            : { mode: "workspace", files },
+      },
+    ]);
+
+    const stat = captureOutput();
+    await diffCommand(client, "cell-1", {}, stat.write);
+    expect(stat.text()).toContain("src/b.ts");
+    expect(stat.text()).toContain(
+      "2 files changed, 13 insertions(+), 1 deletions(-)"
+    );
+
+    const patch = captureOutput();
+    await diffCommand(client, "cell-1", { patch: true }, patch.write);
+    expect(patch.text()).toBe("--- src/a.ts\n--- src/b.ts\n");
+  });
+});
+
+describe("configCommand", () => {
+  const impact = {
+    cellId: "cell-1",
+    cellName: "Login fix",
+    services: [
+      {
+        serviceId: "svc-web",
+        name: "web",
+        change: "modified",
+        fields: [
+          { path: "env.LOG_LEVEL", after: "debug" },
+          { path: "run", before: "bun run dev", after: "bun run dev --watch" },
+        ],
+      },
+      { serviceId: null, name: "docs", change: "added", fields: [] },
+    ],
+  };
+
+  it("previews changed services with their fields", async () => {
+    const { client } = createTestClient([
+      {
+        method: "GET",
+        path: "/api/cells/cell-1/config",
+        respond: () => impact,
+      },
+    ]);
+
+    const output = captureOutput();
+    await configCommand(client, "cell-1", {}, output.write);
+    expect(output.text()).toBe(
+      [
+        "web  modified",
+        "  env.LOG_LEVEL: (unset) -> debug",
+        "  run: bun run dev -> bun run dev --watch",
+        "docs  added",
+        "",
+        "Run with --apply to restart the changed services.",
+        "",
+      ].join("\n")
+    );
+  });
+
+  it("applies the config with --apply", async () => {
+    const { client, fetchMock } = createTestClient([
+      {
+        method: "POST",
+        path: "/api/cells/cell-1/config/apply",
+        respond: () => ({ applied: impact, services: [] }),
+      },
+    ]);
+
+    const output = captureOutput();
+    await configCommand(client, "cell-1", { apply: true }, output.write);
+    expect(fetchMock).toHaveBeenCalledTimes(1);
+    expect(output.text()).toContain(
+      "Applied config to Login fix:\nweb  modified"
+    );
+  });
+});
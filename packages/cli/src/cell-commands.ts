@@ -0,0 +1,616 @@
+import type { HiveApiClient } from "./api-client";
+import { readServerSentEvents } from "./ask";
+
+/**
+ * Headless cell, service and diff commands. Everything here talks to a
+ * running Hive server through the REST API, so these commands work the same
+ * against a local daemon and a remote one.
+ */
+
+export type CellSummary = {
+  id: string;
+  name: string;
+  description: string | null;
+  templateId: string;
+  workspaceId: string;
+  workspacePath: string;
+  status: string;
+  createdAt: string;
+  branchName?: string;
+  lastSetupError?: string;
+};
+
+export type ServiceSummary = {
+  id: string;
+  name: string;
+  type: string;
+  status: string;
+  port?: number;
+  url?: string;
+  pid?: number;
+  lastKnownError: string | null;
+  recentLogs: string | null;
+};
+
+type DiffFile = {
+  path: string;
+  status: "modified" | "added" | "deleted";
+  additions: number;
+  deletions: number;
+};
+
+type DiffPayload = {
+  mode: "workspace" | "branch";
+  baseCommit?: string | null;
+  headCommit?: string | null;
+  files: DiffFile[];
+  details?: Array<DiffFile & { patch?: string }>;
+};
+
+type ConfigFieldChange = {
+  path: string;
+  before?: unknown;
+  after?: unknown;
+};
+
+export type CellConfigImpact = {
+  cellId: string;
+  cellName: string;
+  services: Array<{
+    serviceId: string | null;
+    name: string;
+    change: "added" | "removed" | "modified";
+    fields: ConfigFieldChange[];
+  }>;
+};
+
+type CellBatchItem = {
+  index: number;
+  name: string;
+  status: string;
+  cellId: string | null;
+  promptSent: boolean;
+  error: string | null;
+};
+
+type CellBatchPayload = {
+  id: string;
+  status: "running" | "completed";
+  total: number;
+  counts: Record<string, number>;
+  items: CellBatchItem[];
+};
+
+type WorkspaceListPayload = {
+  workspaces: Array<{ id: string; label: string }>;
+  activeWorkspaceId?: string | null;
+};
+
+export type CommandOutput = (text: string) => void;
+
+export type TableColumn<T> = {
+  header: string;
+  value: (row: T) => string;
+};
+
+export const SERVICE_ACTIONS = ["start", "stop", "restart"] as const;
+export type ServiceAction = (typeof SERVICE_ACTIONS)[number];
+
+const PENDING_CELL_STATUSES = new Set(["pending", "spawning"]);
+const CELL_POLL_INTERVAL_MS = 1000;
+const CELL_WAIT_TIMEOUT_MS = 10 * 60 * 1000;
+
+const sleep = (ms: number) =>
+  new Promise<void>((resolvePromise) => {
+    setTimeout(resolvePromise, ms);
+  });
+
+/** Renders rows as left-aligned columns separated by two spaces. */
+export const renderTable = <T>(rows: T[], columns: TableColumn<T>[]) => {
+  const cells = rows.map((row) => columns.map((column) => column.value(row)));
+  const widths = columns.map((column, index) =>
+    Math.max(
+      column.header.length,
+      ...cells.map((row) => row[index]?.length ?? 0)
+    )
+  );
+  const formatRow = (values: string[]) =>
+    values
+      .map((value, index) => value.padEnd(widths[index] ?? 0))
+      .join("  ")
+      .trimEnd();
+  return [
+    formatRow(columns.map((column) => column.header)),
+    ...cells.map(formatRow),
+  ].join("\n");
+};
+
+export const writeJson = (output: CommandOutput, value: unknown) =>
+  output(`${JSON.stringify(value, null, 2)}\n`);
+
+const cellPath = (cellId: string, suffix = "") =>
+  `/api/cells/${encodeURIComponent(cellId)}${suffix}`;
+
+const CELL_COLUMNS: TableColumn<CellSummary>[] = [
+  { header: "ID", value: (cell) => cell.id },
+  { header: "NAME", value: (cell) => cell.name },
+  { header: "TEMPLATE", value: (cell) => cell.templateId },
+  { header: "STATUS", value: (cell) => cell.status },
+  { header: "BRANCH", value: (cell) => cell.branchName ?? "-" },
+  { header: "CREATED", value: (cell) => cell.createdAt },
+];
+
+const SERVICE_COLUMNS: TableColumn<ServiceSummary>[] = [
+  { header: "NAME", value: (service) => service.name },
+  { header: "STATUS", value: (service) => service.status },
+  { header: "PORT", value: (service) => String(service.port ?? "-") },
+  { header: "PID", value: (service) => String(service.pid ?? "-") },
+  { header: "URL", value: (service) => service.url ?? "-" },
+];
+
+const DIFF_COLUMNS: TableColumn<DiffFile>[] = [
+  { header: "STATUS", value: (file) => file.status },
+  { header: "+", value: (file) => String(file.additions) },
+  { header: "-", value: (file) => String(file.deletions) },
+  { header: "PATH", value: (file) => file.path },
+];
+
+/**
+ * Uses the explicit workspace when given, otherwise the server's active
+ * workspace, otherwise the only registered one.
+ */
+export const resolveWorkspaceId = async (
+  client: HiveApiClient,
+  explicit?: string
+) => {
+  if (explicit) {
+    return explicit;
+  }
+  const { workspaces, activeWorkspaceId } =
+    await client.json<WorkspaceListPayload>("/api/workspaces");
+  if (activeWorkspaceId) {
+    return activeWorkspaceId;
+  }
+  const [only] = workspaces;
+  if (workspaces.length === 1 && only) {
+    return only.id;
+  }
+  throw new Error(
+    workspaces.length === 0
+      ? "No workspaces are registered. Add one in the Hive UI first."
+      : "Several workspaces are registered; pick one with --workspace."
+  );
+};
+
+export const listCellsCommand = async (
+  client: HiveApiClient,
+  options: { workspace?: string; json?: boolean },
+  output: CommandOutput
+) => {
+  const { cells } = await client.json<{ cells: CellSummary[] }>(
+    "/api/cells",
+    { query: { workspaceId: options.workspace } }
+  );
+  if (options.json) {
+    writeJson(output, cells);
+    return 0;
+  }
+  output(
+    cells.length === 0
+      ? "No cells.\n"
+      : `${renderTable(cells, CELL_COLUMNS)}\n`
+  );
+  return 0;
+};
+
+export const waitForCellReady = async (
+  client: HiveApiClient,
+  cellId: string,
+  options: { intervalMs?: number; timeoutMs?: number } = {}
+): Promise<CellSummary> => {
+  const deadline = Date.now() + (options.timeoutMs ?? CELL_WAIT_TIMEOUT_MS);
+  while (true) {
+    const cell = await client.json<CellSummary>(cellPath(cellId), {
+      query: { includeSetupLog: false },
+    });
+    if (!PENDING_CELL_STATUSES.has(cell.status)) {
+      return cell;
+    }
+    if (Date.now() >= deadline) {
+      throw new Error(`Timed out waiting for cell ${cellId} to be ready`);
+    }
+    await sleep(options.intervalMs ?? CELL_POLL_INTERVAL_MS);
+  }
+};
+
+export const createCellCommand = async (
+  client: HiveApiClient,
+  options: {
+    name: string;
+    template: string;
+    workspace?: string;
+    description?: string;
+    startMode?: string;
+    spawnFrom?: string;
+    wait?: boolean;
+    json?: boolean;
+  },
+  output: CommandOutput
+) => {
+  const workspaceId = await resolveWorkspaceId(client, options.workspace);
+  const spawnFrom = options.spawnFrom ? parseSpawnFrom(options.spawnFrom) : {};
+  let cell = await client.json<CellSummary>("/api/cells", {
+    json: {
+      name: options.name,
+      templateId: options.template,
+      workspaceId,
+      ...(options.description ? { description: options.description } : {}),
+      ...(options.startMode ? { startMode: options.startMode } : {}),
+      ...spawnFrom,
+    },
+  });
+  if (options.wait) {
+    cell = await waitForCellReady(client, cell.id);
+  }
+
+  if (options.json) {
+    writeJson(output, cell);
+  } else {
+    output(`${cell.id}\t${cell.name}\t${cell.status}\n`);
+  }
+  return cell.status === "error" ? 1 : 0;
+};
+
+/**
+ * Accepts `branch:<name>`, `pr:<number>`, `cell:<id>` or `head`, matching
+ * the start points offered when creating a cell in the UI.
+ */
+const formatBatchItem = (item: CellBatchItem, batch: CellBatchPayload) => {
+  const done = (batch.counts.ready ?? 0) + (batch.counts.failed ?? 0);
+  const detail = item.error ?? item.cellId ?? "";
+  return `[${done}/${batch.total}] ${item.name}  ${item.status}${
+    detail ? `  ${detail}` : ""
+  }\n`;
+};
+
+/**
+ * Submits a manifest and follows the batch until every cell is ready or has
+ * failed. Exits non-zero when any cell failed.
+ */
+export const batchCellsCommand = async (
+  client: HiveApiClient,
+  options: {
+    manifest: string;
+    workspace?: string;
+    concurrency?: number;
+    json?: boolean;
+  },
+  output: CommandOutput
+) => {
+  const workspaceId = await resolveWorkspaceId(client, options.workspace);
+  let batch = await client.json<CellBatchPayload>("/api/cells/batches", {
+    json: {
+      workspaceId,
+      manifest: options.manifest,
+      ...(options.concurrency ? { concurrency: options.concurrency } : {}),
+    },
+  });
+  if (!options.json) {
+    output(`Batch ${batch.id}: creating ${batch.total} cells\n`);
+  }
+
+  const response = await client.stream(
+    `/api/cells/batches/${encodeURIComponent(batch.id)}/events`
+  );
+  if (!response.body) {
+    throw new Error("Batch event stream returned no body");
+  }
+
+  for await (const event of readServerSentEvents(response.body)) {
+    if (options.json) {
+      output(`${JSON.stringify(event)}\n`);
+    }
+    if (event.event === "snapshot" || event.event === "completed") {
+      batch = event.data as CellBatchPayload;
+    } else if (event.event === "item") {
+      const item = event.data as CellBatchItem;
+      const previous = batch.items[item.index];
+      batch.items[item.index] = item;
+      if (previous) {
+        batch.counts[previous.status] =
+          (batch.counts[previous.status] ?? 0) - 1;
+      }
+      batch.counts[item.status] = (batch.counts[item.status] ?? 0) + 1;
+      if (!options.json) {
+        output(formatBatchItem(item, batch));
+      }
+    }
+    if (event.event === "completed") {
+      break;
+    }
+  }
+
+  const failed = batch.counts.failed ?? 0;
+  if (!options.json) {
+    output(
+      batch.status === "completed"
+        ? `Created ${batch.total - failed} of ${batch.total} cells${
+            failed ? `; ${failed} failed` : ""
+          }\n`
+        : "Batch event stream closed before the batch finished\n"
+    );
+  }
+  return failed > 0 || batch.status !== "completed" ? 1 : 0;
+};
+
+export const parseSpawnFrom = (value: string) => {
+  const separator = value.indexOf(":");
+  const mode = separator === -1 ? value : value.slice(0, separator);
+  const target = separator === -1 ? "" : value.slice(separator + 1).trim();
+  if (mode === "head" && !target) {
+    return { spawnFromMode: "head" as const };
+  }
+  if ((mode === "branch" || mode === "pr" || mode === "cell") && target) {
+    return { spawnFromMode: mode, spawnFromValue: target };
+  }
+  throw new Error(
+    `Invalid --from "${value}". Use head, branch:<name>, pr:<number> or cell:<id>.`
+  );
+};
+
+export const showCellCommand = async (
+  client: HiveApiClient,
+  cellId: string,
+  options: { json?: boolean },
+  output: CommandOutput
+) => {
+  const cell = await client.json<CellSummary>(cellPath(cellId), {
+    query: { includeSetupLog: false },
+  });
+  const { services } = await client.json<{ services: ServiceSummary[] }>(
+    cellPath(cellId, "/services"),
+    { query: { logLines: 1 } }
+  );
+  if (options.json) {
+    writeJson(output, { ...cell, services });
+    return 0;
+  }
+
+  const fields: [string, string][] = [
+    ["ID", cell.id],
+    ["Name", cell.name],
+    ["Status", cell.status],
+    ["Template", cell.templateId],
+    ["Workspace", cell.workspaceId],
+    ["Path", cell.workspacePath],
+    ["Branch", cell.branchName ?? "-"],
+    ["Created", cell.createdAt],
+  ];
+  if (cell.description) {
+    fields.push(["Description", cell.description]);
+  }
+  if (cell.lastSetupError) {
+    fields.push(["Setup error", cell.lastSetupError]);
+  }
+  const labelWidth = Math.max(...fields.map(([label]) => label.length));
+  const lines = fields.map(
+    ([label, value]) => `${`${label}:`.padEnd(labelWidth + 2)}${value}`
+  );
+  lines.push(
+    "",
+    services.length === 0
+      ? "No services."
+      : renderTable(services, SERVICE_COLUMNS)
+  );
+  output(`${lines.join("\n")}\n`);
+  return 0;
+};
+
+export const deleteCellCommand = async (
+  client: HiveApiClient,
+  cellId: string,
+  options: { json?: boolean },
+  output: CommandOutput
+) => {
+  const result = await client.json<{ message: string }>(cellPath(cellId), {
+    method: "DELETE",
+  });
+  if (options.json) {
+    writeJson(output, { id: cellId, ...result });
+  } else {
+    output(`Deleted cell ${cellId}\n`);
+  }
+  return 0;
+};
+
+const findService = (services: ServiceSummary[], nameOrId: string) => {
+  const service = services.find(
+    (candidate) => candidate.name === nameOrId || candidate.id === nameOrId
+  );
+  if (!service) {
+    const names = services.map((candidate) => candidate.name).join(", ");
+    throw new Error(
+      `No service "${nameOrId}" in this cell${names ? ` (services: ${names})` : ""}`
+    );
+  }
+  return service;
+};
+
+export const serviceActionCommand = async (
+  client: HiveApiClient,
+  action: ServiceAction,
+  cellId: string,
+  options: { service?: string; json?: boolean },
+  output: CommandOutput
+) => {
+  let services: ServiceSummary[];
+  if (options.service) {
+    const { services: current } = await client.json<{
+      services: ServiceSummary[];
+    }>(cellPath(cellId, "/services"), { query: { logLines: 1 } });
+    const target = findService(current, options.service);
+    services = [
+      await client.json<ServiceSummary>(
+        cellPath(
+          cellId,
+          `/services/${encodeURIComponent(target.id)}/${action}`
+        ),
+        { method: "POST" }
+      ),
+    ];
+  } else {
+    ({ services } = await client.json<{ services: ServiceSummary[] }>(
+      cellPath(cellId, `/services/${action}`),
+      { method: "POST" }
+    ));
+  }
+
+  if (options.json) {
+    writeJson(output, services);
+  } else {
+    output(
+      services.length === 0
+        ? "No services.\n"
+        : `${renderTable(services, SERVICE_COLUMNS)}\n`
+    );
+  }
+  const failed = services.some((service) => service.status === "error");
+  return failed ? 1 : 0;
+};
+
+export const serviceLogsCommand = async (
+  client: HiveApiClient,
+  cellId: string,
+  options: { service: string; lines?: number; json?: boolean },
+  output: CommandOutput
+) => {
+  const { services } = await client.json<{ services: ServiceSummary[] }>(
+    cellPath(cellId, "/services"),
+    { query: { logLines: options.lines } }
+  );
+  const service = findService(services, options.service);
+  if (options.json) {
+    writeJson(output, {
+      id: service.id,
+      name: service.name,
+      status: service.status,
+      logs: service.recentLogs,
+    });
+    return 0;
+  }
+  if (service.recentLogs) {
+    output(
+      service.recentLogs.endsWith("\n")
+        ? service.recentLogs
+        : `${service.recentLogs}\n`
+    );
+  }
+  return 0;
+};
+
+export const diffCommand = async (
+  client: HiveApiClient,
+  cellId: string,
+  options: { mode?: string; patch?: boolean; json?: boolean },
+  output: CommandOutput
+) => {
+  const query = { mode: options.mode };
+  const summary = await client.json<DiffPayload>(cellPath(cellId, "/diff"), {
+    query,
+  });
+  const details =
+    options.patch && summary.files.length > 0
+      ? ((
+          await client.json<DiffPayload>(cellPath(cellId, "/diff"), {
+            query: {
+              ...query,
+              summary: "none",
+              files: summary.files.map((file) => file.path).join(","),
+            },
+          })
+        ).details ?? [])
+      : undefined;
+
+  if (options.json) {
+    writeJson(output, { ...summary, ...(details ? { details } : {}) });
+    return 0;
+  }
+  if (summary.files.length === 0) {
+    output("No changes.\n");
+    return 0;
+  }
+  if (details) {
+    const patches = details
+      .map((file) => file.patch?.trimEnd())
+      .filter((patch): patch is string => Boolean(patch));
+    output(`${patches.join("\n")}\n`);
+    return 0;
+  }
+
+  const additions = summary.files.reduce(
+    (sum, file) => sum + file.additions,
+    0
+  );
+  const deletions = summary.files.reduce(
+    (sum, file) => sum + file.deletions,
+    0
+  );
+  output(
+    `${renderTable(summary.files, DIFF_COLUMNS)}\n\n${summary.files.length} files changed, ${additions} insertions(+), ${deletions} deletions(-)\n`
+  );
+  return 0;
+};
+
+const formatConfigValue = (value: unknown) => {
+  if (value === undefined) {
+    return "(unset)";
+  }
+  return typeof value === "string" ? value : JSON.stringify(value);
+};
+
+/** One line per changed service, followed by its changed fields. */
+export const formatConfigImpact = (impact: CellConfigImpact) =>
+  impact.services
+    .flatMap((service) => [
+      `${service.name}  ${service.change}`,
+      ...service.fields.map(
+        (field) =>
+          `  ${field.path}: ${formatConfigValue(field.before)} -> ${formatConfigValue(field.after)}`
+      ),
+    ])
+    .join("\n");
+
+/**
+ * Shows how the workspace config differs from what the cell's services run
+ * with; `apply` restarts the changed services to pick it up.
+ */
+export const configCommand = async (
+  client: HiveApiClient,
+  cellId: string,
+  options: { apply?: boolean; json?: boolean },
+  output: CommandOutput
+) => {
+  const impact = options.apply
+    ? (
+        await client.json<{ applied: CellConfigImpact }>(
+          cellPath(cellId, "/config/apply"),
+          { method: "POST" }
+        )
+      ).applied
+    : await client.json<CellConfigImpact>(cellPath(cellId, "/config"));
+
+  if (options.json) {
+    writeJson(output, impact);
+    return 0;
+  }
+  if (impact.services.length === 0) {
+    output("Services match the current config.\n");
+    return 0;
+  }
+  output(
+    options.apply
+      ? `Applied config to ${impact.cellName}:\n${formatConfigImpact(impact)}\n`
+      : `${formatConfigImpact(impact)}\n\nRun with --apply to restart the changed services.\n`
+  );
+  return 0;
+};
@@ -16,16 +16,45 @@ import { basename, dirname, join } from "node:path";
 import { createInterface } from "node:readline/promises";
 
 import {
+  API_TOKEN_SCOPES,
+  type ApiTokenScope,
   binaryDirectory,
   cleanupPidFile,
+  createApiToken,
+  createUser,
+  DatabaseService,
   DEFAULT_API_URL,
   DEFAULT_WEB_URL,
+  deleteUser,
+  findUser,
+  listApiTokens,
+  listUsers,
   pidFilePath,
+  revokeApiToken,
+  runMigrations,
   startServer,
 } from "@hive/server";
 import { Builtins, Cli, Command, Option } from "clipanion";
 import pc from "picocolors";
 
+import { askCommand } from "./ask";
+import {
+  createHiveApiClient,
+  type HiveApiClient,
+  resolveHiveApiClientConfig,
+} from "./api-client";
+import {
+  batchCellsCommand,
+  configCommand,
+  createCellCommand,
+  deleteCellCommand,
+  diffCommand,
+  listCellsCommand,
+  type ServiceAction,
+  serviceActionCommand,
+  serviceLogsCommand,
+  showCellCommand,
+} from "./cell-commands";
 import {
   buildCompletionCommandModel,
   COMPLETION_SHELLS,
@@ -41,6 +70,11 @@ import {
   type WaitForServerReadyConfig,
   waitForServerReady,
 } from "./runtime-utils";
+import {
+  deleteSecretCommand,
+  listSecretsCommand,
+  setSecretCommand,
+} from "./secret-commands";
 import { uninstallHive } from "./uninstall";
 import {
   resolveUninstallConfirmation,
@@ -1464,6 +1498,229 @@ const completionsInstallCommand = (
   return 0;
 };
 
+const isApiTokenScope = (value: string): value is ApiTokenScope =>
+  (API_TOKEN_SCOPES as readonly string[]).includes(value);
+
+const buildLoginUrl = (token: string) => {
+  const url = new URL("/api/auth/login", DEFAULT_API_URL);
+  url.searchParams.set("token", token);
+  url.searchParams.set("redirect", DEFAULT_WEB_URL);
+  return url.toString();
+};
+
+const tokensCreateCommand = async (
+  name: string,
+  scope: string,
+  username?: string
+) => {
+  if (!isApiTokenScope(scope)) {
+    logError(
+      `Unknown scope "${scope}". Supported scopes: ${API_TOKEN_SCOPES.join(", ")}`
+    );
+    return 1;
+  }
+
+  await runMigrations();
+  const user = username ? await findUser(DatabaseService.db, username) : null;
+  if (username && !user) {
+    logError(
+      `No user named "${username}". Create one with \`hive users create\`.`
+    );
+    return 1;
+  }
+
+  const { token, record } = await createApiToken(DatabaseService.db, {
+    name,
+    scope,
+    userId: user?.id ?? null,
+  });
+
+  printSummary("API token created", [
+    ["Name", record.name],
+    ["Scope", record.scope],
+    ["User", user?.username ?? pc.dim("none (service token)")],
+    ["Prefix", record.tokenPrefix],
+  ]);
+  process.stdout.write(`\n${pc.bold(token)}\n\n`);
+  logWarning("This token is shown only once. Store it somewhere safe.");
+  logInfo(`Sign in to the UI with: ${buildLoginUrl(token)}`);
+  return 0;
+};
+
+const tokensListCommand = async () => {
+  await runMigrations();
+  const tokens = await listApiTokens(DatabaseService.db);
+  if (tokens.length === 0) {
+    logInfo("No API tokens. Create one with `hive tokens create <name>`.");
+    return 0;
+  }
+
+  for (const token of tokens) {
+    const status = token.revokedAt
+      ? pc.red("revoked")
+      : pc.green(
+          token.lastUsedAt
+            ? `last used ${token.lastUsedAt.toISOString()}`
+            : "never used"
+        );
+    process.stdout.write(
+      `${token.tokenPrefix}…  ${pc.bold(token.name)}  ${pc.dim(token.scope)}  ${status}\n`
+    );
+  }
+  return 0;
+};
+
+const tokensRevokeCommand = async (idOrPrefix: string) => {
+  await runMigrations();
+  const revoked = await revokeApiToken(DatabaseService.db, idOrPrefix);
+  if (!revoked) {
+    logError(`No active token matches "${idOrPrefix}".`);
+    return 1;
+  }
+
+  logSuccess(`Revoked token "${revoked.name}" (${revoked.tokenPrefix}…).`);
+  return 0;
+};
+
+const usersCreateCommand = async (username: string, displayName?: string) => {
+  await runMigrations();
+  const user = await createUser(DatabaseService.db, {
+    username,
+    displayName: displayName ?? null,
+  });
+  logSuccess(`Created user "${user.username}".`);
+  logInfo(
+    `Give them a token with \`hive tokens create <name> --user ${user.username}\`.`
+  );
+  return 0;
+};
+
+const usersListCommand = async () => {
+  await runMigrations();
+  const users = await listUsers(DatabaseService.db);
+  if (users.length === 0) {
+    logInfo("No users. Create one with `hive users create <username>`.");
+    return 0;
+  }
+
+  for (const user of users) {
+    const label = user.displayName ? `  ${pc.dim(user.displayName)}` : "";
+    process.stdout.write(
+      `${pc.bold(user.username)}${label}  ${pc.dim(user.id)}\n`
+    );
+  }
+  return 0;
+};
+
+const usersDeleteCommand = async (idOrUsername: string) => {
+  await runMigrations();
+  const deleted = await deleteUser(DatabaseService.db, idOrUsername);
+  if (!deleted) {
+    logError(`No user matches "${idOrUsername}".`);
+    return 1;
+  }
+
+  logSuccess(
+    `Deleted user "${deleted.username}" and revoked their tokens. Their cells are now only visible to admins.`
+  );
+  return 0;
+};
+
+const createApiClient = (): HiveApiClient =>
+  createHiveApiClient(resolveHiveApiClientConfig(DEFAULT_API_URL));
+
+const writeStdout = (text: string) => {
+  process.stdout.write(text);
+};
+
+const parsePositiveInteger = (flag: string, value?: string) => {
+  if (value === undefined) {
+    return;
+  }
+  const parsed = Number.parseInt(value, 10);
+  if (!Number.isInteger(parsed) || parsed < 1) {
+    throw new Error(`${flag} must be a positive integer, got "${value}"`);
+  }
+  return parsed;
+};
+
+const readStdinBytes = async () => {
+  const chunks: Buffer[] = [];
+  for await (const chunk of process.stdin) {
+    chunks.push(Buffer.from(chunk));
+  }
+  return new Uint8Array(Buffer.concat(chunks));
+};
+
+type ImportedCellSummary = {
+  id: string;
+  name: string;
+  workspacePath: string;
+  branchName: string | null;
+  services: number;
+  activityEvents: number;
+  transcriptMessages: number;
+  transcriptRestored: boolean;
+};
+
+const cellExportCommand = async (cellId: string, output?: string) => {
+  if (!output && process.stdout.isTTY) {
+    logError(
+      "Refusing to write a tar archive to the terminal. Redirect stdout or pass --output."
+    );
+    return 1;
+  }
+
+  const archive = await createApiClient().bytes(
+    `/api/cells/${encodeURIComponent(cellId)}/export`
+  );
+  if (!output) {
+    process.stdout.write(archive);
+    return 0;
+  }
+
+  writeFileSync(output, archive);
+  logSuccess(`Exported cell ${cellId} to ${output}`);
+  return 0;
+};
+
+const cellImportCommand = async (options: {
+  file?: string;
+  workspace?: string;
+  name?: string;
+}) => {
+  const archive = options.file
+    ? new Uint8Array(readFileSync(options.file))
+    : await readStdinBytes();
+  if (archive.byteLength === 0) {
+    logError("No archive provided. Pass a file or pipe one on stdin.");
+    return 1;
+  }
+
+  const imported = await createApiClient().json<ImportedCellSummary>(
+    "/api/cells/import",
+    {
+      bytes: archive,
+      query: { workspaceId: options.workspace, name: options.name },
+    }
+  );
+
+  printSummary("Cell imported", [
+    ["Name", imported.name],
+    ["ID", imported.id],
+    ["Path", imported.workspacePath],
+    ["Branch", imported.branchName ?? pc.dim("none")],
+    ["Services", `${imported.services} (stopped)`],
+    ["Activity events", String(imported.activityEvents)],
+  ]);
+  if (imported.transcriptMessages > 0 && !imported.transcriptRestored) {
+    logWarning(
+      "The agent transcript could not be loaded into a new session; the cell's files were imported."
+    );
+  }
+  return 0;
+};
+
 const runCommand = async (
   operation: () => number | Promise<number>,
   label: string
@@ -1675,6 +1932,831 @@ class CompletionsInstallCommand extends Command {
   }
 }
 
+class TokensCreateCommand extends Command {
+  static override paths = [["tokens", "create"]];
+  static override usage = Command.Usage({
+    category: "Security",
+    description: "Create an API token for the HTTP and WebSocket API.",
+    details:
+      "Tokens are required when Hive listens on a non-loopback address or HIVE_AUTH=required is set. Scopes are read (view cells and streams), operate (create cells, run services, use terminals) and admin (also manage tokens and workspaces). The token is printed once.",
+    examples: [
+      ["Create an admin token", "hive tokens create laptop --scope admin"],
+      ["Create a read-only token", "hive tokens create dashboard --scope read"],
+      ["Create a token for a user", "hive tokens create ada-laptop --user ada"],
+    ],
+  });
+
+  name = Option.String({
+    name: "name",
+    required: true,
+  });
+
+  scope = Option.String("--scope", "operate", {
+    description: `Token scope (${API_TOKEN_SCOPES.join(", ")})`,
+  });
+
+  user = Option.String("--user", {
+    description: "Username the token acts as (omit for a service token)",
+  });
+
+  override execute() {
+    return runCommand(
+      () => tokensCreateCommand(this.name, this.scope, this.user),
+      "tokens create"
+    );
+  }
+}
+
+class TokensListCommand extends Command {
+  static override paths = [["tokens", "list"], ["tokens"]];
+  static override usage = Command.Usage({
+    category: "Security",
+    description: "List API tokens and when they were last used.",
+    examples: [["List tokens", "hive tokens list"]],
+  });
+
+  override execute() {
+    return runCommand(() => tokensListCommand(), "tokens list");
+  }
+}
+
+class TokensRevokeCommand extends Command {
+  static override paths = [["tokens", "revoke"]];
+  static override usage = Command.Usage({
+    category: "Security",
+    description: "Revoke an API token by id or prefix.",
+    examples: [["Revoke a token", "hive tokens revoke hive_AbCdEfGh"]],
+  });
+
+  token = Option.String({
+    name: "token",
+    required: true,
+  });
+
+  override execute() {
+    return runCommand(
+      () => tokensRevokeCommand(this.token),
+      "tokens revoke"
+    );
+  }
+}
+
+class UsersCreateCommand extends Command {
+  static override paths = [["users", "create"]];
+  static override usage = Command.Usage({
+    category: "Security",
+    description: "Create a user account for a shared Hive server.",
+    details:
+      "Cells are owned by the user whose token created them. Owners can share cells read-only with other users; admin-scoped tokens see every cell.",
+    examples: [
+      ["Create a user", "hive users create ada"],
+      ["Set a display name", 'hive users create ada --display-name "Ada L."'],
+    ],
+  });
+
+  username = Option.String({
+    name: "username",
+    required: true,
+  });
+
+  displayName = Option.String("--display-name", {
+    description: "Human-friendly name shown in the UI",
+  });
+
+  override execute() {
+    return runCommand(
+      () => usersCreateCommand(this.username, this.displayName),
+      "users create"
+    );
+  }
+}
+
+class UsersListCommand extends Command {
+  static override paths = [["users", "list"], ["users"]];
+  static override usage = Command.Usage({
+    category: "Security",
+    description: "List user accounts.",
+    examples: [["List users", "hive users list"]],
+  });
+
+  override execute() {
+    return runCommand(() => usersListCommand(), "users list");
+  }
+}
+
+class UsersDeleteCommand extends Command {
+  static override paths = [["users", "delete"]];
+  static override usage = Command.Usage({
+    category: "Security",
+    description: "Delete a user and revoke their tokens.",
+    examples: [["Delete a user", "hive users delete ada"]],
+  });
+
+  user = Option.String({
+    name: "user",
+    required: true,
+  });
+
+  override execute() {
+    return runCommand(() => usersDeleteCommand(this.user), "users delete");
+  }
+}
+
+class SecretsListCommand extends Command {
+  static override paths = [["secrets", "list"], ["secrets"]];
+  static override usage = Command.Usage({
+    category: "Security",
+    description: "List workspace secret names.",
+    details:
+      "Templates reference secrets in env values as ${secret:NAME}. Values are stored encrypted and are never shown; only names and update times are listed.",
+    examples: [["List secrets", "hive secrets"]],
+  });
+
+  workspace = Option.String("--workspace", {
+    description: "Workspace id (defaults to the active one)",
+  });
+
+  json = Option.Boolean("--json", {
+    description: "Print JSON instead of a table",
+  });
+
+  override execute() {
+    return runCommand(
+      () =>
+        listSecretsCommand(
+          createApiClient(),
+          {
+            ...(this.workspace ? { workspace: this.workspace } : {}),
+            json: Boolean(this.json),
+          },
+          writeStdout
+        ),
+      "secrets list"
+    );
+  }
+}
+
+class SecretsSetCommand extends Command {
+  static override paths = [["secrets", "set"]];
+  static override usage = Command.Usage({
+    category: "Security",
+    description: "Create or replace a workspace secret.",
+    details:
+      "The value is read from stdin unless --value is given, which keeps it out of shell history. A single trailing newline is dropped. Running services pick up the new value the next time they start.",
+    examples: [
+      [
+        "Read the value from stdin",
+        'printf %s "$STRIPE_KEY" | hive secrets set STRIPE_KEY',
+      ],
+      [
+        "Read the value from a file",
+        "hive secrets set DB_PASSWORD < db-password.txt",
+      ],
+    ],
+  });
+
+  name = Option.String({
+    name: "name",
+    required: true,
+  });
+
+  value = Option.String("--value", {
+    description: "Secret value (prefer stdin)",
+  });
+
+  workspace = Option.String("--workspace", {
+    description: "Workspace id (defaults to the active one)",
+  });
+
+  json = Option.Boolean("--json", {
+    description: "Print the saved secret as JSON",
+  });
+
+  override execute() {
+    return runCommand(async () => {
+      const value =
+        this.value ??
+        new TextDecoder()
+          .decode(await readStdinBytes())
+          .replace(/\r?\n$/, "");
+      return setSecretCommand(
+        createApiClient(),
+        this.name,
+        {
+          value,
+          ...(this.workspace ? { workspace: this.workspace } : {}),
+          json: Boolean(this.json),
+        },
+        writeStdout
+      );
+    }, "secrets set");
+  }
+}
+
+class SecretsDeleteCommand extends Command {
+  static override paths = [["secrets", "delete"]];
+  static override usage = Command.Usage({
+    category: "Security",
+    description: "Delete a workspace secret.",
+    details:
+      "Services that still reference the secret fail to start until it is set again.",
+    examples: [["Delete a secret", "hive secrets delete STRIPE_KEY"]],
+  });
+
+  name = Option.String({
+    name: "name",
+    required: true,
+  });
+
+  workspace = Option.String("--workspace", {
+    description: "Workspace id (defaults to the active one)",
+  });
+
+  json = Option.Boolean("--json", {
+    description: "Print the result as JSON",
+  });
+
+  override execute() {
+    return runCommand(
+      () =>
+        deleteSecretCommand(
+          createApiClient(),
+          this.name,
+          {
+            ...(this.workspace ? { workspace: this.workspace } : {}),
+            json: Boolean(this.json),
+          },
+          writeStdout
+        ),
+      "secrets delete"
+    );
+  }
+}
+
+class CellsListCommand extends Command {
+  static override paths = [["cells", "list"], ["cells"]];
+  static override usage = Command.Usage({
+    category: "Cells",
+    description: "List cells in a workspace.",
+    details:
+      "Talks to the running Hive server. Uses the active workspace unless --workspace is given. Set HIVE_API_URL and HIVE_TOKEN to target a remote server.",
+    examples: [
+      ["List cells", "hive cells"],
+      ["List cells as JSON", "hive cells list --json"],
+    ],
+  });
+
+  workspace = Option.String("--workspace", {
+    description: "Workspace id (defaults to the active one)",
+  });
+
+  json = Option.Boolean("--json", {
+    description: "Print JSON instead of a table",
+  });
+
+  override execute() {
+    return runCommand(
+      () =>
+        listCellsCommand(
+          createApiClient(),
+          {
+            ...(this.workspace ? { workspace: this.workspace } : {}),
+            json: Boolean(this.json),
+          },
+          writeStdout
+        ),
+      "cells list"
+    );
+  }
+}
+
+class CellsCreateCommand extends Command {
+  static override paths = [["cells", "create"]];
+  static override usage = Command.Usage({
+    category: "Cells",
+    description: "Create a cell from a template.",
+    details:
+      "Prints the new cell's id, name and status. Pass --wait to block until provisioning finishes; the command then exits non-zero if setup failed. --from picks the start point: head, branch:<name>, pr:<number> or cell:<id>.",
+    examples: [
+      ["Create a cell", "hive cells create auth-fix --template basic"],
+      [
+        "Branch off a PR and wait",
+        "hive cells create review-42 --template basic --from pr:42 --wait",
+      ],
+    ],
+  });
+
+  name = Option.String({
+    name: "name",
+    required: true,
+  });
+
+  template = Option.String("--template", {
+    description: "Template id to create the cell from",
+    required: true,
+  });
+
+  workspace = Option.String("--workspace", {
+    description: "Workspace id (defaults to the active one)",
+  });
+
+  description = Option.String("--description", {
+    description: "Task description handed to the agent",
+  });
+
+  startMode = Option.String("--mode", {
+    description: "Agent start mode (plan or build)",
+  });
+
+  spawnFrom = Option.String("--from", {
+    description: "Start point: head, branch:<name>, pr:<number>, cell:<id>",
+  });
+
+  wait = Option.Boolean("--wait", {
+    description: "Wait until the cell is ready or has failed",
+  });
+
+  json = Option.Boolean("--json", {
+    description: "Print the cell as JSON",
+  });
+
+  override execute() {
+    return runCommand(
+      () =>
+        createCellCommand(
+          createApiClient(),
+          {
+            name: this.name,
+            template: this.template,
+            ...(this.workspace ? { workspace: this.workspace } : {}),
+            ...(this.description ? { description: this.description } : {}),
+            ...(this.startMode ? { startMode: this.startMode } : {}),
+            ...(this.spawnFrom ? { spawnFrom: this.spawnFrom } : {}),
+            wait: Boolean(this.wait),
+            json: Boolean(this.json),
+          },
+          writeStdout
+        ),
+      "cells create"
+    );
+  }
+}
+
+class CellsBatchCommand extends Command {
+  static override paths = [["cells", "batch"]];
+  static override usage = Command.Usage({
+    category: "Cells",
+    description: "Create several cells from a YAML or JSON manifest.",
+    details:
+      "The manifest is a list of cells, or an object with `cells`, an optional `concurrency` and a default `templateId`. Each cell takes name, description, templateId, startMode, spawnFrom (head, branch:<name>, pr:<number>, cell:<id>) and initialPrompt, which is sent to the agent once the cell is ready. Progress is printed as cells move along; the command exits non-zero if any cell failed. Pass - to read the manifest from stdin.",
+    examples: [
+      ["Create cells from a manifest", "hive cells batch refactors.yaml"],
+      [
+        "Limit parallel provisioning",
+        "hive cells batch refactors.yaml --concurrency 2",
+      ],
+    ],
+  });
+
+  file = Option.String({
+    name: "manifest",
+    required: true,
+  });
+
+  workspace = Option.String("--workspace", {
+    description: "Workspace id (defaults to the active one)",
+  });
+
+  concurrency = Option.String("--concurrency", {
+    description: "How many cells to provision at once (overrides the manifest)",
+  });
+
+  json = Option.Boolean("--json", {
+    description: "Print each progress event as a JSON line",
+  });
+
+  override execute() {
+    return runCommand(async () => {
+      const manifest =
+        this.file === "-"
+          ? new TextDecoder().decode(await readStdinBytes())
+          : readFileSync(this.file, "utf8");
+      const concurrency = parsePositiveInteger(
+        "--concurrency",
+        this.concurrency
+      );
+      return batchCellsCommand(
+        createApiClient(),
+        {
+          manifest,
+          ...(this.workspace ? { workspace: this.workspace } : {}),
+          ...(concurrency ? { concurrency } : {}),
+          json: Boolean(this.json),
+        },
+        writeStdout
+      );
+    }, "cells batch");
+  }
+}
+
+class CellsShowCommand extends Command {
+  static override paths = [["cells", "show"]];
+  static override usage = Command.Usage({
+    category: "Cells",
+    description: "Show a cell and its services.",
+    examples: [["Show a cell", "hive cells show 3f2a9c"]],
+  });
+
+  cell = Option.String({
+    name: "cell",
+    required: true,
+  });
+
+  json = Option.Boolean("--json", {
+    description: "Print JSON instead of text",
+  });
+
+  override execute() {
+    return runCommand(
+      () =>
+        showCellCommand(
+          createApiClient(),
+          this.cell,
+          { json: Boolean(this.json) },
+          writeStdout
+        ),
+      "cells show"
+    );
+  }
+}
+
+class CellsConfigCommand extends Command {
+  static override paths = [["cells", "config"]];
+  static override usage = Command.Usage({
+    category: "Cells",
+    description:
+      "Show how the workspace config differs from a cell's running services.",
+    details:
+      "Lists services the current config adds, removes or changes, with each changed field. With --apply, only the changed services are restarted; the rest keep running.",
+    examples: [
+      ["Preview config changes", "hive cells config 3f2a9c"],
+      ["Restart the changed services", "hive cells config 3f2a9c --apply"],
+    ],
+  });
+
+  cell = Option.String({
+    name: "cell",
+    required: true,
+  });
+
+  apply = Option.Boolean("--apply", {
+    description: "Apply the config by restarting the changed services",
+  });
+
+  json = Option.Boolean("--json", {
+    description: "Print the changes as JSON",
+  });
+
+  override execute() {
+    return runCommand(
+      () =>
+        configCommand(
+          createApiClient(),
+          this.cell,
+          { apply: Boolean(this.apply), json: Boolean(this.json) },
+          writeStdout
+        ),
+      "cells config"
+    );
+  }
+}
+
+class CellsDeleteCommand extends Command {
+  static override paths = [["cells", "delete"]];
+  static override usage = Command.Usage({
+    category: "Cells",
+    description: "Delete a cell, its worktree and its services.",
+    examples: [["Delete a cell", "hive cells delete 3f2a9c"]],
+  });
+
+  cell = Option.String({
+    name: "cell",
+    required: true,
+  });
+
+  json = Option.Boolean("--json", {
+    description: "Print the result as JSON",
+  });
+
+  override execute() {
+    return runCommand(
+      () =>
+        deleteCellCommand(
+          createApiClient(),
+          this.cell,
+          { json: Boolean(this.json) },
+          writeStdout
+        ),
+      "cells delete"
+    );
+  }
+}
+
+abstract class ServiceActionCliCommand extends Command {
+  abstract readonly action: ServiceAction;
+
+  cell = Option.String({
+    name: "cell",
+    required: true,
+  });
+
+  service = Option.String({
+    name: "service",
+    required: false,
+  });
+
+  json = Option.Boolean("--json", {
+    description: "Print the services as JSON",
+  });
+
+  override execute() {
+    return runCommand(
+      () =>
+        serviceActionCommand(
+          createApiClient(),
+          this.action,
+          this.cell,
+          {
+            ...(this.service ? { service: this.service } : {}),
+            json: Boolean(this.json),
+          },
+          writeStdout
+        ),
+      `services ${this.action}`
+    );
+  }
+}
+
+class ServicesStartCommand extends ServiceActionCliCommand {
+  static override paths = [["services", "start"]];
+  static override usage = Command.Usage({
+    category: "Cells",
+    description: "Start a cell's services, or one service by name.",
+    examples: [
+      ["Start all services", "hive services start 3f2a9c"],
+      ["Start one service", "hive services start 3f2a9c web"],
+    ],
+  });
+
+  readonly action = "start";
+}
+
+class ServicesStopCommand extends ServiceActionCliCommand {
+  static override paths = [["services", "stop"]];
+  static override usage = Command.Usage({
+    category: "Cells",
+    description: "Stop a cell's services, or one service by name.",
+    examples: [["Stop all services", "hive services stop 3f2a9c"]],
+  });
+
+  readonly action = "stop";
+}
+
+class ServicesRestartCommand extends ServiceActionCliCommand {
+  static override paths = [["services", "restart"]];
+  static override usage = Command.Usage({
+    category: "Cells",
+    description: "Restart a cell's services, or one service by name.",
+    examples: [["Restart one service", "hive services restart 3f2a9c web"]],
+  });
+
+  readonly action = "restart";
+}
+
+class ServicesLogsCommand extends Command {
+  static override paths = [["services", "logs"]];
+  static override usage = Command.Usage({
+    category: "Cells",
+    description: "Print the recent log output of a cell service.",
+    examples: [
+      ["Show recent logs", "hive services logs 3f2a9c web"],
+      ["Show more lines", "hive services logs 3f2a9c web --lines 1000"],
+    ],
+  });
+
+  cell = Option.String({
+    name: "cell",
+    required: true,
+  });
+
+  service = Option.String({
+    name: "service",
+    required: true,
+  });
+
+  lines = Option.String("--lines,-n", {
+    description: "Number of log lines to print (default: 200)",
+  });
+
+  json = Option.Boolean("--json", {
+    description: "Print the logs as JSON",
+  });
+
+  override execute() {
+    return runCommand(() => {
+      const lines = parsePositiveInteger("--lines", this.lines);
+      return serviceLogsCommand(
+        createApiClient(),
+        this.cell,
+        {
+          service: this.service,
+          ...(lines ? { lines } : {}),
+          json: Boolean(this.json),
+        },
+        writeStdout
+      );
+    }, "services logs");
+  }
+}
+
+class DiffCommand extends Command {
+  static override paths = [["diff"]];
+  static override usage = Command.Usage({
+    category: "Cells",
+    description: "Show what changed in a cell.",
+    details:
+      "Prints a per-file summary by default. --patch prints unified diffs instead. --mode branch compares against the commit the cell was created from; the default compares against the worktree's HEAD.",
+    examples: [
+      ["Summarise changes", "hive diff 3f2a9c"],
+      ["Full branch diff", "hive diff 3f2a9c --mode branch --patch | less"],
+    ],
+  });
+
+  cell = Option.String({
+    name: "cell",
+    required: true,
+  });
+
+  mode = Option.String("--mode", {
+    description: "workspace (uncommitted changes) or branch",
+  });
+
+  patch = Option.Boolean("--patch,-p", {
+    description: "Print unified diffs instead of a summary",
+  });
+
+  json = Option.Boolean("--json", {
+    description: "Print the diff payload as JSON",
+  });
+
+  override execute() {
+    return runCommand(
+      () =>
+        diffCommand(
+          createApiClient(),
+          this.cell,
+          {
+            ...(this.mode ? { mode: this.mode } : {}),
+            patch: Boolean(this.patch),
+            json: Boolean(this.json),
+          },
+          writeStdout
+        ),
+      "diff"
+    );
+  }
+}
+
+class AskCommand extends Command {
+  static override paths = [["ask"]];
+  static override usage = Command.Usage({
+    category: "Cells",
+    description: "Send a prompt to a cell's agent and stream the reply.",
+    details:
+      "Prints the agent's text and tool calls as they stream. Exits 0 when the agent finishes its turn, 1 on an agent error or timeout, and 2 when the agent stops to ask for permission or an answer. Pass - as the prompt to read it from stdin.",
+    examples: [
+      ["Ask a question", 'hive ask 3f2a9c "Why does the login test fail?"'],
+      ["Prompt from a file", "hive ask 3f2a9c - < task.md"],
+      ["Stream raw events", 'hive ask 3f2a9c "Run the tests" --json'],
+    ],
+  });
+
+  cell = Option.String({
+    name: "cell",
+    required: true,
+  });
+
+  prompt = Option.String({
+    name: "prompt",
+    required: true,
+  });
+
+  json = Option.Boolean("--json", {
+    description: "Print each stream event as a JSON line",
+  });
+
+  timeout = Option.String("--timeout", {
+    description: "Give up after this many seconds",
+  });
+
+  override execute() {
+    return runCommand(async () => {
+      const prompt =
+        this.prompt === "-"
+          ? new TextDecoder().decode(await readStdinBytes()).trim()
+          : this.prompt;
+      if (!prompt) {
+        logError("The prompt is empty.");
+        return 1;
+      }
+      const timeoutSeconds = parsePositiveInteger("--timeout", this.timeout);
+      return askCommand(
+        createApiClient(),
+        this.cell,
+        prompt,
+        {
+          json: Boolean(this.json),
+          ...(timeoutSeconds ? { timeoutMs: timeoutSeconds * 1000 } : {}),
+        },
+        writeStdout
+      );
+    }, "ask");
+  }
+}
+
+class CellExportCommand extends Command {
+  static override paths = [["cell", "export"]];
+  static override usage = Command.Usage({
+    category: "Cells",
+    description: "Export a cell as a tar archive.",
+    details:
+      "The archive holds the cell's branch and uncommitted work as a git bundle, the template's included files, its services, activity and timings, and the agent transcript. It is written to stdout unless --output is given. Set HIVE_API_URL and HIVE_TOKEN to export from a remote server.",
+    examples: [
+      ["Export to a file", "hive cell export 3f2a9c > cell.tar"],
+      ["Use --output", "hive cell export 3f2a9c --output cell.tar"],
+    ],
+  });
+
+  cell = Option.String({
+    name: "cell",
+    required: true,
+  });
+
+  output = Option.String("--output,-o", {
+    description: "File to write the archive to instead of stdout",
+  });
+
+  override execute() {
+    return runCommand(
+      () => cellExportCommand(this.cell, this.output),
+      "cell export"
+    );
+  }
+}
+
+class CellImportCommand extends Command {
+  static override paths = [["cell", "import"]];
+  static override usage = Command.Usage({
+    category: "Cells",
+    description: "Import a cell archive created by `hive cell export`.",
+    details:
+      "The cell is recreated under a new id in the target workspace, with its worktree path, workspace root and service directories rewritten. Services come back stopped. The archive is read from stdin when no file is given. The cell's template must exist in the target workspace.",
+    examples: [
+      ["Import from a file", "hive cell import cell.tar"],
+      ["Import from stdin", "hive cell import < cell.tar"],
+      [
+        "Pick the workspace and name",
+        'hive cell import cell.tar --workspace api --name "Auth fix"',
+      ],
+    ],
+  });
+
+  file = Option.String({
+    name: "file",
+    required: false,
+  });
+
+  workspace = Option.String("--workspace", {
+    description: "Workspace id to import into (defaults to the active one)",
+  });
+
+  name = Option.String("--name", {
+    description: "Name for the imported cell (defaults to the original)",
+  });
+
+  override execute() {
+    return runCommand(
+      () =>
+        cellImportCommand({
+          ...(this.file ? { file: this.file } : {}),
+          ...(this.workspace ? { workspace: this.workspace } : {}),
+          ...(this.name ? { name: this.name } : {}),
+        }),
+      "cell import"
+    );
+  }
+}
+
 const registeredCommandClasses = [
   StartCommand,
   StopCommand,
@@ -1684,6 +2766,29 @@ const registeredCommandClasses = [
   UpgradeCommand,
   UninstallCommand,
   InfoCommand,
+  TokensCreateCommand,
+  TokensListCommand,
+  TokensRevokeCommand,
+  UsersCreateCommand,
+  UsersListCommand,
+  UsersDeleteCommand,
+  SecretsListCommand,
+  SecretsSetCommand,
+  SecretsDeleteCommand,
+  CellsListCommand,
+  CellsCreateCommand,
+  CellsBatchCommand,
+  CellsShowCommand,
+  CellsConfigCommand,
+  CellsDeleteCommand,
+  CellExportCommand,
+  CellImportCommand,
+  ServicesStartCommand,
+  ServicesStopCommand,
+  ServicesRestartCommand,
+  ServicesLogsCommand,
+  DiffCommand,
+  AskCommand,
   CompletionsCommand,
   CompletionsInstallCommand,
   Builtins.HelpCommand,
@@ -0,0 +1,95 @@
+/// <reference types="vitest" />
+
+import { describe, expect, it, vi } from "vitest";
+
+import { createHiveApiClient } from "./api-client";
+import {
+  deleteSecretCommand,
+  listSecretsCommand,
+  setSecretCommand,
+} from "./secret-commands";
+
+const BASE_URL = "http://localhost:3000";
+
+const createTestClient = (
+  respond: (url: URL, init?: RequestInit) => unknown
+) => {
+  const fetchMock = vi.fn((input: string, init?: RequestInit) =>
+    Promise.resolve(
+      new Response(JSON.stringify(respond(new URL(input), init)), {
+        status: 200,
+      })
+    )
+  );
+  return {
+    client: createHiveApiClient({ baseUrl: BASE_URL, fetchImpl: fetchMock }),
+    fetchMock,
+  };
+};
+
+const captureOutput = () => {
+  const chunks: string[] = [];
+  return {
+    write: (text: string) => {
+      chunks.push(text);
+    },
+    text: () => chunks.join(""),
+  };
+};
+
+const secret = {
+  name: "STRIPE_KEY",
+  workspaceId: "ws-1",
+  createdAt: "2026-01-01T00:00:00.000Z",
+  updatedAt: "2026-01-02T00:00:00.000Z",
+};
+
+describe("secret commands", () => {
+  it("lists secret names for the workspace", async () => {
+    const { client, fetchMock } = createTestClient(() => ({
+      secrets: [secret],
+    }));
+
+    const output = captureOutput();
+    await listSecretsCommand(client, { workspace: "ws-1" }, output.write);
+
+    expect(output.text()).toContain("STRIPE_KEY");
+    expect(fetchMock.mock.calls[0]?.[0]).toBe(
+      `${BASE_URL}/api/secrets?workspaceId=ws-1`
+    );
+  });
+
+  it("sets and deletes secrets without echoing the value", async () => {
+    const { client, fetchMock } = createTestClient((_url, init) =>
+      init?.method === "DELETE" ? { message: "Secret deleted" } : secret
+    );
+
+    const saved = captureOutput();
+    await setSecretCommand(
+      client,
+      "STRIPE_KEY",
+      { value: "sk_test_123", workspace: "ws-1" },
+      saved.write
+    );
+    expect(saved.text()).toBe("Saved secret STRIPE_KEY\n");
+    const [url, init] = fetchMock.mock.calls[0] ?? [];
+    expect(url).toBe(`${BASE_URL}/api/secrets/STRIPE_KEY`);
+    expect(init?.method).toBe("PUT");
+    expect(JSON.parse(String(init?.body))).toEqual({
+      workspaceId: "ws-1",
+      value: "sk_test_123",
+    });
+
+    const deleted = captureOutput();
+    await deleteSecretCommand(
+      client,
+      "STRIPE_KEY",
+      { workspace: "ws-1" },
+      deleted.write
+    );
+    expect(deleted.text()).toBe("Deleted secret STRIPE_KEY\n");
+    expect(fetchMock.mock.calls[1]?.[0]).toBe(
+      `${BASE_URL}/api/secrets/STRIPE_KEY?workspaceId=ws-1`
+    );
+  });
+});
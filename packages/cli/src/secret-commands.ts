@@ -0,0 +1,88 @@
+import type { HiveApiClient } from "./api-client";
+import {
+  type CommandOutput,
+  renderTable,
+  resolveWorkspaceId,
+  type TableColumn,
+  writeJson,
+} from "./cell-commands";
+
+/**
+ * Workspace secrets referenced from templates as `${secret:NAME}`. The API
+ * never returns values, so listing only shows names and timestamps.
+ */
+
+export type SecretSummary = {
+  name: string;
+  workspaceId: string;
+  createdAt: string;
+  updatedAt: string;
+};
+
+const SECRET_COLUMNS: TableColumn<SecretSummary>[] = [
+  { header: "NAME", value: (secret) => secret.name },
+  { header: "UPDATED", value: (secret) => secret.updatedAt },
+];
+
+const secretPath = (name: string) =>
+  `/api/secrets/${encodeURIComponent(name)}`;
+
+export const listSecretsCommand = async (
+  client: HiveApiClient,
+  options: { workspace?: string; json?: boolean },
+  output: CommandOutput
+) => {
+  const workspaceId = await resolveWorkspaceId(client, options.workspace);
+  const { secrets } = await client.json<{ secrets: SecretSummary[] }>(
+    "/api/secrets",
+    { query: { workspaceId } }
+  );
+  if (options.json) {
+    writeJson(output, secrets);
+    return 0;
+  }
+  output(
+    secrets.length === 0
+      ? "No secrets.\n"
+      : `${renderTable(secrets, SECRET_COLUMNS)}\n`
+  );
+  return 0;
+};
+
+export const setSecretCommand = async (
+  client: HiveApiClient,
+  name: string,
+  options: { value: string; workspace?: string; json?: boolean },
+  output: CommandOutput
+) => {
+  const workspaceId = await resolveWorkspaceId(client, options.workspace);
+  const secret = await client.json<SecretSummary>(secretPath(name), {
+    method: "PUT",
+    json: { workspaceId, value: options.value },
+  });
+  if (options.json) {
+    writeJson(output, secret);
+  } else {
+    output(`Saved secret ${secret.name}\n`);
+  }
+  return 0;
+};
+
+export const deleteSecretCommand = async (
+  client: HiveApiClient,
+  name: string,
+  options: { workspace?: string; json?: boolean },
+  output: CommandOutput
+) => {
+  const workspaceId = await resolveWorkspaceId(client, options.workspace);
+  const result = await client.json<{ message: string }>(secretPath(name), {
+    method: "DELETE",
+    query: { workspaceId },
+  });
+  if (options.json) {
+    writeJson(output, { name, ...result });
+  } else {
+    output(`Deleted secret ${name}\n`);
+  }
+  return 0;
+};
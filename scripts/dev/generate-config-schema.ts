@@ -22,6 +22,12 @@ const descriptionMap: Record<string, string> = {
   "templates/*/id": "Unique template identifier",
   "templates/*/label": "Display name for template",
   "templates/*/type": "Template type",
+  "templates/*/extends":
+    "Template whose services, env, setup, prompts and patterns are inherited",
+  "templates/*/parameters":
+    "Inputs interpolated into env, setup, service commands and prompts as ${params.<name>}",
+  "templates/*/parameters/[]":
+    "Input supplied when creating a cell from this template",
   "templates/*/services": "Services required by this template",
   "templates/*/services/*": "Service definition",
   "templates/*/services/*/type": "Service type discriminator",
@@ -34,7 +40,8 @@ const descriptionMap: Record<string, string> = {
   "templates/*/services/*/stop": "Graceful stop command",
   "templates/*/services/*/image": "Docker image to use",
   "templates/*/services/*/command": "Command to override default",
-  "templates/*/services/*/ports": "Port mappings (e.g., '3000:3000')",
+  "templates/*/services/*/ports":
+    "Port mappings (e.g., '5432' or '$PORT:5432' to publish on the assigned port)",
   "templates/*/services/*/volumes": "Volume mappings",
   "templates/*/services/*/file": "Path to docker-compose.yml",
   "templates/*/services/*/services": "Specific services to run",
@@ -54,6 +61,12 @@ const descriptionMap: Record<string, string> = {
     "Patterns to include from gitignored files for worktree copying (e.g., '.env', '*.local')",
   "templates/*/ignorePatterns":
     "Glob patterns to skip when copying included files into worktrees",
+  "templates/*/idle":
+    "Stops services and the agent runtime of idle cells until they are opened again",
+  "templates/*/idle/suspendAfterMinutes":
+    "Minutes without agent activity, terminal input or service traffic before the cell is suspended",
+  templateDefaults:
+    "Fields every template inherits before its own extends chain",
   defaults: "Default values for cell creation",
   "defaults/templateId": "Default template to use when creating cells",
   "defaults/startMode": "Default OpenCode agent mode for new cells",
@@ -1,6 +1,7 @@
-import { existsSync } from "node:fs";
 import { join, resolve, sep } from "node:path";
 
+import { hasConfigFile } from "../../apps/server/src/config/files";
+
 export function resolveWorkspaceRoot(currentDir: string) {
   const normalizedRoot = resolveBaseWorkspaceRoot(currentDir);
 
@@ -34,5 +35,5 @@ function resolveBaseWorkspaceRoot(currentDir: string) {
 }
 
 function hasHiveConfig(directory: string) {
-  return existsSync(join(directory, "hive.config.json"));
+  return hasConfigFile(directory);
 }
@@ -290,6 +290,20 @@ const main = async () => {
     recursive: true,
   });
 
+  const serverPgMigrationsDir = join(
+    repoRoot,
+    "apps",
+    "server",
+    "src",
+    "migrations-pg"
+  );
+  if (!existsSync(serverPgMigrationsDir)) {
+    throw new Error("Server Postgres migrations directory missing.");
+  }
+  await cp(serverPgMigrationsDir, join(releaseDir, "migrations-pg"), {
+    recursive: true,
+  });
+
   await copyDesktopBundle(releaseDir);
 
   const releaseVersion = await resolveReleaseVersion({
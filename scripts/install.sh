@@ -337,6 +337,7 @@ fi
 
 write_env_var "$target/hive.env" "HIVE_WEB_DIST" "$target/public"
 write_env_var "$target/hive.env" "HIVE_MIGRATIONS_DIR" "$target/migrations"
+write_env_var "$target/hive.env" "HIVE_PG_MIGRATIONS_DIR" "$target/migrations-pg"
 if ! env_file_has_key "$target/hive.env" "HIVE_LOG_DIR"; then
   write_env_var "$target/hive.env" "HIVE_LOG_DIR" "$INSTALL_ROOT/logs"
 fi